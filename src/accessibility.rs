@@ -0,0 +1,152 @@
+// src/accessibility.rs
+
+//! Output for screen readers, turned on with `--accessible` or
+//! `accessible = true` in the config.
+//!
+//! The [`ScreenReader`] renderer passes every line the game writes through
+//! [`plain_text`], which drops separator lines and decoration and reads table
+//! columns as a list, so a screen reader speaks only the words. [`Announcer`]
+//! says outright what the normal output leaves to the eye, such as the
+//! balance after each change.
+
+use crate::game::currency::Currency;
+use crate::game::events::{BalanceChangeReason, EventSink, GameEvent};
+use crate::game::money::Units;
+use crate::renderer::Renderer;
+use crate::say;
+use std::borrow::Cow;
+
+/// Characters that only decorate: a line of nothing else is dropped, and a
+/// run of three or more within a line is taken out.
+const DECORATION: &[char] = &['-', '=', '*', '_', '~', '#', '<', '>', '|', '+'];
+
+/// `text` without escape codes, separator lines, brackets, quotes, or runs
+/// of decoration, and with the gaps between table columns read as commas.
+pub fn plain_text(text: &str) -> String {
+    let text = strip_escapes(text);
+    let mut plain = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        };
+        let trimmed = body.trim();
+        if !trimmed.is_empty() && trimmed.chars().all(|c| DECORATION.contains(&c) || c.is_whitespace()) {
+            continue;
+        }
+        plain.push_str(&plain_line(trimmed));
+        plain.push_str(newline);
+    }
+    plain
+}
+
+fn plain_line(line: &str) -> String {
+    let mut words: Vec<String> = Vec::new();
+    // Two or more spaces in a row separate table columns, as does a bar.
+    let line = line.replace('|', "  ");
+    for column in line.split("  ").map(str::trim).filter(|column| !column.is_empty()) {
+        let mut cleaned = String::with_capacity(column.len());
+        let mut chars = column.chars().peekable();
+        while let Some(c) = chars.next() {
+            if matches!(c, '[' | ']' | '"') {
+                continue;
+            }
+            let mut run = 1;
+            while DECORATION.contains(&c) && chars.peek() == Some(&c) {
+                chars.next();
+                run += 1;
+            }
+            // Short runs carry meaning, as in `--tutorial` or `-10`.
+            if run < 3 {
+                cleaned.extend(std::iter::repeat_n(c, run));
+            }
+        }
+        match cleaned.trim() {
+            "" => {}
+            // A dash stands for an empty field, which a screen reader would skip.
+            "-" => words.push("none".to_string()),
+            cleaned => words.push(cleaned.to_string()),
+        }
+    }
+    words.join(", ")
+}
+
+/// Output for screen readers: no colour, and [`plain_text`] throughout. The
+/// spin animation can't be followed this way, so lines are never rewritten.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScreenReader;
+
+impl Renderer for ScreenReader {
+    fn rewrite_line(&self) -> &'static str {
+        "\n"
+    }
+
+    fn finish<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Cow::Owned(plain_text(text))
+    }
+}
+
+/// Removes ANSI escape sequences such as colour codes.
+fn strip_escapes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            stripped.push(c);
+            continue;
+        }
+        // CSI sequences end with a letter; skip through it.
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    stripped
+}
+
+/// Announces changes to the game's state that the normal output shows only
+/// by what it prints next.
+#[derive(Debug)]
+pub struct Announcer {
+    currency: Currency,
+}
+
+impl Announcer {
+    /// Announces amounts in `currency`, the one the table plays in.
+    pub fn new(currency: Currency) -> Self {
+        Announcer { currency }
+    }
+}
+
+impl EventSink for Announcer {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        match event {
+            // Placing a bet and winning already report the new balance.
+            GameEvent::BalanceChange { reason: BalanceChangeReason::BetPlaced | BalanceChangeReason::Winnings, .. } => {}
+            GameEvent::BalanceChange { reason, delta, balance } => {
+                let why = match reason {
+                    BalanceChangeReason::BetPlaced | BalanceChangeReason::Winnings => return,
+                    BalanceChangeReason::Deposit => "deposit",
+                    BalanceChangeReason::Refund => "bets refunded",
+                    BalanceChangeReason::Void => "spin voided",
+                    BalanceChangeReason::Restore => "game restored",
+                    BalanceChangeReason::Comp => "comp",
+                    BalanceChangeReason::Rebate => "loss rebate",
+                    BalanceChangeReason::BuyIn => "buy in",
+                    BalanceChangeReason::CashOut => "cash out",
+                };
+                let change = if delta.is_negative() { "down" } else { "up" };
+                say!(
+                    "Balance {} {} {}, {}. Balance now {} {}.",
+                    change, Units(delta.abs()), self.currency, why, Units(*balance), self.currency
+                );
+            }
+            GameEvent::BetsCleared { .. } => say!("No bets on the table."),
+            GameEvent::RoundComplete { .. } => say!("Round {} over. Place bets for the next round.", round),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,107 @@
+// src/analysis.rs
+
+//! The arithmetic behind the odds: what a bet returns on average and how
+//! much of each stake the house keeps.
+
+#[cfg(feature = "std")]
+pub mod bias;
+
+use crate::game::bets::{Bet, BetType};
+use crate::game::money::Money;
+use crate::game::payout::PayoutTable;
+use crate::game::wheel::{PocketId, Wheel, WheelLike};
+
+/// Average amount a bet returns per unit staked, stake included, when every
+/// pocket is equally likely. 1.0 is break-even.
+///
+/// Custom bets aren't known to the wheel and come out as 0; ask their
+/// [`BetEvaluator`](crate::game::custom::BetEvaluator) instead.
+pub fn expected_return(bet_type: &BetType, wheel: &Wheel, payouts: &PayoutTable) -> f64 {
+    wheel.probability(bet_type) * (payouts.multiplier(bet_type) as f64 + 1.0)
+}
+
+/// Percentage of each stake the house keeps on average for this bet on this
+/// wheel, from the pockets it actually covers. Negative if the bet favours
+/// the player.
+///
+/// A category bet pays the same odds whatever the category's size, so small
+/// categories carry a far bigger edge than large ones.
+pub fn house_edge(bet_type: &BetType, wheel: &Wheel, payouts: &PayoutTable) -> f64 {
+    (1.0 - expected_return(bet_type, wheel, payouts)) * 100.0
+}
+
+/// What a bet wins or (when negative) loses per spin on average, in dollars,
+/// using the spinning wheel's real odds.
+pub fn expected_value(bet: &Bet, wheel: &dyn WheelLike, payouts: &PayoutTable) -> f64 {
+    let multiplier = payouts.multiplier(&bet.bet_type) as f64;
+    bet.amount.as_f64() * (wheel.probability(&bet.bet_type) * (multiplier + 1.0) - 1.0)
+}
+
+/// Share of the bankroll the Kelly criterion would stake on this bet, given
+/// the wheel's true odds and its own payouts. 0 when the bet loses on
+/// average, which on a fair wheel is every bet: Kelly's advice is then not
+/// to play. Halve it for the gentler half-Kelly.
+pub fn kelly_fraction(bet_type: &BetType, wheel: &dyn WheelLike) -> f64 {
+    kelly_fraction_with(bet_type, wheel, wheel.layout().payouts())
+}
+
+/// [`kelly_fraction`] under a different payout table, e.g. a table's house rules.
+pub fn kelly_fraction_with(bet_type: &BetType, wheel: &dyn WheelLike, payouts: &PayoutTable) -> f64 {
+    let odds = payouts.multiplier(bet_type) as f64;
+    if odds == 0.0 {
+        return 0.0;
+    }
+    let p = wheel.probability(bet_type);
+    ((p * (odds + 1.0) - 1.0) / odds).max(0.0)
+}
+
+/// The spread of outcomes for a whole round of bets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskProfile {
+    /// Total staked across the bets.
+    pub stake: Money,
+    /// Net result on the worst pocket for the layout.
+    pub worst_net: Money,
+    /// Net result on the best pocket for the layout.
+    pub best_net: Money,
+    /// Average net result, in whole units.
+    pub expected_net: f64,
+    /// Chance the round returns more than was staked, from 0 to 1.
+    pub chance_ahead: f64,
+}
+
+/// Works out a round's [`RiskProfile`] by settling `bets` against every
+/// pocket of `wheel` in turn, each pocket equally likely and paid at the
+/// wheel's own odds. Custom bets count as losing everywhere.
+pub fn risk_profile(bets: &[Bet], wheel: &Wheel) -> RiskProfile {
+    risk_profile_with(bets, wheel, wheel.payouts())
+}
+
+/// [`risk_profile`] under a different payout table, e.g. a table's house rules.
+pub fn risk_profile_with(bets: &[Bet], wheel: &Wheel, payouts: &PayoutTable) -> RiskProfile {
+    let stake: Money = bets.iter().map(|b| b.amount).sum();
+    let pockets = wheel.get_all_pockets().len();
+    if pockets == 0 {
+        return RiskProfile { stake, worst_net: Money::ZERO, best_net: Money::ZERO, expected_net: 0.0, chance_ahead: 0.0 };
+    }
+    let mut profile = RiskProfile {
+        stake,
+        worst_net: Money::from_cents(i64::MAX),
+        best_net: Money::from_cents(i64::MIN),
+        expected_net: 0.0,
+        chance_ahead: 0.0,
+    };
+    for pocket in (0..pockets).map(PocketId) {
+        let returned: Money = bets.iter().filter(|b| b.check_win(wheel, pocket)).map(|b| b.payout_with(payouts)).sum();
+        let net = returned - stake;
+        profile.worst_net = profile.worst_net.min(net);
+        profile.best_net = profile.best_net.max(net);
+        profile.expected_net += net.as_f64();
+        if net > Money::ZERO {
+            profile.chance_ahead += 1.0;
+        }
+    }
+    profile.expected_net /= pockets as f64;
+    profile.chance_ahead /= pockets as f64;
+    profile
+}
@@ -0,0 +1,207 @@
+// src/analysis/bias.rs
+
+//! Is the wheel fair? A chi-square test of how often each pocket came up
+//! against the even spread a fair wheel would give, or against any other
+//! expected odds, such as a weighted wheel's.
+
+use std::fmt;
+
+/// Confidence levels checked, highest first, with the p-value each needs.
+const LEVELS: [(f64, f64); 4] = [(99.9, 0.001), (99.0, 0.01), (95.0, 0.05), (90.0, 0.10)];
+
+/// Below this many expected hits per pocket the chi-square approximation
+/// isn't trustworthy.
+const MIN_EXPECTED: f64 = 5.0;
+
+/// How many times one pocket came up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PocketFrequency {
+    pub label: String,
+    pub observed: u64,
+    /// Hits the expected odds call for.
+    pub expected: f64,
+    /// Standard deviations above (or below) what a fair wheel would give.
+    pub z_score: f64,
+}
+
+/// Result of testing a spin history for bias.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BiasReport {
+    pub spins: u64,
+    pub chi_square: f64,
+    pub degrees_of_freedom: usize,
+    /// Chance a fair wheel would deviate at least this much.
+    pub p_value: f64,
+    /// One entry per pocket, in the order given.
+    pub pockets: Vec<PocketFrequency>,
+}
+
+impl BiasReport {
+    /// Tests `counts`, the hits of every pocket on the wheel (zeros included).
+    pub fn from_counts(counts: Vec<(String, u64)>) -> BiasReport {
+        let share = 1.0 / counts.len().max(1) as f64;
+        BiasReport::against(counts.into_iter().map(|(label, observed)| (label, observed, share)).collect())
+    }
+
+    /// Tests `counts` against expected odds: each entry is a pocket's label,
+    /// its hits, and its chance of coming up on one spin.
+    pub fn against(counts: Vec<(String, u64, f64)>) -> BiasReport {
+        let spins: u64 = counts.iter().map(|(_, n, _)| n).sum();
+        let mut chi_square = 0.0;
+        let mut possible: usize = 0;
+        let pockets = counts
+            .into_iter()
+            .map(|(label, observed, share)| {
+                let expected = spins as f64 * share;
+                let spread = (expected * (1.0 - share)).sqrt();
+                let diff = observed as f64 - expected;
+                if expected > 0.0 {
+                    possible += 1;
+                    chi_square += diff * diff / expected;
+                } else if observed > 0 {
+                    // A pocket that should never come up did.
+                    chi_square = f64::INFINITY;
+                }
+                let z_score = if spread > 0.0 { diff / spread } else { 0.0 };
+                PocketFrequency { label, observed, expected, z_score }
+            })
+            .collect();
+        let degrees_of_freedom = possible.saturating_sub(1);
+        let p_value = if chi_square.is_infinite() {
+            0.0
+        } else if degrees_of_freedom == 0 || spins == 0 {
+            1.0
+        } else {
+            chi_square_p_value(chi_square, degrees_of_freedom)
+        };
+        BiasReport { spins, chi_square, degrees_of_freedom, p_value, pockets }
+    }
+
+    /// Average hits per pocket a fair wheel would give.
+    pub fn expected_per_pocket(&self) -> f64 {
+        self.spins as f64 / self.pockets.len().max(1) as f64
+    }
+
+    /// True once there are enough spins for the test to mean something.
+    pub fn is_reliable(&self) -> bool {
+        self.spins > 0 && self.pockets.iter().filter(|p| p.expected > 0.0).all(|p| p.expected >= MIN_EXPECTED)
+    }
+
+    /// The highest confidence level (as a percentage) at which the wheel can
+    /// be called biased, or `None` if the spins look fair.
+    pub fn confidence(&self) -> Option<f64> {
+        LEVELS.iter().find(|&&(_, alpha)| self.p_value < alpha).map(|&(level, _)| level)
+    }
+
+    /// Pockets that came up most often relative to a fair wheel.
+    pub fn hottest(&self, n: usize) -> Vec<&PocketFrequency> {
+        let mut pockets: Vec<&PocketFrequency> = self.pockets.iter().collect();
+        pockets.sort_by(|a, b| b.z_score.total_cmp(&a.z_score));
+        pockets.truncate(n);
+        pockets
+    }
+}
+
+impl fmt::Display for BiasReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Bias Check ({} spins, {} pockets) ===", self.spins, self.pockets.len())?;
+        writeln!(f, "Expected hits per pocket: {:.1}", self.expected_per_pocket())?;
+        writeln!(f, "Chi-square: {:.2} with {} degrees of freedom", self.chi_square, self.degrees_of_freedom)?;
+        writeln!(f, "p-value:    {:.4}", self.p_value)?;
+        writeln!(f, "Most frequent pockets:")?;
+        for pocket in self.hottest(5) {
+            writeln!(f, "  {:<8} {:>6} hits  {:>+6.2} sd", pocket.label, pocket.observed, pocket.z_score)?;
+        }
+        match self.confidence() {
+            Some(level) => write!(f, "Verdict: biased, with {}% confidence.", level)?,
+            None => write!(f, "Verdict: no significant deviation from a fair wheel.")?,
+        }
+        if !self.is_reliable() {
+            write!(
+                f,
+                "\nNote: fewer than {} spins per pocket; the test needs more history to be reliable.",
+                MIN_EXPECTED
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Chance that a chi-square variable with `degrees_of_freedom` is at least
+/// `statistic`.
+pub fn chi_square_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    upper_regularized_gamma(degrees_of_freedom as f64 / 2.0, statistic / 2.0)
+}
+
+/// Q(a, x), the upper regularized incomplete gamma function.
+fn upper_regularized_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let log_prefix = a * x.ln() - x - ln_gamma(a);
+    if x < a + 1.0 {
+        // Series for P(a, x), converging quickly below a + 1.
+        let (mut term, mut sum, mut n) = (1.0 / a, 1.0 / a, a);
+        for _ in 0..500 {
+            n += 1.0;
+            term *= x / n;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (1.0 - sum * log_prefix.exp()).clamp(0.0, 1.0)
+    } else {
+        // Continued fraction for Q(a, x) (modified Lentz).
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..500 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let step = d * c;
+            h *= step;
+            if (step - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        (h * log_prefix.exp()).clamp(0.0, 1.0)
+    }
+}
+
+/// ln Γ(x) for x > 0, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // Reflection keeps the approximation in its accurate range.
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += c / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
@@ -0,0 +1,129 @@
+// src/bench.rs
+
+//! Built-in throughput benchmark for the spin and settlement hot paths.
+//!
+//! Not a substitute for a proper harness, but quick enough to run before and
+//! after a change to see whether spinning or settling got slower.
+
+use crate::game::bets::{Bet, BetType};
+use crate::game::compiled::CompiledLayout;
+use crate::game::ticker::Ticker;
+use crate::game::wheel::{PocketId, Wheel, stock_categories};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Fixed seed so every run measures the same sequence of spins.
+const BENCH_SEED: u64 = 0x5EED;
+
+/// A spread of inside, outside, and category bets used as the standard load.
+pub fn standard_bet_mix(wheel: &Wheel) -> Vec<Bet> {
+    let ticker = |text| Ticker::parse(text).expect("valid ticker");
+    let mut bets = vec![
+        Bet::new(BetType::Red, 10),
+        Bet::new(BetType::Odd, 10),
+        Bet::new(BetType::High, 10),
+        Bet::new(BetType::GrowthDozen, 10),
+        Bet::new(BetType::Column(2), 10),
+        Bet::new(BetType::StraightUp(ticker("AAPL")), 1),
+        Bet::new(BetType::Split(ticker("AAPL"), ticker("MSFT")), 2),
+    ];
+    if let Some(mag7) = wheel.category_id(stock_categories::MAG7) {
+        bets.push(Bet::new(BetType::Category(mag7), 5));
+    }
+    bets
+}
+
+/// One measured operation: how many were done and how long it took.
+#[derive(Debug, Clone, Copy)]
+pub struct Measurement {
+    pub operations: u64,
+    pub elapsed: Duration,
+}
+
+impl Measurement {
+    pub fn per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 { 0.0 } else { self.operations as f64 / secs }
+    }
+}
+
+/// Throughput of each hot path.
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub pockets: usize,
+    pub bets: usize,
+    /// Spinning alone.
+    pub spins: Measurement,
+    /// Settling each bet with [`Bet::check_win`], as interactive play does.
+    pub settlements: Measurement,
+    /// Settling each bet through its compiled coverage mask, as simulations do.
+    /// `None` when the wheel is too large to compile.
+    pub compiled_settlements: Option<Measurement>,
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Benchmark ({} pockets, {} bets per round) ===", self.pockets, self.bets)?;
+        writeln!(f, "{:<22} {:>12} {:>10} {:>16}", "Path", "Operations", "Seconds", "Per second")?;
+        let mut row = |name: &str, m: &Measurement| {
+            writeln!(
+                f,
+                "{:<22} {:>12} {:>10.3} {:>16.0}",
+                name, m.operations, m.elapsed.as_secs_f64(), m.per_second()
+            )
+        };
+        row("Spins", &self.spins)?;
+        row("Settlements", &self.settlements)?;
+        match &self.compiled_settlements {
+            Some(m) => row("Compiled settlements", m),
+            None => writeln!(f, "Compiled settlements: wheel too large to compile"),
+        }
+    }
+}
+
+/// Times `rounds` spins, then settles `bets` against `rounds` spins on each path.
+pub fn run(wheel: &Wheel, bets: &[Bet], rounds: u64) -> BenchReport {
+    let spins = measure(rounds, || {
+        let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+        for _ in 0..rounds {
+            black_box(wheel.spin_id_with(&mut rng));
+        }
+    });
+
+    let settled_per_round = bets.len() as u64;
+    let settlements = measure(rounds * settled_per_round, || {
+        let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+        for _ in 0..rounds {
+            let winning_id: PocketId = wheel.spin_id_with(&mut rng);
+            for bet in bets {
+                black_box(bet.check_win(wheel, winning_id));
+            }
+        }
+    });
+
+    let compiled_settlements = CompiledLayout::compile(bets, wheel).map(|layout| {
+        measure(rounds * settled_per_round, || {
+            let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+            for _ in 0..rounds {
+                black_box(layout.settle(wheel.spin_id_with(&mut rng)));
+            }
+        })
+    });
+
+    BenchReport {
+        pockets: wheel.get_all_pockets().len(),
+        bets: bets.len(),
+        spins,
+        settlements,
+        compiled_settlements,
+    }
+}
+
+fn measure(operations: u64, work: impl FnOnce()) -> Measurement {
+    let start = Instant::now();
+    work();
+    Measurement { operations, elapsed: start.elapsed() }
+}
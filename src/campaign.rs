@@ -0,0 +1,139 @@
+// src/campaign.rs
+
+//! The campaign: a run of [challenges](crate::challenge) on a different
+//! wheel each, with one bankroll carried from stage to stage.
+//!
+//! Every stage names a wheel preset, optional ticker weights, and a
+//! difficulty, then sets its goal and rules as a challenge file does. Goals
+//! are in absolute dollars, since the bankroll carries over. The campaign is
+//! played one stage per session and its progress kept on the profile, see
+//! [`CampaignProgress`]; a stage that is lost is played again from the
+//! bankroll it started with.
+//!
+//! ```toml
+//! name = "A Century on Wall Street"
+//! starting_balance = 500
+//!
+//! [[stages]]
+//! name = "Blue Chips"
+//! description = "Grow $500 to $750."
+//! wheel = "european"
+//! difficulty = "normal"
+//!
+//! [stages.weights]         # optional, by ticker as in the config
+//! AAPL = 1.5
+//!
+//! [stages.goal]
+//! balance = 750
+//!
+//! [stages.rules]
+//! max_rounds = 30
+//! ```
+
+use crate::challenge::{ChallengeRules, ChallengeSpec, Goal};
+use crate::config::{Config, WheelKind};
+use crate::game::money::Money;
+use crate::game::rules::Difficulty;
+use crate::profile::CampaignProgress;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const BUILT_IN: (&str, &str) = ("wall-street", include_str!("../campaigns/wall-street.toml"));
+
+/// One stage of a campaign.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Stage {
+    pub name: String,
+    pub description: String,
+    pub wheel: WheelKind,
+    /// Relative chance of each ticker coming up; empty for a fair wheel.
+    #[serde(default)]
+    pub weights: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    pub goal: Goal,
+    #[serde(default)]
+    pub rules: ChallengeRules,
+}
+
+impl Stage {
+    /// The stage as a challenge played from `balance`.
+    pub fn challenge(&self, id: &str, balance: Money) -> ChallengeSpec {
+        ChallengeSpec {
+            id: id.to_string(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            starting_balance: balance,
+            goal: self.goal,
+            rules: self.rules,
+        }
+    }
+
+    /// Sets the stage's wheel and difficulty on `config`.
+    pub fn configure(&self, config: &mut Config) {
+        config.wheel = self.wheel;
+        config.wheel_file = None;
+        config.weights = self.weights.clone();
+        config.weight_by = None;
+        config.difficulty = self.difficulty;
+    }
+}
+
+/// A campaign as written in its file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Campaign {
+    /// Short name, recorded among the profile's challenges once finished.
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub starting_balance: Money,
+    pub stages: Vec<Stage>,
+}
+
+impl Campaign {
+    /// The campaign that ships with the game.
+    pub fn built_in() -> Campaign {
+        let (id, text) = BUILT_IN;
+        let mut campaign: Campaign = toml::from_str(text).expect("the built-in campaign parses");
+        campaign.id = id.to_string();
+        campaign.validate().expect("the built-in campaign is valid");
+        campaign
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.stages.is_empty() {
+            return Err("a campaign needs at least one stage".to_string());
+        }
+        for (number, stage) in self.stages.iter().enumerate() {
+            let mut config = Config::default();
+            stage.configure(&mut config);
+            config.build_wheel().map_err(|msg| format!("stage {}: {}", number + 1, msg))?;
+            stage.challenge(&self.id, self.starting_balance).validate().map_err(|msg| format!("stage {}: {}", number + 1, msg))?;
+        }
+        Ok(())
+    }
+
+    /// Where a player with no progress starts.
+    pub fn start(&self) -> CampaignProgress {
+        CampaignProgress { stage: 0, bankroll: self.starting_balance }
+    }
+
+    /// The stage `progress` is on, or `None` if it's past the last one.
+    pub fn stage(&self, progress: CampaignProgress) -> Option<&Stage> {
+        self.stages.get(progress.stage as usize)
+    }
+
+    /// The challenge `progress` plays next: its stage, from its bankroll.
+    pub fn challenge(&self, progress: CampaignProgress) -> Option<ChallengeSpec> {
+        self.stage(progress).map(|stage| stage.challenge(&self.id, progress.bankroll))
+    }
+
+    /// Progress after the stage was won with `balance`; `None` once the
+    /// last stage is done.
+    pub fn advance(&self, progress: CampaignProgress, balance: Money) -> Option<CampaignProgress> {
+        let next = CampaignProgress { stage: progress.stage + 1, bankroll: balance };
+        self.stage(next).map(|_| next)
+    }
+}
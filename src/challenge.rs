@@ -0,0 +1,233 @@
+// src/challenge.rs
+
+//! Challenge scenarios: a starting balance, a goal, and rules to reach it by.
+//!
+//! Challenges are TOML files. The built-in ones live in `challenges/` and are
+//! compiled in; any other file can be played by its path.
+//!
+//! ```toml
+//! name = "Hundred to a Thousand"
+//! description = "Turn $100 into $1000 using only outside bets."
+//! starting_balance = 100
+//!
+//! [goal]                   # every goal given must be met
+//! balance = 1000           # reach this balance
+//! survive_rounds = 30      # play this many rounds without going broke
+//!
+//! [rules]                  # breaking one loses the challenge
+//! outside_bets_only = true
+//! min_round_stake = 10     # stake at least this much every round
+//! max_rounds = 20          # meet the goal within this many rounds
+//! ```
+
+use crate::config::ConfigError;
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The built-in challenges, by id.
+const BUILT_IN: &[(&str, &str)] = &[
+    ("hundred-to-a-thousand", include_str!("../challenges/hundred-to-a-thousand.toml")),
+    ("survive-thirty", include_str!("../challenges/survive-thirty.toml")),
+    ("double-up", include_str!("../challenges/double-up.toml")),
+];
+
+/// What a challenge asks for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Goal {
+    pub balance: Option<Money>,
+    pub survive_rounds: Option<u32>,
+}
+
+/// What a challenge forbids.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChallengeRules {
+    pub outside_bets_only: bool,
+    pub min_round_stake: Option<Money>,
+    pub max_rounds: Option<u32>,
+}
+
+/// A challenge as written in its file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChallengeSpec {
+    /// Short name, from the file name; recorded on the profile once completed.
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub starting_balance: Money,
+    #[serde(default)]
+    pub goal: Goal,
+    #[serde(default)]
+    pub rules: ChallengeRules,
+}
+
+impl ChallengeSpec {
+    fn parse(id: &str, text: &str) -> Result<Self, String> {
+        let mut spec: ChallengeSpec = toml::from_str(text).map_err(|e| e.to_string())?;
+        spec.id = id.to_string();
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    /// Every built-in challenge.
+    pub fn built_in() -> Vec<ChallengeSpec> {
+        BUILT_IN
+            .iter()
+            .map(|(id, text)| ChallengeSpec::parse(id, text).expect("built-in challenges are valid"))
+            .collect()
+    }
+
+    /// Finds a built-in challenge by id, or reads one from a file.
+    pub fn load(id_or_path: &str) -> Result<Self, ConfigError> {
+        if let Some(spec) = ChallengeSpec::built_in().into_iter().find(|spec| spec.id == id_or_path) {
+            return Ok(spec);
+        }
+        let path = Path::new(id_or_path);
+        let text = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        let id = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        ChallengeSpec::parse(&id, &text).map_err(|msg| ConfigError::Invalid(path.to_path_buf(), msg))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.starting_balance <= Money::ZERO {
+            return Err("starting_balance must be more than 0".to_string());
+        }
+        if self.goal.balance.is_none() && self.goal.survive_rounds.is_none() {
+            return Err("[goal] needs a balance or survive_rounds".to_string());
+        }
+        if let (Some(max), Some(survive)) = (self.rules.max_rounds, self.goal.survive_rounds)
+            && max < survive
+        {
+            return Err(format!("max_rounds ({}) is below survive_rounds ({})", max, survive));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ChallengeSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}: {}", self.name, self.description)?;
+        write!(f, "You start with {}.", self.starting_balance)?;
+        if let Some(max) = self.rules.max_rounds {
+            write!(f, " You have {} rounds.", max)?;
+        }
+        if self.rules.outside_bets_only {
+            write!(f, " Outside bets only.")?;
+        }
+        if let Some(min) = self.rules.min_round_stake {
+            write!(f, " Stake at least {} every round.", min)?;
+        }
+        Ok(())
+    }
+}
+
+/// Where a challenge stands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeStatus {
+    InProgress,
+    Won,
+    /// Lost, and why.
+    Lost(String),
+}
+
+/// A challenge being played. Feed it the game's events.
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    spec: ChallengeSpec,
+    rounds: u32,
+    balance: Money,
+    status: ChallengeStatus,
+}
+
+impl Challenge {
+    pub fn new(spec: ChallengeSpec) -> Self {
+        let balance = spec.starting_balance;
+        Challenge { spec, rounds: 0, balance, status: ChallengeStatus::InProgress }
+    }
+
+    pub fn spec(&self) -> &ChallengeSpec {
+        &self.spec
+    }
+
+    pub fn status(&self) -> &ChallengeStatus {
+        &self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != ChallengeStatus::InProgress
+    }
+
+    /// Ends the challenge as lost for `reason`, unless it's already decided.
+    pub fn forfeit(&mut self, reason: &str) {
+        self.lose(reason.to_string());
+    }
+
+    fn lose(&mut self, reason: String) {
+        if !self.is_over() {
+            self.status = ChallengeStatus::Lost(reason);
+        }
+    }
+
+    /// Progress toward the goal, for the round header.
+    pub fn progress(&self) -> String {
+        let mut parts = vec![format!("round {}", self.rounds + 1)];
+        if let Some(max) = self.spec.rules.max_rounds {
+            parts[0] = format!("round {} of {}", self.rounds + 1, max);
+        }
+        if let Some(target) = self.spec.goal.balance {
+            parts.push(format!("{} of {}", self.balance, target));
+        }
+        if let Some(survive) = self.spec.goal.survive_rounds {
+            parts.push(format!("{} of {} rounds survived", self.rounds, survive));
+        }
+        parts.join(", ")
+    }
+
+    fn check_round(&mut self, wagered: Money) {
+        let rules = self.spec.rules;
+        let goal = self.spec.goal;
+        if let Some(min) = rules.min_round_stake
+            && wagered < min
+        {
+            return self.lose(format!("staked {} in round {}, below the {} minimum", wagered, self.rounds, min));
+        }
+        if self.balance.is_zero() {
+            return self.lose("went broke".to_string());
+        }
+        let met = goal.balance.is_none_or(|target| self.balance >= target)
+            && goal.survive_rounds.is_none_or(|survive| self.rounds >= survive);
+        if met {
+            self.status = ChallengeStatus::Won;
+        } else if rules.max_rounds.is_some_and(|max| self.rounds >= max) {
+            self.lose("ran out of rounds".to_string());
+        }
+    }
+}
+
+impl EventSink for Challenge {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        if self.is_over() {
+            return;
+        }
+        match event {
+            GameEvent::BetPlaced { bet_type, .. } if self.spec.rules.outside_bets_only && !bet_type.is_outside() => {
+                self.lose(format!("placed an inside bet, {}", bet_type));
+            }
+            GameEvent::BalanceChange { balance, .. } => self.balance = *balance,
+            GameEvent::RoundComplete { wagered, balance, .. } => {
+                self.balance = *balance;
+                self.rounds += 1;
+                self.check_round(*wagered);
+            }
+            GameEvent::SpinVoided { .. } => self.rounds = self.rounds.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// src/charts.rs
+
+//! PNG charts of a session, behind the `charts` feature.
+//!
+//! The balance chart plots the balance round by round, with the drawdown
+//! (how far the balance sits below its best so far) in a strip underneath.
+
+use crate::game::money::Money;
+use plotters::prelude::*;
+use std::fmt;
+use std::path::Path;
+
+const WIDTH: u32 = 1000;
+const HEIGHT: u32 = 600;
+
+/// Why a chart could not be drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChartError {
+    /// There was no round to plot.
+    NoRounds,
+    Draw(String),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartError::NoRounds => write!(f, "no rounds to chart"),
+            ChartError::Draw(msg) => write!(f, "can't draw chart: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {}
+
+fn draw_error(e: impl fmt::Display) -> ChartError {
+    ChartError::Draw(e.to_string())
+}
+
+/// Draws `balances` (the starting balance, then the balance after each
+/// round) and their drawdown to a PNG at `path`.
+pub fn balance_chart(balances: &[Money], path: &Path) -> Result<(), ChartError> {
+    if balances.len() < 2 {
+        return Err(ChartError::NoRounds);
+    }
+    let rounds = balances.len() as u32 - 1;
+    let mut peak = Money::ZERO;
+    let drawdowns: Vec<Money> = balances
+        .iter()
+        .map(|&balance| {
+            peak = peak.max(balance);
+            peak - balance
+        })
+        .collect();
+    // Plotted in whole units, so the axes can show cents.
+    let top = balances.iter().copied().max().unwrap_or_default().as_f64().max(1.0);
+    let deepest = drawdowns.iter().copied().max().unwrap_or_default().as_f64().max(1.0);
+
+    let root = BitMapBackend::new(path, (WIDTH, HEIGHT)).into_drawing_area();
+    root.fill(&WHITE).map_err(draw_error)?;
+    let (upper, lower) = root.split_vertically(HEIGHT * 2 / 3);
+
+    let mut chart = ChartBuilder::on(&upper)
+        .caption("Balance", ("sans-serif", 24))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..rounds, 0.0..top * 1.1)
+        .map_err(draw_error)?;
+    chart.configure_mesh().y_label_formatter(&|&v| Money::from_f64(v).to_string()).draw().map_err(draw_error)?;
+    chart
+        .draw_series(LineSeries::new(balances.iter().enumerate().map(|(i, b)| (i as u32, b.as_f64())), &BLUE))
+        .map_err(draw_error)?;
+
+    let mut chart = ChartBuilder::on(&lower)
+        .caption("Drawdown", ("sans-serif", 18))
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..rounds, 0.0..deepest)
+        .map_err(draw_error)?;
+    chart
+        .configure_mesh()
+        .x_desc("Round")
+        .y_label_formatter(&|&v| (-Money::from_f64(v)).to_string())
+        .draw()
+        .map_err(draw_error)?;
+    chart
+        .draw_series(AreaSeries::new(
+            drawdowns.iter().enumerate().map(|(i, d)| (i as u32, d.as_f64())),
+            0.0,
+            RED.mix(0.3),
+        ))
+        .map_err(draw_error)?;
+
+    root.present().map_err(draw_error)
+}
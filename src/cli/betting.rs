@@ -0,0 +1,604 @@
+// src/cli/betting.rs
+
+//! The betting menu: placing, noting, and loading bets, editing the wheel,
+//! and asking for another round.
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use roulette_game::{analysis, input, prompt, say};
+use roulette_game::game::Game;
+use roulette_game::game::bets::{
+    Bet, BetType, MAX_NOTE_CHARS,
+    create_black_bet, create_blue_chip_dozen_bet, create_category_bet, create_column_bet,
+    create_even_bet, create_growth_dozen_bet, create_high_bet, create_low_bet, create_odd_bet,
+    create_red_bet, create_straight_up, create_value_dozen_bet,
+};
+use roulette_game::game::chips::ChipStack;
+use roulette_game::game::comps::{self, CompReward};
+use roulette_game::game::controller::{PlayerController, Turn};
+use roulette_game::game::money::Money;
+use roulette_game::game::wheel::{PocketId, Wheel, WheelEdit};
+use roulette_game::history;
+use roulette_game::presentation;
+
+use super::{amount_prompt, describe_bet, get_amount_input, get_string_input, get_u32_input, money, read_bets_file};
+
+/// Asks `question` for a stake, first suggesting one by the Kelly criterion
+/// when `show_advice` is set and the bet type is known.
+fn ask_stake(game: &Game, show_advice: bool, bet_type: Option<BetType>, question: &str) -> Option<Money> {
+    if show_advice && let Some(bet_type) = bet_type {
+        let fraction = analysis::kelly_fraction_with(&bet_type, game.wheel_variant(), game.payouts());
+        if fraction > 0.0 {
+            let balance = game.get_player_balance().as_f64();
+            say!(
+                "Kelly suggests {} ({:.1}% of your balance); half-Kelly {}.",
+                money(game, Money::from_f64(balance * fraction)),
+                fraction * 100.0,
+                money(game, Money::from_f64(balance * fraction / 2.0))
+            );
+        } else {
+            say!("Kelly suggests not betting: this bet loses money on average.");
+        }
+    }
+    get_amount_input(&amount_prompt(question, &game.rules().currency))
+}
+
+/// The straight-up bet on `ticker`, if the wheel has it.
+fn straight_up_type(game: &Game, ticker: &str) -> Option<BetType> {
+    let id = game.wheel().find_pocket(ticker)?;
+    Some(BetType::StraightUp(game.wheel().pocket(id).ticker))
+}
+
+fn display_wheel(game: &Game) {
+    say!("\n=== Wall Street Roulette Wheel ===");
+    let pockets = game.wheel().get_all_pockets();
+    for pocket in pockets {
+        let dozen = pocket.dozen.map_or_else(|| "-".to_string(), |dozen| dozen.to_string());
+        say!(
+            "Ticker: {:<7} | Name: {:<23} | Dozen: {:<15} | {:<44} | Categories: {:?} | Color: {}",
+            pocket.ticker, pocket.display_name, dozen, pocket.metadata.to_string(), pocket.categories,
+            presentation::color(pocket.color)
+        );
+    }
+    say!("=================================");
+}
+
+/// "This $100 Category bet expects to lose $8.10 per spin." Custom bets are
+/// left out: their odds are the evaluator's business.
+fn expected_value_line(game: &Game, bet: &Bet) -> Option<String> {
+    if matches!(bet.bet_type, BetType::Custom(_)) {
+        return None;
+    }
+    let ev = analysis::expected_value(bet, game.wheel_variant(), game.payouts());
+    let kind = history::bet_type_group(&bet.bet_type);
+    let outlook = if ev < 0.0 { "lose" } else { "win" };
+    let currency = &game.rules().currency;
+    let per_spin = currency.amount(Money::from_f64(ev.abs()));
+    Some(format!("This {} {} bet expects to {} {} per spin.", currency.amount(bet.amount), kind, outlook, per_spin))
+}
+
+/// The spread of outcomes for the bets on the table, shown before the spin.
+fn show_risk(game: &Game) {
+    let bets = game.get_current_bets();
+    if bets.is_empty() {
+        return;
+    }
+    let risk = analysis::risk_profile_with(bets, game.wheel(), game.payouts());
+    say!("This round: worst {:+}, best {:+}, expected {:+}; {:.1}% chance to finish ahead.",
+        money(game, risk.worst_net), money(game, risk.best_net), money(game, Money::from_f64(risk.expected_net)),
+        risk.chance_ahead * 100.0);
+}
+
+/// Asks which standing bet to annotate and what the note says.
+fn note_bet(game: &mut Game) {
+    let count = game.get_current_bets().len();
+    if count == 0 {
+        say!("No bets on the table to add a note to.");
+        return;
+    }
+    for (i, bet) in game.get_current_bets().iter().enumerate() {
+        say!("  {}) {}", i + 1, describe_bet(bet, &game.rules().currency));
+    }
+    let Some(number) = get_u32_input(&format!("Note on which bet? (1-{}): ", count)) else { return };
+    let index = (number as usize).wrapping_sub(1);
+    if index >= count {
+        say!("There's no bet {}.", number);
+        return;
+    }
+    prompt!("Note (up to {} characters, Enter to remove): ", MAX_NOTE_CHARS);
+    let note = input::read_line().unwrap_or_default();
+    game.note_bet(index, &note);
+    say!("  - {}", describe_bet(&game.get_current_bets()[index], &game.rules().currency));
+}
+
+/// Places every bet in a file of bets, `default` unless another path is
+/// typed. Stops at the first bet the table refuses.
+fn load_bets(game: &mut Game, default: Option<&Path>) {
+    match default {
+        Some(path) => prompt!("Bets file (Enter for {}): ", path.display()),
+        None => prompt!("Bets file: "),
+    }
+    let typed = input::read_line().unwrap_or_default().trim().to_string();
+    let path = match (typed.is_empty(), default) {
+        (false, _) => PathBuf::from(typed),
+        (true, Some(path)) => path.to_path_buf(),
+        (true, None) => return,
+    };
+    let bets = match read_bets_file(&path, game.wheel()) {
+        Ok(bets) => bets,
+        Err(e) => {
+            say!("Could not read bets from {}: {}", path.display(), e);
+            return;
+        }
+    };
+    let total = bets.len();
+    let placed = bets.into_iter().take_while(|bet| game.place_bet(bet.clone())).count();
+    if placed < total {
+        say!("Placed {} of {} bets from {}; the rest weren't placed.", placed, total, path.display());
+    } else {
+        say!("Placed {} bet(s) from {}.", placed, path.display());
+    }
+    for bet in game.get_current_bets() {
+        say!("  - {}", describe_bet(bet, &game.rules().currency));
+    }
+    say!("Total Balance: {}", money(game, game.get_player_balance()));
+}
+
+/// Asks for one edit to the wheel and makes it, reporting any bets it
+/// refunded.
+fn edit_wheel(game: &mut Game) {
+    let text = |prompt: &str| {
+        prompt!("{}", prompt);
+        Some(input::read_line().unwrap_or_default().trim().to_string()).filter(|text| !text.is_empty())
+    };
+    say!("1) Add a pocket");
+    say!("2) Remove a pocket");
+    say!("3) Rename a pocket");
+    say!("4) Put a pocket in a category");
+    say!("5) Take a pocket out of a category");
+    let Some(choice) = get_u32_input("Edit number (Enter to go back): ") else {
+        return;
+    };
+    let edit = match choice {
+        1 => {
+            let (Some(number), Some(ticker), Some(name)) =
+                (get_u32_input("Pocket number: "), get_string_input("Ticker: "), text("Company name: "))
+            else {
+                return;
+            };
+            let Ok(number) = u8::try_from(number) else {
+                say!("Pocket numbers go up to {}.", u8::MAX);
+                return;
+            };
+            WheelEdit::AddPocket { number, ticker, name }
+        }
+        2 => {
+            let Some(ticker) = get_string_input("Pocket to remove (number or ticker): ") else { return };
+            WheelEdit::RemovePocket { ticker }
+        }
+        3 => {
+            let (Some(ticker), Some(new_ticker), Some(name)) = (
+                get_string_input("Pocket to rename (number or ticker): "),
+                get_string_input("New ticker: "),
+                text("New company name: "),
+            ) else {
+                return;
+            };
+            WheelEdit::RenamePocket { ticker, new_ticker, name }
+        }
+        4 | 5 => {
+            let (Some(ticker), Some(category)) = (get_string_input("Pocket (number or ticker): "), text("Category: ")) else {
+                return;
+            };
+            if choice == 4 { WheelEdit::AddCategory { ticker, category } } else { WheelEdit::RemoveCategory { ticker, category } }
+        }
+        _ => {
+            say!("No such edit.");
+            return;
+        }
+    };
+    match game.edit_wheel(edit.clone()) {
+        Ok(refunded) => {
+            say!("Wheel edited: {}.", edit);
+            for bet in &refunded {
+                say!("  - {} can no longer win and was refunded.", describe_bet(bet, &game.rules().currency));
+            }
+        }
+        Err(e) => say!("Can't edit the wheel: {}.", e),
+    }
+}
+
+/// Offers the comp rewards and redeems the one picked.
+fn redeem_comps(game: &mut Game) {
+    say!("You have {} comp points, earned at {} per dollar wagered.", game.comp_points(), comps::POINTS_PER_DOLLAR);
+    for (i, reward) in CompReward::MENU.iter().enumerate() {
+        say!("{}) {} for {} points", i + 1, reward.describe(&game.rules().currency), reward.cost());
+    }
+    let Some(choice) = get_u32_input("Reward number (Enter to go back): ") else {
+        return;
+    };
+    let Some(reward) = (choice as usize).checked_sub(1).and_then(|i| CompReward::MENU.get(i)) else {
+        say!("No such reward.");
+        return;
+    };
+    if let Err(e) = game.redeem_comps(*reward) {
+        say!("Can't redeem: {}", e);
+    }
+}
+
+/// What each bet pays at this table and how much of every stake the house
+/// keeps on average, worst bets last.
+fn show_odds(game: &Game) {
+    let report = game.wheel().verify_payouts(game.payouts());
+    let mut lines: Vec<_> = report.lines.iter().collect();
+    lines.sort_by(|a, b| a.house_edge_percent().total_cmp(&b.house_edge_percent()));
+    say!("\n=== Odds on the {} wheel ({} pockets) ===", game.wheel_variant().name(), report.pockets);
+    say!("{:<36} {:>7} {:>6} {:>11}", "Bet", "Covers", "Pays", "House edge");
+    for line in lines {
+        say!("{:<36} {:>7} {:>4}:1 {:>10.2}%", line.label, line.pockets_covered, line.multiplier, line.house_edge_percent());
+    }
+    say!("Category bets pay the same whatever their size, so small categories cost the most.");
+}
+
+/// Asks the croupier which pocket the physical wheel landed on.
+pub fn get_croupier_pocket(wheel: &Wheel) -> PocketId {
+    loop {
+        if let Some(input) = get_string_input("Enter the winning pocket (number or ticker): ") {
+            match wheel.find_pocket(&input) {
+                Some(id) => return id,
+                None => say!("No pocket matches '{}'. Check the wheel and try again.", input),
+            }
+        }
+    }
+}
+
+/// Runs the betting menu for one round. With `allow_empty`, the player may
+/// finish without placing anything (e.g. a bettor sitting out a spin). With
+/// `show_ev`, each accepted bet is followed by what it expects to win or lose,
+/// and the finished round by its range of outcomes. With `chips`, the stack is
+/// shown and can be colored up. With `countdown`, betting closes when it runs
+/// out and the wheel spins with whatever is on the table. With `wheel_editor`,
+/// the wheel's pockets can be edited between bets.
+pub fn handle_betting(
+    game: &mut Game,
+    allow_empty: bool,
+    show_ev: bool,
+    chips: Option<&RefCell<ChipStack>>,
+    countdown: Option<Duration>,
+    bets_file: Option<&Path>,
+    wheel_editor: bool,
+) {
+    say!("\n--- Place Your Wall Street Bets ---");
+    if let Some(countdown) = countdown {
+        say!("You have {} seconds to bet.", countdown.as_secs());
+        input::set_deadline(Some(Instant::now() + countdown));
+    }
+    say!("Current Balance: {}", money(game, game.get_player_balance()));
+    if let Some(chips) = chips {
+        say!("Chips: {}", chips.borrow().describe(&game.rules().currency));
+    }
+    say!("Enter bet type number and follow prompts. Press Enter with no input to finish betting.");
+    display_wheel(game); // Show the wheel's stocks and categories
+
+    loop {
+        if betting_closed(game, show_ev) {
+            break;
+        }
+        say!("\nAvailable Bet Types:");
+        say!(" 1) Straight Up (Single Stock Ticker, e.g., AAPL)");
+        say!(" 2) Category (e.g., Magnificent Seven, Technology, High Beta)");
+        say!(" 3) Growth Dozen (Growth Stocks)");
+        say!(" 4) Value Dozen (Value Stocks)");
+        say!(" 5) Blue Chip Dozen (Blue Chip Stocks)");
+        say!(" 6) Red");
+        say!(" 7) Black");
+        say!(" 8) Odd");
+        say!(" 9) Even");
+        say!("10) Low (1-18)");
+        say!("11) High (19-36)");
+        say!("12) Column (a group of sectors)");
+        for (number, column) in game.wheel().columns().iter().enumerate() {
+            let tickers: Vec<&str> = column.members().iter().map(|id| game.wheel().pocket(id).ticker.as_str()).collect();
+            say!("      {}: {} ({})", number + 1, column.name, tickers.join(", "));
+        }
+        say!("13) Clear All Bets for this Round");
+        say!("14) Rebet Last Round's Bets");
+        say!("15) Show Odds and House Edge");
+        say!("16) Redeem Comp Points ({} available)", game.comp_points());
+        if chips.is_some() {
+            say!("17) Color Up Chips");
+        }
+        say!("18) Add a Note to a Bet");
+        say!("19) Load Bets from a File");
+        if wheel_editor {
+            say!("20) Edit the Wheel");
+        }
+        say!(" 0) Finish Betting for this Round");
+
+        let choice = get_u32_input("Enter bet type number (or 0 to spin): ").unwrap_or_default();
+        if betting_closed(game, show_ev) {
+            break;
+        }
+
+        let mut bet_to_place: Option<Bet> = None;
+
+        match choice {
+            1 => {
+                if let Some(ticker) = get_string_input("Enter stock ticker (e.g., AAPL): ")
+                    && let Some(amount) = ask_stake(game, show_ev, straight_up_type(game, &ticker), "Enter amount to bet")
+                {
+                    if amount > Money::ZERO {
+                        bet_to_place = create_straight_up(&ticker, amount, game.wheel());
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            2 => {
+                if let Some(category) = get_string_input("Enter category (e.g., Magnificent Seven): ")
+                    && let Some(amount) =
+                        ask_stake(game, show_ev, game.wheel().category_id(&category).map(BetType::Category), "Enter amount to bet")
+                {
+                    if amount > Money::ZERO {
+                        bet_to_place = create_category_bet(&category, amount, game.wheel());
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            3 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::GrowthDozen), "Enter amount to bet on Growth Dozen") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_growth_dozen_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            4 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::ValueDozen), "Enter amount to bet on Value Dozen") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_value_dozen_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            5 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::BlueChipDozen), "Enter amount to bet on Blue Chip Dozen") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_blue_chip_dozen_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            6 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::Red), "Enter amount to bet on Red") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_red_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            7 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::Black), "Enter amount to bet on Black") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_black_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            8 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::Odd), "Enter amount to bet on Odd") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_odd_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            9 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::Even), "Enter amount to bet on Even") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_even_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            10 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::Low), "Enter amount to bet on Low (1-18)") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_low_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            11 => {
+                if let Some(amount) = ask_stake(game, show_ev, Some(BetType::High), "Enter amount to bet on High (19-36)") {
+                    if amount > Money::ZERO {
+                        bet_to_place = Some(create_high_bet(amount));
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            12 => {
+                if let Some(col) = get_u32_input("Enter column number (1, 2, or 3): ").map(|x| x as u8)
+                    && let Some(amount) = ask_stake(game, show_ev, Some(BetType::Column(col)), "Enter amount to bet")
+                {
+                    if amount > Money::ZERO {
+                        bet_to_place = create_column_bet(col, amount);
+                    } else {
+                        say!("Bet amount must be greater than 0.");
+                    }
+                }
+            }
+            13 => {
+                game.clear_bets();
+                continue;
+            }
+            14 => {
+                match game.rebet() {
+                    Ok(placed) => say!("Rebet {} bet(s). Total Balance: {}", placed, money(game, game.get_player_balance())),
+                    Err(e) => say!("Can't rebet: {}", e),
+                }
+                continue;
+            }
+            15 => {
+                show_odds(game);
+                continue;
+            }
+            16 => {
+                redeem_comps(game);
+                continue;
+            }
+            17 => {
+                match chips {
+                    Some(chips) => {
+                        let mut stack = chips.borrow_mut();
+                        let (before, after) = stack.color_up();
+                        say!("Colored up {} chips into {}: {}", before, after, stack);
+                    }
+                    None => say!("Invalid choice. Please try again."),
+                }
+                continue;
+            }
+            18 => {
+                note_bet(game);
+                continue;
+            }
+            19 => {
+                load_bets(game, bets_file);
+                continue;
+            }
+            20 if wheel_editor => {
+                edit_wheel(game);
+                continue;
+            }
+            0 => {
+                if !game.has_bets_in_play() && !allow_empty {
+                    say!("No bets placed. Place at least one bet before spinning.");
+                    continue;
+                }
+                say!("--- Betting Finished ---");
+                if show_ev {
+                    show_risk(game);
+                }
+                break;
+            }
+            _ => {
+                say!("Invalid choice. Please try again.");
+            }
+        }
+
+        if let Some(bet) = bet_to_place
+            && game.place_bet(bet.clone())
+        {
+            if show_ev && let Some(line) = expected_value_line(game, &bet) {
+                say!("{}", line);
+            }
+            say!("Current Bets Placed:");
+            for placed_bet in game.get_current_bets() {
+                say!("  - {}", describe_bet(placed_bet, &game.rules().currency));
+            }
+            say!("Total Balance: {}", money(game, game.get_player_balance()));
+        }
+
+        if game.get_player_balance().is_zero() && !game.get_current_bets().is_empty() {
+            say!("You've bet your remaining balance!");
+            say!("--- Betting Finished ---");
+            if show_ev {
+                show_risk(game);
+            }
+            break;
+        }
+    }
+    input::set_deadline(None);
+}
+
+/// True once the betting countdown has run out, which it announces.
+fn betting_closed(game: &Game, show_ev: bool) -> bool {
+    if input::time_left().is_none_or(|left| !left.is_zero()) {
+        return false;
+    }
+    say!("\nNo more bets! Time's up.");
+    if show_ev && game.has_bets_in_play() {
+        show_risk(game);
+    }
+    true
+}
+
+/// What the player answered when asked to play another round.
+pub enum Again {
+    Play,
+    /// Void the last spin and bet again on the corrected one.
+    Void,
+    Stop,
+}
+
+/// Asks whether to play another round, mentioning voids when the table
+/// allows them.
+pub fn ask_play_again(can_void: bool) -> Again {
+    if can_void {
+        prompt!("Play another round? (y/n, v to void the last spin): ");
+    } else {
+        prompt!("Play another round? (y/n): ");
+    }
+    match input::read_line().unwrap_or_default().trim().to_lowercase().as_str() {
+        "y" => Again::Play,
+        "v" => Again::Void,
+        _ => Again::Stop,
+    }
+}
+
+/// The person at the keyboard, betting through the menu.
+pub struct HumanController {
+    pub show_ev: bool,
+    /// The player's chips, when the table plays with them.
+    pub chips: Option<Rc<RefCell<ChipStack>>>,
+    /// Time allowed for betting each round.
+    pub countdown: Option<Duration>,
+    /// Where "Load Bets from a File" looks unless told otherwise.
+    pub bets_file: Option<PathBuf>,
+    pub wheel_editor: bool,
+}
+
+impl PlayerController for HumanController {
+    fn name(&self) -> &str {
+        "manual"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        handle_betting(
+            game,
+            false,
+            self.show_ev,
+            self.chips.as_deref(),
+            self.countdown,
+            self.bets_file.as_deref(),
+            self.wheel_editor,
+        );
+        Turn::Play
+    }
+
+    fn play_again(&mut self, game: &mut Game) -> bool {
+        match ask_play_again(game.voids_allowed()) {
+            Again::Play => true,
+            Again::Void => {
+                if let Err(e) = game.void_last_spin() {
+                    say!("Can't void: {}", e);
+                }
+                true
+            }
+            Again::Stop => false,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// src/cli/export.rs
+
+//! `export` and `chart`: recorded rounds written out as CSV, or drawn as a
+//! balance chart.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[cfg(feature = "charts")]
+use roulette_game::say;
+use roulette_game::game::money::Money;
+use roulette_game::history::{self, RoundStore};
+
+use super::load_config;
+use super::options::{Options, usage};
+
+/// Recorded rounds of the `--session` asked for, or of the latest one.
+/// Exits with a message if there are none.
+pub fn session_records(options: &Options) -> Vec<history::RoundRecord> {
+    let store = RoundStore::default_location();
+    let mut records = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        process::exit(1);
+    });
+    let latest = records.last().map(|r| r.session.clone());
+    let Some(session) = options.session.clone().or(latest) else {
+        eprintln!("No rounds recorded yet.");
+        process::exit(1);
+    };
+    records.retain(|r| r.session == session);
+    if records.is_empty() {
+        eprintln!("No rounds recorded for session {}.", session);
+        process::exit(1);
+    }
+    records
+}
+
+pub fn run_export(options: &Options) {
+    let Some(format) = &options.format else {
+        eprintln!("export needs --format csv or --format hands.");
+        usage();
+    };
+    let mut records = if options.all_time {
+        let store = RoundStore::default_location();
+        store.load_all().unwrap_or_else(|e| {
+            eprintln!("Could not read round history {}: {}", store.path().display(), e);
+            process::exit(1);
+        })
+    } else {
+        session_records(options)
+    };
+    if let Some(rounds) = &options.history_filter.rounds {
+        records.retain(|record| rounds.contains(&record.round));
+    }
+    let write = |out: &mut dyn Write| match format.as_str() {
+        "hands" => history::hand::write_hands(&records, out),
+        _ => history::write_csv(&records, out),
+    };
+    let written = match &options.output {
+        Some(path) => File::create(path).and_then(|file| write(&mut BufWriter::new(file))),
+        None => write(&mut io::stdout().lock()),
+    };
+    if let Err(e) = written {
+        eprintln!("Could not export: {}", e);
+        process::exit(1);
+    }
+}
+
+pub fn run_chart(options: &Options) {
+    let config = load_config(options);
+    let records = session_records(options);
+    let rounds: Vec<&history::RoundRecord> = history::without_voided(&records).collect();
+    let Some(first) = rounds.first() else {
+        eprintln!("Every round of that session was voided.");
+        process::exit(1);
+    };
+    let mut balances = vec![first.balance_after + first.wagered() - first.returned()];
+    balances.extend(rounds.iter().map(|r| r.balance_after));
+    let path = options.output.as_ref().map(PathBuf::from).or(config.chart).unwrap_or_else(|| PathBuf::from("balance.png"));
+    save_chart(&balances, &path);
+}
+
+#[cfg(feature = "charts")]
+pub fn save_chart(balances: &[Money], path: &Path) {
+    match roulette_game::charts::balance_chart(balances, path) {
+        Ok(()) => say!("Balance chart saved to {}.", path.display()),
+        Err(e) => eprintln!("Could not save {}: {}", path.display(), e),
+    }
+}
+
+#[cfg(not(feature = "charts"))]
+pub fn save_chart(_balances: &[Money], _path: &Path) {
+    eprintln!("This build has no chart support (enable the `charts` feature).");
+}
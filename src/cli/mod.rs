@@ -0,0 +1,237 @@
+// src/cli/mod.rs
+
+//! The terminal game's commands, one module to a family of them, and the
+//! prompts and config loading they share.
+
+pub mod betting;
+pub mod export;
+pub mod options;
+pub mod play;
+pub mod profiles;
+pub mod simulate;
+pub mod stats;
+pub mod tables;
+pub mod verify;
+
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process;
+use std::sync::Arc;
+
+use roulette_game::{input, prompt, say};
+use roulette_game::accessibility::ScreenReader;
+use roulette_game::config::{self, Config};
+use roulette_game::game::{Game, dsl};
+use roulette_game::game::bets::Bet;
+use roulette_game::game::currency::{Amount, Currency, ExchangeRates};
+use roulette_game::game::money::{Money, MoneyError};
+use roulette_game::game::rules::TableRules;
+use roulette_game::game::strategy::DeclarativeStrategy;
+use roulette_game::game::wheel::{Wheel, WheelLike};
+use roulette_game::history;
+use roulette_game::presentation;
+use roulette_game::renderer::{self, RendererKind};
+
+use options::Options;
+
+pub fn get_u32_input(prompt: &str) -> Option<u32> {
+    loop {
+        prompt!("{}", prompt);
+        let input = input::read_line().unwrap_or_default();
+        match input.trim().parse::<u32>() {
+            Ok(num) => return Some(num),
+            Err(_) => {
+                if input.trim().is_empty() {
+                    return None;
+                }
+                say!("Invalid input. Please enter a valid positive number.");
+            }
+        }
+    }
+}
+
+/// Reads an amount of money, such as `25`, `12.50` or `$0.75`.
+pub fn get_amount_input(prompt: &str) -> Option<Money> {
+    loop {
+        prompt!("{}", prompt);
+        let input = input::read_line().unwrap_or_default();
+        if input.trim().is_empty() {
+            return None;
+        }
+        match Money::parse(&input) {
+            Ok(money) => return Some(money),
+            Err(e @ MoneyError::TooLarge) => say!("Invalid input: {}.", e),
+            Err(e) => say!("Invalid input: {}. Please enter an amount such as 25 or 12.50.", e),
+        }
+    }
+}
+
+/// "Enter amount to bet: $", or "Enter amount to bet in MBTC: " for a
+/// currency without a sign.
+pub fn amount_prompt(question: &str, currency: &Currency) -> String {
+    match currency.sign() {
+        Some(sign) => format!("{}: {}", question, sign),
+        None => format!("{} in {}: ", question, currency),
+    }
+}
+
+pub fn get_string_input(prompt: &str) -> Option<String> {
+    prompt!("{}", prompt);
+    let input = input::read_line().unwrap_or_default();
+    let trimmed = input.trim().to_uppercase();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed)
+    }
+}
+
+/// "Red for $10", with the bet's note after it if it has one.
+pub fn describe_bet(bet: &Bet, currency: &Currency) -> String {
+    match &bet.note {
+        Some(note) => format!("{} for {} ({})", bet.bet_type, currency.amount(bet.amount), note),
+        None => format!("{} for {}", bet.bet_type, currency.amount(bet.amount)),
+    }
+}
+
+/// `value` in the currency `game` plays in.
+pub fn money(game: &Game, value: impl Into<Money>) -> Amount {
+    game.rules().currency.amount(value)
+}
+
+pub fn load_config(options: &Options) -> Config {
+    let loaded = match &options.config {
+        Some(path) => Config::load(Path::new(path)),
+        None => Config::load_default(),
+    };
+    let mut config = loaded.unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    override_config(options, &mut config);
+    if let Err(e) = config.load_wheel_file() {
+        eprintln!("Can't play on the wheel file: {}", e);
+        process::exit(1);
+    }
+    if let Some(scheme) = options.palette {
+        config.palette.scheme = scheme;
+    }
+    config.palette.symbols |= options.color_symbols;
+    presentation::set_palette(config.palette);
+    if options.accessible || config.accessible {
+        renderer::set_renderer(Arc::new(ScreenReader));
+    } else {
+        let terminal = if options.events.as_deref() == Some("-") { io::stderr().is_terminal() } else { io::stdout().is_terminal() };
+        let kind = options.renderer.or(config.renderer).unwrap_or_else(|| RendererKind::detect(terminal));
+        renderer::set_renderer(kind.build());
+    }
+    config
+}
+
+/// The table rules the settings make, or exits if a bet would favour the
+/// player under them.
+pub fn table_rules(config: &Config) -> TableRules {
+    config.table_rules().unwrap_or_else(|e| {
+        eprintln!("Can't set up the table: {}.", e);
+        process::exit(1);
+    })
+}
+
+/// Puts the flags that override the game's settings in the config's place.
+pub fn override_config(options: &Options, config: &mut Config) {
+    if let Some(kind) = options.spin_model {
+        config.spin_model = kind;
+    }
+    if let Some(kind) = options.wheel {
+        config.wheel = kind;
+        config.wheel_file = None;
+    }
+    if let Some(path) = &options.wheel_file {
+        config.wheel_file = Some(path.clone());
+    }
+    if let Some(level) = options.difficulty {
+        config.difficulty = level;
+    }
+}
+
+pub fn build_wheel(config: &Config) -> Box<dyn WheelLike> {
+    config.build_wheel().unwrap_or_else(|e| {
+        eprintln!("Can't use the configured wheel weights: {}", e);
+        process::exit(1);
+    })
+}
+
+/// `records` in `currency`, converted at `rates` so rounds from tables in
+/// other currencies add up. Says how many had no rate and were left out.
+pub fn in_currency(records: &[history::RoundRecord], currency: &Currency, rates: &ExchangeRates) -> Vec<history::RoundRecord> {
+    let (converted, left_out) = history::in_currency(records, currency, rates);
+    if left_out > 0 {
+        say!("Left out {} round(s) in currencies with no rate in [exchange_rates].", left_out);
+    }
+    converted
+}
+
+#[cfg(feature = "scripting")]
+pub fn load_rules(game: &mut Game, path: &str) {
+    let source = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Could not read rules script {}: {}", path, e);
+        process::exit(1);
+    });
+    if let Err(e) = game.load_rules_script(&source) {
+        eprintln!("Rules script {}: {}", path, e);
+        process::exit(1);
+    }
+    let promotions = game.custom_bets().names();
+    if !promotions.is_empty() {
+        say!("House promotions: {} (bet with 'custom <name> <amount>')", promotions.join(", "));
+    }
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn load_rules(_game: &mut Game, _path: &str) {
+    eprintln!("This build has no rules scripting support (enable the `scripting` feature).");
+    process::exit(2);
+}
+
+/// Parses every `--bet` option and the `--bets` file, exiting with a
+/// message on the first bad one.
+pub fn parse_bet_options(options: &Options, wheel: &Wheel) -> Vec<Bet> {
+    let mut bets: Vec<Bet> = options
+        .bets
+        .iter()
+        .map(|spec| {
+            dsl::parse_bet(spec, wheel).unwrap_or_else(|e| {
+                eprintln!("Invalid bet '{}': {}", spec, e);
+                process::exit(2);
+            })
+        })
+        .collect();
+    if let Some(path) = &options.bets_file {
+        bets.extend(read_bets_file(path, wheel).unwrap_or_else(|e| {
+            eprintln!("Could not read bets from {}: {}", path.display(), e);
+            process::exit(2);
+        }));
+    }
+    bets
+}
+
+/// The bets in a file of bets; see [`dsl::parse_bets`].
+pub fn read_bets_file(path: &Path, wheel: &Wheel) -> Result<Vec<Bet>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    dsl::parse_bets(&text, wheel).map_err(|e| e.to_string())
+}
+
+/// Loads the `--strategy` file, if given, exiting with a message if it
+/// can't be used.
+pub fn load_strategy_option(options: &Options, wheel: &Wheel) -> Option<DeclarativeStrategy> {
+    let path = options.strategy.as_ref()?;
+    let spec = config::load_strategy(Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let strategy = DeclarativeStrategy::new(&spec, wheel).unwrap_or_else(|e| {
+        eprintln!("Invalid bet in strategy {}: {}", path, e);
+        process::exit(2);
+    });
+    Some(strategy)
+}
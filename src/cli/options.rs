@@ -0,0 +1,577 @@
+// src/cli/options.rs
+
+//! The command line: which command to run and the flags it runs with, read
+//! from the arguments and the `ROULETTE_*` environment.
+
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use roulette_game::config::{SpinModelKind, WheelKind};
+use roulette_game::game::currency::Currency;
+use roulette_game::game::house::HouseConfig;
+use roulette_game::game::money::Money;
+use roulette_game::game::rules::Difficulty;
+use roulette_game::history;
+use roulette_game::optimize::{Objective, SearchSpace};
+use roulette_game::presentation::Scheme;
+use roulette_game::profile::LeaderboardMetric;
+use roulette_game::renderer::RendererKind;
+use roulette_game::simulation::SimulationConfig;
+use roulette_game::time_attack::AttackLimit;
+
+/// What the binary was asked to do.
+#[derive(Default)]
+pub enum Command {
+    /// Play interactively (the default).
+    #[default]
+    Play,
+    /// Print the profile leaderboard ranked by a metric.
+    Leaderboard(LeaderboardMetric),
+    /// Print statistics aggregated over every recorded round.
+    Stats,
+    /// Write recorded rounds out for other tools.
+    Export,
+    /// Draw a recorded session's balance to a PNG.
+    Chart,
+    /// Set a recorded session beside another, or beside the average session.
+    Compare,
+    /// Run a Monte Carlo simulation of a fixed bet layout.
+    Simulate,
+    /// Search a strategy file's stake and stop settings for the best ones.
+    Optimize,
+    /// Measure spin and settlement throughput.
+    Bench,
+    /// Check a revealed provably fair spin against its commitment.
+    Verify,
+    /// Print the expected return and house edge of every bet.
+    Payouts,
+    /// Bank a table of bots.
+    House,
+    /// Test the recorded spins for a biased wheel.
+    Bias,
+    /// List pockets and categories by how long since they last hit.
+    Due,
+    /// Re-run a recorded session and check its settlements.
+    Replay,
+    /// Spin many times and check the results fit the wheel's odds.
+    SelfTest,
+    /// List the built-in challenges.
+    Challenges,
+    /// List the tables, and which the profile may sit at.
+    Tables,
+    /// Show a profile's balances, or change money from one currency to another.
+    Exchange,
+    /// Check a wheel file for mistakes.
+    LintWheel,
+}
+
+/// Command-line options.
+#[derive(Default)]
+pub struct Options {
+    pub command: Command,
+    /// Profile whose lifetime stats this session counts toward.
+    pub profile: Option<String>,
+    /// Where profiles are stored: a directory, `sqlite:PATH`, or an HTTP URL.
+    pub profile_store: Option<String>,
+    /// Don't record rounds to the cross-session history store.
+    pub no_history: bool,
+    pub no_journal: bool,
+    pub encrypt: bool,
+    /// The journal or events `replay` and `verify` read, or the wheel file
+    /// `lint-wheel` checks.
+    pub input: Option<String>,
+    /// Hand histories for `verify` to check.
+    pub hands: Option<String>,
+    /// The session `compare` sets beside `--session`.
+    pub against: Option<String>,
+    pub step: bool,
+    /// Where to write NDJSON game events; `-` means stdout.
+    pub events: Option<String>,
+    /// Take winning pockets from a physical wheel instead of simulating spins.
+    pub croupier: bool,
+    /// Names of the bettors at a croupier-mode table.
+    pub bettors: Vec<String>,
+    /// Raise desktop notifications for notable events.
+    pub notify: bool,
+    /// Net profit on a single bet that counts as a big win.
+    pub notify_big_win: Option<Money>,
+    /// Minimum payout multiplier that counts as a jackpot.
+    pub notify_jackpot: Option<u32>,
+    /// No sound effects this session, whatever the config says.
+    pub mute: bool,
+    /// Simulation settings; the seed is chosen at random when not given.
+    pub simulation: SimulationConfig,
+    /// Starting balance for play without asking, and for `simulate`,
+    /// `optimize`, and `house` in place of theirs.
+    pub balance: Option<Money>,
+    pub seed: Option<u64>,
+    /// Bets to simulate, in the bet syntax (e.g. `red 10`).
+    pub bets: Vec<String>,
+    /// A file of bets in the bet syntax, one per line, placed along with
+    /// `--bet` and loaded from the betting menu.
+    pub bets_file: Option<PathBuf>,
+    /// File format for `export`; only `csv` so far.
+    pub format: Option<String>,
+    /// Session `export` or `chart` reads; the most recent one when not given.
+    pub session: Option<String>,
+    /// Export every recorded session rather than one.
+    pub all_time: bool,
+    /// Where `stats` writes its JSON export; `-` means stdout.
+    pub stats_export: Option<String>,
+    /// Which rounds and bets `stats` counts; `export` keeps to its rounds.
+    pub history_filter: history::Filter,
+    /// PNG to draw this session's balance to when it ends.
+    pub chart: Option<String>,
+    /// File the balance audit log, and any wheel edits, are written to, one
+    /// JSON entry per line, when the session ends.
+    pub audit_log: Option<String>,
+    /// Where `export` and `chart` write; for `export`, stdout when not given.
+    pub output: Option<String>,
+    /// Strategy file to simulate or autoplay instead of fixed bets.
+    pub strategy: Option<String>,
+    /// Values `optimize` tries, and how it ranks and lists them.
+    pub search: SearchSpace,
+    pub objective: Objective,
+    pub top: Option<usize>,
+    /// Spins measured by `bench`.
+    pub bench_spins: Option<u64>,
+    /// Commit to every spin's outcome before bets and reveal it afterwards.
+    pub fair: bool,
+    /// Player seed mixed into fair spins; random when not given.
+    pub client_seed: Option<String>,
+    /// Revealed spin checked by `verify`.
+    pub server_seed: Option<String>,
+    pub commitment: Option<String>,
+    pub nonce: Option<u64>,
+    /// Pocket (ticker or number) `verify` should confirm.
+    pub pocket: Option<String>,
+    /// Settings file to use instead of the default one.
+    pub config: Option<String>,
+    /// Overrides the config file's spin model.
+    pub spin_model: Option<SpinModelKind>,
+    /// Overrides the config file's wheel layout.
+    pub wheel: Option<WheelKind>,
+    /// Plays on a wheel file instead of the config file's wheel.
+    pub wheel_file: Option<PathBuf>,
+    /// Show the ball passing pockets while the wheel spins.
+    pub animate: bool,
+    /// Plain output for screen readers.
+    pub accessible: bool,
+    /// Overrides the config file's colour scheme.
+    pub palette: Option<Scheme>,
+    /// Mark pocket colours with letters, whatever the config says.
+    pub color_symbols: bool,
+    /// Overrides the config file's renderer and the detected one.
+    pub renderer: Option<RendererKind>,
+    /// Time allowed for betting each round.
+    pub countdown: Option<Duration>,
+    /// Play at several tables from one wallet.
+    pub multi_table: bool,
+    /// After the first round, rebet and spin on without asking.
+    pub turbo: bool,
+    /// Pause between turbo spins.
+    pub turbo_delay: Option<Duration>,
+    /// End turbo once this much of the balance is gone.
+    pub stop_loss: Option<Money>,
+    /// Let a bot play the `--bet` layout or `--strategy` for this many rounds.
+    pub autoplay: Option<u32>,
+    /// Play the autoplay bot's rounds slowly enough to watch.
+    pub spectate: bool,
+    /// Pause between spectated rounds.
+    pub spectate_delay: Option<Duration>,
+    /// Wait for a remote player on this address and let them play.
+    pub remote: Option<String>,
+    /// Play by the machine protocol on stdin (`Some(true)`) or answer menus
+    /// (`Some(false)`); by default, the protocol whenever stdin isn't a terminal.
+    pub machine: Option<bool>,
+    /// Offer pocket and category edits to the wheel in the betting menu.
+    pub wheel_editor: bool,
+    /// Table settings for `house`; rounds, bot balance, and seed come from
+    /// the simulation options.
+    pub house: HouseConfig,
+    /// Rhai house-rules script (scripting builds only).
+    pub rules: Option<String>,
+    /// Play on a secretly biased wheel, its favoured pockets this many
+    /// times as likely as the rest.
+    pub training: Option<f64>,
+    /// Play against the clock or a round count, scored on profit and risk.
+    pub time_attack: Option<AttackLimit>,
+    /// Built-in challenge id or challenge file to play.
+    pub challenge: Option<String>,
+    /// Overrides the configured difficulty.
+    pub difficulty: Option<Difficulty>,
+    /// Walk through each kind of bet before the real game.
+    pub tutorial: bool,
+    /// Play money that tops itself up, kept out of profiles and history.
+    pub practice: bool,
+    /// Play the profile's next campaign stage.
+    pub campaign: bool,
+    /// Id of the table to sit at.
+    pub table: Option<String>,
+    /// Chips to buy at the table; the rest of the balance stays in the wallet.
+    pub buy_in: Option<Money>,
+    /// Overrides the currency the table plays in.
+    pub currency: Option<Currency>,
+    /// What `exchange` changes: an amount, and the currencies from and to.
+    pub exchange_amount: Option<Money>,
+    pub exchange_from: Option<Currency>,
+    pub exchange_to: Option<Currency>,
+    /// Rig every spin to land on this pocket number (demo builds only).
+    #[cfg(feature = "forced-outcome")]
+    pub force_pocket: Option<u8>,
+}
+
+pub fn usage() -> ! {
+    eprintln!("Usage: roulette_game leaderboard [--by peak|win|rounds|attack|roi] [--profile-store <DIR|sqlite:PATH|URL>]");
+    eprintln!("       roulette_game challenges [--profile <NAME>] [--profile-store <DIR|sqlite:PATH|URL>]");
+    eprintln!("       roulette_game tables [--profile <NAME>] [--profile-store <DIR|sqlite:PATH|URL>]");
+    eprintln!("       roulette_game exchange --profile <NAME> [--amount <N> --from <CURRENCY> --to <CURRENCY>] [--config <FILE>] [--profile-store <DIR|sqlite:PATH|URL>]");
+    eprintln!("       roulette_game stats [--all-time] [--practice] [--export <FILE|->] [--session <ID>] [--bet-type <TYPE>] [--outcome win|loss|push|surrender|released] [--ticker <TICKER>] [--round <N|A-B>] [--since <YYYY-MM-DD>] [--until <YYYY-MM-DD>]");
+    eprintln!("       roulette_game export --format csv|hands [--session <ID> | --all-time] [--round <N|A-B>] [--output <FILE>]");
+    eprintln!("       roulette_game chart [--session <ID>] [--output <FILE>]");
+    eprintln!("       roulette_game compare [--session <ID>] [--against <ID>] [--practice]");
+    eprintln!("       roulette_game payouts [--wheel european|american|mini|<FILE>] [--difficulty easy|normal|brutal]");
+    eprintln!("       roulette_game bias [--wheel european|american|mini|<FILE>]");
+    eprintln!("       roulette_game lint-wheel <FILE>");
+    eprintln!("       roulette_game due [--top <N>] [--wheel european|american|mini|<FILE>]");
+    eprintln!("       roulette_game replay [--input <JOURNAL|EVENTS>] [--step] [--config <FILE>] [--rules <SCRIPT>] [--wheel <WHEEL|FILE>] [--difficulty easy|normal|brutal]");
+    eprintln!("       roulette_game house [--bots <N>] [--bankroll <AMOUNT>] [--balance <AMOUNT>] [--min-bet <AMOUNT>] [--max-bet <AMOUNT>] [--rounds <N>] [--seed <N>] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game selftest [--spins <N>] [--seed <N>] [--spin-model uniform|physics] [--wheel <WHEEL|FILE>] [--config <FILE>]");
+    eprintln!("       roulette_game bench [--spins <N>] [--bet <BET>...] [--bets <FILE>] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game verify (--input <JOURNAL|EVENTS> | --session <ID>) [--spin-model uniform|physics] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game verify --hands <FILE|-> [--wheel <WHEEL|FILE>] [--bet-type <TYPE>] [--outcome win|loss|push|surrender|released] [--ticker <TICKER>] [--round <N|A-B>] [--since <YYYY-MM-DD>] [--until <YYYY-MM-DD>]");
+    eprintln!("       roulette_game verify --server-seed <SEED> --client-seed <SEED> --nonce <N> --commitment <HASH> [--pocket <TICKER|NUMBER>] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game optimize --strategy <FILE> [--base-stakes <N,N,...>] [--max-stakes <N|none,...>] [--stop-losses <N|none,...>] [--rank mean|ahead|bust] [--top <N>] [--trials <N>] [--rounds <N>] [--balance <AMOUNT>] [--seed <N>] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game simulate (--bet <BET>... | --bets <FILE> | --strategy <FILE>) [--trials <N>] [--rounds <N>] [--balance <AMOUNT>] [--seed <N>] [--wheel <WHEEL|FILE>]");
+    eprintln!("       roulette_game [--tutorial] [--practice | --profile <NAME> [--encrypt]] [--profile-store <DIR|sqlite:PATH|URL>] [--no-history] [--no-journal] [--croupier [--bettors <NAME,NAME,...>]] [--events <FILE|->] [--config <FILE>] [--spin-model uniform|physics] [--wheel <WHEEL|FILE>] [--difficulty easy|normal|brutal] [--table <ID>] [--currency <CODE>] [--buy-in <AMOUNT>] [--rules <SCRIPT>] [--animate] [--accessible] [--palette classic|color_blind|high_contrast] [--color-symbols] [--renderer plain|ansi|tui] [--chart <FILE>] [--audit-log <FILE>] [--training [--bias-strength <X>]] [--bets <FILE>] [--autoplay <ROUNDS> (--bet <BET>... | --bets <FILE> | --strategy <FILE>) [--spectate [--spectate-delay <MS>]] | --remote <ADDR> | --turbo [--turbo-delay <MS>] [--stop-loss <AMOUNT>]] [--countdown <SECONDS>] [--multi-table] [--balance <AMOUNT>] [--wheel-editor] [--machine | --interactive] [--fair [--client-seed <SEED>]] [--time-attack <5m|90s|30r> | --challenge <ID|FILE> | --campaign] [--notify [--notify-big-win <AMOUNT>] [--notify-jackpot <MULTIPLIER>]] [--mute]");
+    eprintln!("Environment: {} stand in for {} when those flags aren't given.",
+        ENV_FLAGS.map(|(name, _)| name).join(", "), ENV_FLAGS.map(|(_, flag)| flag).join(", "));
+    process::exit(2);
+}
+
+/// Environment variables standing in for flags, for deployments where
+/// editing files isn't an option. A flag on the command line overrides its
+/// variable, and either overrides the config file.
+const ENV_FLAGS: [(&str, &str); 5] = [
+    ("ROULETTE_BALANCE", "--balance"),
+    ("ROULETTE_SEED", "--seed"),
+    ("ROULETTE_WHEEL", "--wheel"),
+    ("ROULETTE_RULES", "--rules"),
+    ("ROULETTE_RENDERER", "--renderer"),
+];
+
+/// Sets the options [`ENV_FLAGS`] name, exiting with a message on a value
+/// the flag wouldn't take.
+fn apply_env(options: &mut Options) {
+    fn invalid(name: &str, value: &str, flag: &str) -> ! {
+        eprintln!("{}={} isn't a valid {}.", name, value, flag);
+        usage()
+    }
+    for (name, flag) in ENV_FLAGS {
+        let Some(value) = std::env::var(name).ok().filter(|value| !value.trim().is_empty()) else { continue };
+        let value = value.trim();
+        match flag {
+            "--balance" => options.balance = Some(value.parse().unwrap_or_else(|_| invalid(name, value, flag))),
+            "--seed" => options.seed = Some(value.parse().unwrap_or_else(|_| invalid(name, value, flag))),
+            "--wheel" => match WheelKind::parse(value) {
+                Some(kind) => options.wheel = Some(kind),
+                None => options.wheel_file = Some(PathBuf::from(value)),
+            },
+            "--rules" => options.rules = Some(value.to_string()),
+            _ => options.renderer = Some(RendererKind::parse(value).unwrap_or_else(|| invalid(name, value, flag))),
+        }
+    }
+}
+
+pub fn parse_args() -> Options {
+    let mut options = Options::default();
+    apply_env(&mut options);
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "leaderboard" => {
+                if !matches!(options.command, Command::Leaderboard(_)) {
+                    options.command = Command::Leaderboard(LeaderboardMetric::PeakBalance);
+                }
+            }
+            "stats" => options.command = Command::Stats,
+            "export" => options.command = Command::Export,
+            "chart" => options.command = Command::Chart,
+            "compare" => options.command = Command::Compare,
+            "--against" => options.against = Some(args.next().unwrap_or_else(|| usage())),
+            "--chart" => options.chart = Some(args.next().unwrap_or_else(|| usage())),
+            "--audit-log" => options.audit_log = Some(args.next().unwrap_or_else(|| usage())),
+            "--format" => {
+                let format = args.next().filter(|f| f == "csv" || f == "hands");
+                options.format = Some(format.unwrap_or_else(|| usage()));
+            }
+            "--export" => options.stats_export = Some(args.next().unwrap_or_else(|| usage())),
+            "--session" => options.session = Some(args.next().unwrap_or_else(|| usage())),
+            "--bet-type" => options.history_filter.bet_type = Some(args.next().unwrap_or_else(|| usage())),
+            "--outcome" => {
+                let outcome = args.next().map(|o| o.to_lowercase()).filter(|o| history::OUTCOMES.contains(&o.as_str()));
+                options.history_filter.outcome = Some(outcome.unwrap_or_else(|| usage()));
+            }
+            "--ticker" => options.history_filter.ticker = Some(args.next().unwrap_or_else(|| usage())),
+            "--round" => options.history_filter.rounds = Some(parse_round_range(args.next())),
+            "--since" => options.history_filter.since = Some(parse_date(args.next())),
+            // The whole of the --until day counts.
+            "--until" => options.history_filter.until = Some(parse_date(args.next()) + 24 * 60 * 60),
+            "--output" => options.output = Some(args.next().unwrap_or_else(|| usage())),
+            "simulate" => options.command = Command::Simulate,
+            "optimize" => options.command = Command::Optimize,
+            "bench" => options.command = Command::Bench,
+            "verify" => options.command = Command::Verify,
+            "payouts" => options.command = Command::Payouts,
+            "house" => options.command = Command::House,
+            "bias" => options.command = Command::Bias,
+            "due" => options.command = Command::Due,
+            "replay" => options.command = Command::Replay,
+            "selftest" => options.command = Command::SelfTest,
+            "challenges" => options.command = Command::Challenges,
+            "tables" => options.command = Command::Tables,
+            "exchange" => options.command = Command::Exchange,
+            "lint-wheel" => {
+                options.command = Command::LintWheel;
+                options.input = Some(args.next().unwrap_or_else(|| usage()));
+            }
+            "--input" => options.input = Some(args.next().unwrap_or_else(|| usage())),
+            "--hands" => options.hands = Some(args.next().unwrap_or_else(|| usage())),
+            "--step" => options.step = true,
+            "--bots" => options.house.bots = parse_number(args.next()),
+            "--bankroll" => options.house.bankroll = parse_number(args.next()),
+            "--min-bet" => options.house.limits.min_bet = parse_number(args.next()),
+            "--max-bet" => options.house.limits.max_bet = parse_number(args.next()),
+            "--fair" => options.fair = true,
+            "--animate" => options.animate = true,
+            "--accessible" => options.accessible = true,
+            "--palette" => options.palette = Some(args.next().as_deref().and_then(Scheme::parse).unwrap_or_else(|| usage())),
+            "--color-symbols" => options.color_symbols = true,
+            "--renderer" => options.renderer = Some(args.next().as_deref().and_then(RendererKind::parse).unwrap_or_else(|| usage())),
+            "--tutorial" => options.tutorial = true,
+            "--practice" => options.practice = true,
+            "--autoplay" => options.autoplay = Some(parse_number(args.next())),
+            "--turbo" => options.turbo = true,
+            "--multi-table" => options.multi_table = true,
+            "--countdown" => options.countdown = Some(Duration::from_secs(parse_number(args.next()))).filter(|limit| !limit.is_zero()),
+            "--turbo-delay" => options.turbo_delay = Some(Duration::from_millis(parse_number(args.next()))),
+            "--spectate" => options.spectate = true,
+            "--spectate-delay" => options.spectate_delay = Some(Duration::from_millis(parse_number(args.next()))),
+            "--stop-loss" => options.stop_loss = Some(parse_number(args.next())),
+            "--training" => options.training = options.training.or(Some(DEFAULT_BIAS_STRENGTH)),
+            "--bias-strength" => options.training = Some(parse_number(args.next())),
+            "--remote" => options.remote = Some(args.next().unwrap_or_else(|| usage())),
+            "--machine" => options.machine = Some(true),
+            "--interactive" => options.machine = Some(false),
+            "--wheel-editor" => options.wheel_editor = true,
+            #[cfg(feature = "forced-outcome")]
+            "--force-pocket" => options.force_pocket = Some(parse_number(args.next())),
+            "--config" => options.config = Some(args.next().unwrap_or_else(|| usage())),
+            "--spin-model" => {
+                let kind = args.next().and_then(|k| SpinModelKind::parse(&k));
+                options.spin_model = Some(kind.unwrap_or_else(|| usage()));
+            }
+            "--wheel" => {
+                let value = args.next().unwrap_or_else(|| usage());
+                match WheelKind::parse(&value) {
+                    Some(kind) => options.wheel = Some(kind),
+                    None => options.wheel_file = Some(PathBuf::from(value)),
+                }
+            }
+            "--difficulty" => {
+                let level = args.next().and_then(|d| Difficulty::parse(&d));
+                options.difficulty = Some(level.unwrap_or_else(|| usage()));
+            }
+            "--rules" => options.rules = Some(args.next().unwrap_or_else(|| usage())),
+            "--client-seed" => options.client_seed = Some(args.next().unwrap_or_else(|| usage())),
+            "--server-seed" => options.server_seed = Some(args.next().unwrap_or_else(|| usage())),
+            "--commitment" => options.commitment = Some(args.next().unwrap_or_else(|| usage())),
+            "--nonce" => options.nonce = Some(parse_number(args.next())),
+            "--pocket" => options.pocket = Some(args.next().unwrap_or_else(|| usage())),
+            "--spins" => options.bench_spins = Some(parse_number(args.next())),
+            "--bet" => options.bets.push(args.next().unwrap_or_else(|| usage())),
+            "--bets" => options.bets_file = Some(PathBuf::from(args.next().unwrap_or_else(|| usage()))),
+            "--strategy" => options.strategy = Some(args.next().unwrap_or_else(|| usage())),
+            "--base-stakes" => {
+                options.search.base_stakes = parse_list(args.next()).into_iter().map(|v| v.unwrap_or_else(|| usage())).collect();
+            }
+            "--max-stakes" => options.search.max_stakes = parse_list(args.next()),
+            "--stop-losses" => options.search.stop_losses = parse_list(args.next()),
+            "--rank" => {
+                let objective = args.next().and_then(|o| Objective::parse(&o));
+                options.objective = objective.unwrap_or_else(|| usage());
+            }
+            "--top" => options.top = Some(parse_number(args.next())),
+            "--trials" => options.simulation.trials = parse_number(args.next()),
+            "--rounds" => options.simulation.rounds_per_trial = parse_number(args.next()),
+            "--balance" => options.balance = Some(parse_number(args.next())),
+            "--seed" => options.seed = Some(parse_number(args.next())),
+            "--all-time" => options.all_time = true,
+            "--no-history" => options.no_history = true,
+            "--no-journal" => options.no_journal = true,
+            "--by" => {
+                let metric = args.next().and_then(|m| LeaderboardMetric::parse(&m));
+                options.command = Command::Leaderboard(metric.unwrap_or_else(|| usage()));
+            }
+            "--profile" => options.profile = Some(args.next().unwrap_or_else(|| usage())),
+            "--encrypt" => options.encrypt = true,
+            "--time-attack" => {
+                options.time_attack = Some(args.next().and_then(|l| AttackLimit::parse(&l)).unwrap_or_else(|| usage()));
+            }
+            "--campaign" => options.campaign = true,
+            "--table" => options.table = Some(args.next().unwrap_or_else(|| usage())),
+            "--buy-in" => options.buy_in = Some(args.next().and_then(|n| n.parse().ok()).unwrap_or_else(|| usage())),
+            "--currency" => options.currency = Some(parse_currency(args.next())),
+            "--amount" => {
+                options.exchange_amount = Some(args.next().and_then(|n| n.parse().ok()).unwrap_or_else(|| usage()))
+            }
+            "--from" => options.exchange_from = Some(parse_currency(args.next())),
+            "--to" => options.exchange_to = Some(parse_currency(args.next())),
+            "--challenge" => options.challenge = Some(args.next().unwrap_or_else(|| usage())),
+            "--profile-store" | "--profiles-dir" => {
+                options.profile_store = Some(args.next().unwrap_or_else(|| usage()));
+            }
+            "--events" => options.events = Some(args.next().unwrap_or_else(|| usage())),
+            "--croupier" => options.croupier = true,
+            "--bettors" => {
+                let names = args.next().unwrap_or_else(|| usage());
+                options.bettors = names
+                    .split(',')
+                    .map(|n| n.trim().to_string())
+                    .filter(|n| !n.is_empty())
+                    .collect();
+            }
+            "--notify" => options.notify = true,
+            "--mute" => options.mute = true,
+            "--notify-big-win" => options.notify_big_win = Some(parse_number(args.next())),
+            "--notify-jackpot" => options.notify_jackpot = Some(parse_number(args.next())),
+            "-h" | "--help" => usage(),
+            _ => {
+                eprintln!("Unknown argument: {}", arg);
+                usage();
+            }
+        }
+    }
+    if let Some(balance) = options.balance {
+        options.simulation.starting_balance = balance;
+    }
+    if !options.bettors.is_empty() && !options.croupier {
+        eprintln!("--bettors requires --croupier.");
+        usage();
+    }
+    if options.autoplay.is_some() && options.bets.is_empty() && options.bets_file.is_none() && options.strategy.is_none() {
+        eprintln!("--autoplay needs at least one --bet, a --bets file, or a --strategy.");
+        usage();
+    }
+    if options.spectate && options.autoplay.is_none() {
+        eprintln!("--spectate watches an --autoplay bot.");
+        usage();
+    }
+    if options.strategy.is_some() && (!options.bets.is_empty() || options.bets_file.is_some()) {
+        eprintln!("--strategy places its own bets; drop the --bet and --bets options.");
+        usage();
+    }
+    if options.machine == Some(true) && (options.autoplay.is_some() || options.remote.is_some()) {
+        eprintln!("--machine takes bets from stdin; it can't be used with --autoplay or --remote.");
+        usage();
+    }
+    if options.wheel_editor && (options.autoplay.is_some() || options.remote.is_some() || options.multi_table) {
+        eprintln!("--wheel-editor adds to the betting menu; it can't be used with --autoplay, --remote, or --multi-table.");
+        usage();
+    }
+    if (options.autoplay.is_some() || options.remote.is_some()) && options.croupier {
+        eprintln!("--autoplay and --remote can't be used with --croupier.");
+        usage();
+    }
+    if options.house.limits.min_bet.is_zero() || options.house.limits.min_bet > options.house.limits.max_bet {
+        eprintln!("--min-bet must be more than 0 and no more than --max-bet.");
+        usage();
+    }
+    if options.challenge.is_some() && (options.time_attack.is_some() || options.croupier) {
+        eprintln!("--challenge can't be used with --time-attack or --croupier.");
+        usage();
+    }
+    if options.campaign && (options.profile.is_none() || options.challenge.is_some() || options.time_attack.is_some()) {
+        eprintln!("--campaign keeps its progress on a --profile, and can't be used with --challenge or --time-attack.");
+        usage();
+    }
+    if options.buy_in.is_some()
+        && (options.challenge.is_some() || options.campaign || options.practice || options.time_attack.is_some())
+    {
+        eprintln!("--buy-in keeps the rest of your bankroll in the wallet, so it can't be used with --challenge, --campaign, --practice, or --time-attack.");
+        usage();
+    }
+    if options.multi_table
+        && (options.table.is_some()
+            || options.buy_in.is_some()
+            || options.profile.is_some()
+            || options.challenge.is_some()
+            || options.campaign
+            || options.time_attack.is_some()
+            || options.croupier
+            || options.autoplay.is_some()
+            || options.remote.is_some())
+    {
+        eprintln!("--multi-table opens its tables from a menu and is played by hand; it can't be used with --table, --buy-in, --profile, --challenge, --campaign, --time-attack, --croupier, --autoplay, or --remote.");
+        usage();
+    }
+    if options.practice
+        && matches!(options.command, Command::Play)
+        && (options.profile.is_some() || options.challenge.is_some() || options.time_attack.is_some())
+    {
+        eprintln!("--practice rounds don't count, so it can't be used with --profile, --challenge, or --time-attack.");
+        usage();
+    }
+    if options.tutorial && (options.autoplay.is_some() || options.remote.is_some() || options.croupier) {
+        eprintln!("--tutorial is played at the keyboard; it can't be used with --autoplay, --remote, or --croupier.");
+        usage();
+    }
+    if options.fair && options.croupier {
+        eprintln!("--fair can't be used with --croupier: a physical wheel can't be committed to.");
+        usage();
+    }
+    options
+}
+
+fn parse_currency(code: Option<String>) -> Currency {
+    code.as_deref().and_then(Currency::parse).unwrap_or_else(|| usage())
+}
+
+fn parse_number<T: std::str::FromStr>(value: Option<String>) -> T {
+    value.and_then(|v| v.parse().ok()).unwrap_or_else(|| usage())
+}
+
+/// Parses `N` or `A-B` as an inclusive range of round numbers.
+fn parse_round_range(value: Option<String>) -> RangeInclusive<u32> {
+    let value = value.unwrap_or_else(|| usage());
+    let (first, last) = value.split_once('-').unwrap_or((&value, &value));
+    match (first.trim().parse(), last.trim().parse()) {
+        (Ok(first), Ok(last)) if first <= last => first..=last,
+        _ => usage(),
+    }
+}
+
+fn parse_date(value: Option<String>) -> u64 {
+    value.as_deref().and_then(history::parse_date).unwrap_or_else(|| {
+        eprintln!("Dates are written YYYY-MM-DD.");
+        usage()
+    })
+}
+
+/// Parses a comma-separated list of amounts, where `none` means no limit.
+fn parse_list<T: std::str::FromStr>(value: Option<String>) -> Vec<Option<T>> {
+    let value = value.unwrap_or_else(|| usage());
+    value
+        .split(',')
+        .map(|item| match item.trim() {
+            "none" => None,
+            item => Some(item.parse().unwrap_or_else(|_| usage())),
+        })
+        .collect()
+}
+
+/// How much likelier the favoured pockets are in training unless
+/// `--bias-strength` says otherwise.
+const DEFAULT_BIAS_STRENGTH: f64 = 3.0;
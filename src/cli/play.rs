@@ -0,0 +1,956 @@
+// src/cli/play.rs
+
+//! An interactive session, from choosing a profile and a table to the
+//! summary once the player gets up, and the bots, remote players, journal,
+//! and event sinks that can take part in it.
+
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::rc::Rc;
+
+use roulette_game::{console, input, prompt, say, tutorial};
+use roulette_game::accessibility::Announcer;
+use roulette_game::campaign::Campaign;
+use roulette_game::challenge::{Challenge, ChallengeSpec, ChallengeStatus};
+use roulette_game::config::{Config, ConfigError, ConfigWatcher};
+use roulette_game::game::Game;
+use roulette_game::game::bets::Bet;
+use roulette_game::game::chips::ChipStack;
+use roulette_game::game::controller::{FlatBettor, PlayerController, RemotePlayer, Turn};
+use roulette_game::game::currency::Currency;
+use roulette_game::game::entropy::EntropySource;
+use roulette_game::game::events::{EventSink, NdjsonSink};
+use roulette_game::game::money::Money;
+use roulette_game::game::rules::{Difficulty, TableRules};
+use roulette_game::game::spin::{SpinFrame, SpinPhase};
+use roulette_game::game::training::TrainingWheel;
+use roulette_game::game::wallet::Wallet;
+use roulette_game::game::wheel::{PocketId, Wheel, WheelLike};
+use roulette_game::history::{HistoryRecorder, RoundStore, SessionStats};
+use roulette_game::integrity::{Integrity, IntegrityKey, MacChain};
+use roulette_game::journal::{self, InterruptedSession, Journal};
+use roulette_game::machine::MachinePlayer;
+use roulette_game::presentation;
+use roulette_game::profile::{self, CampaignProgress, Profile, ProfileBackend, ProfileTracker};
+use roulette_game::profile::progression::Progress;
+use roulette_game::renderer;
+use roulette_game::spectate::{DEFAULT_SPECTATE_DELAY, Spectator};
+use roulette_game::tables::Table;
+use roulette_game::time_attack::TimeAttack;
+use roulette_game::turbo::{DEFAULT_TURBO_DELAY, Turbo};
+
+use super::betting::{HumanController, get_croupier_pocket};
+use super::export::save_chart;
+use super::{
+    amount_prompt, build_wheel, describe_bet, get_amount_input, get_string_input, load_config, load_rules,
+    load_strategy_option, money, override_config, parse_bet_options, table_rules,
+};
+use super::options::Options;
+use super::profiles::{choose_profile, load_profile};
+use super::stats::show_session_stats;
+use super::tables::{run_dealer_ledger, run_multi_table};
+
+/// Puts settings saved to the config file mid-session into play: the wheel
+/// with any new pockets, categories, or weights, the rules and their
+/// payouts, the difficulty, the exposure limit, and the spin model. If any
+/// of it can't change mid-session, none of it does.
+fn reload_config(
+    game: &mut Game,
+    options: &Options,
+    config: &mut Config,
+    table_rules: &mut TableRules,
+    path: &Path,
+    loaded: Result<Config, ConfigError>,
+) {
+    let refuse = |reason: &dyn std::fmt::Display| {
+        say!("Changes to {} not applied: {}. Fix the file, or restart to play with it.", path.display(), reason)
+    };
+    let mut saved = match loaded {
+        Ok(saved) => saved,
+        Err(e) => return refuse(&e),
+    };
+    override_config(options, &mut saved);
+    if let Err(e) = saved.load_wheel_file() {
+        return refuse(&format_args!("the wheel file: {}", e));
+    }
+    if let Some(currency) = &options.currency {
+        saved.rules.currency = currency.clone();
+    }
+    if saved.rules.currency != config.rules.currency {
+        return refuse(&format_args!("the table plays in {} until the session ends", config.rules.currency));
+    }
+    if saved.rules.chips != config.rules.chips {
+        return refuse(&"the chips in play can't change until the session ends");
+    }
+    let rules = match saved.table_rules() {
+        Ok(rules) => rules,
+        Err(e) => return refuse(&e),
+    };
+    let mut changed = Vec::new();
+    if (saved.wheel, &saved.wheel_file, &saved.weights, saved.weight_by, &saved.metadata)
+        != (config.wheel, &config.wheel_file, &config.weights, config.weight_by, &config.metadata)
+    {
+        let wheel = match saved.build_wheel() {
+            Ok(wheel) => wheel,
+            Err(e) => return refuse(&format_args!("the wheel weights: {}", e)),
+        };
+        if let Err(e) = game.replace_wheel(wheel) {
+            return refuse(&e);
+        }
+        changed.push("the wheel");
+    }
+    if rules != *table_rules {
+        game.set_rules(rules.clone());
+        *table_rules = rules;
+        changed.push("the table rules");
+    }
+    if saved.exposure != config.exposure {
+        game.set_exposure_limit(saved.exposure);
+        changed.push("the exposure limit");
+    }
+    if (saved.spin_model, &saved.physics) != (config.spin_model, &config.physics) {
+        game.set_spin_model(saved.spin_model());
+        changed.push("the spin model");
+    }
+    if changed.is_empty() {
+        if saved != *config {
+            say!("Changes to {} take effect next session.", path.display());
+        }
+    } else {
+        say!("Reloaded {}: {} changed.", path.display(), changed.join(", "));
+    }
+    *config = saved;
+}
+
+fn training_wheel(config: &Config, strength: f64) -> TrainingWheel {
+    TrainingWheel::random(config.layout(), strength, &mut rand::thread_rng()).unwrap_or_else(|e| {
+        eprintln!("Can't start training: {}", e);
+        process::exit(2);
+    })
+}
+
+/// Ends a training session: takes the player's guess, then shows the bias.
+fn reveal_training(wheel: &Wheel, favoured: &[PocketId], strength: f64, ask: bool) {
+    let mut guesses = Vec::new();
+    if ask {
+        let answer = get_string_input("\nWhich pockets did the wheel favour? (tickers or numbers, comma-separated): ")
+            .unwrap_or_default();
+        for name in answer.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match wheel.find_pocket(name) {
+                Some(id) => guesses.push(id),
+                None => say!("No pocket matches '{}'; ignored.", name),
+            }
+        }
+    }
+    let names = |ids: &[PocketId]| {
+        ids.iter().map(|&id| wheel.pocket(id).ticker.as_str()).collect::<Vec<_>>().join(", ")
+    };
+    say!("\n=== Training Result ===");
+    say!("The wheel favoured {} ({}x as likely as any other pocket).", names(favoured), strength);
+    if ask {
+        let score = TrainingWheel::score_guesses(favoured, &guesses);
+        say!("You named: {}", if guesses.is_empty() { "nothing".to_string() } else { names(&guesses) });
+        say!("Detection score: {}/100", score);
+    }
+}
+
+/// Finds the profile's place in the campaign and sets up its wheel.
+fn campaign_stage(campaign: Campaign, profile: &Profile, config: &mut Config) -> (Campaign, CampaignProgress) {
+    let progress = profile.lifetime.campaign.filter(|&p| campaign.stage(p).is_some()).unwrap_or_else(|| campaign.start());
+    let stage = campaign.stage(progress).expect("progress is on a stage");
+    say!("\n=== {}: stage {} of {} ===", campaign.name, progress.stage + 1, campaign.stages.len());
+    stage.configure(config);
+    (campaign, progress)
+}
+
+/// Moves the campaign on after a stage, or sets it up to be played again.
+fn update_campaign(campaign: &Campaign, progress: CampaignProgress, won: bool, balance: Money, tracker: &mut ProfileTracker) {
+    if !won {
+        say!("Next session plays the stage again, from the {} you brought to it.", progress.bankroll);
+        tracker.set_campaign(Some(progress));
+        return;
+    }
+    match campaign.advance(progress, balance) {
+        Some(next) => {
+            let stage = campaign.stage(next).expect("advance stops at the last stage");
+            say!("On to stage {}, {}, with {} carried over.", next.stage + 1, stage.name, balance);
+            tracker.set_campaign(Some(next));
+        }
+        None => {
+            say!("You've beaten {}!", campaign.name);
+            tracker.set_campaign(None);
+            if tracker.record_challenge(&campaign.id) {
+                say!("Added to your completed challenges.");
+            }
+        }
+    }
+}
+
+/// Finds the table `id` and seats the player there, if it's open to them.
+fn take_seat(id: &str, profile: Option<&Profile>, config: &mut Config) -> Table {
+    let table = Table::find(id).unwrap_or_else(|| {
+        eprintln!("No table named '{}'. See roulette_game tables.", id);
+        process::exit(1);
+    });
+    if !table.is_open_to(profile) {
+        match profile {
+            Some(profile) => eprintln!(
+                "The {} opens with {}; {} has {} at level {}.",
+                table.name,
+                table.unlock,
+                profile.name,
+                profile.bankroll.unwrap_or_default(),
+                profile.progress().level
+            ),
+            None => eprintln!("The {} opens with {}; play with --profile to sit there.", table.name, table.unlock),
+        }
+        process::exit(1);
+    }
+    say!("\nTable: {} ({}).", table.name, table.limits());
+    if !table.rules.side_bets.is_empty() {
+        say!("Bet on a side bet with 'custom <name> <amount>'.");
+    }
+    config.rules = table.rules.clone();
+    table
+}
+
+fn load_challenge(id_or_path: &str) -> Challenge {
+    let spec = ChallengeSpec::load(id_or_path).unwrap_or_else(|e| {
+        eprintln!("Can't load challenge {}: {}", id_or_path, e);
+        process::exit(1);
+    });
+    Challenge::new(spec)
+}
+
+/// Offers to pick up the session a crash or power cut left unfinished,
+/// showing where it left off, and returns it if the player accepts.
+fn recover_session(path: &Path) -> Option<InterruptedSession> {
+    let interrupted = match journal::find_interrupted(path) {
+        Ok(interrupted) => interrupted?,
+        Err(e) => {
+            eprintln!("Could not read session journal {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    match &interrupted.profile {
+        Some(name) => say!("\nAn unfinished session was found, played as {} on the {} wheel.", name, interrupted.wheel),
+        None => say!("\nAn unfinished session was found on the {} wheel.", interrupted.wheel),
+    }
+    say!("  Left off at round {} ({} settled)", interrupted.rounds + 1, interrupted.rounds);
+    say!("  Balance: {}", interrupted.balance);
+    if interrupted.standing.is_empty() {
+        say!("  Standing bets: none");
+    } else {
+        say!("  Standing bets, {} waiting on a spin:", interrupted.unspun());
+        for bet in &interrupted.standing {
+            say!("    - {}", describe_bet(bet, &Currency::usd()));
+        }
+    }
+    if interrupted.integrity == Integrity::Tampered {
+        say!("The session journal was edited outside the game, so it can't be resumed.");
+        return None;
+    }
+    say!("Journal integrity: {}.", interrupted.integrity);
+    if get_string_input("Resume it where it left off? (y/n): ").is_some_and(|answer| answer.starts_with('Y')) {
+        say!("Session resumed.");
+        Some(interrupted)
+    } else {
+        say!("Starting fresh; the unfinished session is discarded.");
+        None
+    }
+}
+
+/// Puts a resumed session's standing bets back on the table. A bet the
+/// table now refuses has its stake left in the balance.
+fn restore_standing_bets(game: &mut Game, standing: Vec<Bet>) {
+    if standing.is_empty() {
+        return;
+    }
+    say!("Putting your standing bets back on the table:");
+    for bet in standing {
+        game.place_bet(bet);
+    }
+}
+
+/// Opens a fresh journal at `path` and writes every event of the session to it.
+fn start_journal(
+    game: &mut Game,
+    path: &Path,
+    starting_balance: Money,
+    difficulty: Difficulty,
+    table: Option<&str>,
+    profile: Option<&str>,
+) -> Option<Rc<RefCell<Journal>>> {
+    match Journal::start(path, starting_balance, game.wheel_variant().name(), difficulty, table, profile) {
+        Ok(journal) => {
+            let journal = Rc::new(RefCell::new(journal));
+            game.add_event_sink(Box::new(journal.clone()));
+            Some(journal)
+        }
+        Err(e) => {
+            eprintln!("Could not start session journal {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Writes every balance change of the game to `path` as NDJSON, each line
+/// signed so later edits show up.
+fn save_audit_log(game: &Game, path: &str) {
+    let written = IntegrityKey::load_or_create(&IntegrityKey::default_location()).and_then(|key| {
+        let mut chain = MacChain::new(key);
+        let mut out = BufWriter::new(File::create(path)?);
+        // Wheel edits go in among the balance changes, where they were made.
+        let mut edits = game.wheel_edits().iter().peekable();
+        for (seq, entry) in game.audit_log().iter().enumerate() {
+            while let Some(edit) = edits.next_if(|edit| edit.after <= seq as u64) {
+                writeln!(out, "{}", chain.seal(edit).map_err(io::Error::other)?)?;
+            }
+            writeln!(out, "{}", chain.seal(entry).map_err(io::Error::other)?)?;
+        }
+        for edit in edits {
+            writeln!(out, "{}", chain.seal(edit).map_err(io::Error::other)?)?;
+        }
+        out.flush()
+    });
+    match written {
+        Ok(()) if game.wheel_edits().is_empty() => {
+            say!("Audit log of {} balance changes saved to {}.", game.audit_log().len(), path)
+        }
+        Ok(()) => say!(
+            "Audit log of {} balance changes and {} wheel edits saved to {}.",
+            game.audit_log().len(),
+            game.wheel_edits().len(),
+            path
+        ),
+        Err(e) => eprintln!("Could not save audit log {}: {}", path, e),
+    }
+}
+
+/// The balance a returning profile starts from: its bankroll, plus the daily
+/// bonus on the first session of the day. `None` for a profile that has never
+/// played. Exits if the bankroll is empty and no bonus is due. A table in
+/// another currency plays from the profile's money in that currency instead.
+fn profile_bankroll(profile: &mut Profile, currency: &Currency) -> Option<Money> {
+    let bonus = profile.check_in();
+    if !bonus.is_zero() {
+        say!("Daily bonus: {} added to your bankroll.", bonus);
+    }
+    if !currency.is_usd() {
+        let balance = profile.balance(currency).unwrap_or_default();
+        if balance.is_zero() {
+            say!(
+                "You have no {} yet. Change some dollars with: roulette_game exchange --profile {} --amount <N> --from USD --to {}",
+                currency, profile.name, currency
+            );
+            process::exit(0);
+        }
+        say!("Your {} balance: {}", currency, currency.amount(balance));
+        return Some(balance);
+    }
+    let bankroll = profile.bankroll?;
+    if bankroll.is_zero() {
+        say!("Your bankroll is empty. Come back tomorrow for your {} daily bonus!", profile::DAILY_BONUS);
+        process::exit(0);
+    }
+    say!("Your bankroll: {}", bankroll);
+    Some(bankroll)
+}
+
+/// Counts the session toward the profile and carries its comp points over.
+/// A challenge session leaves the profile's bankroll alone.
+fn track_profile(
+    game: &mut Game,
+    backend: Box<dyn ProfileBackend>,
+    profile: Profile,
+    starting_balance: Money,
+    challenge: bool,
+) -> Rc<RefCell<ProfileTracker>> {
+    game.set_comp_points(profile.lifetime.comp_points);
+    let tracker = if challenge {
+        ProfileTracker::start_challenge(backend, profile, starting_balance)
+    } else {
+        ProfileTracker::start_session(backend, profile, game.rules().currency.clone(), starting_balance)
+    };
+    let tracker = Rc::new(RefCell::new(tracker));
+    game.add_event_sink(Box::new(tracker.clone()));
+    tracker
+}
+
+/// Sets the bets-per-round limit the player's rank allows, unless the
+/// table rules fix one for everybody.
+fn apply_rank_limit(game: &mut Game, table_rules: &TableRules, progress: Progress) {
+    if table_rules.max_bets_per_round.is_some() {
+        return;
+    }
+    let mut rules = game.rules().clone();
+    rules.max_bets_per_round = progress.rank().max_bets_per_round;
+    game.set_rules(rules);
+}
+
+/// Spins onto `--force-pocket` if it was given; returns false otherwise.
+#[cfg(feature = "forced-outcome")]
+fn forced_spin(game: &mut Game, options: &Options) -> bool {
+    let Some(number) = options.force_pocket else {
+        return false;
+    };
+    if let Err(e) = game.spin_with_outcome(number) {
+        eprintln!("Can't force the spin: {}", e);
+        process::exit(2);
+    }
+    true
+}
+
+#[cfg(not(feature = "forced-outcome"))]
+fn forced_spin(_game: &mut Game, _options: &Options) -> bool {
+    false
+}
+
+#[cfg(feature = "notifications")]
+fn notification_sink(options: &Options, currency: &Currency) -> Box<dyn EventSink> {
+    use roulette_game::notifications::{NotificationSink, NotificationThresholds};
+
+    let mut thresholds = NotificationThresholds::default();
+    if let Some(big_win) = options.notify_big_win {
+        thresholds.big_win = big_win;
+    }
+    if let Some(multiplier) = options.notify_jackpot {
+        thresholds.jackpot_multiplier = multiplier;
+    }
+    Box::new(NotificationSink::new(thresholds, currency.clone()))
+}
+
+#[cfg(not(feature = "notifications"))]
+fn notification_sink(_options: &Options, _currency: &Currency) -> Box<dyn EventSink> {
+    eprintln!("This build has no desktop notification support (enable the `notifications` feature).");
+    process::exit(2);
+}
+
+/// The sound effects for this session, unless muted. Autoplay runs rounds
+/// too quickly for them, so it stays quiet.
+#[cfg(feature = "audio")]
+fn sound_sink(options: &Options, config: &Config) -> Option<Box<dyn EventSink>> {
+    use roulette_game::sound::SoundSink;
+
+    if options.mute || config.sound.muted || options.autoplay.is_some() {
+        return None;
+    }
+    match SoundSink::open(config.sound.volume) {
+        Ok(sink) => Some(Box::new(sink)),
+        Err(e) => {
+            console::warn(format_args!("No sound: {}", e));
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "audio"))]
+fn sound_sink(_options: &Options, _config: &Config) -> Option<Box<dyn EventSink>> {
+    None
+}
+
+/// Shows the pocket under the ball on one line, replaying the spin at
+/// `ANIMATION_SPEEDUP` times real speed.
+fn attach_animation(game: &mut Game) {
+    const ANIMATION_SPEEDUP: f64 = 2.0;
+    let wheel = game.wheel().clone();
+    let mut last_time = 0.0;
+    game.set_spin_observer(Box::new(move |frame: &SpinFrame| {
+        if frame.time < last_time {
+            last_time = 0.0;
+        }
+        let wait = (frame.time - last_time) / ANIMATION_SPEEDUP;
+        last_time = frame.time;
+        std::thread::sleep(std::time::Duration::from_secs_f64(wait.max(0.0)));
+        let pocket = wheel.pocket(frame.pocket);
+        let start = renderer::current().rewrite_line();
+        prompt!("{}  ... {:<6} {:<9}", start, pocket.ticker, presentation::color(pocket.color));
+        if frame.phase == SpinPhase::Settled {
+            say!("");
+        }
+    }));
+}
+
+fn attach_event_stream(game: &mut Game, target: &str) {
+    if target == "-" {
+        game.add_event_sink(Box::new(NdjsonSink::new(io::stdout())));
+    } else {
+        match File::create(target) {
+            Ok(file) => game.add_event_sink(Box::new(NdjsonSink::new(BufWriter::new(file)))),
+            Err(e) => {
+                eprintln!("Could not open event file {}: {}", target, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Who places the bets in a single-player game.
+fn player_controller(
+    options: &Options,
+    config: &Config,
+    wheel: &Wheel,
+    chips: Option<Rc<RefCell<ChipStack>>>,
+) -> Box<dyn PlayerController> {
+    if let Some(rounds) = options.autoplay {
+        let bot: Box<dyn PlayerController> = match load_strategy_option(options, wheel) {
+            Some(mut strategy) => {
+                strategy.limit_rounds(rounds);
+                Box::new(strategy)
+            }
+            None => Box::new(FlatBettor::new(parse_bet_options(options, wheel)).for_rounds(rounds)),
+        };
+        if !options.spectate {
+            return bot;
+        }
+        return Box::new(Spectator::new(bot).with_delay(options.spectate_delay.unwrap_or(DEFAULT_SPECTATE_DELAY)));
+    }
+    let Some(addr) = &options.remote else {
+        let human = Box::new(HumanController {
+            show_ev: !config.hide_expected_value,
+            chips,
+            countdown: options.countdown,
+            bets_file: options.bets_file.clone(),
+            wheel_editor: options.wheel_editor,
+        });
+        if !options.turbo {
+            return human;
+        }
+        let mut turbo = Turbo::new(human).with_delay(options.turbo_delay.unwrap_or(DEFAULT_TURBO_DELAY));
+        if let Some(limit) = options.stop_loss {
+            turbo = turbo.with_stop_loss(limit);
+        }
+        return Box::new(turbo);
+    };
+    say!("Waiting for a remote player on {}...", addr);
+    let stream = std::net::TcpListener::bind(addr)
+        .and_then(|listener| listener.accept())
+        .and_then(|(stream, peer)| {
+            say!("Remote player connected from {}.", peer);
+            Ok((io::BufReader::new(stream.try_clone()?), stream))
+        });
+    match stream {
+        Ok((input, output)) => Box::new(RemotePlayer::new(input, output)),
+        Err(e) => {
+            eprintln!("Could not accept a remote player on {}: {}", addr, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// The interactive session: betting at the keyboard, or for a bot, a
+/// remote player, or a script speaking the machine protocol.
+pub fn run(options: Options) {
+    // Scripts feeding the game get the machine protocol rather than menus.
+    let machine = options.machine.unwrap_or_else(|| !io::stdin().is_terminal())
+        && options.autoplay.is_none()
+        && options.remote.is_none();
+    if machine {
+        if options.tutorial
+            || options.croupier
+            || options.multi_table
+            || options.turbo
+            || options.countdown.is_some()
+            || options.wheel_editor
+        {
+            eprintln!("--tutorial, --croupier, --multi-table, --turbo, --countdown, and --wheel-editor are played at the keyboard; add --interactive to answer their prompts from piped input.");
+            process::exit(2);
+        }
+        if options.events.as_deref() == Some("-") {
+            eprintln!("The machine protocol answers on stdout; write --events to a file, or add --interactive.");
+            process::exit(2);
+        }
+        console::mute();
+    }
+    if options.events.as_deref() == Some("-") {
+        // Keep stdout clean for the event stream; prompts and messages go to stderr.
+        console::redirect_to_stderr();
+    }
+    let mut config = load_config(&options);
+    let notifications = options.notify.then(|| notification_sink(&options, &config.rules.currency));
+    let accessible = options.accessible || config.accessible;
+
+    say!("=================================");
+    say!(" Welcome to Wall Street Roulette!");
+    say!("=================================");
+    say!("Bet on stocks and sectors! Spin the wheel to see which stock wins!");
+    if options.croupier {
+        say!("Croupier mode: spin your own wheel and enter where the ball lands.");
+    }
+    if options.practice {
+        say!("Practice mode: play money, topped up whenever it runs out. These rounds stay out of your all-time stats.");
+    }
+    if options.tutorial {
+        match tutorial::run(&mut input::Lines::default()) {
+            Ok(true) => say!("\nNow for the real game."),
+            Ok(false) => say!("\nSkipping to the real game."),
+            Err(e) => eprintln!("Tutorial stopped: {}", e),
+        }
+    } else if !RoundStore::default_location().path().exists() {
+        say!("New to roulette? Run with --tutorial for a guided first session.");
+    }
+
+    // An unfinished session is offered first; resuming it plays on as the
+    // profile it was played as.
+    let journal_path = Journal::default_location();
+    let recovered = if options.no_journal
+        || machine
+        || options.croupier
+        || options.practice
+        || options.buy_in.is_some()
+        || options.multi_table
+        || !options.currency.as_ref().unwrap_or(&config.rules.currency).is_usd()
+        || options.challenge.is_some()
+        || options.campaign
+    {
+        None
+    } else {
+        recover_session(&journal_path)
+    };
+
+    // Players at a terminal pick a profile from a menu; scripts and piped
+    // input go straight to the balance prompt as before.
+    let chosen = match (&options.profile, &recovered) {
+        (Some(name), _) => Some(name.clone()),
+        (None, Some(session)) => session.profile.clone(),
+        (None, None) if io::stdin().is_terminal()
+            && options.bettors.is_empty()
+            && !options.practice
+            && !options.multi_table
+            && options.autoplay.is_none()
+            && options.remote.is_none()
+            && options.challenge.is_none() =>
+        {
+            choose_profile(&options)
+        }
+        (None, None) => None,
+    };
+    let mut profile = match &chosen {
+        Some(name) if options.bettors.is_empty() => Some(load_profile(&options, name)),
+        _ => None,
+    };
+    let campaign = match (options.campaign, &profile) {
+        (true, Some((_, profile))) => Some(campaign_stage(Campaign::built_in(), profile, &mut config)),
+        _ => None,
+    };
+    let table = options.table.as_deref().map(|id| take_seat(id, profile.as_ref().map(|(_, p)| p), &mut config));
+    if let Some(currency) = &options.currency {
+        config.rules.currency = currency.clone();
+    }
+    let currency = config.rules.currency.clone();
+    if config.exchange_rates.rate(&currency).is_none() {
+        eprintln!("No exchange rate for {}; add one under [exchange_rates] in the config.", currency);
+        process::exit(1);
+    }
+    if !currency.is_usd() {
+        say!("This table plays in {}.", currency);
+    }
+    let challenge = match &campaign {
+        Some((campaign, progress)) => campaign.challenge(*progress).map(Challenge::new),
+        None => options.challenge.as_deref().map(load_challenge),
+    };
+    if let Some(challenge) = &challenge {
+        say!("\nChallenge: {}", challenge.spec());
+    }
+    // A recovered session carries on the interrupted one rather than checking in anew,
+    // and a challenge brings its own balance.
+    let bankroll = match (&recovered, profile.as_mut()) {
+        (None, Some((_, profile))) if challenge.is_none() => profile_bankroll(profile, &currency),
+        _ => None,
+    };
+    let challenge_balance = challenge.as_ref().map(|c| c.spec().starting_balance);
+    let recovered_balance = recovered.as_ref().map(InterruptedSession::recovered_balance);
+    let starting_balance = match recovered_balance.or(bankroll).or(challenge_balance).or(options.balance) {
+        Some(balance) => balance,
+        // Scripts set their balance up front rather than answer a prompt.
+        None if machine => options.simulation.starting_balance,
+        None => match get_amount_input(&amount_prompt("Enter your starting balance", &currency)) {
+            Some(bal) if !bal.is_zero() => bal,
+            _ => {
+                say!("Invalid starting balance. Defaulting to {}.", currency.amount(1000));
+                Money::from_units(1000)
+            }
+        },
+    };
+
+    if !options.bettors.is_empty() {
+        run_dealer_ledger(&options.bettors, starting_balance, &config);
+        return;
+    }
+    if options.multi_table {
+        run_multi_table(&options, &config, starting_balance);
+        return;
+    }
+
+    let training = options.training.map(|strength| training_wheel(&config, strength));
+    let secret = training.as_ref().map(|wheel| (wheel.favoured().to_vec(), wheel.strength()));
+    let wheel: Box<dyn WheelLike> = match training {
+        Some(wheel) => {
+            say!("Training mode: this wheel favours one pocket or a short sector. Find it and bet on it!");
+            Box::new(wheel)
+        }
+        None => build_wheel(&config),
+    };
+    let mut table_rules = table_rules(&config);
+    if config.difficulty != Difficulty::Normal {
+        say!("Difficulty: {}.", config.difficulty);
+    }
+    let mut wallet = options.buy_in.map(|_| Wallet::new(currency.clone(), starting_balance));
+    let mut game = Game::with_wheel(if wallet.is_some() { Money::ZERO } else { starting_balance }, wheel);
+    game.set_rules(table_rules.clone());
+    game.set_exposure_limit(config.exposure);
+    if let (Some(wallet), Some(amount)) = (wallet.as_mut(), options.buy_in) {
+        if let Err(e) = wallet.buy_in(&mut game, amount) {
+            eprintln!("Can't buy in for {}: {}.", currency.amount(amount), e);
+            process::exit(1);
+        }
+        say!("{} left in your wallet.", currency.amount(wallet.cash(&currency)));
+    }
+    // What the player sits down with, which the wallet may hold more than.
+    let opening_balance = game.get_player_balance();
+    if let Some(path) = &options.rules {
+        load_rules(&mut game, path);
+    }
+    let chips = table_rules.chips.clone().map(|set| {
+        let stack = Rc::new(RefCell::new(ChipStack::new(set, opening_balance)));
+        game.add_event_sink(Box::new(stack.clone()));
+        stack
+    });
+    let mut controller: Box<dyn PlayerController> = if machine {
+        Box::new(MachinePlayer::new(&mut game, io::stdout()))
+    } else {
+        player_controller(&options, &config, game.wheel(), chips)
+    };
+    game.set_spin_model(config.spin_model());
+    if let Err(e) = game.set_entropy_source(config.entropy) {
+        eprintln!("Can't use the {:?} entropy source: {}", config.entropy, e);
+        process::exit(1);
+    }
+    if config.entropy != EntropySource::Thread {
+        say!("Spin seeds come from the {}.", config.entropy);
+    }
+    if options.animate && !accessible {
+        attach_animation(&mut game);
+    }
+    if accessible {
+        game.add_event_sink(Box::new(Announcer::new(game.rules().currency.clone())));
+    }
+    if let Some(target) = &options.events {
+        attach_event_stream(&mut game, target);
+    }
+    if let Some(sink) = notifications {
+        game.add_event_sink(sink);
+    }
+    if let Some(sink) = sound_sink(&options, &config) {
+        game.add_event_sink(sink);
+    }
+    if !options.no_history {
+        let store = if options.practice { RoundStore::practice_location() } else { RoundStore::default_location() };
+        let mut recorder = HistoryRecorder::new(store, game.wheel_variant().name(), controller.name()).with_currency(&currency);
+        if let Some((_, profile)) = &profile {
+            recorder = recorder.with_profile(&profile.name);
+        }
+        game.add_event_sink(Box::new(recorder));
+    }
+    let profile = profile.map(|(backend, profile)| {
+        let tracker = track_profile(&mut game, backend, profile, starting_balance, challenge.is_some());
+        if let Some(wallet) = &wallet {
+            tracker.borrow_mut().set_cash_off_table(wallet.cash(&currency));
+        }
+        tracker
+    });
+    let session = Rc::new(RefCell::new(SessionStats::at_difficulty(config.difficulty)));
+    game.add_event_sink(Box::new(session.clone()));
+    let attack = options.time_attack.map(|limit| {
+        say!("Time attack: {}. Make as much as you can; steady gains score best.", limit);
+        let attack = Rc::new(RefCell::new(TimeAttack::start(limit, starting_balance)));
+        game.add_event_sink(Box::new(attack.clone()));
+        attack
+    });
+    let challenge = challenge.map(|challenge| {
+        let challenge = Rc::new(RefCell::new(challenge));
+        game.add_event_sink(Box::new(challenge.clone()));
+        challenge
+    });
+    let journal = (!options.no_journal && !options.croupier && !options.practice)
+        .then(|| {
+            let table = table.as_ref().map(|t| t.id.as_str());
+            start_journal(&mut game, &journal_path, opening_balance, config.difficulty, table, chosen.as_deref())
+        })
+        .flatten();
+    if let Some(session) = recovered {
+        restore_standing_bets(&mut game, session.standing);
+    }
+    // Practice chips and a croupier's typos can be taken back; a session
+    // that counts for a profile, a challenge, or a time attack can't.
+    game.allow_voids((options.practice || options.croupier) && profile.is_none() && challenge.is_none() && attack.is_none());
+    if options.fair {
+        let client_seed = options.client_seed.clone().unwrap_or_else(|| game.entropy_source().random_seed());
+        say!("Provably fair mode. Your client seed: {}", client_seed);
+        game.enable_fairness(&client_seed);
+    }
+
+    // Tables, campaign stages, and training bring their own wheel and rules,
+    // which a saved config mustn't replace.
+    let mut watcher = (options.table.is_none() && campaign.is_none() && secret.is_none()).then(|| {
+        ConfigWatcher::new(options.config.as_ref().map_or_else(Config::default_location, PathBuf::from))
+    });
+
+    loop {
+        say!("\n------------------------------------");
+        if let Some(watcher) = watcher.as_mut()
+            && let Some(loaded) = watcher.poll()
+        {
+            reload_config(&mut game, &options, &mut config, &mut table_rules, watcher.path(), loaded);
+        }
+        if let Some(attack) = &attack {
+            if attack.borrow().is_over() {
+                say!("Time's up!");
+                break;
+            }
+            say!("Time attack: {}", attack.borrow().remaining());
+        }
+        if let Some(challenge) = &challenge {
+            say!("Challenge: {}", challenge.borrow().progress());
+        }
+        say!("Starting new round...");
+        if let Some(tracker) = &profile {
+            let tracker = tracker.borrow();
+            let progress = tracker.profile().progress();
+            say!("{} | {}", tracker.profile().name, progress);
+            apply_rank_limit(&mut game, &table_rules, progress);
+        }
+        if let Some((nonce, commitment)) = game.fairness_commitment() {
+            say!("Spin #{} commitment: {}", nonce, commitment);
+        }
+
+        if controller.place_bets(&mut game) == Turn::Leave {
+            if !game.get_current_bets().is_empty() {
+                game.clear_bets();
+            }
+            say!("Leaving the table. Final Balance: {}", money(&game, game.get_player_balance()));
+            break;
+        }
+        if let Some(attack) = &attack
+            && attack.borrow().is_over()
+        {
+            say!("Time ran out before the spin.");
+            if !game.get_current_bets().is_empty() {
+                game.clear_bets();
+            }
+            break;
+        }
+        let had_bets = game.has_bets_in_play();
+
+        if options.croupier {
+            if had_bets {
+                let pocket = get_croupier_pocket(game.wheel());
+                game.resolve_with_pocket(pocket);
+            }
+        } else if forced_spin(&mut game, &options) {
+            // The demo build rigged this spin.
+        } else {
+            let previous = game.fairness_commitment().map(|(nonce, _)| nonce);
+            game.spin_wheel_and_resolve();
+            if let Some(reveal) = game.last_reveal()
+                && Some(reveal.nonce) == previous
+            {
+                say!("Spin #{} server seed: {}", reveal.nonce, reveal.server_seed);
+                say!("Verify with: roulette_game verify --server-seed {} --client-seed {} --nonce {} --commitment {}",
+                    reveal.server_seed, reveal.client_seed, reveal.nonce, reveal.commitment);
+            }
+        }
+        if had_bets && let Some((pocket, returned)) = game.last_outcome() {
+            controller.spin_settled(game.wheel(), pocket, returned);
+        }
+
+        if options.practice && game.get_player_balance().is_zero() {
+            say!("Out of practice chips; here's another {}.", currency.amount(starting_balance));
+            game.deposit(starting_balance);
+        }
+        if game.get_player_balance().is_zero() {
+            if let Some(wallet) = wallet.as_ref().filter(|wallet| !wallet.cash(&currency).is_zero()) {
+                say!("\nYou're out of chips at this table, with {} still in your wallet.", currency.amount(wallet.cash(&currency)));
+                break;
+            }
+            say!("\n------------------------------------");
+            say!("Game Over! You are out of money.");
+            say!("------------------------------------");
+            break;
+        }
+        if challenge.as_ref().is_some_and(|challenge| challenge.borrow().is_over()) {
+            break;
+        }
+
+        if !controller.play_again(&mut game) {
+            say!("Thanks for playing! Final Balance: {}", money(&game, game.get_player_balance()));
+            break;
+        }
+    }
+    if !game.settle_rebate().is_zero() {
+        say!("Final Balance after the rebate: {}", money(&game, game.get_player_balance()));
+    }
+    if let Some(attack) = &attack {
+        let score = attack.borrow().score();
+        say!("\n{}", score);
+        if let Some(tracker) = &profile
+            && tracker.borrow_mut().record_time_attack(score.score)
+        {
+            say!("New personal best!");
+        }
+    }
+    if let Some(challenge) = &challenge {
+        let mut challenge = challenge.borrow_mut();
+        challenge.forfeit("left the table");
+        let name = &challenge.spec().name;
+        match challenge.status() {
+            ChallengeStatus::Won => {
+                say!("\nChallenge complete: {}!", name);
+                if let Some(tracker) = &profile
+                    && campaign.is_none()
+                    && tracker.borrow_mut().record_challenge(&challenge.spec().id)
+                {
+                    say!("Added to your completed challenges.");
+                }
+            }
+            ChallengeStatus::Lost(reason) => say!("\nChallenge failed: {} ({}).", name, reason),
+            ChallengeStatus::InProgress => {}
+        }
+        if let (Some((campaign, progress)), Some(tracker)) = (&campaign, &profile) {
+            let won = *challenge.status() == ChallengeStatus::Won;
+            update_campaign(campaign, *progress, won, game.get_player_balance(), &mut tracker.borrow_mut());
+        }
+    }
+    let closing_balance = match wallet.as_mut() {
+        Some(wallet) => {
+            let chips = wallet.cash_out(&mut game);
+            say!("Cashed out {} in chips. Your wallet holds {}.", currency.amount(chips), currency.amount(wallet.cash(&currency)));
+            wallet.cash(&currency)
+        }
+        None => game.get_player_balance(),
+    };
+    if let Some(tracker) = &profile {
+        tracker.borrow_mut().finish(closing_balance);
+    }
+    if let Some(journal) = &journal
+        && let Err(e) = journal.borrow_mut().finish(game.get_player_balance())
+    {
+        eprintln!("Could not close session journal {}: {}", journal_path.display(), e);
+    }
+    show_session_stats(&session.borrow(), &currency);
+    if let Some(path) = options.chart.as_ref().map(PathBuf::from).or(config.chart) {
+        let mut balances = vec![opening_balance];
+        balances.extend(session.borrow().balance_series());
+        save_chart(&balances, &path);
+    }
+    if let Some(path) = &options.audit_log {
+        save_audit_log(&game, path);
+    }
+    if let Some((favoured, strength)) = secret {
+        let interactive = options.autoplay.is_none() && options.remote.is_none() && !machine;
+        reveal_training(game.wheel(), &favoured, strength, interactive);
+    }
+}
@@ -0,0 +1,300 @@
+// src/cli/profiles.rs
+
+//! Profiles at the command line: choosing one, the leaderboard, the
+//! challenges and tables a profile may take on, and changing its money
+//! between currencies.
+
+use std::process;
+
+use roulette_game::{input, prompt, say};
+use roulette_game::challenge::ChallengeSpec;
+use roulette_game::game::currency::{Currency, ExchangeRates};
+use roulette_game::history::RoundStore;
+use roulette_game::integrity::{Integrity, IntegrityKey};
+use roulette_game::profile::{self, BackendError, FileBackend, LeaderboardMetric, Passphrase, Profile, ProfileBackend};
+use roulette_game::tables::{Table, Unlock};
+
+use super::{get_string_input, get_u32_input, in_currency, load_config};
+use super::options::{Options, usage};
+
+pub fn profile_backend(options: &Options, passphrase: Option<Passphrase>) -> Box<dyn ProfileBackend> {
+    match &options.profile_store {
+        Some(spec) => profile::open_backend(spec, passphrase).unwrap_or_else(|e| {
+            eprintln!("Could not open profile store {}: {}", spec, e);
+            process::exit(1);
+        }),
+        None => {
+            let backend = FileBackend::default_location();
+            Box::new(match passphrase {
+                Some(passphrase) => backend.with_passphrase(passphrase),
+                None => backend,
+            })
+        }
+    }
+}
+
+fn ask_passphrase() -> Passphrase {
+    prompt!("Profile passphrase: ");
+    let input = input::read_line().unwrap_or_default();
+    let passphrase = input.trim_end_matches(['\r', '\n']);
+    if passphrase.is_empty() {
+        eprintln!("The passphrase can't be empty.");
+        process::exit(2);
+    }
+    Passphrase::new(passphrase)
+}
+
+pub fn show_leaderboard(backend: &dyn ProfileBackend, metric: LeaderboardMetric, rates: &ExchangeRates) {
+    let store = RoundStore::default_location();
+    let history = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        Vec::new()
+    });
+    // Bankrolls are in dollars, so the board ranks every table's rounds in them.
+    let history = in_currency(&history, &Currency::usd(), rates);
+    let board = match profile::leaderboard(backend, metric, &history) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Could not read profiles in {}: {}", backend.describe(), e);
+            process::exit(1);
+        }
+    };
+    say!("=== Leaderboard: {} ===", metric);
+    if board.ranking.is_empty() && metric == LeaderboardMetric::SessionRoi {
+        say!("No profile has played a session of {} rounds or more yet.", profile::MIN_ROI_ROUNDS);
+    } else if board.ranking.is_empty() {
+        say!("No profiles yet. Play with --profile <NAME> to get on the board.");
+    }
+    for (rank, (name, value)) in board.ranking.iter().enumerate() {
+        say!("{:>3}. {:<20} {:>10}", rank + 1, name, metric.format(*value));
+    }
+    if !board.rejected.is_empty() {
+        say!("Left off for tampering: {}", board.rejected.join(", "));
+    }
+}
+
+/// Lists the built-in challenges, ticking those the profile has completed.
+pub fn show_challenges(options: &Options) {
+    let completed = match &options.profile {
+        Some(name) => load_profile(options, name).1.lifetime.challenges,
+        None => Default::default(),
+    };
+    say!("=== Challenges ===");
+    for spec in ChallengeSpec::built_in() {
+        let mark = if completed.contains(&spec.id) { "[x]" } else { "[ ]" };
+        say!("{} {:<24} {}", mark, spec.id, spec.name);
+        say!("    {}", spec.description);
+    }
+    say!("Play one with --challenge <ID>, or --challenge <FILE> for your own.");
+}
+
+/// Lists the tables, marking those the profile hasn't opened yet.
+pub fn show_tables(options: &Options) {
+    let profile = options.profile.as_ref().map(|name| load_profile(options, name).1);
+    say!("=== Tables ===");
+    for table in Table::built_in() {
+        let mark = if table.is_open_to(profile.as_ref()) { "[open]  " } else { "[locked]" };
+        say!("{} {:<16} {:<20} {}", mark, table.id, table.name, table.limits());
+        say!("    {}", table.description);
+        if table.unlock != Unlock::default() {
+            say!("    Opens with {}.", table.unlock);
+        }
+    }
+    if profile.is_none() {
+        say!("VIP tables open with a profile's bankroll or level; see them with --profile <NAME>.");
+    }
+    say!("Sit at one with --table <ID>.");
+}
+
+/// Lists the profile's balances and the exchange rates, or changes money
+/// from one currency to another when given an amount.
+pub fn run_exchange(options: &Options) {
+    let Some(name) = &options.profile else {
+        eprintln!("exchange works on a --profile.");
+        usage();
+    };
+    let rates = load_config(options).exchange_rates;
+    let (backend, mut profile) = load_profile(options, name);
+    match (options.exchange_amount, &options.exchange_from, &options.exchange_to) {
+        (Some(amount), Some(from), Some(to)) => {
+            match profile.exchange(amount, from, to, &rates) {
+                Ok(converted) => say!("Changed {} into {}.", from.amount(amount), to.amount(converted)),
+                Err(e) => {
+                    eprintln!("Can't exchange: {}.", e);
+                    process::exit(1);
+                }
+            }
+            if let Err(e) = backend.save(&mut profile) {
+                eprintln!("Could not save profile {}: {}", name, e);
+                process::exit(1);
+            }
+        }
+        (None, None, None) => {}
+        _ => {
+            eprintln!("An exchange needs --amount, --from, and --to.");
+            usage();
+        }
+    }
+    say!("=== Balances ===");
+    for currency in rates.currencies() {
+        let rate = rates.rate(currency).unwrap_or(1.0);
+        let held = profile.balance(currency).unwrap_or_default();
+        say!("{:<6} {:>14}   ({} to the dollar)", currency, currency.amount(held), rate);
+    }
+}
+
+fn report_profile_integrity(backend: &dyn ProfileBackend, profile: &profile::Profile) {
+    if profile.version == 0 || !backend.signs() {
+        return;
+    }
+    let key = IntegrityKey::load(&IntegrityKey::default_location()).ok().flatten();
+    match profile.check(key.as_ref()) {
+        Integrity::Tampered => {
+            say!("Warning: this profile was edited outside the game. You can keep playing, but it stays off the leaderboard.")
+        }
+        integrity => say!("Profile integrity: {}.", integrity),
+    }
+}
+
+/// Loads the profile. With `--encrypt` the passphrase is asked for up
+/// front; otherwise only if the stored profile turns out to be encrypted.
+pub fn load_profile(options: &Options, name: &str) -> (Box<dyn ProfileBackend>, Profile) {
+    let mut passphrase = options.encrypt.then(ask_passphrase);
+    loop {
+        let backend = profile_backend(options, passphrase.clone());
+        match backend.load_or_create(name) {
+            Ok(profile) => {
+                say!(
+                    "Playing as {} ({}, sessions: {}, peak balance: {}).",
+                    profile.name, profile.progress(), profile.lifetime.sessions, profile.lifetime.peak_balance
+                );
+                report_profile_integrity(backend.as_ref(), &profile);
+                return (backend, profile);
+            }
+            Err(BackendError::Encrypted) if passphrase.is_none() => {
+                say!("Profile {} is encrypted.", name);
+                passphrase = Some(ask_passphrase());
+            }
+            Err(e) => {
+                eprintln!("Could not load profile {}: {}", name, e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// The profile screen shown at startup when no `--profile` is given. Returns
+/// the profile to play as, or `None` to play without one.
+pub fn choose_profile(options: &Options) -> Option<String> {
+    let backend = profile_backend(options, None);
+    loop {
+        let mut profiles = backend.list().unwrap_or_else(|e| {
+            eprintln!("Could not read profiles in {}: {}", backend.describe(), e);
+            Vec::new()
+        });
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        say!("\n=== Profiles ===");
+        for (i, profile) in profiles.iter().enumerate() {
+            let bankroll = profile.bankroll.map_or("no games yet".to_string(), |b| format!("bankroll {}", b));
+            say!("  {}) {:<20} {}, {}", i + 1, profile.name, profile.progress(), bankroll);
+        }
+        if profiles.is_empty() {
+            say!("  (no profiles yet)");
+        }
+        say!("n) New profile  l) Load  v) View stats  d) Delete  b) Leaderboard  Enter) Play without a profile");
+        // Enter on its own plays without a profile.
+        let choice = get_string_input("Choose: ")?;
+        match choice.as_str() {
+            "N" => {
+                prompt!("Profile name: ");
+                let name = input::read_line().unwrap_or_default().trim().to_string();
+                if name.is_empty() {
+                    continue;
+                }
+                match backend.load(&name) {
+                    Ok(None) => {
+                        say!("New profile {}. Its starting balance carries over from session to session.", name);
+                        return Some(name);
+                    }
+                    Ok(Some(_)) | Err(BackendError::Encrypted) => say!("There's already a profile called {}.", name),
+                    Err(e) => say!("Could not check for {}: {}.", name, e),
+                }
+            }
+            "B" => {
+                for (i, metric) in LeaderboardMetric::ALL.iter().enumerate() {
+                    say!("  {}) {}", i + 1, metric);
+                }
+                let number = get_u32_input(&format!("Rank by? (1-{}): ", LeaderboardMetric::ALL.len()));
+                match number.and_then(|n| LeaderboardMetric::ALL.get((n as usize).wrapping_sub(1))) {
+                    Some(&metric) => show_leaderboard(backend.as_ref(), metric, &load_config(options).exchange_rates),
+                    None => say!("Invalid choice. Please try again."),
+                }
+            }
+            "L" | "V" | "D" => {
+                let Some(profile) = pick_profile(&profiles) else { continue };
+                match choice.as_str() {
+                    "L" => return Some(profile.name.clone()),
+                    "V" => show_profile_stats(profile),
+                    _ => {
+                        let sure = get_string_input(&format!("Delete {} and all its progress for good? (y/N): ", profile.name));
+                        if sure.as_deref() != Some("Y") {
+                            continue;
+                        }
+                        match backend.delete(&profile.name) {
+                            Ok(_) => say!("Deleted {}.", profile.name),
+                            Err(e) => say!("Could not delete {}: {}.", profile.name, e),
+                        }
+                    }
+                }
+            }
+            _ => say!("Invalid choice. Please try again."),
+        }
+    }
+}
+
+/// Asks for one of `profiles` by number.
+fn pick_profile(profiles: &[Profile]) -> Option<&Profile> {
+    if profiles.is_empty() {
+        say!("There are no profiles yet.");
+        return None;
+    }
+    let number = get_u32_input(&format!("Which profile? (1-{}): ", profiles.len()))?;
+    let profile = profiles.get((number as usize).wrapping_sub(1));
+    if profile.is_none() {
+        say!("There's no profile {}.", number);
+    }
+    profile
+}
+
+fn show_profile_stats(profile: &Profile) {
+    let stats = &profile.lifetime;
+    say!("\n=== {} ===", profile.name);
+    say!("{}", profile.progress());
+    match profile.bankroll {
+        Some(bankroll) => say!("Bankroll: {}", bankroll),
+        None => say!("Bankroll: none yet"),
+    }
+    for (currency, amount) in &profile.balances {
+        say!("{} balance: {}", currency, amount);
+    }
+    match profile.days_since_played() {
+        Some(0) => say!("Last played: today"),
+        Some(days) => say!("Last played: {} day(s) ago", days),
+        None => say!("Last played: never"),
+    }
+    say!("Sessions: {}", stats.sessions);
+    say!("Rounds played: {}", stats.total_rounds);
+    say!("Most rounds in a session: {}", stats.rounds_survived);
+    say!("Peak balance: {}", stats.peak_balance);
+    say!("Biggest win: {}", stats.biggest_win);
+    say!("Comp points: {}", stats.comp_points);
+    if let Some(score) = stats.best_time_attack {
+        say!("Best time attack: {}", score);
+    }
+    if !stats.challenges.is_empty() {
+        say!("Challenges completed: {}", stats.challenges.len());
+    }
+    if profile.tampered {
+        say!("Edited outside the game, so kept off the leaderboard.");
+    }
+}
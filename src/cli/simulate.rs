@@ -0,0 +1,139 @@
+// src/cli/simulate.rs
+
+//! `simulate`, `optimize`, `bench`, and `house`: strategies and tables run
+//! without a player.
+
+use std::path::Path;
+use std::process;
+
+use roulette_game::{bench, say};
+use roulette_game::config;
+use roulette_game::game::controller::PlayerController;
+use roulette_game::game::dsl;
+use roulette_game::game::house::HouseTable;
+use roulette_game::game::money::Money;
+use roulette_game::optimize;
+use roulette_game::simulation;
+
+use super::{build_wheel, load_config, load_strategy_option, parse_bet_options, table_rules};
+use super::options::{Options, usage};
+
+pub fn run_bench(options: &Options) {
+    let wheel = load_config(options).layout();
+    let mut bets = parse_bet_options(options, &wheel);
+    if bets.is_empty() {
+        bets = bench::standard_bet_mix(&wheel);
+    }
+    let spins = options.bench_spins.unwrap_or(1_000_000);
+    say!("Benchmarking {} spins with:", spins);
+    for bet in &bets {
+        say!("  {}", dsl::format_bet(bet));
+    }
+    if cfg!(debug_assertions) {
+        say!("(debug build: build with --release for representative numbers)");
+    }
+    say!("{}", bench::run(&wheel, &bets, spins));
+}
+
+pub fn run_simulation(mut options: Options) {
+    let config = load_config(&options);
+    options.simulation.rules = table_rules(&config);
+    let wheel = build_wheel(&config);
+    let strategy = load_strategy_option(&options, wheel.layout());
+    let bets = parse_bet_options(&options, wheel.layout());
+    options.simulation.seed = options.seed.unwrap_or_else(rand::random);
+
+    say!("Simulating {} trials of up to {} rounds, starting with {} (seed {}).",
+        options.simulation.trials, options.simulation.rounds_per_trial,
+        options.simulation.starting_balance, options.simulation.seed);
+    let report = match &strategy {
+        Some(strategy) => {
+            say!("  {} ({} on {})", strategy.name(), strategy.stake(),
+                strategy.bet_types().iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", "));
+            simulation::simulate_strategy(wheel.as_ref(), strategy, &options.simulation)
+        }
+        None => {
+            for bet in &bets {
+                say!("  {}", dsl::format_bet(bet));
+            }
+            simulation::simulate(wheel.as_ref(), &bets, &options.simulation)
+        }
+    };
+    match report {
+        Ok(report) => say!("{}", report),
+        Err(e) => {
+            eprintln!("Simulation failed: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn run_optimize(mut options: Options) {
+    let config = load_config(&options);
+    options.simulation.rules = table_rules(&config);
+    let wheel = build_wheel(&config);
+    let Some(path) = &options.strategy else {
+        eprintln!("optimize needs a --strategy file.");
+        usage();
+    };
+    let spec = config::load_strategy(Path::new(path)).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    options.simulation.seed = options.seed.unwrap_or_else(rand::random);
+    let candidates = options.search.candidates(&spec).len();
+    say!("Trying {} settings of {} over {} trials of up to {} rounds each, starting with {} (seed {}).",
+        candidates, spec.name.as_deref().unwrap_or(path), options.simulation.trials,
+        options.simulation.rounds_per_trial, options.simulation.starting_balance, options.simulation.seed);
+
+    match optimize::grid_search(wheel.as_ref(), &spec, &options.search, options.objective, &options.simulation) {
+        Ok(results) => {
+            say!("\n{:>4} {:>6} {:>7} {:>10} {:>11} {:>8} {:>7} {:>7} {:>7}",
+                "Rank", "Base", "Cap", "Stop-loss", "Mean final", "Median", "Worst", "Ahead", "Bust");
+            for (rank, candidate) in results.iter().take(options.top.unwrap_or(10)).enumerate() {
+                say!("{:>4} {}", rank + 1, candidate);
+            }
+        }
+        Err(e) => {
+            eprintln!("Optimization failed: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+pub fn run_house(mut options: Options) {
+    let config = load_config(&options);
+    options.house.bot_balance = options.simulation.starting_balance;
+    options.house.seed = options.seed.unwrap_or_else(rand::random);
+    let rounds = options.simulation.rounds_per_trial;
+    let house = &options.house;
+    let starting_bankroll = house.bankroll;
+    let mut table = HouseTable::new(build_wheel(&config), house);
+    table.set_rules(&table_rules(&config));
+
+    say!("You are the house: {} bankroll against {} bots with {} each (seed {}).",
+        house.bankroll, house.bots, house.bot_balance, house.seed);
+    say!("Table limits: {} to {} per bet.", house.limits.min_bet, house.limits.max_bet);
+    for (name, strategy) in table.bots() {
+        say!("  {:<8} plays {}", name, strategy);
+    }
+    let mut played = 0;
+    while played < rounds {
+        let Some(round) = table.play_round() else { break };
+        say!("{}", round);
+        played += 1;
+    }
+
+    let net = table.bankroll() - starting_bankroll;
+    say!("\n=== House Results after {} rounds ===", played);
+    say!("Bots wagered:     {}", table.wagered());
+    say!("House net:        {:+}", net);
+    if !table.wagered().is_zero() {
+        say!("Realized edge:    {:.2}%", net.as_f64() / table.wagered().as_f64() * 100.0);
+    }
+    if table.bankroll() <= Money::ZERO {
+        say!("The house went broke!");
+    } else if !table.is_open() {
+        say!("Every bot has left the table.");
+    }
+}
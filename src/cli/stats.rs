@@ -0,0 +1,249 @@
+// src/cli/stats.rs
+
+//! `stats`, `compare`, `bias`, and `due`: what the recorded rounds add up to.
+
+use std::process;
+
+use roulette_game::say;
+use roulette_game::analysis::bias::BiasReport;
+use roulette_game::game::currency::Currency;
+use roulette_game::history::{self, AllTimeStats, Droughts, History, RoundStore, SessionReport, SessionStats, StatsExport, Totals};
+
+use super::{in_currency, load_config};
+use super::options::{Options, usage};
+
+/// `stats`: the all-time summary, or with `--export`, every recorded round
+/// as JSON.
+pub fn run(options: &Options) {
+    if let Some(target) = &options.stats_export {
+        if options.history_filter != history::Filter::default() || options.session.is_some() {
+            eprintln!("--export writes every recorded round; filters only apply to the summary.");
+            usage();
+        }
+        export_stats(options, target);
+        return;
+    }
+    show_all_time_stats(options);
+}
+
+fn show_all_time_stats(options: &Options) {
+    let store = if options.practice { RoundStore::practice_location() } else { RoundStore::default_location() };
+    let history = match History::load(&store) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Could not read round history {}: {}", store.path().display(), e);
+            process::exit(1);
+        }
+    };
+    if history.is_empty() {
+        say!("No rounds recorded yet.");
+        return;
+    }
+    let config = load_config(options);
+    let currency = &config.rules.currency;
+    let history = History::new(in_currency(history.records(), currency, &config.exchange_rates));
+    let filter = history::Filter { session: options.session.clone(), ..options.history_filter.clone() };
+    match filtered_stats(&history, filter) {
+        Some(stats) => print_all_time_stats("All-Time Statistics", &stats, currency),
+        None => say!("No recorded rounds match."),
+    }
+}
+
+/// Statistics over the rounds `filter` keeps, or `None` if it keeps none.
+pub fn filtered_stats(history: &History, filter: history::Filter) -> Option<AllTimeStats> {
+    if filter == history::Filter::default() {
+        return Some(AllTimeStats::from_records(history.records()));
+    }
+    let query = history.query().filter(filter);
+    query.matching_rounds().next()?;
+    Some(AllTimeStats::from_query(&query))
+}
+
+pub fn print_all_time_stats(title: &str, stats: &AllTimeStats, currency: &Currency) {
+    say!("=== {} ===", title);
+    say!("Sessions: {}", stats.sessions);
+    print_totals_header();
+    print_totals_row("All rounds", &stats.rounds, currency);
+    for (title, groups) in [
+        ("By Bet Type", &stats.by_bet_type),
+        ("By Wheel", &stats.by_wheel),
+        ("By Strategy", &stats.by_strategy),
+    ] {
+        say!("\n--- {} ---", title);
+        print_totals_header();
+        for (name, totals) in groups {
+            print_totals_row(name, totals, currency);
+        }
+    }
+}
+
+/// Writes every recorded round's statistics as JSON to `target`, in the
+/// table's currency.
+fn export_stats(options: &Options, target: &str) {
+    let store = RoundStore::default_location();
+    let records = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        process::exit(1);
+    });
+    let config = load_config(options);
+    let records = in_currency(&records, &config.rules.currency, &config.exchange_rates);
+    let export = StatsExport::from_records(&records, &config.rules.currency);
+    let json = serde_json::to_string_pretty(&export).expect("statistics always serialize");
+    if target == "-" {
+        say!("{}", json);
+        return;
+    }
+    if let Err(e) = std::fs::write(target, json + "\n") {
+        eprintln!("Could not export statistics to {}: {}", target, e);
+        process::exit(1);
+    }
+    say!("Wrote statistics for {} rounds to {}.", export.balance_series.len(), target);
+}
+
+/// Sets `--session` (or the latest session) beside `--against`, or beside
+/// the average of every recorded session.
+pub fn run_compare(options: &Options) {
+    let store = if options.practice { RoundStore::practice_location() } else { RoundStore::default_location() };
+    let records = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        process::exit(1);
+    });
+    let config = load_config(options);
+    let records = in_currency(&records, &config.rules.currency, &config.exchange_rates);
+    let latest = records.last().map(|r| r.session.clone());
+    let Some(session) = options.session.clone().or(latest) else {
+        eprintln!("No rounds recorded yet.");
+        process::exit(1);
+    };
+    let report = |session: &str| {
+        let rounds: Vec<history::RoundRecord> = records.iter().filter(|r| r.session == session).cloned().collect();
+        SessionReport::from_records(&rounds).unwrap_or_else(|| {
+            eprintln!("No rounds recorded for session {}.", session);
+            process::exit(1);
+        })
+    };
+    let left = report(&session);
+    let (label, right) = match &options.against {
+        Some(other) => (other.clone(), report(other)),
+        None => {
+            let average = SessionReport::from_records(&records).expect("the session's rounds are on record");
+            ("All-time average".to_string(), average)
+        }
+    };
+
+    say!("=== Session Comparison ===");
+    if options.against.is_none() {
+        say!("Averaged over {} session(s).", right.sessions);
+    }
+    say!("Amounts in {}.", config.rules.currency);
+    say!("{:<22} {:>18} {:>18} {:>12}", "", session, label, "Difference");
+    let row = |name: &str, a: f64, b: f64, unit: &str| {
+        let figure = |value: f64| format!("{}{}", round_figure(value), unit);
+        let difference = a - b;
+        let sign = if difference < 0.0 { "-" } else { "+" };
+        say!("{:<22} {:>18} {:>18} {:>12}", name, figure(a), figure(b), format!("{}{}", sign, figure(difference.abs())));
+    };
+    row("Rounds", left.rounds, right.rounds, "");
+    row("Wagered", left.wagered, right.wagered, "");
+    row("Net", left.net, right.net, "");
+    row("ROI", left.roi_percent, right.roi_percent, "%");
+    row("Round variance", left.variance, right.variance, "");
+    row("Round std dev", left.std_dev(), right.std_dev(), "");
+    row("Max drawdown", left.max_drawdown, right.max_drawdown, "");
+    row("Longest drawdown", left.longest_drawdown, right.longest_drawdown, " rds");
+    say!("\n--- Bet Mix (% of wagered) ---");
+    let kinds: std::collections::BTreeSet<&String> = left.bet_mix.keys().chain(right.bet_mix.keys()).collect();
+    for kind in kinds {
+        let share = |report: &SessionReport| report.bet_mix.get(kind).copied().unwrap_or(0.0);
+        row(kind, share(&left), share(&right), "%");
+    }
+}
+
+/// `value` to one decimal place, dropping a trailing `.0`.
+fn round_figure(value: f64) -> String {
+    let text = format!("{:.1}", value);
+    text.strip_suffix(".0").map(str::to_string).unwrap_or(text)
+}
+
+pub fn show_session_stats(session: &SessionStats, currency: &Currency) {
+    let by_bet_type = session.by_bet_type();
+    if by_bet_type.is_empty() {
+        return;
+    }
+    say!("\n--- This Session by Bet Type ({}) ---", session.difficulty());
+    print_totals_header();
+    for (name, totals) in &by_bet_type {
+        print_totals_row(name, totals, currency);
+    }
+    let rebates = session.rebates_paid();
+    if !rebates.is_zero() {
+        say!("Loss rebates paid: {}", currency.amount(rebates));
+    }
+}
+
+/// Tests the recorded spins on the chosen wheel (weighted variants included)
+/// for pockets that come up more often than chance allows.
+pub fn run_bias_check(options: &Options) {
+    let wheel = load_config(options).layout();
+    let store = RoundStore::default_location();
+    let records = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        process::exit(1);
+    });
+    let mut counts = vec![0u64; wheel.get_all_pockets().len()];
+    for record in history::spins_on_wheel(&records, wheel.name()) {
+        if let Some(id) = wheel.id_of_number(record.number) {
+            counts[id.0] += 1;
+        }
+    }
+    if counts.iter().all(|&n| n == 0) {
+        say!("No recorded spins on the {} wheel yet.", wheel.name());
+        return;
+    }
+    let labelled = wheel.get_all_pockets().iter().map(|p| p.ticker.to_string()).zip(counts).collect();
+    say!("{}", BiasReport::from_counts(labelled));
+}
+
+/// How many pockets and categories `due` lists unless `--top` says otherwise.
+const DUE_LISTED: usize = 10;
+
+pub fn run_due(options: &Options) {
+    let wheel = load_config(options).layout();
+    let store = RoundStore::default_location();
+    let records = store.load_all().unwrap_or_else(|e| {
+        eprintln!("Could not read round history {}: {}", store.path().display(), e);
+        process::exit(1);
+    });
+    let droughts = Droughts::from_records(&records, &wheel);
+    if droughts.spins == 0 {
+        say!("No recorded spins on the {} wheel yet.", wheel.name());
+        return;
+    }
+    say!("=== Longest Since Last Hit ({} spins on the {} wheel) ===", droughts.spins, wheel.name());
+    say!("Nothing is ever \"due\". The wheel has no memory: a pocket that hasn't hit in 200");
+    say!("spins is exactly as likely to hit next as one that hit last spin.");
+    let listed = options.top.unwrap_or(DUE_LISTED);
+    for (title, list) in [("Pockets", &droughts.pockets), ("Categories", &droughts.categories)] {
+        say!("\n{:<28} {:>12} {:>14}", title, "Spins since", "Average gap");
+        for drought in list.iter().take(listed) {
+            let since = drought.spins_since.map_or("never hit".to_string(), |s| s.to_string());
+            say!("{:<28} {:>12} {:>14.1}", drought.label, since, drought.expected_gap);
+        }
+    }
+}
+
+fn print_totals_header() {
+    say!("{:<20} {:>8} {:>12} {:>12} {:>12} {:>8}", "", "Count", "Wagered", "Returned", "Net", "ROI");
+}
+
+fn print_totals_row(name: &str, totals: &Totals, currency: &Currency) {
+    say!(
+        "{:<20} {:>8} {:>12} {:>12} {:>12} {:>7.1}%",
+        name,
+        totals.count,
+        currency.amount(totals.wagered),
+        currency.amount(totals.returned),
+        currency.amount(totals.net()),
+        totals.roi_percent()
+    );
+}
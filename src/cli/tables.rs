@@ -0,0 +1,183 @@
+// src/cli/tables.rs
+
+//! Croupier mode's shared ledger and multi-table play, where one player
+//! bets at several tables from one wallet.
+
+use roulette_game::say;
+use roulette_game::config::Config;
+use roulette_game::game::Game;
+use roulette_game::game::ledger::DealerLedger;
+use roulette_game::game::money::Money;
+use roulette_game::game::wallet::Wallet;
+use roulette_game::history::{HistoryRecorder, RoundStore};
+use roulette_game::tables::{Table, TableManager};
+
+use super::betting::{Again, ask_play_again, get_croupier_pocket, handle_betting};
+use super::{amount_prompt, build_wheel, get_amount_input, get_string_input, get_u32_input, money, table_rules};
+use super::options::Options;
+
+/// Croupier mode with several named bettors sharing one physical wheel.
+pub fn run_dealer_ledger(names: &[String], starting_balance: Money, config: &Config) {
+    let mut ledger = DealerLedger::with_wheel(names, starting_balance, config.layout());
+    ledger.set_rules(&table_rules(config));
+
+    loop {
+        say!("\n------------------------------------");
+        say!("Starting new round...");
+
+        for seat in ledger.seats_mut() {
+            if seat.game.get_player_balance().is_zero() {
+                continue;
+            }
+            say!("\n=== Bets for {} ===", seat.name);
+            handle_betting(&mut seat.game, true, !config.hide_expected_value, None, None, None, false);
+        }
+
+        if ledger.seats().iter().all(|s| !s.game.has_bets_in_play()) {
+            say!("No bets on the table this round.");
+        } else {
+            let pocket = get_croupier_pocket(ledger.wheel());
+            let sheet = ledger.settle(pocket);
+            say!("\n{}", sheet);
+        }
+
+        if !ledger.has_active_bettors() {
+            say!("\nEvery bettor is out of money. Table closed.");
+            break;
+        }
+
+        match ask_play_again(true) {
+            Again::Play => {}
+            Again::Void => match ledger.void_last_spin() {
+                Ok(()) => say!("Last spin voided. Its bets stand for the corrected spin."),
+                Err(e) => say!("Can't void: {}", e),
+            },
+            Again::Stop => {
+                say!("Table closed. Final balances:");
+                for seat in ledger.seats() {
+                    say!("  {:<16} {}", seat.name, money(&seat.game, seat.game.get_player_balance()));
+                }
+                break;
+            }
+        }
+    }
+}
+
+/// Several tables at once, each with its own bets, all bought into from one
+/// wallet holding the starting `bankroll`.
+pub fn run_multi_table(options: &Options, config: &Config, bankroll: Money) {
+    let currency = config.rules.currency.clone();
+    let mut manager = TableManager::new(Wallet::new(currency.clone(), bankroll));
+    say!("\nMulti-table play: buy in at as many tables as you like and switch between them.");
+    open_table(&mut manager, options, config);
+    loop {
+        say!("\n=== Your Tables === Wallet: {}", currency.amount(manager.wallet().cash(&currency)));
+        for (i, seat) in manager.seats().iter().enumerate() {
+            let mark = if manager.current_index() == Some(i) { ">" } else { " " };
+            let bets = game_bets_summary(&seat.game);
+            say!("{} {}) {:<20} {} in chips{}", mark, i + 1, seat.table.name, money(&seat.game, seat.game.get_player_balance()), bets);
+        }
+        if manager.seats().is_empty() {
+            say!("  (no tables open)");
+        }
+        say!("b) Bet here  s) Spin here  1-{}) Switch table  o) Open a table  t) Top up  l) Leave this table  q) Cash out and quit", manager.seats().len().max(1));
+        let Some(choice) = get_string_input("Choose: ") else { continue };
+        if let Ok(number) = choice.parse::<usize>() {
+            match manager.switch(number.wrapping_sub(1)) {
+                Ok(seat) => say!("Now at table {}, the {}.", number, seat.table.name),
+                Err(e) => say!("Can't switch: {}.", e),
+            }
+            continue;
+        }
+        match choice.as_str() {
+            "O" => open_table(&mut manager, options, config),
+            "Q" => break,
+            _ => {
+                let Some(index) = manager.current_index() else {
+                    say!("Open a table first.");
+                    continue;
+                };
+                match choice.as_str() {
+                    "B" => {
+                        let seat = manager.current_mut().expect("current table is open");
+                        handle_betting(
+                            &mut seat.game,
+                            true,
+                            !config.hide_expected_value,
+                            None,
+                            options.countdown,
+                            options.bets_file.as_deref(),
+                            false,
+                        );
+                    }
+                    "S" => {
+                        let seat = manager.current_mut().expect("current table is open");
+                        say!("Spinning at the {}...", seat.table.name);
+                        seat.game.spin_wheel_and_resolve();
+                    }
+                    "T" => {
+                        if let Some(amount) = get_amount_input(&amount_prompt("Buy how much more in chips", &currency))
+                            && let Err(e) = manager.top_up(index, amount)
+                        {
+                            say!("Can't buy in: {}.", e);
+                        }
+                    }
+                    "L" => {
+                        if let Ok((table, amount)) = manager.leave(index) {
+                            say!("Left the {} with {} in chips.", table.name, table.rules.currency.amount(amount));
+                        }
+                    }
+                    _ => say!("Invalid choice. Please try again."),
+                }
+            }
+        }
+    }
+    let wallet = manager.leave_all();
+    say!("Cashed out at every table. Your wallet holds {} (started with {}).",
+        currency.amount(wallet.cash(&currency)), currency.amount(bankroll));
+}
+
+/// ", 2 bet(s) down" for a game with bets waiting on a spin, else nothing.
+fn game_bets_summary(game: &Game) -> String {
+    let bets = game.get_current_bets();
+    if bets.is_empty() {
+        return String::new();
+    }
+    let staked: Money = bets.iter().map(|bet| bet.amount).sum();
+    format!(", {} bet(s) down for {}", bets.len(), money(game, staked))
+}
+
+/// Asks which table to sit down at and how much to buy in for.
+fn open_table(manager: &mut TableManager, options: &Options, config: &Config) {
+    let tables: Vec<Table> = Table::built_in().into_iter().filter(|table| table.is_open_to(None)).collect();
+    say!("\nTables open to you:");
+    for (i, table) in tables.iter().enumerate() {
+        say!("  {}) {:<20} {}", i + 1, table.name, table.limits());
+    }
+    let Some(mut table) = get_u32_input("Sit at which table? (Enter for none): ")
+        .and_then(|n| tables.get((n as usize).wrapping_sub(1)).cloned())
+    else {
+        return;
+    };
+    let minimum = table.rules.currency.amount(table.rules.buy_in_minimum());
+    let Some(amount) = get_amount_input(&format!("Buy in for how much? (at least {}): ", minimum)) else { return };
+    table.rules = match config.difficulty.apply(&table.rules, &config.layout()) {
+        Ok(rules) => rules,
+        Err(e) => {
+            say!("Can't open the {}: {}.", table.name, e);
+            return;
+        }
+    };
+    let mut game = Game::with_wheel(0, build_wheel(config));
+    game.set_exposure_limit(config.exposure);
+    if !options.no_history {
+        let recorder = HistoryRecorder::new(RoundStore::default_location(), game.wheel_variant().name(), "manual")
+            .with_currency(&table.rules.currency);
+        game.add_event_sink(Box::new(recorder));
+    }
+    let (name, chips) = (table.name.clone(), table.rules.currency.amount(amount));
+    match manager.open(table, game, amount) {
+        Ok(_) => say!("Sat down at the {} with {} in chips.", name, chips),
+        Err(e) => say!("Can't sit at the {}: {}.", name, e),
+    }
+}
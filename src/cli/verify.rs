@@ -0,0 +1,312 @@
+// src/cli/verify.rs
+
+//! `verify`, `replay`, `selftest`, `payouts`, and `lint-wheel`: checks that
+//! spins, settlements, payouts, and wheel files are what they should be.
+
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use roulette_game::{input, prompt, say, selftest};
+use roulette_game::config::{Config, WheelKind};
+use roulette_game::game::Game;
+use roulette_game::game::events::GameEvent;
+use roulette_game::game::fairness::{FairnessError, Reveal};
+use roulette_game::game::money::Money;
+use roulette_game::game::wheel::Wheel;
+use roulette_game::history::{self, History};
+use roulette_game::integrity;
+use roulette_game::journal::Journal;
+use roulette_game::replay::{self, RecordedSession, Replay, ReplayError};
+use roulette_game::tables::Table;
+use roulette_game::wheel_file::{Severity, WheelFile};
+
+use super::export::session_records;
+use super::{build_wheel, in_currency, load_config, load_rules, table_rules};
+use super::options::{Options, usage};
+use super::stats::{filtered_stats, print_all_time_stats};
+
+/// Reads `--input`, or the session journal if none is given.
+fn read_recording(options: &Options) -> (PathBuf, RecordedSession) {
+    let path = options.input.as_ref().map(PathBuf::from).unwrap_or_else(Journal::default_location);
+    let recorded = File::open(&path)
+        .map_err(ReplayError::from)
+        .and_then(|file| replay::read_session(io::BufReader::new(file)))
+        .unwrap_or_else(|e| {
+            eprintln!("Could not read recording {}: {}", path.display(), e);
+            process::exit(1);
+        });
+    (path, recorded)
+}
+
+/// The settings, with the wheel the recording names unless `--wheel` overrides it.
+fn recording_config(options: &Options, recorded: &RecordedSession) -> Config {
+    let mut config = load_config(options);
+    if options.wheel.is_none()
+        && options.wheel_file.is_none()
+        && let Some(kind) = recorded.wheel.as_deref().and_then(WheelKind::parse)
+    {
+        config.wheel = kind;
+        config.wheel_file = None;
+    }
+    if options.difficulty.is_none()
+        && let Some(level) = recorded.difficulty
+    {
+        config.difficulty = level;
+    }
+    if let Some(table) = options.table.as_deref().or(recorded.table.as_deref()) {
+        match Table::find(table) {
+            Some(table) => config.rules = table.rules,
+            None => eprintln!("The recording names table '{}', which doesn't exist; replaying with the configured rules.", table),
+        }
+    }
+    config
+}
+
+/// Replays a session journal or `--events` recording through a fresh game
+/// and reports any settlement that comes out differently.
+pub fn run_replay(options: &Options) {
+    let (path, recorded) = read_recording(options);
+    let config = recording_config(options, &recorded);
+    let mut game = Game::with_wheel(recorded.starting_balance, build_wheel(&config));
+    game.set_rules(table_rules(&config));
+    game.set_exposure_limit(config.exposure);
+    if let Some(path) = &options.rules {
+        load_rules(&mut game, path);
+    }
+
+    say!("Replaying {} from a starting balance of {}.", path.display(), recorded.starting_balance);
+    // Journals are signed; an --events stream never is.
+    if recorded.wheel.is_none() {
+        say!("Recording integrity: not signed (an event stream).");
+    } else {
+        match integrity::check_log_file(&path) {
+            Ok(integrity) => say!("Recording integrity: {}.", integrity),
+            Err(e) => eprintln!("Could not check the recording's integrity: {}", e),
+        }
+    }
+    let mut replay = Replay::new(game);
+    for (round, event) in &recorded.events {
+        let reported = replay.discrepancies().len();
+        let round_done = replay.apply(*round, event);
+        for discrepancy in &replay.discrepancies()[reported..] {
+            say!("MISMATCH {}", discrepancy);
+        }
+        if round_done && options.step {
+            prompt!("Press Enter for the next round...");
+            if input::read_line().is_none() {
+                break;
+            }
+        }
+    }
+
+    say!("\nReplayed {} round(s); final balance {}.", replay.rounds(), replay.game().get_player_balance());
+    if replay.discrepancies().is_empty() {
+        say!("Every settlement matches the recording.");
+    } else {
+        say!("{} discrepancies found.", replay.discrepancies().len());
+        process::exit(1);
+    }
+}
+
+/// Prints every problem with a wheel file, exiting 1 if any would stop it
+/// making a working wheel.
+pub fn run_lint_wheel(options: &Options) {
+    let path = Path::new(options.input.as_deref().unwrap_or_else(|| usage()));
+    let wheel = WheelFile::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let diagnostics = wheel.lint();
+    for diagnostic in &diagnostics {
+        say!("{}: {}", path.display(), diagnostic);
+    }
+    let errors = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error).count();
+    let warnings = diagnostics.len() - errors;
+    if diagnostics.is_empty() {
+        say!("{}: the {} wheel's {} pockets look sound.", path.display(), wheel.name, wheel.pockets.len());
+    } else {
+        say!("{}: {} error(s), {} warning(s).", path.display(), errors, warnings);
+    }
+    if errors > 0 {
+        process::exit(1);
+    }
+}
+
+/// A history session's spins, in the form [`replay::verify_spins`] takes.
+fn history_spins(options: &Options) -> RecordedSession {
+    let records = session_records(options);
+    let events = history::without_voided(&records)
+        .flat_map(|record| {
+            let seeded = record.seed.clone().map(|seed| (record.round, GameEvent::SpinSeeded { seed }));
+            let spin = GameEvent::Spin {
+                number: record.number,
+                ticker: record.ticker.clone(),
+                color: record.color,
+                categories: Vec::new(),
+            };
+            seeded.into_iter().chain([(record.round, spin)])
+        })
+        .collect();
+    // History records "european", or "european-weighted" for a weighted wheel.
+    let wheel = records[0].wheel.split('-').next().map(str::to_string);
+    RecordedSession { starting_balance: Money::ZERO, wheel, difficulty: None, table: None, events }
+}
+
+/// Reads hand histories back, settles every hand again, and shows the
+/// statistics they add up to. Exits with 1 if any payout is off.
+fn run_verify_hands(options: &Options, path: &str) {
+    let text = if path == "-" { io::read_to_string(io::stdin()) } else { std::fs::read_to_string(path) };
+    let hands = text
+        .map_err(|e| e.to_string())
+        .and_then(|text| history::hand::read_hands(&text, |name| hand_wheel(options, name)).map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Could not read hand histories {}: {}", path, e);
+            process::exit(1);
+        });
+    if hands.is_empty() {
+        say!("No hands found in {}.", path);
+        return;
+    }
+    let discrepancies: Vec<_> = hands
+        .iter()
+        .flat_map(|hand| hand.check(&hand_wheel(options, &hand.record.wheel).expect("every hand read names a known wheel")))
+        .collect();
+    say!("=== Hand History Verification: {} ===", path);
+    say!("Hands read:    {}", hands.len());
+    say!("Bets settled:  {}", hands.iter().map(|hand| hand.record.bets.len()).sum::<usize>());
+    for discrepancy in &discrepancies {
+        say!("MISMATCH {}", discrepancy);
+    }
+    let config = load_config(options);
+    let records: Vec<history::RoundRecord> = hands.into_iter().map(|hand| hand.record).collect();
+    let history = History::new(in_currency(&records, &config.rules.currency, &config.exchange_rates));
+    say!("");
+    match filtered_stats(&history, options.history_filter.clone()) {
+        Some(stats) => print_all_time_stats("Statistics", &stats, &config.rules.currency),
+        None => say!("No hands match."),
+    }
+    if !discrepancies.is_empty() {
+        say!("\nVerification FAILED: {} discrepanc{}.", discrepancies.len(), if discrepancies.len() == 1 { "y" } else { "ies" });
+        process::exit(1);
+    }
+    say!("\nEvery payout matches the engine.");
+}
+
+/// The wheel a hand history names, by its layout: a weighted or training
+/// variant pays like the wheel it's built on. `--wheel` overrides it.
+fn hand_wheel(options: &Options, name: &str) -> Option<Wheel> {
+    if let Some(path) = &options.wheel_file {
+        return WheelFile::load(path).ok()?.build().ok();
+    }
+    let kind = match options.wheel {
+        Some(kind) => kind,
+        None => WheelKind::parse(name.split('-').next()?)?,
+    };
+    Some(kind.layout())
+}
+
+/// Redoes every spin of a recorded session from its seed.
+fn run_verify_session(options: &Options, label: &str, recorded: RecordedSession) {
+    let config = recording_config(options, &recorded);
+    let wheel = build_wheel(&config);
+    let audit = replay::verify_spins(&recorded, wheel.as_ref(), &config.spin_model());
+    say!("=== Spin Verification: {} ===", label);
+    say!("Seeded spins reproduced:  {}", audit.verified);
+    if audit.fair > 0 {
+        say!("Provably fair spins:      {}", audit.fair);
+    }
+    if audit.unseeded > 0 {
+        say!("Spins without a seed:     {} (entered by a croupier or forced)", audit.unseeded);
+    }
+    for mismatch in &audit.mismatches {
+        say!("MISMATCH {}", mismatch);
+    }
+    for (round, error) in &audit.unfair {
+        say!("MISMATCH round {}: {}", round, error);
+    }
+    if audit.passed() {
+        say!("Verified: every seeded spin lands where the session says it did.");
+    } else {
+        say!("Verification FAILED. (Check that --wheel and --spin-model match the session.)");
+        process::exit(1);
+    }
+}
+
+pub fn run_verify(options: Options) {
+    if let Some(path) = &options.hands {
+        run_verify_hands(&options, path);
+        return;
+    }
+    if options.input.is_some() {
+        let (path, recorded) = read_recording(&options);
+        run_verify_session(&options, &path.display().to_string(), recorded);
+        return;
+    }
+    if let Some(session) = &options.session {
+        run_verify_session(&options, &format!("session {}", session), history_spins(&options));
+        return;
+    }
+    let wheel = load_config(&options).layout();
+    let (Some(server_seed), Some(commitment), Some(nonce)) = (options.server_seed, options.commitment, options.nonce)
+    else {
+        eprintln!("verify needs --server-seed, --nonce, and --commitment.");
+        usage();
+    };
+    let reveal = Reveal { server_seed, client_seed: options.client_seed.unwrap_or_default(), nonce, commitment };
+    let pocket_count = wheel.get_all_pockets().len();
+    let result = match &options.pocket {
+        Some(query) => {
+            let claimed = wheel.find_pocket(query).unwrap_or_else(|| {
+                eprintln!("Unknown pocket: {}", query);
+                process::exit(2);
+            });
+            reveal.verify_outcome(pocket_count, claimed).map(|_| claimed)
+        }
+        None => reveal.verify(pocket_count),
+    };
+    match result {
+        Ok(id) => {
+            let pocket = wheel.pocket(id);
+            say!("Verified: spin {} was committed in advance and lands on {} ({}).", reveal.nonce, pocket.ticker, pocket.display_name);
+        }
+        Err(FairnessError::OutcomeMismatch { expected, claimed }) => {
+            say!(
+                "Verification FAILED: the seeds give {}, not {}.",
+                wheel.pocket(expected).ticker, wheel.pocket(claimed).ticker
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            say!("Verification FAILED: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Tests the configured wheel and spin model, exiting with status 1 on failure.
+/// `payouts`: the expected return and house edge of every bet, exiting with
+/// an error when any pays more than it should.
+pub fn run_payouts(options: &Options) {
+    let config = load_config(options);
+    let wheel = config.layout();
+    let rules = table_rules(&config);
+    let report = wheel.verify_payouts(rules.payouts.as_ref().unwrap_or(wheel.payouts()));
+    say!("{}", report);
+    if !report.is_sound() {
+        process::exit(1);
+    }
+}
+
+pub fn run_selftest(options: &Options) {
+    let config = load_config(options);
+    let wheel = build_wheel(&config);
+    let spins = options.bench_spins.unwrap_or(selftest::DEFAULT_SPINS);
+    say!("Spinning {} times...", spins);
+    let report = selftest::run(wheel.as_ref(), &config.spin_model(), spins, options.seed, config.entropy);
+    say!("{}", report);
+    if !report.passed() {
+        process::exit(1);
+    }
+}
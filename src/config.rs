@@ -0,0 +1,334 @@
+// src/config.rs
+
+//! Settings file, `$HOME/.roulette/config.toml` by default.
+//!
+//! ```toml
+//! spin_model = "physics"   # or "uniform" (the default)
+//! wheel = "american"       # or "european" (the default) or "mini"
+//! wheel_file = "meme.toml" # plays this wheel file instead, see wheel_file
+//! hide_expected_value = true  # no odds advice (expected value, Kelly stake, round risk) when betting
+//! accessible = true        # plain output for screen readers, as with --accessible
+//! renderer = "plain"       # or "ansi" or "tui"; detected from the terminal by default
+//! chart = "balance.png"    # draw each session's balance here when it ends (charts builds)
+//! entropy = "os"           # or "rdseed", or "thread" (the default); see EntropySource
+//! difficulty = "brutal"    # or "easy", or "normal" (the default); adjusts [rules]
+//! weight_by = "beta"       # or "dividend_yield" or "cap_tier"; instead of [weights]
+//!
+//! [weights]                # optional bias, by ticker; unlisted pockets weigh 1
+//! NVDA = 1.5
+//!
+//! [metadata.TSLA]          # corrects the built-in figures, see PocketMetadata
+//! beta = 2.1
+//!
+//! [rules]                  # house rules, see TableRules
+//! zero_rule = "en_prison"
+//!
+//! [exchange_rates]         # units per US dollar, see ExchangeRates
+//! EUR = 0.92
+//!
+//! [exposure]               # warn past this share of the balance, see ExposureLimit
+//! percent = 50
+//!
+//! [palette]                # pocket colours, see Palette
+//! scheme = "color_blind"
+//! symbols = true
+//!
+//! [sound]                  # audio builds only
+//! volume = 0.5             # 0 to 1; 0.8 by default
+//! muted = true
+//!
+//! [physics]                # optional tuning, see PhysicsModel
+//! drop_speed = 5.5
+//! deflector_chance = 0.8
+//! ```
+//!
+//! Saving the file mid-session changes the game between rounds, as far as
+//! it can change without a restart; see [`ConfigWatcher`].
+
+use crate::game::currency::ExchangeRates;
+use crate::game::entropy::EntropySource;
+use crate::game::metadata::{MetadataWeighting, PocketMetadata};
+use crate::game::player::ExposureLimit;
+use crate::game::rules::{Difficulty, PlayerEdge, TableRules};
+use crate::game::spin::{PhysicsModel, SpinModel};
+use crate::game::strategy::StrategySpec;
+use crate::game::wheel::{WeightedWheel, Wheel, WheelLike};
+use crate::presentation::Palette;
+use crate::renderer::RendererKind;
+use crate::wheel_file::WheelFile;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Which spin model the config selects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpinModelKind {
+    #[default]
+    Uniform,
+    Physics,
+}
+
+impl SpinModelKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "uniform" => Some(SpinModelKind::Uniform),
+            "physics" => Some(SpinModelKind::Physics),
+            _ => None,
+        }
+    }
+}
+
+/// Which built-in wheel layout the config selects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WheelKind {
+    #[default]
+    European,
+    American,
+    Mini,
+}
+
+impl WheelKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "european" => Some(WheelKind::European),
+            "american" => Some(WheelKind::American),
+            "mini" => Some(WheelKind::Mini),
+            _ => None,
+        }
+    }
+
+    pub fn layout(self) -> Wheel {
+        match self {
+            WheelKind::European => Wheel::european(),
+            WheelKind::American => Wheel::american(),
+            WheelKind::Mini => Wheel::mini(),
+        }
+    }
+}
+
+/// Sound effect settings. Builds without the `audio` feature accept them
+/// and play nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SoundConfig {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        SoundConfig { volume: 0.8, muted: false }
+    }
+}
+
+/// Contents of the settings file. Every setting is optional.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub spin_model: SpinModelKind,
+    pub physics: PhysicsModel,
+    pub wheel: WheelKind,
+    /// A [wheel file](crate::wheel_file) to play on in place of `wheel`.
+    pub wheel_file: Option<PathBuf>,
+    /// The wheel file as [`load_wheel_file`](Config::load_wheel_file) read it.
+    #[serde(skip)]
+    custom_wheel: Option<WheelFile>,
+    /// Relative chance of each ticker coming up; empty for a fair wheel.
+    pub weights: BTreeMap<String, f64>,
+    /// Weights the wheel by the stocks' metadata instead.
+    pub weight_by: Option<MetadataWeighting>,
+    /// Stock metadata by ticker, over the built-in figures.
+    pub metadata: BTreeMap<String, PocketMetadata>,
+    pub rules: TableRules,
+    /// Skip the odds advice while betting: the Kelly stake, each bet's
+    /// expected value, and the round's risk summary.
+    pub hide_expected_value: bool,
+    /// Write for screen readers, see [`accessibility`](crate::accessibility).
+    pub accessible: bool,
+    pub palette: Palette,
+    /// How to write to the terminal; detected when unset.
+    pub renderer: Option<RendererKind>,
+    pub exposure: Option<ExposureLimit>,
+    /// PNG the session's balance chart is saved to when play ends.
+    pub chart: Option<PathBuf>,
+    /// Where spin seeds are drawn from.
+    pub entropy: EntropySource,
+    pub difficulty: Difficulty,
+    /// What other currencies are worth, for tables that play in them.
+    pub exchange_rates: ExchangeRates,
+    pub sound: SoundConfig,
+}
+
+/// Why the settings file could not be used.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, String),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, e) => write!(f, "can't read {}: {}", path.display(), e),
+            ConfigError::Parse(path, msg) => write!(f, "invalid config {}: {}", path.display(), msg),
+            ConfigError::Invalid(path, msg) => write!(f, "invalid config {}: {}", path.display(), msg),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    pub fn default_location() -> PathBuf {
+        crate::data_dir().join("config.toml")
+    }
+
+    /// Reads and validates a settings file.
+    pub fn load(path: &Path) -> Result<Config, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        let mut config: Config =
+            toml::from_str(&text).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))?;
+        config
+            .load_wheel_file()
+            .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("wheel_file: {}", msg)))?;
+        config
+            .physics
+            .validate()
+            .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("[physics] {}", msg)))?;
+        config
+            .rules
+            .validate()
+            .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("[rules] {}", msg)))?;
+        config
+            .table_rules()
+            .map_err(|e| ConfigError::Invalid(path.to_path_buf(), format!("[rules] {}", e)))?;
+        if config.exchange_rates.rate(&config.rules.currency).is_none() {
+            let msg = format!("[rules] currency {} has no rate in [exchange_rates]", config.rules.currency);
+            return Err(ConfigError::Invalid(path.to_path_buf(), msg));
+        }
+        if !(0.0..=1.0).contains(&config.sound.volume) {
+            let msg = format!("[sound] volume must be from 0 to 1, not {}", config.sound.volume);
+            return Err(ConfigError::Invalid(path.to_path_buf(), msg));
+        }
+        if let Some(exposure) = &config.exposure {
+            exposure
+                .validate()
+                .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("[exposure] {}", msg)))?;
+        }
+        config
+            .layout()
+            .with_metadata(&config.metadata)
+            .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("[metadata] {}", msg)))?;
+        config
+            .build_wheel()
+            .map_err(|msg| ConfigError::Invalid(path.to_path_buf(), format!("[weights] {}", msg)))?;
+        config
+            .entropy
+            .check()
+            .map_err(|e| ConfigError::Invalid(path.to_path_buf(), format!("entropy: {}", e)))?;
+        Ok(config)
+    }
+
+    /// Loads the default settings file, or the defaults if there isn't one.
+    pub fn load_default() -> Result<Config, ConfigError> {
+        let path = Config::default_location();
+        if path.exists() { Config::load(&path) } else { Ok(Config::default()) }
+    }
+
+    /// Reads `wheel_file`, if it's set, for [`layout`](Config::layout) to
+    /// play on. Fails if the file can't be read or can't make a wheel.
+    pub fn load_wheel_file(&mut self) -> Result<(), String> {
+        self.custom_wheel = None;
+        let Some(path) = &self.wheel_file else { return Ok(()) };
+        let file = WheelFile::load(path).map_err(|e| e.to_string())?;
+        file.build().map_err(|msg| format!("{}: {}", path.display(), msg))?;
+        self.custom_wheel = Some(file);
+        Ok(())
+    }
+
+    /// The wheel layout these settings select: the wheel file's once it has
+    /// been loaded, otherwise the `wheel` preset.
+    pub fn layout(&self) -> Wheel {
+        match (&self.wheel_file, &self.custom_wheel) {
+            // `load_wheel_file` already built it once, so it builds again.
+            (Some(_), Some(file)) => file.build().unwrap_or_else(|_| self.wheel.layout()),
+            _ => self.wheel.layout(),
+        }
+    }
+
+    /// The table rules with the difficulty applied, refused if some bet
+    /// would then favour the player.
+    pub fn table_rules(&self) -> Result<TableRules, PlayerEdge> {
+        self.difficulty.apply(&self.rules, &self.layout())
+    }
+
+    /// The spin model these settings select.
+    pub fn spin_model(&self) -> SpinModel {
+        match self.spin_model {
+            SpinModelKind::Uniform => SpinModel::Uniform,
+            SpinModelKind::Physics => SpinModel::Physics(self.physics.clone()),
+        }
+    }
+
+    /// The wheel these settings select with their metadata, weighted if
+    /// `weights` or `weight_by` is set.
+    pub fn build_wheel(&self) -> Result<Box<dyn WheelLike>, String> {
+        let layout = self.layout().with_metadata(&self.metadata)?;
+        match (self.weights.is_empty(), self.weight_by) {
+            (true, None) => Ok(Box::new(layout)),
+            (false, None) => Ok(Box::new(WeightedWheel::from_ticker_weights(layout, &self.weights)?)),
+            (true, Some(weighting)) => Ok(Box::new(WeightedWheel::from_metadata(layout, weighting)?)),
+            (false, Some(_)) => Err("can't be used together with weight_by".to_string()),
+        }
+    }
+}
+
+/// Notices when a settings file is saved, so a session can pick up the
+/// changes between rounds.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Watches `path` from now on; it needn't exist yet.
+    pub fn new(path: PathBuf) -> Self {
+        let modified = modified_time(&path);
+        ConfigWatcher { path, modified }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The settings saved since the last look, or why they can't be read.
+    /// `None` if the file hasn't changed or has been removed.
+    pub fn poll(&mut self) -> Option<Result<Config, ConfigError>> {
+        let modified = modified_time(&self.path)?;
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+        Some(Config::load(&self.path))
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Reads and checks a [strategy file](crate::game::strategy).
+pub fn load_strategy(path: &Path) -> Result<StrategySpec, ConfigError> {
+    let text = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+    let spec: StrategySpec = toml::from_str(&text).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))?;
+    spec.validate().map_err(|msg| ConfigError::Invalid(path.to_path_buf(), msg))?;
+    Ok(spec)
+}
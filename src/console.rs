@@ -0,0 +1,93 @@
+// src/console.rs
+
+//! Human-readable console output.
+//!
+//! Messages normally go to stdout. When stdout is claimed by a machine-readable
+//! stream (e.g. NDJSON events), call [`redirect_to_stderr`] so the two never mix.
+//!
+//! Without the `cli` feature nothing is written at all: an engine embedded in
+//! a server or compiled to WASM has no terminal to talk to.
+
+use core::fmt;
+#[cfg(feature = "cli")]
+use std::io::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static USE_STDERR: AtomicBool = AtomicBool::new(false);
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Sends all subsequent human-readable output to stderr instead of stdout.
+pub fn redirect_to_stderr() {
+    USE_STDERR.store(true, Ordering::Relaxed);
+}
+
+/// Switches human-readable output off for the rest of the run, for when
+/// stdout belongs to a program rather than a person.
+pub fn mute() {
+    MUTED.store(true, Ordering::Relaxed);
+}
+
+/// Runs `f` with human-readable output switched off, e.g. while bots bet.
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let was_muted = MUTED.swap(true, Ordering::Relaxed);
+    let result = f();
+    MUTED.store(was_muted, Ordering::Relaxed);
+    result
+}
+
+/// Writes formatted text without a trailing newline and flushes it,
+/// so prompts show up before input is read. The current
+/// [renderer](crate::renderer) has the last word on how it looks.
+#[cfg(feature = "cli")]
+pub fn write(args: fmt::Arguments<'_>) {
+    if MUTED.load(Ordering::Relaxed) {
+        return;
+    }
+    let text = args.to_string();
+    let text = crate::renderer::current().finish(&text).into_owned();
+    if USE_STDERR.load(Ordering::Relaxed) {
+        let mut err = io::stderr();
+        let _ = err.write_all(text.as_bytes());
+        let _ = err.flush();
+    } else {
+        let mut out = io::stdout();
+        let _ = out.write_all(text.as_bytes());
+        let _ = out.flush();
+    }
+}
+
+#[cfg(not(feature = "cli"))]
+pub fn write(_args: fmt::Arguments<'_>) {}
+
+/// Writes formatted text followed by a newline.
+pub fn write_line(args: fmt::Arguments<'_>) {
+    write(format_args!("{}\n", args));
+}
+
+/// Reports a problem the game can carry on through, such as a history file
+/// that couldn't be written. Always goes to stderr, even when muted.
+pub fn warn(args: fmt::Arguments<'_>) {
+    #[cfg(feature = "cli")]
+    eprintln!("{}", args);
+    #[cfg(not(feature = "cli"))]
+    let _ = args;
+}
+
+/// Like `println!`, but honours [`redirect_to_stderr`].
+#[macro_export]
+macro_rules! say {
+    () => {
+        $crate::console::write_line(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::write_line(format_args!($($arg)*))
+    };
+}
+
+/// Like `print!` followed by a flush, but honours [`redirect_to_stderr`].
+#[macro_export]
+macro_rules! prompt {
+    ($($arg:tt)*) => {
+        $crate::console::write(format_args!($($arg)*))
+    };
+}
@@ -1,16 +1,21 @@
 // src/game/bets.rs
 
-use super::wheel::{Color, Pocket};
-use crate::game::Wheel;
-use std::collections::HashSet;
-use std::fmt;
+use super::category::CategoryId;
+use super::money::Money;
+use super::payout::PayoutTable;
+use super::ticker::Ticker;
+use super::wheel::{Color, Dozen, PocketId, Wheel};
+use crate::say;
+use alloc::string::String;
+use core::fmt;
+use serde::{Deserialize, Serialize};
 
 /// Represents the different types of bets a player can make.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BetType {
     // Inside Bets
-    StraightUp(String),         // Bet on a single ticker (e.g., "AAPL")
-    Split(String, String),     // Bet on two tickers
+    StraightUp(Ticker),         // Bet on a single ticker (e.g., "AAPL")
+    Split(Ticker, Ticker),     // Bet on two tickers
     // Note: Street, Corner, SixLine may need ticker-based equivalents or removal if less relevant
 
     // Outside Bets (Traditional)
@@ -22,11 +27,14 @@ pub enum BetType {
     High,                      // Bet on numbers 19-36
 
     // Outside Bets (Wall Street-themed)
-    Category(String),          // Bet on a stock category (e.g., "Magnificent Seven")
+    Category(CategoryId),      // Bet on a stock category (e.g., "Magnificent Seven")
     GrowthDozen,               // Equivalent to Dozen 1 (Growth-focused stocks)
     ValueDozen,                // Equivalent to Dozen 2 (Value-focused stocks)
     BlueChipDozen,             // Equivalent to Dozen 3 (Blue-chip stocks)
-    Column(u8),                // Keep for compatibility, can represent sector groups later
+    Column(u8),                // One of the wheel's three sector columns, 1 to 3
+
+    // Bets registered at runtime, see `custom::BetEvaluator`
+    Custom(String),            // Name of the registered bet
 }
 
 impl fmt::Display for BetType {
@@ -45,150 +53,178 @@ impl fmt::Display for BetType {
             BetType::ValueDozen => write!(f, "Value Dozen"),
             BetType::BlueChipDozen => write!(f, "Blue Chip Dozen"),
             BetType::Column(c) => write!(f, "Column {}", c),
+            BetType::Custom(name) => write!(f, "{}", name),
         }
     }
 }
 
+/// Longest note a bet keeps, in characters; longer ones are cut short.
+pub const MAX_NOTE_CHARS: usize = 80;
+
 #[derive(Debug, Clone)]
 pub struct Bet {
     pub bet_type: BetType,
-    pub amount: u32,
+    pub amount: Money,
+    /// Why the player made the bet, kept through settlement into history.
+    pub note: Option<String>,
 }
 
 impl Bet {
-    pub fn new(bet_type: BetType, amount: u32) -> Self {
-        if amount == 0 {
+    pub fn new(bet_type: BetType, amount: impl Into<Money>) -> Self {
+        let amount = amount.into();
+        if amount <= Money::ZERO {
             panic!("Bet amount must be positive.");
         }
-        Bet { bet_type, amount }
+        Bet { bet_type, amount, note: None }
+    }
+
+    /// The bet with `note` attached, trimmed and cut to [`MAX_NOTE_CHARS`].
+    /// A blank note removes any earlier one.
+    pub fn with_note(mut self, note: &str) -> Self {
+        let note: String = note.trim().chars().take(MAX_NOTE_CHARS).collect();
+        self.note = (!note.is_empty()).then_some(note);
+        self
+    }
+
+    pub fn calculate_payout(&self) -> Money {
+        self.payout_with(&PayoutTable::STANDARD)
+    }
+
+    /// Total returned on a win under `table`, including the stake.
+    pub fn payout_with(&self, table: &PayoutTable) -> Money {
+        self.amount * table.multiplier(&self.bet_type) + self.amount
+    }
+
+    /// Checks the bet against the winning pocket.
+    pub fn check_win(&self, wheel: &Wheel, winning_id: PocketId) -> bool {
+        self.bet_type.covers(wheel, winning_id)
+    }
+}
+
+impl BetType {
+    /// Red, Black, Odd, Even, Low, and High: the bets zero rules apply to.
+    pub fn is_even_money(&self) -> bool {
+        matches!(self, BetType::Red | BetType::Black | BetType::Odd | BetType::Even | BetType::Low | BetType::High)
     }
 
-    pub fn calculate_payout(&self) -> u32 {
-        self.amount * payout_multiplier(&self.bet_type) + self.amount
+    /// Bets on the outside of the layout: colors, parity, halves, dozens,
+    /// columns, and categories. Custom bets count as neither.
+    pub fn is_outside(&self) -> bool {
+        !matches!(self, BetType::StraightUp(_) | BetType::Split(_, _) | BetType::Custom(_))
     }
 
-    pub fn check_win(&self, winning_pocket: &Pocket) -> bool {
+    /// Returns true if this bet type wins on the given pocket, using the
+    /// wheel's precomputed indexes for category, dozen, and column bets.
+    pub fn covers(&self, wheel: &Wheel, winning_id: PocketId) -> bool {
+        let winning_pocket = wheel.pocket(winning_id);
         let winning_number = winning_pocket.number;
         let winning_color = winning_pocket.color;
-        let winning_ticker = &winning_pocket.ticker;
-        let winning_categories = &winning_pocket.categories;
+        let winning_ticker = winning_pocket.ticker;
 
         // Zero (Recession/Surge) handling
-        if winning_number == 0 {
-            return match &self.bet_type {
-                BetType::StraightUp(ticker) => ticker == winning_ticker,
-                _ => false, // Zero loses for all standard outside bets
+        if winning_pocket.is_zero() {
+            return match self {
+                BetType::StraightUp(ticker) => *ticker == winning_ticker,
+                _ => false, // Outside bets don't cover a zero; the green-pocket rule settles them
             };
         }
 
-        match &self.bet_type {
+        match self {
+            // Custom bets are judged by their evaluator, which the wheel doesn't know.
+            BetType::Custom(_) => false,
+
             // Inside Bets
-            BetType::StraightUp(ticker) => winning_ticker == ticker,
-            BetType::Split(t1, t2) => winning_ticker == t1 || winning_ticker == t2,
+            BetType::StraightUp(ticker) => winning_ticker == *ticker,
+            BetType::Split(t1, t2) => winning_ticker == *t1 || winning_ticker == *t2,
 
             // Traditional Outside Bets
             BetType::Red => winning_color == Color::Red,
             BetType::Black => winning_color == Color::Black,
-            BetType::Odd => winning_number % 2 != 0,
-            BetType::Even => winning_number % 2 == 0,
-            BetType::Low => winning_number >= 1 && winning_number <= 18,
-            BetType::High => winning_number >= 19 && winning_number <= 36,
-            BetType::Column(col) => match col {
-                1 => winning_number % 3 == 1,
-                2 => winning_number % 3 == 2,
-                3 => winning_number % 3 == 0,
-                _ => false,
-            },
+            BetType::Odd => !winning_number.is_multiple_of(2),
+            BetType::Even => winning_number.is_multiple_of(2),
+            BetType::Low => (1..=wheel.highest_number() / 2).contains(&winning_number),
+            BetType::High => winning_number > wheel.highest_number() / 2,
 
             // Wall Street-themed Bets
-            BetType::Category(cat) => winning_categories.contains(cat),
-            BetType::GrowthDozen => winning_categories.contains(&"Growth Dozen A".to_string()),
-            BetType::ValueDozen => winning_categories.contains(&"Value Dozen B".to_string()),
-            BetType::BlueChipDozen => winning_categories.contains(&"Blue Chip Dozen C".to_string()),
+            BetType::Column(col) => wheel.in_column(*col, winning_id),
+            BetType::Category(cat) => wheel.in_category(*cat, winning_id),
+            BetType::GrowthDozen => wheel.in_dozen(Dozen::Growth, winning_id),
+            BetType::ValueDozen => wheel.in_dozen(Dozen::Value, winning_id),
+            BetType::BlueChipDozen => wheel.in_dozen(Dozen::BlueChip, winning_id),
         }
     }
 }
 
+/// Payout odds under the [standard table](PayoutTable::STANDARD).
 pub fn payout_multiplier(bet_type: &BetType) -> u32 {
-    match bet_type {
-        // Inside Bets
-        BetType::StraightUp(_) => 35,
-        BetType::Split(_, _) => 17,
-        // Outside Bets
-        BetType::Red => 1,
-        BetType::Black => 1,
-        BetType::Odd => 1,
-        BetType::Even => 1,
-        BetType::Low => 1,
-        BetType::High => 1,
-        BetType::Column(_) => 2,
-        BetType::Category(_) => 2, // Adjust based on category size if needed
-        BetType::GrowthDozen => 2,
-        BetType::ValueDozen => 2,
-        BetType::BlueChipDozen => 2,
-    }
+    PayoutTable::STANDARD.multiplier(bet_type)
 }
 
 // Helper functions for creating bets
-pub fn create_straight_up(ticker: &str, amount: u32, wheel: &Wheel) -> Option<Bet> {
-    if wheel.get_all_pockets().iter().any(|p| p.ticker == ticker) {
-        Some(Bet::new(BetType::StraightUp(ticker.to_string()), amount))
-    } else {
-        println!("Invalid ticker: {}. Please choose a valid stock ticker.", ticker);
-        None
+pub fn create_straight_up(ticker: &str, amount: impl Into<Money>, wheel: &Wheel) -> Option<Bet> {
+    match wheel.ticker(ticker) {
+        Some(ticker) => Some(Bet::new(BetType::StraightUp(ticker), amount)),
+        None => {
+            say!("Invalid ticker: {}. Please choose a valid stock ticker.", ticker);
+            None
+        }
     }
 }
 
-pub fn create_category_bet(category: &str, amount: u32, wheel: &Wheel) -> Option<Bet> {
-    if wheel.get_all_pockets().iter().any(|p| p.categories.contains(&category.to_string())) {
-        Some(Bet::new(BetType::Category(category.to_string()), amount))
-    } else {
-        println!("Invalid category: {}. Please choose a valid category.", category);
-        None
+pub fn create_category_bet(category: &str, amount: impl Into<Money>, wheel: &Wheel) -> Option<Bet> {
+    match wheel.resolve_category(category) {
+        Ok(id) => Some(Bet::new(BetType::Category(id), amount)),
+        Err(e) => {
+            match e.suggestion {
+                Some(suggestion) => say!("Invalid category: {}. Did you mean {}?", category, suggestion),
+                None => say!("Invalid category: {}. Please choose a valid category.", category),
+            }
+            None
+        }
     }
 }
 
-pub fn create_red_bet(amount: u32) -> Bet {
+pub fn create_red_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::Red, amount)
 }
 
-pub fn create_black_bet(amount: u32) -> Bet {
+pub fn create_black_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::Black, amount)
 }
 
-pub fn create_even_bet(amount: u32) -> Bet {
+pub fn create_even_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::Even, amount)
 }
 
-pub fn create_odd_bet(amount: u32) -> Bet {
+pub fn create_odd_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::Odd, amount)
 }
 
-pub fn create_low_bet(amount: u32) -> Bet {
+pub fn create_low_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::Low, amount)
 }
 
-pub fn create_high_bet(amount: u32) -> Bet {
+pub fn create_high_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::High, amount)
 }
 
-pub fn create_growth_dozen_bet(amount: u32) -> Bet {
+pub fn create_growth_dozen_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::GrowthDozen, amount)
 }
 
-pub fn create_value_dozen_bet(amount: u32) -> Bet {
+pub fn create_value_dozen_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::ValueDozen, amount)
 }
 
-pub fn create_blue_chip_dozen_bet(amount: u32) -> Bet {
+pub fn create_blue_chip_dozen_bet(amount: impl Into<Money>) -> Bet {
     Bet::new(BetType::BlueChipDozen, amount)
 }
 
-pub fn create_column_bet(column: u8, amount: u32) -> Option<Bet> {
-    if column >= 1 && column <= 3 {
+pub fn create_column_bet(column: u8, amount: impl Into<Money>) -> Option<Bet> {
+    if (1..=3).contains(&column) {
         Some(Bet::new(BetType::Column(column), amount))
     } else {
-        println!("Invalid column number (must be 1, 2, or 3).");
+        say!("Invalid column number (must be 1, 2, or 3).");
         None
     }
 }
@@ -0,0 +1,229 @@
+// src/game/category.rs
+
+//! Interned stock categories.
+//!
+//! Category names are interned once into a process-wide registry, and pockets
+//! and bets carry the resulting [`CategoryId`]. Comparing ids is a plain
+//! integer compare, and an id always knows its name for display.
+//!
+//! Each wheel keeps a [`CategoryRegistry`] of the categories its pockets
+//! have. That is where a name typed by a player is looked up, with aliases
+//! and without regard to case, and where a near miss finds a suggestion.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use spin::{Mutex, Once};
+
+/// An interned category name.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CategoryId(u32);
+
+impl CategoryId {
+    /// Returns the id for `name`, registering it if it hasn't been seen before.
+    pub fn intern(name: &str) -> CategoryId {
+        let mut registry = registry().lock();
+        registry.intern(name)
+    }
+
+    /// The category's name.
+    pub fn name(self) -> &'static str {
+        let registry = registry().lock();
+        registry.names[self.0 as usize]
+    }
+}
+
+struct Registry {
+    names: Vec<&'static str>,
+    ids: BTreeMap<&'static str, CategoryId>,
+}
+
+impl Registry {
+    fn intern(&mut self, name: &str) -> CategoryId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        // Categories are few and live for the whole program, so leaking is fine.
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let id = CategoryId(self.names.len() as u32);
+        self.names.push(name);
+        self.ids.insert(name, id);
+        id
+    }
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: Once<Mutex<Registry>> = Once::new();
+    REGISTRY.call_once(|| Mutex::new(Registry { names: Vec::new(), ids: BTreeMap::new() }))
+}
+
+impl fmt::Display for CategoryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Debug for CategoryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.name())
+    }
+}
+
+impl Serialize for CategoryId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for CategoryId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(CategoryId::intern(&name))
+    }
+}
+
+/// A category as a wheel offers it: the interned name and the other names
+/// players may call it by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub id: CategoryId,
+    pub aliases: Vec<String>,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        self.id.name()
+    }
+}
+
+/// The categories on one wheel, found by name or alias regardless of case,
+/// spacing, and punctuation: "mag 7", "MAGNIFICENT SEVEN", and
+/// "Magnificent-Seven" all name the Magnificent Seven.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryRegistry {
+    categories: BTreeMap<CategoryId, Category>,
+    /// Every name and alias, normalized by [`lookup_key`].
+    keys: BTreeMap<String, CategoryId>,
+}
+
+impl CategoryRegistry {
+    pub fn new(ids: impl IntoIterator<Item = CategoryId>) -> Self {
+        let mut registry = CategoryRegistry::default();
+        for id in ids {
+            registry.categories.insert(id, Category { id, aliases: Vec::new() });
+            registry.keys.entry(lookup_key(id.name())).or_insert(id);
+        }
+        registry
+    }
+
+    /// A registry of `ids` keeping the aliases this one has for them.
+    pub fn rebuilt(&self, ids: impl IntoIterator<Item = CategoryId>) -> Self {
+        let mut registry = CategoryRegistry::new(ids);
+        for category in self.categories.values() {
+            for alias in &category.aliases {
+                registry.add_alias(category.id, alias);
+            }
+        }
+        registry
+    }
+
+    /// Lets `alias` name the category. Returns false, changing nothing, if
+    /// the category isn't here or the alias already names something.
+    pub fn add_alias(&mut self, id: CategoryId, alias: &str) -> bool {
+        let key = lookup_key(alias);
+        let Some(category) = self.categories.get_mut(&id) else {
+            return false;
+        };
+        if key.is_empty() || self.keys.contains_key(&key) {
+            return false;
+        }
+        category.aliases.push(alias.to_string());
+        self.keys.insert(key, id);
+        true
+    }
+
+    pub fn get(&self, id: CategoryId) -> Option<&Category> {
+        self.categories.get(&id)
+    }
+
+    pub fn contains(&self, id: CategoryId) -> bool {
+        self.categories.contains_key(&id)
+    }
+
+    /// Every category, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &Category> {
+        let mut categories: Vec<&Category> = self.categories.values().collect();
+        categories.sort_by_key(|category| category.name());
+        categories.into_iter()
+    }
+
+    /// The category `name` refers to, by its name or an alias.
+    pub fn resolve(&self, name: &str) -> Result<CategoryId, UnknownCategory> {
+        let key = lookup_key(name);
+        self.keys
+            .get(&key)
+            .copied()
+            .ok_or_else(|| UnknownCategory { name: name.to_string(), suggestion: self.suggest(&key) })
+    }
+
+    /// The category whose name or alias starts with `key`, or is within a
+    /// couple of typos of it.
+    fn suggest(&self, key: &str) -> Option<CategoryId> {
+        if key.is_empty() {
+            return None;
+        }
+        if let Some((_, &id)) = self.keys.range(key.to_string()..).next().filter(|(known, _)| known.starts_with(key)) {
+            return Some(id);
+        }
+        let allowed = (key.len() / 3).max(2);
+        self.keys
+            .iter()
+            .map(|(known, &id)| (edit_distance(key, known), id))
+            .filter(|&(distance, _)| distance <= allowed)
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, id)| id)
+    }
+}
+
+/// A category name that matched nothing on the wheel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCategory {
+    pub name: String,
+    /// The closest category there is, if any is close.
+    pub suggestion: Option<CategoryId>,
+}
+
+impl fmt::Display for UnknownCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown category '{}'", self.name)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, "; did you mean '{}'?", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lower case letters and digits only, so lookups ignore case, spaces, and
+/// punctuation.
+fn lookup_key(name: &str) -> String {
+    name.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// Levenshtein distance: single-character inserts, deletes, and changes.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -0,0 +1,202 @@
+// src/game/chips.rs
+
+//! Chips: the balance as a stack of casino chips.
+//!
+//! A table that sets [`TableRules::chips`](super::rules::TableRules::chips)
+//! takes bets only in multiples of its smallest chip. A [`ChipStack`] fed the
+//! game's events keeps the player's balance as chips of those
+//! denominations: a stake is taken from the stack (a larger chip is broken
+//! for change when needed) and winnings are paid like a dealer pays them, in
+//! chips no bigger than the largest one staked. The stack fills up with small
+//! chips that way; [`ChipStack::color_up`] trades them in for as few chips as
+//! possible.
+//!
+//! The stack is only a picture of the balance; the [`Player`](super::player::Player)
+//! balance stays the figure that counts.
+
+use super::currency::Currency;
+use super::events::{BalanceChangeReason, EventSink, GameEvent};
+use super::money::Money;
+use serde::Deserialize;
+use std::fmt;
+
+/// The chip denominations a table uses, smallest first.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "Vec<u32>")]
+pub struct ChipSet(Vec<u32>);
+
+impl ChipSet {
+    pub fn new(mut denominations: Vec<u32>) -> Result<Self, String> {
+        if denominations.is_empty() {
+            return Err("chips needs at least one denomination".to_string());
+        }
+        if denominations.contains(&0) {
+            return Err("chip denominations must be at least 1".to_string());
+        }
+        denominations.sort_unstable();
+        denominations.dedup();
+        Ok(ChipSet(denominations))
+    }
+
+    /// The smallest chip; every stake is a multiple of it.
+    pub fn min_chip(&self) -> u32 {
+        self.0[0]
+    }
+
+    pub fn denominations(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<u32>> for ChipSet {
+    type Error = String;
+
+    fn try_from(denominations: Vec<u32>) -> Result<Self, String> {
+        ChipSet::new(denominations)
+    }
+}
+
+/// The usual casino denominations, $1 to $5000.
+impl Default for ChipSet {
+    fn default() -> Self {
+        ChipSet(vec![1, 5, 25, 100, 500, 1000, 5000])
+    }
+}
+
+/// A player's chips, kept level with their balance.
+#[derive(Debug, Clone)]
+pub struct ChipStack {
+    set: ChipSet,
+    /// Chips held of each denomination, in the set's order.
+    counts: Vec<u32>,
+    /// Money below the smallest chip, such as half of an odd stake handed
+    /// back on a surrender.
+    loose: Money,
+    /// Largest stake placed this round; winnings are paid in chips up to it.
+    largest_stake: Money,
+}
+
+impl ChipStack {
+    /// A stack worth `balance`, in as few chips as possible.
+    pub fn new(set: ChipSet, balance: Money) -> Self {
+        let counts = vec![0; set.0.len()];
+        let mut stack = ChipStack { set, counts, loose: Money::ZERO, largest_stake: Money::ZERO };
+        stack.add(balance, Money::MAX);
+        stack
+    }
+
+    pub fn chip_set(&self) -> &ChipSet {
+        &self.set
+    }
+
+    /// What the stack is worth.
+    pub fn total(&self) -> Money {
+        self.set.0.iter().zip(&self.counts).map(|(&chip, &count)| Money::from(chip) * count).sum::<Money>() + self.loose
+    }
+
+    /// Number of chips in the stack.
+    pub fn chip_count(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Chips of each denomination held, largest first, leaving out those
+    /// there are none of.
+    pub fn chips(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.set.0.iter().zip(&self.counts).rev().filter(|(_, count)| **count > 0).map(|(&chip, &count)| (chip, count))
+    }
+
+    /// "3 x €25, 1 x €5, €2 loose": the stack with its chips valued in
+    /// `currency`.
+    pub fn describe(&self, currency: &Currency) -> String {
+        let mut parts: Vec<String> = self.chips().map(|(chip, count)| format!("{} x {}", count, currency.amount(chip))).collect();
+        if self.loose > Money::ZERO {
+            parts.push(format!("{} loose", currency.amount(self.loose)));
+        }
+        if parts.is_empty() { "no chips".to_string() } else { parts.join(", ") }
+    }
+
+    /// Trades every chip in for the fewest chips of the same worth. Returns
+    /// the number of chips before and after.
+    pub fn color_up(&mut self) -> (u32, u32) {
+        let before = self.chip_count();
+        let total = self.total();
+        self.counts.iter_mut().for_each(|count| *count = 0);
+        self.loose = Money::ZERO;
+        self.add(total, Money::MAX);
+        (before, self.chip_count())
+    }
+
+    /// Adds `amount` in the largest chips up to `largest`, with anything
+    /// below the smallest chip kept loose.
+    fn add(&mut self, amount: Money, largest: Money) {
+        let mut left = amount.cents();
+        for (&chip, count) in self.set.0.iter().zip(self.counts.iter_mut()).rev() {
+            let chip = Money::from(chip).cents();
+            if chip <= largest.cents() {
+                *count += u32::try_from(left / chip).unwrap_or(u32::MAX);
+                left %= chip;
+            }
+        }
+        self.loose += Money::from_cents(left);
+    }
+
+    /// Takes `amount` off the stack, largest chips first, breaking a bigger
+    /// chip for change if what's left can't be made up exactly.
+    fn take(&mut self, amount: Money) {
+        let mut left = amount;
+        for (&chip, count) in self.set.0.iter().zip(self.counts.iter_mut()).rev() {
+            let chip = Money::from(chip);
+            let used = (*count).min(u32::try_from(left.cents() / chip.cents()).unwrap_or(u32::MAX));
+            *count -= used;
+            left -= chip * used;
+        }
+        let loose = self.loose.min(left);
+        self.loose -= loose;
+        left -= loose;
+        if left.is_zero() {
+            return;
+        }
+        // Every chip still held is bigger than what's left to take.
+        if let Some(index) = self.counts.iter().position(|&count| count > 0) {
+            let chip = Money::from(self.set.0[index]);
+            self.counts[index] -= 1;
+            self.add(chip - left, chip - Money::from_cents(1));
+        }
+    }
+
+    /// Starts over from `balance` if the stack no longer matches it, as after
+    /// a restored snapshot.
+    fn settle_on(&mut self, balance: Money) {
+        if self.total() != balance {
+            *self = ChipStack::new(self.set.clone(), balance);
+        }
+    }
+}
+
+impl EventSink for ChipStack {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::BetPlaced { amount, .. } => self.largest_stake = self.largest_stake.max(*amount),
+            GameEvent::BalanceChange { reason, delta, balance } => {
+                let amount = delta.abs();
+                if delta.is_negative() {
+                    self.take(amount);
+                } else if *reason == BalanceChangeReason::Winnings && self.largest_stake > Money::ZERO {
+                    self.add(amount, self.largest_stake);
+                } else {
+                    self.add(amount, Money::MAX);
+                }
+                self.settle_on(*balance);
+            }
+            GameEvent::RoundComplete { .. } => self.largest_stake = Money::ZERO,
+            _ => {}
+        }
+    }
+}
+
+/// The stack in dollars; see [`ChipStack::describe`] for other currencies.
+impl fmt::Display for ChipStack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(&Currency::usd()))
+    }
+}
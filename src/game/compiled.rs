@@ -0,0 +1,107 @@
+// src/game/compiled.rs
+
+//! Bets precompiled into pocket coverage masks for fast mass settlement.
+//!
+//! Interactive play settles through [`Bet::check_win`], which is easy to read
+//! and explain. Simulations settle millions of rounds, so they compile each
+//! bet once into a 64-bit mask over pocket ids; settling is then one shift and
+//! AND per bet.
+
+use super::bets::{Bet, BetType};
+use super::money::Money;
+use super::payout::PayoutTable;
+use super::wheel::{PocketId, Wheel};
+use alloc::vec::Vec;
+
+/// Largest wheel a coverage mask can describe.
+pub const MAX_COMPILED_POCKETS: usize = 64;
+
+/// Computes the pockets a bet type covers as a mask, or `None` if the wheel
+/// has more than [`MAX_COMPILED_POCKETS`] pockets.
+pub fn coverage_mask(bet_type: &BetType, wheel: &Wheel) -> Option<u64> {
+    let pockets = wheel.get_all_pockets().len();
+    if pockets > MAX_COMPILED_POCKETS {
+        return None;
+    }
+    Some(
+        (0..pockets)
+            .filter(|&i| bet_type.covers(wheel, PocketId(i)))
+            .fold(0u64, |mask, i| mask | (1 << i)),
+    )
+}
+
+/// A bet reduced to which pockets it covers and what it pays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompiledBet {
+    /// Bit `i` is set when the bet wins on `PocketId(i)`.
+    pub mask: u64,
+    /// Stake deducted when the bet is placed.
+    pub amount: Money,
+    /// Total returned on a win, including the stake.
+    pub payout: Money,
+}
+
+impl CompiledBet {
+    /// Compiles a bet against a wheel, or returns `None` if the wheel has
+    /// more than [`MAX_COMPILED_POCKETS`] pockets.
+    pub fn compile(bet: &Bet, wheel: &Wheel) -> Option<CompiledBet> {
+        Some(CompiledBet::from_mask(coverage_mask(&bet.bet_type, wheel)?, bet, wheel.payouts()))
+    }
+
+    /// Builds a compiled bet from an already computed coverage mask.
+    pub fn from_mask(mask: u64, bet: &Bet, payouts: &PayoutTable) -> CompiledBet {
+        CompiledBet { mask, amount: bet.amount, payout: bet.payout_with(payouts) }
+    }
+
+    pub fn wins(&self, winning_id: PocketId) -> bool {
+        (self.mask >> winning_id.0) & 1 == 1
+    }
+
+    /// Amount returned for the winning pocket (0 on a loss).
+    pub fn settle(&self, winning_id: PocketId) -> Money {
+        if self.wins(winning_id) { self.payout } else { Money::ZERO }
+    }
+
+    /// Number of pockets the bet covers.
+    pub fn coverage(&self) -> u32 {
+        self.mask.count_ones()
+    }
+}
+
+/// A full set of compiled bets for one round.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompiledLayout {
+    bets: Vec<CompiledBet>,
+    total_stake: Money,
+}
+
+impl CompiledLayout {
+    /// Compiles every bet, or returns `None` if the wheel is too large.
+    pub fn compile(bets: &[Bet], wheel: &Wheel) -> Option<CompiledLayout> {
+        let bets = bets
+            .iter()
+            .map(|bet| CompiledBet::compile(bet, wheel))
+            .collect::<Option<Vec<_>>>()?;
+        let total_stake = bets.iter().map(|b| b.amount).sum();
+        Some(CompiledLayout { bets, total_stake })
+    }
+
+    pub fn bets(&self) -> &[CompiledBet] {
+        &self.bets
+    }
+
+    /// Total amount staked on the layout.
+    pub fn total_stake(&self) -> Money {
+        self.total_stake
+    }
+
+    /// Total returned (stakes included) when the ball lands on `winning_id`.
+    pub fn settle(&self, winning_id: PocketId) -> Money {
+        self.bets.iter().map(|b| b.settle(winning_id)).sum()
+    }
+
+    /// Union of the pockets covered by any bet.
+    pub fn coverage_mask(&self) -> u64 {
+        self.bets.iter().fold(0, |mask, b| mask | b.mask)
+    }
+}
@@ -0,0 +1,88 @@
+// src/game/comps.rs
+
+//! Comp points, the house's thanks for playing.
+//!
+//! As on a casino player card, every dollar wagered earns points when the
+//! round is settled, whether the bet won or lost. Points can be cashed in
+//! for a small balance credit, or for insurance that pays back part of the
+//! next losing round.
+
+use super::currency::Currency;
+use super::money::Money;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Points earned per dollar wagered.
+pub const POINTS_PER_DOLLAR: u64 = 1;
+
+/// What comp points can be exchanged for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reward", rename_all = "snake_case")]
+pub enum CompReward {
+    /// Money added to the balance.
+    Credit { amount: u32 },
+    /// Half the net loss of the next losing round comes back, up to `cap`.
+    Insurance { cap: u32 },
+}
+
+impl CompReward {
+    /// Everything on offer, cheapest first.
+    pub const MENU: [CompReward; 3] = [
+        CompReward::Credit { amount: 5 },
+        CompReward::Insurance { cap: 50 },
+        CompReward::Credit { amount: 25 },
+    ];
+
+    /// Points the reward costs.
+    pub fn cost(self) -> u64 {
+        match self {
+            // Half a cent back per dollar wagered, a little better in bulk.
+            CompReward::Credit { amount } if amount >= 25 => amount as u64 * 180,
+            CompReward::Credit { amount } => amount as u64 * 200,
+            CompReward::Insurance { cap } => cap as u64 * 40,
+        }
+    }
+
+    /// What the reward is, with its amount in `currency`.
+    pub fn describe(self, currency: &Currency) -> String {
+        match self {
+            CompReward::Credit { amount } => format!("{} balance credit", currency.amount(amount)),
+            CompReward::Insurance { cap } => {
+                format!("insurance on the next losing round (half back, up to {})", currency.amount(cap))
+            }
+        }
+    }
+}
+
+impl fmt::Display for CompReward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(&Currency::usd()))
+    }
+}
+
+/// Why points couldn't be redeemed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompError {
+    NotEnoughPoints { cost: u64, points: u64 },
+    /// Insurance was bought and hasn't paid out yet.
+    AlreadyInsured,
+}
+
+impl fmt::Display for CompError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompError::NotEnoughPoints { cost, points } => {
+                write!(f, "that costs {} points and you have {}", cost, points)
+            }
+            CompError::AlreadyInsured => write!(f, "the next losing round is already insured"),
+        }
+    }
+}
+
+impl std::error::Error for CompError {}
+
+/// What insurance bought for up to `cap` pays on a round that staked
+/// `wagered` and returned `returned`.
+pub fn insurance_payout(cap: Money, wagered: Money, returned: Money) -> Money {
+    (wagered.saturating_sub(returned) / 2).min(cap)
+}
@@ -0,0 +1,185 @@
+// src/game/controller.rs
+
+//! Where a seat's bets come from.
+//!
+//! The table loop asks a [`PlayerController`] for bets every round, so an
+//! interactive player, a betting bot, and a player on the other end of a
+//! network connection all sit at the table the same way.
+
+use super::Game;
+use super::bets::{Bet, BetType};
+use super::dsl;
+use super::money::{Money, Units};
+use super::wheel::{PocketId, Wheel};
+use std::io::{self, BufRead, Write};
+
+/// What a controller wants to do this round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// Spin with whatever bets were placed (none sits the spin out).
+    Play,
+    /// Leave the table.
+    Leave,
+}
+
+/// A source of betting decisions for one seat.
+pub trait PlayerController {
+    /// Short label, recorded as the strategy in round history.
+    fn name(&self) -> &str;
+
+    /// Places this round's bets with [`Game::place_bet`].
+    fn place_bets(&mut self, game: &mut Game) -> Turn;
+
+    /// Sees the settled spin: the winning pocket and the total returned.
+    fn spin_settled(&mut self, _wheel: &Wheel, _pocket: PocketId, _returned: Money) {}
+
+    /// Asked after each round; returning false ends the session.
+    fn play_again(&mut self, _game: &mut Game) -> bool {
+        true
+    }
+}
+
+/// Places the same bets every round, optionally for a fixed number of rounds.
+#[derive(Debug, Clone)]
+pub struct FlatBettor {
+    bets: Vec<Bet>,
+    rounds_left: Option<u32>,
+}
+
+impl FlatBettor {
+    pub fn new(bets: Vec<Bet>) -> Self {
+        FlatBettor { bets, rounds_left: None }
+    }
+
+    /// Stops after `rounds` rounds.
+    pub fn for_rounds(mut self, rounds: u32) -> Self {
+        self.rounds_left = Some(rounds);
+        self
+    }
+}
+
+impl PlayerController for FlatBettor {
+    fn name(&self) -> &str {
+        "flat"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        let bets: Vec<Bet> = self
+            .bets
+            .iter()
+            .map(|bet| Bet { amount: game.rules().round_stake(bet.amount), ..bet.clone() })
+            .collect();
+        let stake: Money = bets.iter().map(|b| b.amount).sum();
+        if self.rounds_left == Some(0) || stake > game.get_player_balance() {
+            return Turn::Leave;
+        }
+        if let Some(rounds) = self.rounds_left.as_mut() {
+            *rounds -= 1;
+        }
+        for bet in bets {
+            game.place_bet(bet);
+        }
+        Turn::Play
+    }
+}
+
+/// Doubles its stake after every loss and drops back to the base stake after
+/// a win. Leaves once it can't cover the next stake.
+#[derive(Debug, Clone)]
+pub struct Martingale {
+    bet_type: BetType,
+    base: Money,
+    stake: Money,
+    cap: Money,
+}
+
+impl Martingale {
+    pub fn new(bet_type: BetType, base: impl Into<Money>) -> Self {
+        let base = base.into();
+        Martingale { bet_type, base, stake: base, cap: Money::MAX }
+    }
+
+    /// Starts over at the base stake instead of doubling past `cap`, as a
+    /// table maximum forces.
+    pub fn with_cap(mut self, cap: impl Into<Money>) -> Self {
+        self.cap = cap.into();
+        self
+    }
+}
+
+impl PlayerController for Martingale {
+    fn name(&self) -> &str {
+        "martingale"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        let stake = game.rules().round_stake(self.stake);
+        if self.stake.is_zero() || stake > game.get_player_balance() {
+            return Turn::Leave;
+        }
+        game.place_bet(Bet::new(self.bet_type.clone(), stake));
+        Turn::Play
+    }
+
+    fn spin_settled(&mut self, _wheel: &Wheel, _pocket: PocketId, returned: Money) {
+        let doubled = self.stake * 2;
+        self.stake = if returned > Money::ZERO || doubled > self.cap { self.base } else { doubled };
+    }
+}
+
+/// A player speaking a line protocol, e.g. over a TCP connection.
+///
+/// Each round the controller writes `balance <N>`, then reads bets in the
+/// [bet syntax](super::dsl), one per line, until an empty line or `spin`.
+/// Every line is answered with `ok` or `error: <reason>`. `quit` or the end
+/// of input leaves the table; after each spin it writes `result <TICKER> <RETURNED>`.
+pub struct RemotePlayer<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> RemotePlayer<R, W> {
+    pub fn new(input: R, output: W) -> Self {
+        RemotePlayer { input, output }
+    }
+
+    fn read_bets(&mut self, game: &mut Game) -> io::Result<Turn> {
+        writeln!(self.output, "balance {}", Units(game.get_player_balance()))?;
+        self.output.flush()?;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.input.read_line(&mut line)? == 0 {
+                return Ok(Turn::Leave);
+            }
+            match line.trim() {
+                "" | "spin" => return Ok(Turn::Play),
+                "quit" => return Ok(Turn::Leave),
+                spec => match dsl::parse_bet(spec, game.wheel()) {
+                    Ok(bet) => match game.try_place_bet(bet) {
+                        Ok(()) => writeln!(self.output, "ok")?,
+                        Err(e) => writeln!(self.output, "error: {}", e)?,
+                    },
+                    Err(e) => writeln!(self.output, "error: {}", e)?,
+                },
+            }
+            self.output.flush()?;
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> PlayerController for RemotePlayer<R, W> {
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        // A dropped connection is the player walking away.
+        self.read_bets(game).unwrap_or(Turn::Leave)
+    }
+
+    fn spin_settled(&mut self, wheel: &Wheel, pocket: PocketId, returned: Money) {
+        let _ = writeln!(self.output, "result {} {}", wheel.pocket(pocket).ticker, Units(returned));
+        let _ = self.output.flush();
+    }
+}
@@ -0,0 +1,197 @@
+// src/game/currency.rs
+
+//! Currencies and exchange between them.
+//!
+//! Every table plays in one [`Currency`], set by its
+//! [rules](super::rules::TableRules::currency); amounts at the table are
+//! [`Money`] in it. Balances in other currencies wait in the player's
+//! [`Wallet`](super::wallet::Wallet) or profile, and [`ExchangeRates`] convert
+//! between them.
+//!
+//! ```toml
+//! [exchange_rates]         # units of each currency per US dollar
+//! EUR = 0.92
+//! MBTC = 0.016             # Bitcoin chips of a thousandth of a coin
+//! ```
+
+use super::money::{Money, Units};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A currency code such as `USD`, always upper case.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Currency(String);
+
+impl Currency {
+    /// US dollars, the currency rates are quoted against and the one a table
+    /// plays in unless its rules say otherwise.
+    pub fn usd() -> Self {
+        Currency("USD".to_string())
+    }
+
+    /// Reads a code of 2 to 8 letters or digits, in any case.
+    pub fn parse(code: &str) -> Option<Self> {
+        let valid = (2..=8).contains(&code.len()) && code.chars().all(|c| c.is_ascii_alphanumeric());
+        valid.then(|| Currency(code.to_ascii_uppercase()))
+    }
+
+    pub fn code(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_usd(&self) -> bool {
+        self.0 == "USD"
+    }
+
+    /// `value` units of this currency, to show to the player.
+    pub fn amount(&self, value: impl Into<Money>) -> Amount {
+        Amount { value: value.into(), currency: self.clone() }
+    }
+
+    /// The sign written before an amount, for the currencies that have one
+    /// in everyday use.
+    pub fn sign(&self) -> Option<&'static str> {
+        match self.code() {
+            "USD" => Some("$"),
+            "EUR" => Some("€"),
+            "GBP" => Some("£"),
+            "JPY" => Some("¥"),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::usd()
+    }
+}
+
+impl TryFrom<String> for Currency {
+    type Error = String;
+
+    fn try_from(code: String) -> Result<Self, String> {
+        Currency::parse(&code).ok_or_else(|| format!("'{}' isn't a currency code", code))
+    }
+}
+
+impl From<Currency> for String {
+    fn from(currency: Currency) -> String {
+        currency.0
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+/// An amount of money as the player reads it: `$12.50` or `-€5` for a
+/// currency with a sign, `40 MBTC` for one without.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Amount {
+    pub value: Money,
+    pub currency: Currency,
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minus = match self.value {
+            value if value.is_negative() => "-",
+            _ if f.sign_plus() => "+",
+            _ => "",
+        };
+        let digits = Units(self.value.abs());
+        let text = match self.currency.sign() {
+            Some(sign) => format!("{}{}{}", minus, sign, digits),
+            None => format!("{}{} {}", minus, digits, self.currency),
+        };
+        f.pad(&text)
+    }
+}
+
+/// Why an exchange couldn't be made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExchangeError {
+    /// No rate is known for the currency.
+    UnknownCurrency(Currency),
+    /// The amount is worth less than a cent of the other currency.
+    TooSmall { amount: Money, from: Currency, to: Currency },
+    /// More than the balance holds.
+    NotEnough { balance: Money, currency: Currency },
+    /// More than can be held of the other currency, with what's held already.
+    TooMuch { currency: Currency },
+}
+
+impl fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExchangeError::UnknownCurrency(currency) => write!(f, "no exchange rate for {}", currency),
+            ExchangeError::TooSmall { amount, from, to } => {
+                write!(f, "{} is worth less than a cent in {}", from.amount(*amount), to)
+            }
+            ExchangeError::NotEnough { balance, currency } => write!(f, "only {} to exchange", currency.amount(*balance)),
+            ExchangeError::TooMuch { currency } => write!(f, "that's more {} than can be held", currency),
+        }
+    }
+}
+
+impl std::error::Error for ExchangeError {}
+
+/// Units of each currency a US dollar buys.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "BTreeMap<Currency, f64>")]
+pub struct ExchangeRates(BTreeMap<Currency, f64>);
+
+impl ExchangeRates {
+    pub fn new(mut rates: BTreeMap<Currency, f64>) -> Result<Self, String> {
+        if let Some((currency, rate)) = rates.iter().find(|(_, rate)| !(rate.is_finite() && **rate > 0.0)) {
+            return Err(format!("the {} rate must be above 0, not {}", currency, rate));
+        }
+        if rates.get(&Currency::usd()).is_some_and(|&rate| rate != 1.0) {
+            return Err("rates are per US dollar, so USD must be 1".to_string());
+        }
+        rates.insert(Currency::usd(), 1.0);
+        Ok(ExchangeRates(rates))
+    }
+
+    pub fn rate(&self, currency: &Currency) -> Option<f64> {
+        self.0.get(currency).copied()
+    }
+
+    pub fn currencies(&self) -> impl Iterator<Item = &Currency> {
+        self.0.keys()
+    }
+
+    /// What `amount` of `from` comes to in `to`, rounded down to the cent.
+    pub fn convert(&self, amount: Money, from: &Currency, to: &Currency) -> Result<Money, ExchangeError> {
+        let unknown = |currency: &Currency| ExchangeError::UnknownCurrency(currency.clone());
+        let from_rate = self.rate(from).ok_or_else(|| unknown(from))?;
+        let to_rate = self.rate(to).ok_or_else(|| unknown(to))?;
+        // Nudged up so a round trip at the same rates doesn't lose a cent to rounding.
+        let cents = (amount.cents() as f64 / from_rate * to_rate + 1e-6).floor();
+        if cents < 1.0 && amount > Money::ZERO {
+            return Err(ExchangeError::TooSmall { amount, from: from.clone(), to: to.clone() });
+        }
+        Ok(Money::from_cents(cents.min(Money::MAX.cents() as f64) as i64))
+    }
+}
+
+impl TryFrom<BTreeMap<Currency, f64>> for ExchangeRates {
+    type Error = String;
+
+    fn try_from(rates: BTreeMap<Currency, f64>) -> Result<Self, String> {
+        ExchangeRates::new(rates)
+    }
+}
+
+/// Dollars, euros, and Bitcoin chips of a thousandth of a coin.
+impl Default for ExchangeRates {
+    fn default() -> Self {
+        let rates = [("USD", 1.0), ("EUR", 0.92), ("MBTC", 0.016)];
+        ExchangeRates(rates.into_iter().map(|(code, rate)| (Currency(code.to_string()), rate)).collect())
+    }
+}
@@ -0,0 +1,145 @@
+// src/game/custom.rs
+
+//! Bet types defined outside this crate.
+//!
+//! A [`BetEvaluator`] decides which pockets a bet covers and what it pays.
+//! Once registered with [`Game::register_bet`](super::Game::register_bet),
+//! bets of type [`BetType::Custom`] with its name are placed and settled like
+//! any other, so an operator can offer, say, an "ESG stocks only" bet without
+//! touching [`BetType`].
+//!
+//! The game's own [`SideBet`]s are evaluators too, registered when a table's
+//! rules offer them.
+
+use super::bets::{Bet, BetType};
+use super::money::Money;
+use super::payout::PayoutTable;
+use super::wheel::{PocketId, PocketSet, Wheel};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Rules for one custom bet type.
+pub trait BetEvaluator: Send + Sync {
+    /// Name players bet on; matched case-insensitively.
+    fn name(&self) -> &str;
+
+    /// True if the bet wins when the ball lands on `pocket`.
+    fn covers(&self, wheel: &Wheel, pocket: PocketId) -> bool;
+
+    /// Payout odds ("N to 1") on this wheel.
+    fn payout_multiplier(&self, wheel: &Wheel) -> u32;
+
+    /// Every pocket the bet covers.
+    fn coverage(&self, wheel: &Wheel) -> PocketSet {
+        let mut set = PocketSet::default();
+        for id in (0..wheel.get_all_pockets().len()).map(PocketId) {
+            if self.covers(wheel, id) {
+                set.insert(id);
+            }
+        }
+        set
+    }
+}
+
+/// Why a bet type could not be registered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterBetError {
+    /// Another custom bet already uses the name.
+    Duplicate(String),
+}
+
+impl fmt::Display for RegisterBetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterBetError::Duplicate(name) => write!(f, "a bet named '{}' is already registered", name),
+        }
+    }
+}
+
+impl std::error::Error for RegisterBetError {}
+
+/// Custom bet types known to a game, by lowercased name.
+#[derive(Default)]
+pub struct BetRegistry {
+    evaluators: HashMap<String, Box<dyn BetEvaluator>>,
+}
+
+impl BetRegistry {
+    pub fn register(&mut self, evaluator: Box<dyn BetEvaluator>) -> Result<(), RegisterBetError> {
+        let key = evaluator.name().to_lowercase();
+        if self.evaluators.contains_key(&key) {
+            return Err(RegisterBetError::Duplicate(evaluator.name().to_string()));
+        }
+        self.evaluators.insert(key, evaluator);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn BetEvaluator> {
+        self.evaluators.get(&name.to_lowercase()).map(|e| e.as_ref())
+    }
+
+    /// Names of the registered bets, sorted.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.evaluators.values().map(|e| e.name()).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// True if the bet can be settled: built-in, or custom and registered.
+    pub fn knows(&self, bet_type: &BetType) -> bool {
+        match bet_type {
+            BetType::Custom(name) => self.get(name).is_some(),
+            _ => true,
+        }
+    }
+
+    /// Amount returned for `bet` when the ball lands on `pocket`, stake
+    /// included, or nothing if it loses. Built-in bets are paid from `payouts`.
+    pub fn settle(&self, bet: &Bet, wheel: &Wheel, payouts: &PayoutTable, pocket: PocketId) -> Money {
+        match &bet.bet_type {
+            BetType::Custom(name) => match self.get(name) {
+                Some(evaluator) if evaluator.covers(wheel, pocket) => {
+                    bet.amount * evaluator.payout_multiplier(wheel) + bet.amount
+                }
+                _ => Money::ZERO,
+            },
+            _ if bet.check_win(wheel, pocket) => bet.payout_with(payouts),
+            _ => Money::ZERO,
+        }
+    }
+}
+
+/// Side bets that come with the game, offered where the
+/// [table rules](super::rules::TableRules::side_bets) list them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SideBet {
+    /// The ball lands on a zero.
+    BlackSwan,
+    /// The ball lands on the lowest or the highest number.
+    Bookends,
+}
+
+impl BetEvaluator for SideBet {
+    fn name(&self) -> &str {
+        match self {
+            SideBet::BlackSwan => "Black Swan",
+            SideBet::Bookends => "Bookends",
+        }
+    }
+
+    fn covers(&self, wheel: &Wheel, pocket: PocketId) -> bool {
+        let pocket = wheel.pocket(pocket);
+        match self {
+            SideBet::BlackSwan => pocket.is_zero(),
+            SideBet::Bookends => !pocket.is_zero() && (pocket.number == 1 || pocket.number == wheel.highest_number()),
+        }
+    }
+
+    /// Paid as a wheel without zeros would pay, like the built-in bets.
+    fn payout_multiplier(&self, wheel: &Wheel) -> u32 {
+        let covered = self.coverage(wheel).len().max(1) as u32;
+        (wheel.highest_number() as u32 / covered).saturating_sub(1).max(1)
+    }
+}
@@ -0,0 +1,179 @@
+// src/game/dsl.rs
+
+//! A compact text format for bets, e.g. `red 10`, `straight AAPL 5`,
+//! `category Magnificent Seven 20`, or `column 2 $12.50`.
+//!
+//! The bet kind comes first and the amount last; anything in between is the
+//! kind's argument (a ticker, two tickers, a category name, a column, or
+//! the name of a custom bet, as in `custom ESG Leaders 10`).
+//!
+//! A file of bets, as [`parse_bets`] reads it, has one bet per line. Blank
+//! lines and lines starting with `#` are skipped, and a `#` after a bet
+//! starts its note: `red 10  # hedging the tech bets`.
+
+use super::category::UnknownCategory;
+use super::bets::{Bet, BetType};
+use super::money::{Money, MoneyError, Units};
+use super::ticker::{InvalidTicker, Ticker};
+use super::wheel::Wheel;
+use std::fmt;
+
+/// Why a bet line could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBetError {
+    Empty,
+    MissingAmount,
+    InvalidAmount(String),
+    AmountTooLarge,
+    UnknownKind(String),
+    MissingArgument(&'static str),
+    InvalidTicker(InvalidTicker),
+    UnknownTicker(String),
+    UnknownCategory(UnknownCategory),
+    InvalidColumn(String),
+}
+
+impl fmt::Display for ParseBetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBetError::Empty => write!(f, "empty bet"),
+            ParseBetError::MissingAmount => write!(f, "missing bet amount"),
+            ParseBetError::InvalidAmount(a) => write!(f, "invalid amount '{}' (must be more than 0, to the cent)", a),
+            ParseBetError::AmountTooLarge => write!(f, "{}", MoneyError::TooLarge),
+            ParseBetError::UnknownKind(k) => write!(f, "unknown bet type '{}'", k),
+            ParseBetError::MissingArgument(what) => write!(f, "missing {}", what),
+            ParseBetError::InvalidTicker(e) => write!(f, "{}", e),
+            ParseBetError::UnknownTicker(t) => write!(f, "unknown ticker '{}'", t),
+            ParseBetError::UnknownCategory(e) => write!(f, "{}", e),
+            ParseBetError::InvalidColumn(c) => write!(f, "invalid column '{}' (must be 1, 2, or 3)", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseBetError {}
+
+/// A bad line in a list of bets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBetsError {
+    /// Counting from 1.
+    pub line: usize,
+    pub error: ParseBetError,
+}
+
+impl fmt::Display for ParseBetsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl std::error::Error for ParseBetsError {}
+
+/// Parses one bet, validating tickers and categories against the wheel.
+pub fn parse_bet(line: &str, wheel: &Wheel) -> Result<Bet, ParseBetError> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (kind, rest) = words.split_first().ok_or(ParseBetError::Empty)?;
+    let (amount, args) = rest.split_last().ok_or(ParseBetError::MissingAmount)?;
+    let amount = parse_amount(amount)?;
+    let bet_type = parse_bet_type(kind, args, wheel)?;
+    Ok(Bet::new(bet_type, amount))
+}
+
+/// Parses one bet with an optional note after a `#`, as in
+/// `red 10  # hedging the tech bets`.
+pub fn parse_bet_line(line: &str, wheel: &Wheel) -> Result<Bet, ParseBetError> {
+    let (bet, note) = line.split_once('#').unwrap_or((line, ""));
+    Ok(parse_bet(bet, wheel)?.with_note(note))
+}
+
+/// Parses a list of bets, one per line; see the [module docs](self).
+pub fn parse_bets(text: &str, wheel: &Wheel) -> Result<Vec<Bet>, ParseBetsError> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| parse_bet_line(line, wheel).map_err(|error| ParseBetsError { line: i + 1, error }))
+        .collect()
+}
+
+/// Parses what a bet is on without an amount, e.g. `straight AAPL`.
+pub fn parse_bet_kind(text: &str, wheel: &Wheel) -> Result<BetType, ParseBetError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let (kind, args) = words.split_first().ok_or(ParseBetError::Empty)?;
+    parse_bet_type(kind, args, wheel)
+}
+
+fn parse_amount(word: &str) -> Result<Money, ParseBetError> {
+    match Money::parse(word) {
+        Ok(amount) if amount > Money::ZERO => Ok(amount),
+        Err(MoneyError::TooLarge) => Err(ParseBetError::AmountTooLarge),
+        _ => Err(ParseBetError::InvalidAmount(word.to_string())),
+    }
+}
+
+fn parse_ticker(word: Option<&&str>, wheel: &Wheel) -> Result<Ticker, ParseBetError> {
+    let word = word.ok_or(ParseBetError::MissingArgument("ticker"))?;
+    let ticker = Ticker::parse(word).map_err(ParseBetError::InvalidTicker)?;
+    match wheel.id_of_ticker(ticker) {
+        Some(_) => Ok(ticker),
+        None => Err(ParseBetError::UnknownTicker(word.to_string())),
+    }
+}
+
+fn parse_bet_type(kind: &str, args: &[&str], wheel: &Wheel) -> Result<BetType, ParseBetError> {
+    let bet_type = match kind.to_lowercase().as_str() {
+        "red" => BetType::Red,
+        "black" => BetType::Black,
+        "odd" => BetType::Odd,
+        "even" => BetType::Even,
+        "low" => BetType::Low,
+        "high" => BetType::High,
+        "growth" | "growth-dozen" => BetType::GrowthDozen,
+        "value" | "value-dozen" => BetType::ValueDozen,
+        "bluechip" | "blue-chip" | "blue-chip-dozen" => BetType::BlueChipDozen,
+        "straight" | "straight-up" => BetType::StraightUp(parse_ticker(args.first(), wheel)?),
+        "split" => BetType::Split(parse_ticker(args.first(), wheel)?, parse_ticker(args.get(1), wheel)?),
+        "category" => {
+            if args.is_empty() {
+                return Err(ParseBetError::MissingArgument("category name"));
+            }
+            let name = args.join(" ");
+            let id = wheel.resolve_category(&name).map_err(ParseBetError::UnknownCategory)?;
+            BetType::Category(id)
+        }
+        "custom" => {
+            if args.is_empty() {
+                return Err(ParseBetError::MissingArgument("custom bet name"));
+            }
+            BetType::Custom(args.join(" "))
+        }
+        "column" => {
+            let arg = args.first().ok_or(ParseBetError::MissingArgument("column number"))?;
+            match arg.parse::<u8>() {
+                Ok(column @ 1..=3) => BetType::Column(column),
+                _ => return Err(ParseBetError::InvalidColumn(arg.to_string())),
+            }
+        }
+        _ => return Err(ParseBetError::UnknownKind(kind.to_string())),
+    };
+    Ok(bet_type)
+}
+
+/// Formats a bet in the same syntax [`parse_bet`] accepts.
+pub fn format_bet(bet: &Bet) -> String {
+    let kind = match &bet.bet_type {
+        BetType::Red => "red".to_string(),
+        BetType::Black => "black".to_string(),
+        BetType::Odd => "odd".to_string(),
+        BetType::Even => "even".to_string(),
+        BetType::Low => "low".to_string(),
+        BetType::High => "high".to_string(),
+        BetType::GrowthDozen => "growth".to_string(),
+        BetType::ValueDozen => "value".to_string(),
+        BetType::BlueChipDozen => "bluechip".to_string(),
+        BetType::StraightUp(ticker) => format!("straight {}", ticker),
+        BetType::Split(t1, t2) => format!("split {} {}", t1, t2),
+        BetType::Category(id) => format!("category {}", id),
+        BetType::Column(column) => format!("column {}", column),
+        BetType::Custom(name) => format!("custom {}", name),
+    };
+    format!("{} {}", kind, Units(bet.amount))
+}
@@ -0,0 +1,130 @@
+// src/game/entropy.rs
+
+//! Where spin seeds come from.
+//!
+//! By default seeds are drawn from `rand`'s thread generator, a ChaCha
+//! stream the operating system seeds once per thread. Operators who have to
+//! document their entropy source can instead take every seed straight from
+//! the operating system's CSPRNG (`getrandom`) or, on x86-64 processors that
+//! have it, from the hardware `RDSEED` instruction. Both need the
+//! `hardware-entropy` feature.
+
+use super::fairness::to_hex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Bytes of entropy behind each seed.
+const SEED_BYTES: usize = 32;
+
+/// Where spin and server seeds are drawn from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntropySource {
+    /// `rand`'s thread generator, seeded from the operating system.
+    #[default]
+    Thread,
+    /// The operating system's CSPRNG, read afresh for every seed.
+    Os,
+    /// The CPU's `RDSEED` instruction (x86-64 only).
+    Rdseed,
+}
+
+/// Why an entropy source can't be used here.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntropyError {
+    /// The build lacks the `hardware-entropy` feature.
+    NotBuilt,
+    /// This processor has no `RDSEED`.
+    NoRdseed,
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyError::NotBuilt => {
+                write!(f, "this build has no hardware entropy support (enable the `hardware-entropy` feature)")
+            }
+            EntropyError::NoRdseed => write!(f, "this processor has no RDSEED instruction"),
+        }
+    }
+}
+
+impl std::error::Error for EntropyError {}
+
+impl EntropySource {
+    /// Checks that this build and machine can draw from the source.
+    pub fn check(self) -> Result<(), EntropyError> {
+        match self {
+            EntropySource::Thread => Ok(()),
+            EntropySource::Os if cfg!(feature = "hardware-entropy") => Ok(()),
+            EntropySource::Rdseed if cfg!(feature = "hardware-entropy") => {
+                if has_rdseed() { Ok(()) } else { Err(EntropyError::NoRdseed) }
+            }
+            _ => Err(EntropyError::NotBuilt),
+        }
+    }
+
+    /// A fresh 256-bit seed as lowercase hex. The source must have passed
+    /// [`EntropySource::check`].
+    pub fn random_seed(self) -> String {
+        let mut bytes = [0u8; SEED_BYTES];
+        match self {
+            EntropySource::Thread => rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut bytes),
+            EntropySource::Os => os_fill(&mut bytes),
+            EntropySource::Rdseed => rdseed_fill(&mut bytes),
+        }
+        to_hex(&bytes)
+    }
+}
+
+impl fmt::Display for EntropySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropySource::Thread => write!(f, "thread generator (ChaCha, seeded by the OS)"),
+            EntropySource::Os => write!(f, "operating system CSPRNG (getrandom)"),
+            EntropySource::Rdseed => write!(f, "CPU hardware generator (RDSEED)"),
+        }
+    }
+}
+
+#[cfg(feature = "hardware-entropy")]
+fn os_fill(bytes: &mut [u8]) {
+    rand::RngCore::fill_bytes(&mut rand::rngs::OsRng, bytes);
+}
+
+#[cfg(not(feature = "hardware-entropy"))]
+fn os_fill(_bytes: &mut [u8]) {
+    panic!("{}", EntropyError::NotBuilt);
+}
+
+#[cfg(all(feature = "hardware-entropy", target_arch = "x86_64"))]
+fn has_rdseed() -> bool {
+    std::arch::is_x86_feature_detected!("rdseed")
+}
+
+#[cfg(not(all(feature = "hardware-entropy", target_arch = "x86_64")))]
+fn has_rdseed() -> bool {
+    false
+}
+
+#[cfg(all(feature = "hardware-entropy", target_arch = "x86_64"))]
+fn rdseed_fill(bytes: &mut [u8]) {
+    assert!(has_rdseed(), "{}", EntropyError::NoRdseed);
+    for chunk in bytes.chunks_mut(8) {
+        let word = loop {
+            let mut word = 0u64;
+            // SAFETY: the processor supports RDSEED, checked above.
+            if unsafe { std::arch::x86_64::_rdseed64_step(&mut word) } == 1 {
+                break word;
+            }
+            // RDSEED runs dry under contention; Intel's advice is to retry.
+            std::hint::spin_loop();
+        };
+        chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+    }
+}
+
+#[cfg(not(all(feature = "hardware-entropy", target_arch = "x86_64")))]
+fn rdseed_fill(_bytes: &mut [u8]) {
+    panic!("{}", EntropyError::NotBuilt);
+}
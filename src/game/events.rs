@@ -0,0 +1,206 @@
+// src/game/events.rs
+
+//! Structured game events and the sinks that consume them.
+
+use super::bets::BetType;
+use super::category::CategoryId;
+use super::comps::CompReward;
+use super::fairness::Reveal;
+use super::money::Money;
+use super::wheel::{Color, WheelEdit};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+/// Something that happened during play, in the order it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent {
+    /// A bet was accepted and its stake deducted.
+    BetPlaced {
+        bet_type: BetType,
+        amount: Money,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+    /// A note was attached to a standing bet, the `index`th placed this round.
+    BetNoted { index: usize, note: String },
+    /// All standing bets were cancelled and refunded.
+    BetsCleared { refunded: Money },
+    /// The wheel was spun and the ball landed on a pocket.
+    Spin {
+        number: u8,
+        ticker: String,
+        color: Color,
+        categories: Vec<CategoryId>,
+    },
+    /// A single bet was settled against the winning pocket.
+    Settlement {
+        bet_type: BetType,
+        amount: Money,
+        won: bool,
+        payout: Money,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+    /// A losing even-money bet was surrendered: half its stake came back.
+    /// Follows the bet's `Settlement`, whose payout already includes the refund.
+    Surrendered { bet_type: BetType, amount: Money, refunded: Money },
+    /// Every bet of the round has been settled.
+    RoundComplete {
+        wagered: Money,
+        returned: Money,
+        balance: Money,
+    },
+    /// The player's balance moved.
+    BalanceChange {
+        reason: BalanceChangeReason,
+        delta: Money,
+        balance: Money,
+    },
+    /// An ordinary spin was drawn from this seed; feeding it to
+    /// [`seeded_rng`](super::spin::seeded_rng) with the same wheel and spin
+    /// model gives the same pocket.
+    SpinSeeded { seed: String },
+    /// The outcome of the next spin was fixed and its commitment published.
+    SpinCommitted { nonce: u64, commitment: String },
+    /// The green-pocket rule carried outside bets of `of_round` to a re-spin,
+    /// which follows as a round of its own.
+    Respin { of_round: u32 },
+    /// A settled spin was reversed: its winnings were taken back and its bets
+    /// stand again.
+    SpinVoided { voided_round: u32, ticker: String, returned: Money },
+    /// A different wheel was brought in between rounds, with every pocket
+    /// of the old one and possibly more.
+    WheelChanged { wheel: String },
+    /// A pocket of the wheel in play was added, removed, renamed, or put in
+    /// or taken out of a category. `refunded` is the total of the bets
+    /// handed back because they could no longer win.
+    WheelEdited {
+        #[serde(flatten)]
+        edit: WheelEdit,
+        refunded: Money,
+    },
+    /// A standing bet was handed back, stake and all, because a wheel edit
+    /// left it unable to win.
+    BetRefunded { bet_type: BetType, amount: Money },
+    /// The seed behind a committed spin was revealed after settlement.
+    SpinRevealed {
+        #[serde(flatten)]
+        reveal: Reveal,
+    },
+    /// The player's comp points moved: earned on a settled round, taken back
+    /// with a voided one, or spent.
+    CompPoints { delta: i64, points: u64 },
+    /// The loss-rebate promotion paid back part of a period's net loss.
+    RebatePaid { loss: Money, amount: Money },
+    /// Comp points were exchanged for a reward.
+    CompRedeemed {
+        #[serde(flatten)]
+        reward: CompReward,
+        cost: u64,
+    },
+}
+
+/// Why the player's balance changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalanceChangeReason {
+    /// Money brought to the table, such as the opening balance.
+    Deposit,
+    BetPlaced,
+    Refund,
+    Winnings,
+    /// Winnings taken back when a spin was voided.
+    Void,
+    /// The game was restored from a snapshot.
+    Restore,
+    /// Comp points paid out, as a credit or through round insurance.
+    Comp,
+    /// A loss rebate, see [`LossRebate`](super::rules::LossRebate).
+    Rebate,
+    /// Wallet cash changed into chips on sitting down, see
+    /// [`Wallet`](super::wallet::Wallet).
+    BuyIn,
+    /// Chips changed back into wallet cash on leaving.
+    CashOut,
+}
+
+/// Receives every event emitted by a [`Game`](super::Game).
+pub trait EventSink {
+    fn record(&mut self, round: u32, event: &GameEvent);
+}
+
+/// Lets a caller keep reading a sink after handing it to the game.
+impl<S: EventSink> EventSink for Rc<RefCell<S>> {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        self.borrow_mut().record(round, event);
+    }
+}
+
+/// Fans events out to registered sinks, stamping each with the current round.
+#[derive(Default)]
+pub struct EventBus {
+    round: u32,
+    sinks: Vec<Box<dyn EventSink>>,
+}
+
+impl EventBus {
+    pub fn new(round: u32) -> Self {
+        EventBus { round, sinks: Vec::new() }
+    }
+
+    pub fn add_sink(&mut self, sink: Box<dyn EventSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn next_round(&mut self) {
+        self.round += 1;
+    }
+
+    /// Sends an event to every sink. The event is only built when someone is
+    /// listening, so unobserved games don't pay for the allocations.
+    pub fn emit(&mut self, make_event: impl FnOnce() -> GameEvent) {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let event = make_event();
+        for sink in self.sinks.iter_mut() {
+            sink.record(self.round, &event);
+        }
+    }
+}
+
+/// Writes each event as one JSON object per line (NDJSON).
+pub struct NdjsonSink<W: Write> {
+    writer: W,
+}
+
+#[derive(Serialize)]
+struct Envelope<'a> {
+    round: u32,
+    #[serde(flatten)]
+    event: &'a GameEvent,
+}
+
+impl<W: Write> NdjsonSink<W> {
+    pub fn new(writer: W) -> Self {
+        NdjsonSink { writer }
+    }
+}
+
+impl<W: Write> EventSink for NdjsonSink<W> {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        let line = serde_json::to_string(&Envelope { round, event })
+            .expect("game events are always serializable");
+        // Flush per line so `roulette --events - | jq` sees events as they happen.
+        if let Err(e) = writeln!(self.writer, "{}", line).and_then(|_| self.writer.flush()) {
+            crate::console::warn(format_args!("Failed to write event: {}", e));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// src/game/fairness.rs
+
+//! Provably fair spins using commit–reveal.
+//!
+//! Before bets are taken the house picks a fresh random server seed and
+//! publishes a commitment, `SHA-256("{server_seed}:{nonce}")`. The pocket is
+//! derived from `SHA-256("{server_seed}:{client_seed}:{nonce}:{attempt}")`,
+//! so the player's client seed also shapes the outcome. Once the spin is
+//! settled the server seed is revealed, and anyone can check that it matches
+//! the commitment and reproduces the winning pocket.
+
+use super::entropy::EntropySource;
+use super::wheel::PocketId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Why a revealed spin failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FairnessError {
+    /// The revealed seed and nonce don't hash to the published commitment.
+    CommitmentMismatch { expected: String, actual: String },
+    /// The seeds reproduce a different pocket than the one claimed.
+    OutcomeMismatch { expected: PocketId, claimed: PocketId },
+}
+
+impl fmt::Display for FairnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FairnessError::CommitmentMismatch { expected, actual } => {
+                write!(f, "commitment mismatch: published {}, revealed seed hashes to {}", expected, actual)
+            }
+            FairnessError::OutcomeMismatch { expected, claimed } => {
+                write!(f, "outcome mismatch: seeds give pocket {}, but pocket {} was claimed", expected.0, claimed.0)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FairnessError {}
+
+/// A spin whose outcome is fixed and committed to, but not yet revealed.
+#[derive(Debug, Clone)]
+pub struct FairSpin {
+    server_seed: String,
+    client_seed: String,
+    nonce: u64,
+    commitment: String,
+}
+
+impl FairSpin {
+    /// Commits to a new spin with a freshly generated server seed.
+    pub fn new(client_seed: &str, nonce: u64) -> Self {
+        FairSpin::with_entropy(client_seed, nonce, EntropySource::Thread)
+    }
+
+    /// Like [`FairSpin::new`], drawing the server seed from `source`.
+    pub fn with_entropy(client_seed: &str, nonce: u64, source: EntropySource) -> Self {
+        let server_seed = source.random_seed();
+        let commitment = commitment_for(&server_seed, nonce);
+        FairSpin { server_seed, client_seed: client_seed.to_string(), nonce, commitment }
+    }
+
+    /// Hash to publish before bets are taken.
+    pub fn commitment(&self) -> &str {
+        &self.commitment
+    }
+
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The pocket this spin lands on, for a wheel with `pocket_count` pockets.
+    pub fn outcome(&self, pocket_count: usize) -> PocketId {
+        outcome_for(&self.server_seed, &self.client_seed, self.nonce, pocket_count)
+    }
+
+    /// Gives up the server seed so the spin can be verified.
+    pub fn reveal(self) -> Reveal {
+        Reveal {
+            server_seed: self.server_seed,
+            client_seed: self.client_seed,
+            nonce: self.nonce,
+            commitment: self.commitment,
+        }
+    }
+}
+
+/// Everything a player needs to verify a settled spin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reveal {
+    pub server_seed: String,
+    pub client_seed: String,
+    pub nonce: u64,
+    pub commitment: String,
+}
+
+impl Reveal {
+    /// Checks the seed against the commitment and returns the pocket it produces.
+    pub fn verify(&self, pocket_count: usize) -> Result<PocketId, FairnessError> {
+        let actual = commitment_for(&self.server_seed, self.nonce);
+        if !actual.eq_ignore_ascii_case(&self.commitment) {
+            return Err(FairnessError::CommitmentMismatch { expected: self.commitment.clone(), actual });
+        }
+        Ok(outcome_for(&self.server_seed, &self.client_seed, self.nonce, pocket_count))
+    }
+
+    /// Like [`Reveal::verify`], but also checks that the claimed pocket is the one produced.
+    pub fn verify_outcome(&self, pocket_count: usize, claimed: PocketId) -> Result<(), FairnessError> {
+        let expected = self.verify(pocket_count)?;
+        if expected != claimed {
+            return Err(FairnessError::OutcomeMismatch { expected, claimed });
+        }
+        Ok(())
+    }
+}
+
+/// A random 256-bit seed as lowercase hex, usable as a server or client seed.
+pub fn random_seed() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().r#gen();
+    to_hex(&bytes)
+}
+
+/// The commitment published for a server seed and nonce.
+pub fn commitment_for(server_seed: &str, nonce: u64) -> String {
+    to_hex(&Sha256::digest(format!("{}:{}", server_seed, nonce).as_bytes()))
+}
+
+/// Maps seeds and nonce to a pocket, uniformly over `pocket_count` pockets.
+pub fn outcome_for(server_seed: &str, client_seed: &str, nonce: u64, pocket_count: usize) -> PocketId {
+    let count = pocket_count as u64;
+    // Reject draws from the incomplete final block so every pocket is equally likely.
+    let limit = u64::MAX - u64::MAX % count;
+    let mut attempt = 0u32;
+    loop {
+        let digest = Sha256::digest(format!("{}:{}:{}:{}", server_seed, client_seed, nonce, attempt).as_bytes());
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&digest[..8]);
+        let draw = u64::from_be_bytes(word);
+        if draw < limit {
+            return PocketId((draw % count) as usize);
+        }
+        attempt += 1;
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,212 @@
+// src/game/house.rs
+
+//! Banking the table: the player is the house, taking on a table of bots.
+//!
+//! Every stake a bot loses goes into the house bankroll and every win is paid
+//! out of it. Over enough rounds the bankroll drifts upward at about the
+//! house edge, which is the point of the mode.
+
+use super::bets::{
+    Bet, BetType, create_black_bet, create_blue_chip_dozen_bet, create_column_bet, create_red_bet,
+    create_straight_up,
+};
+use super::controller::{FlatBettor, Martingale, PlayerController, Turn};
+use super::ledger::{DealerLedger, SettlementSheet};
+use super::money::Money;
+use super::rules::TableRules;
+use super::wheel::{Wheel, WheelLike};
+use crate::console;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// Smallest and largest single bet the table accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableLimits {
+    pub min_bet: Money,
+    pub max_bet: Money,
+}
+
+impl TableLimits {
+    pub fn allows(&self, bet: &Bet) -> bool {
+        (self.min_bet..=self.max_bet).contains(&bet.amount)
+    }
+}
+
+impl Default for TableLimits {
+    fn default() -> Self {
+        TableLimits { min_bet: Money::from_units(5), max_bet: Money::from_units(500) }
+    }
+}
+
+/// Settings for a house session.
+#[derive(Debug, Clone)]
+pub struct HouseConfig {
+    pub bots: usize,
+    /// The house's starting bankroll.
+    pub bankroll: Money,
+    /// What each bot sits down with.
+    pub bot_balance: Money,
+    pub limits: TableLimits,
+    pub seed: u64,
+}
+
+impl Default for HouseConfig {
+    fn default() -> Self {
+        HouseConfig {
+            bots: 4,
+            bankroll: Money::from_units(10_000),
+            bot_balance: Money::from_units(1_000),
+            limits: TableLimits::default(),
+            seed: 0,
+        }
+    }
+}
+
+/// How one round went for the house.
+#[derive(Debug, Clone)]
+pub struct HouseRound {
+    pub sheet: SettlementSheet,
+    /// Stakes kept minus winnings paid; negative when the bots came out ahead.
+    pub house_net: Money,
+    pub bankroll: Money,
+    /// Bots whose bets broke the table limits and were handed back.
+    pub refused: Vec<String>,
+}
+
+/// A table the player banks against bots.
+pub struct HouseTable {
+    wheel: Box<dyn WheelLike>,
+    ledger: DealerLedger,
+    controllers: Vec<Box<dyn PlayerController>>,
+    seated: Vec<bool>,
+    limits: TableLimits,
+    bankroll: Money,
+    wagered: Money,
+    rng: StdRng,
+}
+
+impl HouseTable {
+    pub fn new(wheel: Box<dyn WheelLike>, config: &HouseConfig) -> Self {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let names: Vec<String> = (1..=config.bots).map(|i| format!("Bot {}", i)).collect();
+        let controllers =
+            (0..config.bots).map(|i| bot_controller(i, wheel.layout(), config.limits, &mut rng)).collect();
+        let ledger = DealerLedger::with_wheel(&names, config.bot_balance, wheel.layout().clone());
+        HouseTable {
+            wheel,
+            ledger,
+            controllers,
+            seated: vec![true; config.bots],
+            limits: config.limits,
+            bankroll: config.bankroll,
+            wagered: Money::ZERO,
+            rng,
+        }
+    }
+
+    /// Applies the table rules to every bot's seat; bots round their
+    /// stakes to what the rules take.
+    pub fn set_rules(&mut self, rules: &TableRules) {
+        self.ledger.set_rules(rules);
+    }
+
+    /// The house bankroll; negative once the house can't cover its losses.
+    pub fn bankroll(&self) -> Money {
+        self.bankroll
+    }
+
+    /// Total staked by the bots so far.
+    pub fn wagered(&self) -> Money {
+        self.wagered
+    }
+
+    /// Names of the bots and the strategy each plays.
+    pub fn bots(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.ledger.seats().iter().zip(&self.controllers).map(|(seat, c)| (seat.name.as_str(), c.name()))
+    }
+
+    /// True while the house is solvent and at least one bot is still playing.
+    pub fn is_open(&self) -> bool {
+        self.bankroll > Money::ZERO && self.seated.iter().any(|&s| s)
+    }
+
+    /// Takes the bots' bets, spins, and settles. Returns `None` once the
+    /// table has closed.
+    pub fn play_round(&mut self) -> Option<HouseRound> {
+        if !self.is_open() {
+            return None;
+        }
+        let mut refused = Vec::new();
+        for (index, seat) in self.ledger.seats_mut().iter_mut().enumerate() {
+            if !self.seated[index] {
+                continue;
+            }
+            let controller = &mut self.controllers[index];
+            let turn = console::quietly(|| controller.place_bets(&mut seat.game));
+            let within_limits = seat.game.get_current_bets().iter().all(|b| self.limits.allows(b));
+            if turn == Turn::Leave || !within_limits {
+                if !within_limits {
+                    refused.push(seat.name.clone());
+                }
+                if !seat.game.get_current_bets().is_empty() {
+                    console::quietly(|| seat.game.clear_bets());
+                }
+            }
+            if turn == Turn::Leave {
+                self.seated[index] = false;
+            }
+        }
+
+        let winning_id = self.wheel.spin(&mut self.rng);
+        let sheet = console::quietly(|| self.ledger.settle(winning_id));
+        let mut house_net = Money::ZERO;
+        for (line, controller) in sheet.lines.iter().zip(self.controllers.iter_mut()) {
+            if line.wagered > Money::ZERO {
+                house_net -= line.net();
+                controller.spin_settled(self.wheel.layout(), winning_id, line.returned);
+            }
+            self.wagered += line.wagered;
+        }
+        self.bankroll += house_net;
+        Some(HouseRound { sheet, house_net, bankroll: self.bankroll, refused })
+    }
+}
+
+impl fmt::Display for HouseRound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wagered: Money = self.sheet.lines.iter().map(|l| l.wagered).sum();
+        let house = if self.house_net.is_negative() { self.house_net.to_string() } else { format!("+{}", self.house_net) };
+        write!(
+            f,
+            "Round {:>4}: {:<6} wagered {:<7} house {:>8}  bankroll {}",
+            self.sheet.round, self.sheet.pocket.ticker, wagered, house, self.bankroll
+        )?;
+        if !self.refused.is_empty() {
+            write!(f, "  (over limits: {})", self.refused.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A bot for seat `index`. Seats cycle through a handful of common player
+/// types, each staking between the table minimum and a few times it.
+fn bot_controller(index: usize, wheel: &Wheel, limits: TableLimits, rng: &mut StdRng) -> Box<dyn PlayerController> {
+    let top = limits.max_bet.min(limits.min_bet * 5).max(limits.min_bet);
+    let (low, high) = (limits.min_bet.round_up(), top.round_down());
+    let stake = if low <= high { Money::from_units(rng.gen_range(low..=high)) } else { limits.min_bet };
+    let flat = |bets: Vec<Option<Bet>>| -> Box<dyn PlayerController> {
+        Box::new(FlatBettor::new(bets.into_iter().flatten().collect()))
+    };
+    match index % 5 {
+        0 => flat(vec![Some(create_red_bet(stake))]),
+        1 => Box::new(Martingale::new(BetType::Black, limits.min_bet).with_cap(limits.max_bet)),
+        2 => {
+            let pockets = wheel.get_all_pockets();
+            let ticker = pockets[rng.gen_range(0..pockets.len())].ticker;
+            flat(vec![create_straight_up(ticker.as_str(), limits.min_bet, wheel)])
+        }
+        3 => flat(vec![Some(create_blue_chip_dozen_bet(stake))]),
+        _ => flat(vec![create_column_bet(rng.gen_range(1..=3), stake), Some(create_black_bet(limits.min_bet))]),
+    }
+}
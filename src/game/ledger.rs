@@ -0,0 +1,158 @@
+// src/game/ledger.rs
+
+//! Dealer ledger: tracks several named bettors at one physical table.
+
+use super::{Game, VoidError};
+use super::money::Money;
+use super::rules::TableRules;
+use super::wheel::{Pocket, PocketId, Wheel};
+use crate::say;
+use std::fmt;
+
+/// A named bettor and their own bankroll and standing bets.
+pub struct Seat {
+    pub name: String,
+    pub game: Game,
+}
+
+/// One bettor's line on the settlement sheet.
+#[derive(Debug, Clone)]
+pub struct SheetLine {
+    pub name: String,
+    pub wagered: Money,
+    pub returned: Money,
+    pub balance: Money,
+}
+
+impl SheetLine {
+    /// Net result of the round for this bettor.
+    pub fn net(&self) -> Money {
+        self.returned - self.wagered
+    }
+}
+
+/// Printable summary of a single spin across all bettors.
+#[derive(Debug, Clone)]
+pub struct SettlementSheet {
+    pub round: u32,
+    pub pocket: Pocket,
+    pub lines: Vec<SheetLine>,
+}
+
+impl fmt::Display for SettlementSheet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Settlement Sheet: Round {} ===", self.round)?;
+        writeln!(f, "Winning pocket: {}", self.pocket)?;
+        writeln!(f, "{:<16} {:>10} {:>10} {:>10} {:>10}", "Bettor", "Wagered", "Returned", "Net", "Balance")?;
+        for line in &self.lines {
+            writeln!(
+                f,
+                "{:<16} {:>10} {:>10} {:>10} {:>10}",
+                line.name, line.wagered, line.returned, line.net(), line.balance
+            )?;
+        }
+        let house_net: Money = self.lines.iter().map(|l| -l.net()).sum();
+        write!(f, "House net this round: {}", house_net)
+    }
+}
+
+/// Banks a physical game for several named bettors.
+pub struct DealerLedger {
+    seats: Vec<Seat>,
+    wheel: Wheel,
+    round: u32,
+    /// Seats that had bets on the most recent spin.
+    last_settled: Vec<usize>,
+}
+
+impl DealerLedger {
+    /// Creates a ledger where every bettor starts with the same balance.
+    pub fn new(names: &[String], starting_balance: impl Into<Money>) -> Self {
+        DealerLedger::with_wheel(names, starting_balance, Wheel::new())
+    }
+
+    /// Like [`DealerLedger::new`], for a table with a different wheel layout.
+    pub fn with_wheel(names: &[String], starting_balance: impl Into<Money>, wheel: Wheel) -> Self {
+        let starting_balance = starting_balance.into();
+        let seats = names
+            .iter()
+            .map(|name| {
+                let mut game = Game::with_wheel(starting_balance, Box::new(wheel.clone()));
+                // The croupier may have entered the wrong pocket.
+                game.allow_voids(true);
+                Seat { name: name.clone(), game }
+            })
+            .collect();
+        DealerLedger { seats, wheel, round: 1, last_settled: Vec::new() }
+    }
+
+    /// Applies the same house rules to every seat.
+    pub fn set_rules(&mut self, rules: &TableRules) {
+        for seat in &mut self.seats {
+            seat.game.set_rules(rules.clone());
+        }
+    }
+
+    /// The wheel layout shared by every seat.
+    pub fn wheel(&self) -> &Wheel {
+        &self.wheel
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    pub fn seats_mut(&mut self) -> &mut [Seat] {
+        &mut self.seats
+    }
+
+    /// Returns true while at least one bettor still has money or bets on the table.
+    pub fn has_active_bettors(&self) -> bool {
+        self.seats
+            .iter()
+            .any(|s| s.game.get_player_balance() > Money::ZERO || s.game.has_bets_in_play())
+    }
+
+    /// Settles every bettor's wagers against the winning pocket and returns the sheet.
+    pub fn settle(&mut self, winning_id: PocketId) -> SettlementSheet {
+        let mut lines = Vec::with_capacity(self.seats.len());
+        self.last_settled.clear();
+        for (index, seat) in self.seats.iter_mut().enumerate() {
+            let wagered: Money = seat.game.get_current_bets().iter().map(|b| b.amount).sum();
+            let before = seat.game.get_player_balance();
+            if seat.game.has_bets_in_play() {
+                say!("\n--- Settling {} ---", seat.name);
+                seat.game.resolve_with_pocket(winning_id);
+                self.last_settled.push(index);
+            }
+            let balance = seat.game.get_player_balance();
+            lines.push(SheetLine {
+                name: seat.name.clone(),
+                wagered,
+                returned: balance - before,
+                balance,
+            });
+        }
+        let pocket = self.wheel.pocket(winning_id).clone();
+        let sheet = SettlementSheet { round: self.round, pocket, lines };
+        self.round += 1;
+        sheet
+    }
+
+    /// Voids the last spin for every bettor who had bets on it, e.g. after the
+    /// croupier entered the wrong pocket. Their bets stand again, ready to be
+    /// settled against the corrected pocket.
+    pub fn void_last_spin(&mut self) -> Result<(), VoidError> {
+        if self.seats.iter().any(|s| !s.game.get_current_bets().is_empty()) {
+            return Err(VoidError::BetsPlaced);
+        }
+        if self.last_settled.is_empty() {
+            return Err(VoidError::NoSpin);
+        }
+        for index in std::mem::take(&mut self.last_settled) {
+            self.seats[index].game.void_last_spin()?;
+        }
+        self.round -= 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+// src/game/metadata.rs
+
+//! Market data about the stock behind each pocket: its sector, market cap,
+//! dividend yield, and beta.
+//!
+//! The stock wheels come with rough figures for every stock, meant for play
+//! rather than investing; the config can correct or extend them by ticker.
+//! The metadata puts pockets into extra categories to bet on, such as High
+//! Beta (see [`PocketMetadata::categories`]), and can weight the wheel, see
+//! [`MetadataWeighting`].
+//!
+//! ```toml
+//! weight_by = "beta"       # volatile stocks come up more often
+//!
+//! [metadata.NVDA]
+//! beta = 1.9
+//! dividend_yield = 0.03    # percent a year
+//! cap_tier = "mega"
+//! sector = "technology"
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// A beta at or above this puts a pocket in the High Beta category.
+pub const HIGH_BETA_MIN: f64 = 1.2;
+/// A beta at or below this puts a pocket in the Low Beta category.
+pub const LOW_BETA_MAX: f64 = 0.6;
+/// A yield (percent) at or above this puts a pocket in the High Yield category.
+pub const HIGH_YIELD_MIN: f64 = 3.0;
+
+pub const HIGH_BETA: &str = "High Beta";
+pub const LOW_BETA: &str = "Low Beta";
+pub const HIGH_YIELD: &str = "High Yield";
+
+/// The market sector a stock trades in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sector {
+    Technology,
+    Energy,
+    Financials,
+    Healthcare,
+    Industrials,
+    Telecom,
+    Consumer,
+    Automotive,
+}
+
+impl fmt::Display for Sector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Sector::Technology => "Technology",
+            Sector::Energy => "Energy",
+            Sector::Financials => "Financials",
+            Sector::Healthcare => "Healthcare",
+            Sector::Industrials => "Industrials",
+            Sector::Telecom => "Telecom",
+            Sector::Consumer => "Consumer",
+            Sector::Automotive => "Automotive",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How big a company is by market value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapTier {
+    /// Below $10 billion.
+    Mid,
+    /// $10 to $200 billion.
+    Large,
+    /// Over $200 billion.
+    Mega,
+}
+
+impl fmt::Display for CapTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CapTier::Mid => "Mid Cap",
+            CapTier::Large => "Large Cap",
+            CapTier::Mega => "Mega Cap",
+        };
+        f.write_str(name)
+    }
+}
+
+/// What's known about a pocket's stock. Every field is optional; the zeros
+/// have none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PocketMetadata {
+    pub sector: Option<Sector>,
+    pub cap_tier: Option<CapTier>,
+    /// Yearly dividend as a percentage of the share price.
+    pub dividend_yield: Option<f64>,
+    /// How much the stock swings with the market; 1 moves in step with it.
+    pub beta: Option<f64>,
+}
+
+impl PocketMetadata {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(dividend_yield) = self.dividend_yield
+            && !(dividend_yield.is_finite() && dividend_yield >= 0.0)
+        {
+            return Err(format!("dividend_yield must be 0 or more, not {}", dividend_yield));
+        }
+        if let Some(beta) = self.beta
+            && !beta.is_finite()
+        {
+            return Err(format!("beta must be a number, not {}", beta));
+        }
+        Ok(())
+    }
+
+    /// Takes every field `other` sets, keeping ours where it has none.
+    pub fn merge(&mut self, other: &PocketMetadata) {
+        self.sector = other.sector.or(self.sector);
+        self.cap_tier = other.cap_tier.or(self.cap_tier);
+        self.dividend_yield = other.dividend_yield.or(self.dividend_yield);
+        self.beta = other.beta.or(self.beta);
+    }
+
+    /// The categories the metadata puts the pocket in: High Beta or Low
+    /// Beta, and High Yield. The thresholds keep each to a dozen stocks or
+    /// fewer on the stock wheels, so the usual 2 to 1 category odds favor
+    /// the house.
+    ///
+    /// Cap tiers make no category: two tiers split the stock wheels between
+    /// them, and either would pay out more than it takes in.
+    pub fn categories(&self) -> Vec<&'static str> {
+        let mut categories = Vec::new();
+        if let Some(beta) = self.beta {
+            if beta >= HIGH_BETA_MIN {
+                categories.push(HIGH_BETA);
+            } else if beta <= LOW_BETA_MAX {
+                categories.push(LOW_BETA);
+            }
+        }
+        if self.dividend_yield.is_some_and(|dividend_yield| dividend_yield >= HIGH_YIELD_MIN) {
+            categories.push(HIGH_YIELD);
+        }
+        categories
+    }
+}
+
+/// "Technology, Mega Cap, 0.50% yield, beta 1.25", leaving out what isn't known.
+impl fmt::Display for PocketMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(sector) = self.sector {
+            parts.push(format!("{}", sector));
+        }
+        if let Some(tier) = self.cap_tier {
+            parts.push(format!("{}", tier));
+        }
+        if let Some(dividend_yield) = self.dividend_yield {
+            parts.push(format!("{:.2}% yield", dividend_yield));
+        }
+        if let Some(beta) = self.beta {
+            parts.push(format!("beta {:.2}", beta));
+        }
+        if parts.is_empty() {
+            return f.write_str("-");
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// Weights the wheel by a metadata field, chosen with `weight_by` in the
+/// config. Pockets without the field, like the zeros, weigh 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataWeighting {
+    /// Each pocket weighs its beta, so volatile stocks come up more often.
+    Beta,
+    /// Each pocket weighs 1 plus its yield in percent.
+    DividendYield,
+    /// Mega caps weigh 3, large caps 2, and mid caps 1.
+    CapTier,
+}
+
+impl MetadataWeighting {
+    /// Smallest weight a pocket can get, so a beta of 0 or below still
+    /// leaves the pocket in play.
+    const MIN_WEIGHT: f64 = 0.1;
+
+    pub fn weight(self, metadata: &PocketMetadata) -> f64 {
+        let weight = match self {
+            MetadataWeighting::Beta => metadata.beta,
+            MetadataWeighting::DividendYield => metadata.dividend_yield.map(|dividend_yield| 1.0 + dividend_yield),
+            MetadataWeighting::CapTier => metadata.cap_tier.map(|tier| match tier {
+                CapTier::Mid => 1.0,
+                CapTier::Large => 2.0,
+                CapTier::Mega => 3.0,
+            }),
+        };
+        weight.map_or(1.0, |weight| weight.max(Self::MIN_WEIGHT))
+    }
+}
+
+/// Built-in figures for the stock wheels: ticker, sector, cap tier, dividend
+/// yield, and beta.
+const STOCK_METADATA: &[(&str, Sector, CapTier, f64, f64)] = &[
+    ("AAPL", Sector::Technology, CapTier::Mega, 0.45, 1.25),
+    ("MSFT", Sector::Technology, CapTier::Mega, 0.70, 0.90),
+    ("GOOGL", Sector::Technology, CapTier::Mega, 0.45, 1.05),
+    ("AMZN", Sector::Technology, CapTier::Mega, 0.0, 1.15),
+    ("NVDA", Sector::Technology, CapTier::Mega, 0.03, 1.70),
+    ("META", Sector::Technology, CapTier::Mega, 0.35, 1.25),
+    ("TSLA", Sector::Technology, CapTier::Mega, 0.0, 2.30),
+    ("XOM", Sector::Energy, CapTier::Mega, 3.40, 0.85),
+    ("CVX", Sector::Energy, CapTier::Large, 4.30, 0.90),
+    ("COP", Sector::Energy, CapTier::Large, 3.20, 1.00),
+    ("2222.SR", Sector::Energy, CapTier::Mega, 6.50, 0.40),
+    ("PTR", Sector::Energy, CapTier::Large, 7.00, 0.70),
+    ("JPM", Sector::Financials, CapTier::Mega, 2.10, 1.10),
+    ("BRK-A", Sector::Financials, CapTier::Mega, 0.0, 0.85),
+    ("WFC", Sector::Financials, CapTier::Large, 2.20, 1.15),
+    ("V", Sector::Financials, CapTier::Mega, 0.70, 0.95),
+    ("MA", Sector::Financials, CapTier::Mega, 0.55, 1.05),
+    ("PFE", Sector::Healthcare, CapTier::Large, 6.50, 0.55),
+    ("JNJ", Sector::Healthcare, CapTier::Large, 3.20, 0.50),
+    ("UNH", Sector::Healthcare, CapTier::Large, 2.70, 0.60),
+    ("GE", Sector::Industrials, CapTier::Large, 0.50, 1.20),
+    ("IBM", Sector::Technology, CapTier::Large, 2.80, 0.70),
+    ("INTC", Sector::Technology, CapTier::Large, 0.0, 1.30),
+    ("CSCO", Sector::Technology, CapTier::Large, 2.60, 0.85),
+    ("T", Sector::Telecom, CapTier::Large, 4.00, 0.60),
+    ("VZ", Sector::Telecom, CapTier::Large, 6.20, 0.40),
+    ("HD", Sector::Consumer, CapTier::Large, 2.30, 1.00),
+    ("WMT", Sector::Consumer, CapTier::Mega, 1.00, 0.50),
+    ("KO", Sector::Consumer, CapTier::Large, 2.90, 0.60),
+    ("PEP", Sector::Consumer, CapTier::Large, 3.70, 0.55),
+    ("PG", Sector::Consumer, CapTier::Large, 2.50, 0.45),
+    ("MCD", Sector::Consumer, CapTier::Large, 2.40, 0.65),
+    ("NKE", Sector::Consumer, CapTier::Large, 2.50, 1.20),
+    ("COST", Sector::Consumer, CapTier::Large, 0.50, 0.80),
+    ("F", Sector::Automotive, CapTier::Large, 5.50, 1.50),
+    ("GM", Sector::Automotive, CapTier::Large, 1.00, 1.35),
+];
+
+/// The built-in metadata for a stock wheel ticker; empty for the zeros.
+pub(crate) fn stock_metadata(ticker: &str) -> PocketMetadata {
+    STOCK_METADATA
+        .iter()
+        .find(|(known, ..)| *known == ticker)
+        .map(|&(_, sector, cap_tier, dividend_yield, beta)| PocketMetadata {
+            sector: Some(sector),
+            cap_tier: Some(cap_tier),
+            dividend_yield: Some(dividend_yield),
+            beta: Some(beta),
+        })
+        .unwrap_or_default()
+}
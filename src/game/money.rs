@@ -0,0 +1,395 @@
+// src/game/money.rs
+
+//! Amounts of money, down to the cent.
+//!
+//! Balances, stakes, and payouts are all [`Money`]: a count of cents of the
+//! table's [currency](super::currency), so a $0.50 stake pays $17.50 on a
+//! straight-up win without rounding. Saved files and the event stream write
+//! an amount as a plain number of whole units (`12` or `12.5`), which is how
+//! they were written when the game only took whole dollars, so older files
+//! still read the same.
+//!
+//! No amount goes past [`Money::MAX`] either way: reading one that does is an
+//! error, and arithmetic that would stops there (see
+//! [`checked_add`](Money::checked_add) to be told instead).
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use core::str::FromStr;
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+
+const CENTS_PER_UNIT: i64 = 100;
+
+/// An amount of money, counted in cents. Negative for what a round or a
+/// change to the balance took away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    /// The most a balance or stake can be, as when they were counted in
+    /// whole units that fit a `u32`.
+    pub const MAX: Money = Money(u32::MAX as i64 * CENTS_PER_UNIT);
+
+    pub const fn from_cents(cents: i64) -> Self {
+        Money(cents)
+    }
+
+    /// `units` whole dollars, or whole units of the table's currency.
+    pub const fn from_units(units: u32) -> Self {
+        Money(units as i64 * CENTS_PER_UNIT)
+    }
+
+    /// `units` rounded to the nearest cent, as for a stake worked out as a
+    /// fraction of the balance.
+    pub fn from_f64(units: f64) -> Self {
+        // `f64::round` needs std; the cast truncates, so add half a cent first.
+        let cents = units * CENTS_PER_UNIT as f64;
+        Money((cents + if cents < 0.0 { -0.5 } else { 0.5 }) as i64)
+    }
+
+    /// `cents` held to within [`Money::MAX`] either side of zero.
+    const fn bounded(cents: i64) -> Self {
+        let max = Money::MAX.0;
+        Money(if cents > max { max } else if cents < -max { -max } else { cents })
+    }
+
+    pub const fn cents(self) -> i64 {
+        self.0
+    }
+
+    /// The amount in whole units, for odds and statistics.
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / CENTS_PER_UNIT as f64
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub const fn abs(self) -> Money {
+        Money(self.0.abs())
+    }
+
+    /// The amount in whole units, if it has no cents and fits a `u32`.
+    pub fn whole(self) -> Option<u32> {
+        if self.0 % CENTS_PER_UNIT != 0 {
+            return None;
+        }
+        u32::try_from(self.0 / CENTS_PER_UNIT).ok()
+    }
+
+    /// The whole units at or below the amount, saturating at `u32::MAX`.
+    pub fn round_down(self) -> u32 {
+        u32::try_from(self.0.max(0).div_euclid(CENTS_PER_UNIT)).unwrap_or(u32::MAX)
+    }
+
+    /// The whole units at or above the amount, saturating at `u32::MAX`.
+    pub fn round_up(self) -> u32 {
+        u32::try_from((self.0.max(0) + CENTS_PER_UNIT - 1).div_euclid(CENTS_PER_UNIT)).unwrap_or(u32::MAX)
+    }
+
+    /// The amount less `other`, or `None` if that would go below zero.
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        (other <= self).then_some(Money(self.0 - other.0))
+    }
+
+    /// The amount less `other`, stopping at zero.
+    pub fn saturating_sub(self, other: Money) -> Money {
+        Money((self.0 - other.0).max(0))
+    }
+
+    /// The sum, or `None` if it would come to more than [`Money::MAX`].
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        let sum = Money(self.0.checked_add(other.0)?);
+        (sum <= Money::MAX).then_some(sum)
+    }
+
+    /// `percent` of the amount, rounded down to the cent.
+    pub fn percent(self, percent: u32) -> Money {
+        Money(self.0 * percent as i64 / 100)
+    }
+
+    /// True if the amount is a whole number of `step`s.
+    pub fn is_multiple_of(self, step: Money) -> bool {
+        step.is_zero() || self.0.rem_euclid(step.0) == 0
+    }
+
+    /// Reads an amount such as `12`, `$0.50`, `1,250.5` or `.75`. A leading
+    /// `$` and thousands separators are allowed; more than two decimals
+    /// aren't, since there's nothing smaller than a cent to round them to.
+    pub fn parse(text: &str) -> Result<Money, MoneyError> {
+        let invalid = || MoneyError::Invalid(text.trim().to_string());
+        let digits: String = text.trim().trim_start_matches('$').chars().filter(|&c| c != ',').collect();
+        let (units, cents) = digits.split_once('.').unwrap_or((&digits, ""));
+        let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (units.is_empty() && cents.is_empty()) || !all_digits(units) || !all_digits(cents) {
+            return Err(invalid());
+        }
+        if cents.len() > 2 {
+            return Err(MoneyError::FractionOfCent(text.trim().to_string()));
+        }
+        let units: i64 = if units.is_empty() { 0 } else { units.parse().map_err(|_| MoneyError::TooLarge)? };
+        let cents: i64 = match cents.len() {
+            0 => 0,
+            1 => cents.parse::<i64>().map_err(|_| invalid())? * 10,
+            _ => cents.parse().map_err(|_| invalid())?,
+        };
+        units
+            .checked_mul(CENTS_PER_UNIT)
+            .and_then(|total| total.checked_add(cents))
+            .map(Money)
+            .filter(|&money| money <= Money::MAX)
+            .ok_or(MoneyError::TooLarge)
+    }
+}
+
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(text: &str) -> Result<Money, MoneyError> {
+        Money::parse(text)
+    }
+}
+
+impl From<u32> for Money {
+    fn from(units: u32) -> Self {
+        Money::from_units(units)
+    }
+}
+
+/// Stops at [`Money::MAX`] either side of zero.
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money::bounded(self.0.saturating_add(other.0))
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        *self = *self + other;
+    }
+}
+
+/// Stops at [`Money::MAX`] either side of zero.
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money::bounded(self.0.saturating_sub(other.0))
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Money {
+    type Output = Money;
+
+    fn neg(self) -> Money {
+        Money(-self.0)
+    }
+}
+
+/// Payouts: the stake times the odds, stopping at [`Money::MAX`].
+impl Mul<u32> for Money {
+    type Output = Money;
+
+    fn mul(self, times: u32) -> Money {
+        Money::bounded(self.0.saturating_mul(times as i64))
+    }
+}
+
+/// Shares such as half a stake, rounded towards zero to the cent.
+impl Div<u32> for Money {
+    type Output = Money;
+
+    fn div(self, by: u32) -> Money {
+        Money(self.0 / by as i64)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Money> for Money {
+    fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+        iter.copied().sum()
+    }
+}
+
+/// Whole amounts show no cents (`$12`); others show two places (`$0.50`).
+/// `{:+}` signs gains too (`+$5`). [`Amount`](super::currency::Amount)
+/// writes them in the table's currency.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match self.0 {
+            ..0 => "-",
+            _ if f.sign_plus() => "+",
+            _ => "",
+        };
+        f.pad(&alloc::format!("{}${}", sign, Units(self.abs())))
+    }
+}
+
+/// The digits of an amount without its sign: `12` or `0.50`.
+pub(crate) struct Units(pub(crate) Money);
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (units, cents) = (self.0.0 / CENTS_PER_UNIT, self.0.0 % CENTS_PER_UNIT);
+        if cents == 0 { write!(f, "{}", units) } else { write!(f, "{}.{:02}", units, cents) }
+    }
+}
+
+/// Written as a number of whole units: an integer when there are no cents.
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.0 % CENTS_PER_UNIT == 0 {
+            serializer.serialize_i64(self.0 / CENTS_PER_UNIT)
+        } else {
+            serializer.serialize_f64(self.as_f64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+struct MoneyVisitor;
+
+impl Visitor<'_> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an amount of money such as 25 or 12.50")
+    }
+
+    fn visit_i64<E: de::Error>(self, units: i64) -> Result<Money, E> {
+        units
+            .checked_mul(CENTS_PER_UNIT)
+            .map(Money)
+            .filter(|money| money.abs() <= Money::MAX)
+            .ok_or_else(|| E::custom(MoneyError::TooLarge))
+    }
+
+    fn visit_u64<E: de::Error>(self, units: u64) -> Result<Money, E> {
+        i64::try_from(units).map_err(|_| E::custom(MoneyError::TooLarge)).and_then(|units| self.visit_i64(units))
+    }
+
+    fn visit_f64<E: de::Error>(self, units: f64) -> Result<Money, E> {
+        if !units.is_finite() || units.abs() > Money::MAX.as_f64() {
+            return Err(E::custom(MoneyError::TooLarge));
+        }
+        Ok(Money::from_f64(units))
+    }
+
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Money, E> {
+        Money::parse(text).map_err(E::custom)
+    }
+}
+
+/// Why text couldn't be read as [`Money`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    Invalid(String),
+    /// More than two decimal places.
+    FractionOfCent(String),
+    /// More than [`Money::MAX`].
+    TooLarge,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::Invalid(text) => write!(f, "'{}' isn't an amount of money", text),
+            MoneyError::FractionOfCent(text) => write!(f, "'{}' goes past the cents", text),
+            MoneyError::TooLarge => write!(f, "that's more than the game can count, which stops at {}", Money::MAX),
+        }
+    }
+}
+
+impl core::error::Error for MoneyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_dollars_cents_and_separators() {
+        assert_eq!(Money::parse("12"), Ok(Money::from_units(12)));
+        assert_eq!(Money::parse(" $0.50 "), Ok(Money::from_cents(50)));
+        assert_eq!(Money::parse("1,250.5"), Ok(Money::from_cents(125_050)));
+        assert_eq!(Money::parse(".75"), Ok(Money::from_cents(75)));
+        assert_eq!(Money::parse("1.005"), Err(MoneyError::FractionOfCent("1.005".to_string())));
+        for text in ["", "$", ".", "-5", "1.2.3", "ten"] {
+            assert!(matches!(Money::parse(text), Err(MoneyError::Invalid(_))), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn parse_stops_at_the_maximum() {
+        assert_eq!(Money::parse("4294967295"), Ok(Money::MAX));
+        assert_eq!(Money::parse("4294967295.01"), Err(MoneyError::TooLarge));
+        assert_eq!(Money::parse("99999999999999999999"), Err(MoneyError::TooLarge));
+    }
+
+    #[test]
+    fn arithmetic_stops_at_the_maximum_either_side_of_zero() {
+        let cent = Money::from_cents(1);
+        assert_eq!(Money::MAX + cent, Money::MAX);
+        assert_eq!(-Money::MAX - cent, -Money::MAX);
+        assert_eq!(Money::ZERO - Money::MAX - Money::MAX, -Money::MAX);
+        assert_eq!(Money::MAX * 36, Money::MAX);
+        let mut total = Money::MAX;
+        total += Money::MAX;
+        assert_eq!(total, Money::MAX);
+        assert_eq!([Money::MAX, Money::MAX].iter().sum::<Money>(), Money::MAX);
+        assert_eq!(Money::MAX.checked_add(cent), None);
+        assert_eq!(Money::from_units(5) - Money::from_units(8), -Money::from_units(3));
+    }
+
+    #[test]
+    fn fractions_round_to_the_nearest_cent() {
+        assert_eq!(Money::from_f64(0.125), Money::from_cents(13));
+        assert_eq!(Money::from_f64(0.124), Money::from_cents(12));
+        assert_eq!(Money::from_f64(-0.125), Money::from_cents(-13));
+        assert_eq!(Money::from_cents(175) / 2, Money::from_cents(87));
+        assert_eq!(Money::from_cents(1_999).percent(10), Money::from_cents(199));
+        assert_eq!(Money::from_cents(1_201).round_down(), 12);
+        assert_eq!(Money::from_cents(1_201).round_up(), 13);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reading_stops_at_the_maximum() {
+        let read = |json: &str| serde_json::from_str::<Money>(json).ok();
+        assert_eq!(read("4294967295"), Some(Money::MAX));
+        assert_eq!(read("-4294967295"), Some(-Money::MAX));
+        assert_eq!(read("4294967296"), None);
+        assert_eq!(read("-4294967296"), None);
+        assert_eq!(read("92233720368547758"), None);
+        assert_eq!(read("4294967296.5"), None);
+        assert_eq!(read("12.5"), Some(Money::from_cents(1_250)));
+        assert_eq!(read("\"$0.50\""), Some(Money::from_cents(50)));
+    }
+}
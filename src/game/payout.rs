@@ -0,0 +1,198 @@
+// src/game/payout.rs
+
+//! Payout tables and a check of what each bet returns on a given wheel.
+//!
+//! A bet's fairness depends on both its payout and how many pockets it
+//! covers, and on this wheel categories come in every size. A category bet
+//! paying 2 to 1 is a poor bet on the 7-stock Magnificent Seven and a money
+//! machine on the 19-stock Dividend Aristocrats. [`Wheel::verify_payouts`]
+//! works out the expected return of every bet the wheel offers so such
+//! configurations are caught up front.
+
+use super::bets::BetType;
+use super::wheel::{PocketId, Wheel};
+use crate::analysis;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Payout odds ("N to 1") for each kind of bet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PayoutTable {
+    pub straight_up: u32,
+    pub split: u32,
+    /// Red, Black, Odd, Even, Low, and High.
+    pub even_money: u32,
+    /// Growth, Value, and Blue Chip dozens.
+    pub dozen: u32,
+    pub column: u32,
+    /// Any category without an entry in `categories`.
+    pub category: u32,
+    /// Per-category odds, by category name.
+    pub categories: BTreeMap<String, u32>,
+}
+
+impl PayoutTable {
+    /// The odds the game pays out of the box.
+    pub const STANDARD: PayoutTable = PayoutTable {
+        straight_up: 35,
+        split: 17,
+        even_money: 1,
+        dozen: 2,
+        column: 2,
+        category: 2,
+        categories: BTreeMap::new(),
+    };
+
+    /// Odds for the 13-pocket mini wheel, scaled so each bet keeps the same
+    /// 1-in-13 edge a full wheel's bets have.
+    pub const MINI: PayoutTable = PayoutTable {
+        straight_up: 11,
+        split: 5,
+        even_money: 1,
+        dozen: 2,
+        column: 2,
+        category: 2,
+        categories: BTreeMap::new(),
+    };
+
+    /// Payout odds for a bet type under this table.
+    pub fn multiplier(&self, bet_type: &BetType) -> u32 {
+        match bet_type {
+            BetType::StraightUp(_) => self.straight_up,
+            BetType::Split(_, _) => self.split,
+            BetType::Red | BetType::Black | BetType::Odd | BetType::Even | BetType::Low | BetType::High => {
+                self.even_money
+            }
+            BetType::GrowthDozen | BetType::ValueDozen | BetType::BlueChipDozen => self.dozen,
+            BetType::Column(_) => self.column,
+            // Skip the name lookup (and registry lock) when there are no overrides.
+            BetType::Category(_) if self.categories.is_empty() => self.category,
+            BetType::Category(id) => self.categories.get(id.name()).copied().unwrap_or(self.category),
+            // Custom bets set their own odds through their evaluator.
+            BetType::Custom(_) => 0,
+        }
+    }
+}
+
+impl Default for PayoutTable {
+    fn default() -> Self {
+        PayoutTable::STANDARD
+    }
+}
+
+/// Expected return of one bet (or group of equivalent bets).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutLine {
+    pub label: String,
+    pub pockets_covered: usize,
+    pub multiplier: u32,
+    /// Average amount returned per unit staked, stake included (1.0 is break-even).
+    pub expected_return: f64,
+}
+
+impl PayoutLine {
+    /// Share of each stake the house keeps on average, as a percentage.
+    /// Negative when the bet favours the player.
+    pub fn house_edge_percent(&self) -> f64 {
+        (1.0 - self.expected_return) * 100.0
+    }
+
+    /// True if the bet returns more than it costs on average.
+    pub fn favours_player(&self) -> bool {
+        self.expected_return > 1.0
+    }
+}
+
+/// Expected returns of every bet a wheel offers under a payout table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutReport {
+    pub pockets: usize,
+    pub lines: Vec<PayoutLine>,
+}
+
+impl PayoutReport {
+    pub(crate) fn for_wheel(wheel: &Wheel, table: &PayoutTable) -> PayoutReport {
+        let pockets = wheel.get_all_pockets();
+        let mut lines = Vec::new();
+        let mut add = |label: String, bet_type: BetType| {
+            let covered = (0..pockets.len()).filter(|&i| bet_type.covers(wheel, PocketId(i))).count();
+            let multiplier = table.multiplier(&bet_type);
+            let expected_return = analysis::expected_return(&bet_type, wheel, table);
+            lines.push(PayoutLine { label, pockets_covered: covered, multiplier, expected_return });
+        };
+
+        // Every straight-up and neighbouring split covers the same number of
+        // pockets, so one representative of each stands in for the rest.
+        if let [first, second, ..] = pockets.iter().filter(|p| p.number != 0).collect::<Vec<_>>()[..] {
+            add("Straight Up".to_string(), BetType::StraightUp(first.ticker));
+            add("Split".to_string(), BetType::Split(first.ticker, second.ticker));
+        }
+        for bet_type in [
+            BetType::Red,
+            BetType::Black,
+            BetType::Odd,
+            BetType::Even,
+            BetType::Low,
+            BetType::High,
+            BetType::GrowthDozen,
+            BetType::ValueDozen,
+            BetType::BlueChipDozen,
+            BetType::Column(1),
+            BetType::Column(2),
+            BetType::Column(3),
+        ] {
+            add(bet_type.to_string(), bet_type);
+        }
+        for id in wheel.categories() {
+            add(BetType::Category(id).to_string(), BetType::Category(id));
+        }
+        PayoutReport { pockets: pockets.len(), lines }
+    }
+
+    /// Bets that return more than 100% on average.
+    pub fn flagged(&self) -> impl Iterator<Item = &PayoutLine> {
+        self.lines.iter().filter(|l| l.favours_player())
+    }
+
+    /// True if no bet favours the player.
+    pub fn is_sound(&self) -> bool {
+        self.flagged().next().is_none()
+    }
+
+    /// The smallest and largest house edge across all bets, as percentages.
+    pub fn house_edge_range(&self) -> (f64, f64) {
+        self.lines.iter().map(PayoutLine::house_edge_percent).fold((f64::MAX, f64::MIN), |(lo, hi), edge| {
+            (lo.min(edge), hi.max(edge))
+        })
+    }
+}
+
+impl fmt::Display for PayoutReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Payout Check ({} pockets) ===", self.pockets)?;
+        writeln!(f, "{:<36} {:>7} {:>6} {:>9} {:>10}", "Bet", "Covers", "Pays", "Return", "House edge")?;
+        for line in &self.lines {
+            writeln!(
+                f,
+                "{:<36} {:>7} {:>4}:1 {:>8.2}% {:>9.2}%{}",
+                line.label,
+                line.pockets_covered,
+                line.multiplier,
+                line.expected_return * 100.0,
+                line.house_edge_percent(),
+                if line.favours_player() { "  <-- returns more than 100%" } else { "" }
+            )?;
+        }
+        let (lowest, highest) = self.house_edge_range();
+        write!(f, "Effective house edge: {:.2}% (best bet) to {:.2}% (worst bet)", lowest, highest)?;
+        let flagged = self.flagged().count();
+        if flagged > 0 {
+            write!(f, "\nWARNING: {} bet(s) return more than 100% of the stake on average.", flagged)?;
+        }
+        Ok(())
+    }
+}
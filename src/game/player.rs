@@ -2,11 +2,63 @@
 
 //! Defines the player structure and associated methods.
 
+use super::currency::{Amount, Currency};
+use super::events::BalanceChangeReason;
+use super::money::Money;
+use crate::say;
+use serde::{Deserialize, Serialize};
+
+/// Guards against staking too much of the bankroll on one spin, however many
+/// small bets it is spread over.
+///
+/// ```toml
+/// [exposure]
+/// percent = 50    # of the balance the round started with
+/// block = true    # refuse bets past it instead of just warning
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExposureLimit {
+    pub percent: u32,
+    #[serde(default)]
+    pub block: bool,
+}
+
+impl ExposureLimit {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=100).contains(&self.percent) {
+            return Err(format!("percent must be between 1 and 100, got {}", self.percent));
+        }
+        Ok(())
+    }
+
+    /// True if `staked` is more than the allowed share of `bankroll`.
+    pub fn exceeded_by(&self, staked: Money, bankroll: Money) -> bool {
+        staked.cents() * 100 > bankroll.cents() * self.percent as i64
+    }
+}
+
+/// One entry of a player's audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AuditEntry {
+    /// Position in the log, from 0.
+    pub seq: u64,
+    pub cause: BalanceChangeReason,
+    /// Signed change to the balance.
+    pub amount: Money,
+    /// Balance after the change.
+    pub balance: Money,
+}
+
 /// Represents a player in the game.
 #[derive(Debug)]
 pub struct Player {
     /// The current balance of the player.
-    balance: u32,
+    balance: Money,
+    /// Every change to `balance`, oldest first. Only ever appended to.
+    audit: Vec<AuditEntry>,
+    /// What the balance is counted in.
+    currency: Currency,
 }
 
 impl Player {
@@ -15,23 +67,60 @@ impl Player {
     /// # Arguments
     ///
     /// * `starting_balance` - The initial amount of money the player has.
-    pub fn new(starting_balance: u32) -> Self {
-        Player { balance: starting_balance }
+    pub fn new(starting_balance: Money) -> Self {
+        let mut player = Player { balance: Money::ZERO, audit: Vec::new(), currency: Currency::usd() };
+        player.apply(BalanceChangeReason::Deposit, starting_balance);
+        player
     }
 
     /// Returns the current balance of the player.
-    pub fn balance(&self) -> u32 {
+    pub fn balance(&self) -> Money {
         self.balance
     }
 
+    /// Counts the balance in `currency` from now on, for the messages.
+    pub fn set_currency(&mut self, currency: Currency) {
+        self.currency = currency;
+    }
+
+    fn money(&self, value: Money) -> Amount {
+        self.currency.amount(value)
+    }
+
+    /// Every balance change so far, starting with the opening deposit. The
+    /// last entry's balance is always the current balance.
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        &self.audit
+    }
+
+    /// Moves the balance by `amount`, which the caller has checked is
+    /// affordable, and logs it.
+    fn apply(&mut self, cause: BalanceChangeReason, amount: Money) {
+        self.balance += amount;
+        assert!(!self.balance.is_negative(), "balance out of range");
+        self.audit.push(AuditEntry { seq: self.audit.len() as u64, cause, amount, balance: self.balance });
+    }
+
+    /// Sets the balance outright, as when a game is restored from a snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `balance` - The balance to restore.
+    pub fn restore_balance(&mut self, balance: Money) {
+        let delta = balance - self.balance;
+        if !delta.is_zero() {
+            self.apply(BalanceChangeReason::Restore, delta);
+        }
+    }
+
     /// Adds winnings to the player's balance.
     ///
     /// # Arguments
     ///
     /// * `amount` - The amount to add.
-    pub fn add_winnings(&mut self, amount: u32) {
-        self.balance += amount;
-        println!("You won ${}! New balance: ${}", amount, self.balance);
+    pub fn add_winnings(&mut self, amount: Money) {
+        self.apply(BalanceChangeReason::Winnings, amount);
+        say!("You won {}! New balance: {}", self.money(amount), self.money(self.balance));
     }
 
     /// Deducts a bet amount from the player's balance.
@@ -40,24 +129,93 @@ impl Player {
     /// # Arguments
     ///
     /// * `amount` - The amount to deduct.
-    pub fn place_bet(&mut self, amount: u32) -> bool {
+    pub fn place_bet(&mut self, amount: Money) -> bool {
         if amount > self.balance {
-            println!("Insufficient balance. You have ${}, but tried to bet ${}", self.balance, amount);
+            say!("Insufficient balance. You have {}, but tried to bet {}", self.money(self.balance), self.money(amount));
             false
         } else {
-            self.balance -= amount;
-            println!("Bet ${} placed. Remaining balance: ${}", amount, self.balance);
+            self.apply(BalanceChangeReason::BetPlaced, -(amount));
+            say!("Bet {} placed. Remaining balance: {}", self.money(amount), self.money(self.balance));
             true
         }
     }
 
+    /// Takes back winnings paid on a spin that was voided, as far as the
+    /// balance covers them. Returns the amount actually taken back.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to take back.
+    pub fn reverse_winnings(&mut self, amount: Money) -> Money {
+        let taken = amount.min(self.balance);
+        if taken > Money::ZERO {
+            self.apply(BalanceChangeReason::Void, -taken);
+        }
+        if taken < amount {
+            say!("Winnings of {} reversed, {} of them already gone. Balance: {}",
+                self.money(taken), self.money(amount - taken), self.money(self.balance));
+        } else if amount > Money::ZERO {
+            say!("Winnings of {} reversed. Balance: {}", self.money(amount), self.money(self.balance));
+        }
+        taken
+    }
+
+    /// Adds money brought to the table during play.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to add.
+    pub fn deposit(&mut self, amount: Money) {
+        self.apply(BalanceChangeReason::Deposit, amount);
+        say!("Deposited {}. New balance: {}", self.money(amount), self.money(self.balance));
+    }
+
+    /// Adds chips bought with wallet cash.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount bought in for.
+    pub fn buy_in(&mut self, amount: Money) {
+        self.apply(BalanceChangeReason::BuyIn, amount);
+        say!("Bought in for {}. New balance: {}", self.money(amount), self.money(self.balance));
+    }
+
+    /// Takes the whole balance off the table to be cashed out, returning it.
+    pub fn cash_out(&mut self) -> Money {
+        let amount = self.balance;
+        if amount > Money::ZERO {
+            self.apply(BalanceChangeReason::CashOut, -(amount));
+        }
+        amount
+    }
+
+    /// Pays out a comp: a credit bought with comp points, or insurance.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to add.
+    pub fn add_comp(&mut self, amount: Money) {
+        self.apply(BalanceChangeReason::Comp, amount);
+        say!("Comp of {} added. New balance: {}", self.money(amount), self.money(self.balance));
+    }
+
+    /// Pays a loss rebate.
+    ///
+    /// # Arguments
+    ///
+    /// * `amount` - The amount to add.
+    pub fn add_rebate(&mut self, amount: Money) {
+        self.apply(BalanceChangeReason::Rebate, amount);
+        say!("Loss rebate of {} paid. New balance: {}", self.money(amount), self.money(self.balance));
+    }
+
      /// Adds back the bet amount if the bet was invalid or cancelled.
      ///
      /// # Arguments
      ///
      /// * `amount` - The amount to refund.
-     pub fn refund_bet(&mut self, amount: u32) {
-         self.balance += amount;
-         println!("Bet ${} refunded. Balance: ${}", amount, self.balance);
+     pub fn refund_bet(&mut self, amount: Money) {
+         self.apply(BalanceChangeReason::Refund, amount);
+         say!("Bet {} refunded. Balance: {}", self.money(amount), self.money(self.balance));
      }
 }
@@ -0,0 +1,553 @@
+// src/game/rules.rs
+
+//! House rules for a table, set in one place and applied by [`Game`](super::Game).
+//!
+//! ```toml
+//! [rules]
+//! zero_rule = "en_prison"     # or "lose" (the default)
+//! green_pocket = "half_back"  # outside bets on a green pocket: "lose", "half_back", "push", or "respin"
+//! surrender = "on_zero"       # half back on losing even-money bets: "off", "on_zero", or "always"
+//! max_bets_per_round = 8
+//! rebet = true
+//! min_bet = 25                # stake limits on every bet; 0.50 for fifty cents
+//! max_bet = 2500
+//! stake_increment = 5         # stakes must be multiples of this
+//! min_buy_in = 500            # least cash changed into chips on sitting down
+//! side_bets = ["black_swan"]  # extra bets on offer, see `SideBet`
+//! chips = [5, 25, 100, 500]   # play with chips; stakes are multiples of the smallest
+//! currency = "EUR"            # USD unless set, see `Currency`
+//!
+//! [rules.rebate]              # optional promotion: part of the net loss back
+//! percent = 10
+//! every_rounds = 50           # leave out to pay once, when play ends
+//!
+//! [rules.payouts]             # optional; replaces the wheel's own odds
+//! straight_up = 30
+//! ```
+//!
+//! A [`Difficulty`] other than normal adjusts these rules once they are set.
+
+use super::bets::BetType;
+use super::chips::ChipSet;
+use super::currency::{Amount, Currency};
+use super::custom::SideBet;
+use super::money::Money;
+use super::payout::{PayoutLine, PayoutTable};
+use super::wheel::Wheel;
+use crate::analysis;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What happens to even-money bets when a zero lands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZeroRule {
+    /// They lose, like every other bet that doesn't cover the zero.
+    #[default]
+    Lose,
+    /// They stay on the table for one more spin. If that spin wins the stake
+    /// comes back (without winnings); otherwise it is lost.
+    EnPrison,
+}
+
+/// What happens to every outside bet when the ball lands in a green pocket
+/// (Recession, or the Market Surge on the American wheel).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GreenPocket {
+    /// They lose, and the zero rule and surrender decide the even-money bets.
+    #[default]
+    Lose,
+    /// Half of each stake comes back.
+    HalfBack,
+    /// Each stake comes back whole, as if the bet were never made.
+    Push,
+    /// The ball is spun again at once for the outside bets alone, which
+    /// then win or lose on the new pocket.
+    Respin,
+}
+
+/// Re-spins played in a row for one round's outside bets under
+/// [`GreenPocket::Respin`]. Bets still riding after that many wait for the
+/// next spin.
+pub const MAX_RESPINS: u32 = 10;
+
+/// How a bet that didn't win is settled, see [`TableRules::settle_loss`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossSettlement {
+    Lose,
+    /// Held for the next spin under en prison.
+    Imprison,
+    /// Half the stake back to a surrendered even-money bet.
+    Surrender,
+    /// Half the stake back on a green pocket.
+    HalfBack,
+    /// The whole stake back.
+    Push,
+    /// Carried to a re-spin.
+    Respin,
+}
+
+/// When losing even-money bets get half their stake back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Surrender {
+    /// Never: losing bets lose everything.
+    #[default]
+    Off,
+    /// Only when a zero lands, as on American tables (la partage).
+    OnZero,
+    /// Whenever they lose.
+    Always,
+}
+
+impl Surrender {
+    /// True if a losing even-money bet is surrendered on this spin.
+    pub fn applies(self, zero_hit: bool) -> bool {
+        match self {
+            Surrender::Off => false,
+            Surrender::OnZero => zero_hit,
+            Surrender::Always => true,
+        }
+    }
+}
+
+/// A promotion that pays back a share of the player's net losses, once
+/// when the session ends or every so many rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LossRebate {
+    /// Share of the net loss paid back, in percent.
+    pub percent: u32,
+    /// Rounds per rebate period; `None` for one period covering the session.
+    #[serde(default)]
+    pub every_rounds: Option<u32>,
+}
+
+impl LossRebate {
+    /// The rebate on a period that came out `net` (negative for a loss).
+    pub fn on(&self, net: Money) -> Money {
+        (-net).max(Money::ZERO).percent(self.percent)
+    }
+}
+
+/// The rules a table plays by. The defaults are the game's classic rules.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableRules {
+    pub zero_rule: ZeroRule,
+    pub green_pocket: GreenPocket,
+    pub surrender: Surrender,
+    /// Most bets a player may have on the table at once.
+    pub max_bets_per_round: Option<usize>,
+    /// Whether players may repeat the previous round's bets in one step.
+    pub rebet: bool,
+    /// Odds to pay instead of the wheel's own.
+    pub payouts: Option<PayoutTable>,
+    pub rebate: Option<LossRebate>,
+    /// Smallest stake taken on a single bet.
+    pub min_bet: Option<Money>,
+    /// Largest stake taken on a single bet.
+    pub max_bet: Option<Money>,
+    /// Every stake must be a multiple of this, chips or not.
+    pub stake_increment: Option<Money>,
+    /// Least cash taken for chips when sitting down; the minimum bet if unset.
+    pub min_buy_in: Option<Money>,
+    /// Side bets offered besides the wheel's own.
+    pub side_bets: Vec<SideBet>,
+    /// Chip denominations, when the table plays with chips.
+    pub chips: Option<ChipSet>,
+    /// What every amount at the table is counted in.
+    pub currency: Currency,
+}
+
+impl Default for TableRules {
+    fn default() -> Self {
+        TableRules {
+            zero_rule: ZeroRule::Lose,
+            green_pocket: GreenPocket::Lose,
+            surrender: Surrender::Off,
+            max_bets_per_round: None,
+            rebet: true,
+            payouts: None,
+            rebate: None,
+            min_bet: None,
+            max_bet: None,
+            stake_increment: None,
+            min_buy_in: None,
+            side_bets: Vec::new(),
+            chips: None,
+            currency: Currency::usd(),
+        }
+    }
+}
+
+impl TableRules {
+    /// Checks that the rules can be applied together.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.surrender != Surrender::Off && self.zero_rule == ZeroRule::EnPrison {
+            return Err("surrender and en prison both settle even-money bets on zero; choose one".to_string());
+        }
+        if self.green_pocket != GreenPocket::Lose
+            && (self.zero_rule == ZeroRule::EnPrison || self.surrender == Surrender::OnZero)
+        {
+            return Err("green_pocket already settles even-money bets on zero; leave out en prison and surrender".to_string());
+        }
+        if self.max_bets_per_round == Some(0) {
+            return Err("max_bets_per_round must be at least 1".to_string());
+        }
+        if let Some(rebate) = &self.rebate {
+            if !(1..=100).contains(&rebate.percent) {
+                return Err("rebate percent must be between 1 and 100".to_string());
+            }
+            if rebate.every_rounds == Some(0) {
+                return Err("rebate every_rounds must be at least 1".to_string());
+            }
+        }
+        let limits = [
+            ("min_bet", self.min_bet),
+            ("max_bet", self.max_bet),
+            ("stake_increment", self.stake_increment),
+            ("min_buy_in", self.min_buy_in),
+        ];
+        if let Some((name, _)) = limits.iter().find(|(_, limit)| limit.is_some_and(|limit| limit <= Money::ZERO)) {
+            return Err(format!("{} must be more than 0", name));
+        }
+        let money = |value: Money| self.currency.amount(value);
+        if let (Some(min), Some(max)) = (self.min_bet, self.max_bet)
+            && min > max
+        {
+            return Err(format!("min_bet ({}) is above max_bet ({})", money(min), money(max)));
+        }
+        if let Some(increment) = self.stake_increment {
+            for (name, limit) in [("min_bet", self.min_bet), ("max_bet", self.max_bet)] {
+                if let Some(limit) = limit
+                    && !limit.is_multiple_of(increment)
+                {
+                    return Err(format!("{} ({}) isn't a multiple of stake_increment ({})", name, money(limit), money(increment)));
+                }
+            }
+        }
+        if let (Some(buy_in), Some(min)) = (self.min_buy_in, self.min_bet)
+            && buy_in < min
+        {
+            return Err(format!("min_buy_in ({}) is below min_bet ({})", money(buy_in), money(min)));
+        }
+        if let (Some(min), Some(chips)) = (self.min_bet, &self.chips)
+            && !min.is_multiple_of(chips.min_chip().into())
+        {
+            return Err(format!("min_bet ({}) isn't a multiple of the smallest chip ({})", money(min), money(chips.min_chip().into())));
+        }
+        Ok(())
+    }
+
+    /// The settlement policy for a bet of `bet_type` that didn't cover the
+    /// winning pocket. On a green pocket the green-pocket rule takes the
+    /// outside bets first; even-money bets it lets lose may still go to
+    /// prison or be surrendered.
+    pub fn settle_loss(&self, bet_type: &BetType, zero_hit: bool) -> LossSettlement {
+        if zero_hit && bet_type.is_outside() {
+            match self.green_pocket {
+                GreenPocket::Lose => {}
+                GreenPocket::HalfBack => return LossSettlement::HalfBack,
+                GreenPocket::Push => return LossSettlement::Push,
+                GreenPocket::Respin => return LossSettlement::Respin,
+            }
+        }
+        if !bet_type.is_even_money() {
+            return LossSettlement::Lose;
+        }
+        if zero_hit && self.zero_rule == ZeroRule::EnPrison {
+            LossSettlement::Imprison
+        } else if self.surrender.applies(zero_hit) {
+            LossSettlement::Surrender
+        } else {
+            LossSettlement::Lose
+        }
+    }
+
+    /// Least a player may buy in for.
+    pub fn buy_in_minimum(&self) -> Money {
+        self.min_buy_in.or(self.min_bet).unwrap_or(Money::from_cents(1))
+    }
+
+    /// Checks a single bet's stake against the table limits.
+    pub fn check_stake(&self, amount: Money) -> Result<(), LimitBreach> {
+        let money = |value: Money| self.currency.amount(value);
+        if let Some(min) = self.min_bet
+            && amount < min
+        {
+            return Err(LimitBreach::BelowMinimum(money(min)));
+        }
+        if let Some(max) = self.max_bet
+            && amount > max
+        {
+            return Err(LimitBreach::AboveMaximum(money(max)));
+        }
+        if let Some(chips) = &self.chips
+            && !amount.is_multiple_of(chips.min_chip().into())
+        {
+            return Err(LimitBreach::OffChip(money(chips.min_chip().into())));
+        }
+        let step = self.stake_step();
+        if self.stake_increment.is_some() && !amount.is_multiple_of(step) {
+            // Suggest the nearest stakes the table would take, which the
+            // chips can make up and which sit inside the limits.
+            let least = self.least_stake();
+            let takes = |stake: Money| stake >= least && self.max_bet.is_none_or(|max| stake <= max);
+            let below = Money::from_cents(amount.cents() / step.cents() * step.cents());
+            let above = below.checked_add(step).map(|above| above.max(least));
+            return Err(LimitBreach::OffIncrement {
+                step: money(step),
+                below: Some(below).filter(|&stake| takes(stake)).map(money),
+                above: above.filter(|&stake| takes(stake)).map(money),
+            });
+        }
+        Ok(())
+    }
+
+    /// What every stake must be a multiple of: the stake increment and the
+    /// smallest chip both divide it. A cent if neither is set.
+    pub fn stake_step(&self) -> Money {
+        let cent = Money::from_cents(1);
+        let chip = self.chips.as_ref().map_or(cent, |chips| chips.min_chip().into()).cents();
+        let increment = self.stake_increment.unwrap_or(cent).cents();
+        let (mut a, mut b) = (chip, increment);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        Money::from_cents(chip / a * increment).min(Money::MAX)
+    }
+
+    /// The smallest stake the table takes: the minimum bet, rounded up to a
+    /// multiple of [`stake_step`](TableRules::stake_step).
+    fn least_stake(&self) -> Money {
+        let step = self.stake_step().cents();
+        let min = self.min_bet.unwrap_or(Money::from_cents(1)).cents();
+        Money::from_cents((min + step - 1) / step * step)
+    }
+
+    /// `amount` rounded down to a multiple of [`stake_step`], but no less
+    /// than the smallest stake the table takes; for bots sizing their bets.
+    /// It may still be over the maximum.
+    ///
+    /// [`stake_step`]: TableRules::stake_step
+    pub fn round_stake(&self, amount: Money) -> Money {
+        let step = self.stake_step().cents();
+        Money::from_cents(amount.cents() / step * step).max(self.least_stake())
+    }
+}
+
+/// A stake outside the table limits, with the limit it breaks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitBreach {
+    BelowMinimum(Amount),
+    AboveMaximum(Amount),
+    /// Not a multiple of the smallest chip.
+    OffChip(Amount),
+    /// Not a multiple of the table's stake increment, or of the chips that
+    /// have to make it up. `below` and `above` are the nearest stakes either
+    /// side that the table would take, if there are any.
+    OffIncrement { step: Amount, below: Option<Amount>, above: Option<Amount> },
+}
+
+impl fmt::Display for LimitBreach {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitBreach::BelowMinimum(min) => write!(f, "the table minimum is {}", min),
+            LimitBreach::AboveMaximum(max) => write!(f, "the table maximum is {}", max),
+            LimitBreach::OffChip(chip) => write!(f, "the smallest chip is {}, so stakes go up in {}", chip, chip),
+            LimitBreach::OffIncrement { step, below, above } => {
+                write!(f, "stakes here go up in {}", step)?;
+                match (below, above) {
+                    (Some(below), Some(above)) => write!(f, "; try {} or {}", below, above),
+                    (Some(stake), None) | (None, Some(stake)) => write!(f, "; try {}", stake),
+                    (None, None) => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// How kind the table is. Applied on top of the configured [`TableRules`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    /// Half back on even-money bets when a zero lands, and inside bets pay
+    /// one more where the house still keeps an edge at those odds.
+    Easy,
+    /// The rules as configured.
+    #[default]
+    Normal,
+    /// No zero protection, inside bets pay one less, and at most
+    /// [`BRUTAL_MAX_BETS`] bets a round.
+    Brutal,
+}
+
+/// Most bets per round on a brutal table.
+pub const BRUTAL_MAX_BETS: usize = 4;
+
+impl Difficulty {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "brutal" => Some(Difficulty::Brutal),
+            _ => None,
+        }
+    }
+
+    /// `rules` adjusted for this difficulty on `wheel`, whose own odds apply
+    /// when the rules don't replace them. The adjusted odds go through
+    /// [`Wheel::verify_payouts`]: a difficulty that would leave some bet
+    /// returning more than it costs, when it didn't before, is refused.
+    pub fn apply(self, rules: &TableRules, wheel: &Wheel) -> Result<TableRules, PlayerEdge> {
+        let mut rules = rules.clone();
+        if self == Difficulty::Normal {
+            return Ok(rules);
+        }
+        let configured = rules.payouts.take().unwrap_or_else(|| wheel.payouts().clone());
+        let payouts = self.adjust(&mut rules, configured.clone(), wheel);
+        // A wheel's own odds may already favour a bet; the difficulty mustn't add one.
+        let before = wheel.verify_payouts(&configured);
+        let bets: Vec<PayoutLine> = wheel
+            .verify_payouts(&payouts)
+            .lines
+            .into_iter()
+            .zip(&before.lines)
+            .filter(|(after, before)| after.favours_player() && after.expected_return > before.expected_return)
+            .map(|(after, _)| after)
+            .collect();
+        if !bets.is_empty() {
+            return Err(PlayerEdge { difficulty: self, bets });
+        }
+        rules.payouts = Some(payouts);
+        Ok(rules)
+    }
+
+    fn adjust(self, rules: &mut TableRules, mut payouts: PayoutTable, wheel: &Wheel) -> PayoutTable {
+        match self {
+            Difficulty::Normal => {}
+            Difficulty::Easy => {
+                // En prison and the green-pocket rule already protect even-money bets; surrender would clash.
+                if rules.zero_rule == ZeroRule::Lose
+                    && rules.green_pocket == GreenPocket::Lose
+                    && rules.surrender == Surrender::Off
+                {
+                    rules.surrender = Surrender::OnZero;
+                }
+                // One more only while the bet stays below break-even: on a
+                // 37-pocket wheel 36 to 1 on a single number already is.
+                let numbered: Vec<_> = wheel.get_all_pockets().iter().filter(|p| !p.is_zero()).map(|p| p.ticker).collect();
+                if let [first, second, ..] = numbered[..] {
+                    let mut kinder = payouts.clone();
+                    kinder.straight_up += 1;
+                    kinder.split += 1;
+                    if analysis::expected_return(&BetType::StraightUp(first), wheel, &kinder) < 1.0 {
+                        payouts.straight_up = kinder.straight_up;
+                    }
+                    if analysis::expected_return(&BetType::Split(first, second), wheel, &kinder) < 1.0 {
+                        payouts.split = kinder.split;
+                    }
+                }
+            }
+            Difficulty::Brutal => {
+                rules.zero_rule = ZeroRule::Lose;
+                rules.green_pocket = GreenPocket::Lose;
+                rules.surrender = Surrender::Off;
+                payouts.straight_up = payouts.straight_up.saturating_sub(1).max(1);
+                payouts.split = payouts.split.saturating_sub(1).max(1);
+                rules.max_bets_per_round =
+                    Some(rules.max_bets_per_round.map_or(BRUTAL_MAX_BETS, |max| max.min(BRUTAL_MAX_BETS)));
+            }
+        }
+        payouts
+    }
+}
+
+/// A difficulty that would hand the player an edge, refused by
+/// [`Difficulty::apply`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerEdge {
+    pub difficulty: Difficulty,
+    /// The bets it would leave returning more than they cost.
+    pub bets: Vec<PayoutLine>,
+}
+
+impl fmt::Display for PlayerEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} difficulty would let {} bet(s) return more than they cost", self.difficulty, self.bets.len())?;
+        if let Some(line) = self.bets.first() {
+            write!(f, ", such as {} at {:.2}%", line.label, line.expected_return * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PlayerEdge {}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difficulty::Easy => write!(f, "Easy"),
+            Difficulty::Normal => write!(f, "Normal"),
+            Difficulty::Brutal => write!(f, "Brutal"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inside_odds(rules: &TableRules) -> (u32, u32) {
+        let payouts = rules.payouts.as_ref().expect("a difficulty sets the odds");
+        (payouts.straight_up, payouts.split)
+    }
+
+    #[test]
+    fn normal_leaves_the_rules_alone() {
+        let rules = TableRules { zero_rule: ZeroRule::EnPrison, ..TableRules::default() };
+        assert_eq!(Difficulty::Normal.apply(&rules, &Wheel::european()).unwrap(), rules);
+    }
+
+    #[test]
+    fn easy_never_lifts_a_bet_to_break_even() {
+        for wheel in [Wheel::european(), Wheel::american(), Wheel::mini()] {
+            let rules = Difficulty::Easy.apply(&TableRules::default(), &wheel).unwrap();
+            let easy = wheel.verify_payouts(rules.payouts.as_ref().unwrap());
+            let normal = wheel.verify_payouts(wheel.payouts());
+            for (easy, normal) in easy.lines.iter().zip(&normal.lines) {
+                assert!(easy.expected_return < 1.0 || easy.expected_return == normal.expected_return, "{}", easy.label);
+            }
+        }
+    }
+
+    #[test]
+    fn easy_raises_inside_odds_only_below_break_even() {
+        // 36 to 1 on one of 37 pockets would return every stake in full.
+        let european = Difficulty::Easy.apply(&TableRules::default(), &Wheel::european()).unwrap();
+        assert_eq!(inside_odds(&european), (35, 17));
+        let american = Difficulty::Easy.apply(&TableRules::default(), &Wheel::american()).unwrap();
+        assert_eq!(inside_odds(&american), (36, 17));
+        let stingy = TableRules { payouts: Some(PayoutTable { straight_up: 30, split: 15, ..PayoutTable::STANDARD }), ..TableRules::default() };
+        assert_eq!(inside_odds(&Difficulty::Easy.apply(&stingy, &Wheel::european()).unwrap()), (31, 16));
+    }
+
+    #[test]
+    fn easy_adds_surrender_only_without_other_zero_protection() {
+        let easy = Difficulty::Easy.apply(&TableRules::default(), &Wheel::european()).unwrap();
+        assert_eq!(easy.surrender, Surrender::OnZero);
+        let prison = TableRules { zero_rule: ZeroRule::EnPrison, ..TableRules::default() };
+        assert_eq!(Difficulty::Easy.apply(&prison, &Wheel::european()).unwrap().surrender, Surrender::Off);
+    }
+
+    #[test]
+    fn brutal_cuts_inside_odds_and_zero_protection() {
+        let rules = TableRules { zero_rule: ZeroRule::EnPrison, max_bets_per_round: Some(10), ..TableRules::default() };
+        let brutal = Difficulty::Brutal.apply(&rules, &Wheel::european()).unwrap();
+        assert_eq!(inside_odds(&brutal), (34, 16));
+        assert_eq!(brutal.zero_rule, ZeroRule::Lose);
+        assert_eq!(brutal.max_bets_per_round, Some(BRUTAL_MAX_BETS));
+    }
+}
@@ -0,0 +1,232 @@
+// src/game/script.rs
+
+//! House rules scripted in [Rhai](https://rhai.rs), behind the `scripting`
+//! feature.
+//!
+//! A rules script may define any of these functions:
+//!
+//! ```text
+//! // Called for every settled bet; returns the amount to pay, stake included.
+//! fn on_settle(bet) { if bet.zero && bet.kind == "Red" { bet.amount / 2 } else { bet.payout } }
+//!
+//! // Promotional bets, registered as custom bets when the script loads.
+//! fn promotions() { [#{ name: "Chip Makers", tickers: ["NVDA", "AAPL", "MSFT"], pays: 10 }] }
+//!
+//! // Called after each round; a returned string is shown to the player.
+//! fn on_round_end(round) { if round.returned > 500 { "Hot streak!" } }
+//! ```
+//!
+//! `bet` carries `kind`, `amount`, `won`, `payout`, `ticker`, `number`,
+//! `color`, `zero`, and `round`; `round` carries `round`, `wagered`,
+//! `returned`, and `balance`. Amounts are in whole units of the table's
+//! currency: an integer when there are no cents, else a float, and
+//! `on_settle` may return either. Scripts only see these values: the engine has
+//! no file or network access, and every call runs under operation, depth,
+//! and size limits so a runaway script can't hang the table.
+
+use super::bets::Bet;
+use super::custom::{BetEvaluator, BetRegistry};
+use super::money::Money;
+use super::wheel::{PocketId, PocketSet, Wheel};
+use crate::say;
+use rhai::{AST, Array, CallFnOptions, Dynamic, Engine, FuncArgs, Map, Scope};
+use std::fmt;
+
+/// Operations one hook call may run before it is stopped.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// Why a rules script could not be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    Compile(String),
+    Runtime(String),
+    /// A promotion from `promotions()` is malformed or clashes with another bet.
+    Promotion(String),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Compile(msg) => write!(f, "script doesn't compile: {}", msg),
+            ScriptError::Runtime(msg) => write!(f, "script failed: {}", msg),
+            ScriptError::Promotion(msg) => write!(f, "bad promotion: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// A compiled rules script.
+pub struct HouseScript {
+    engine: Engine,
+    ast: AST,
+}
+
+impl HouseScript {
+    /// Compiles `source` and runs its top-level statements once.
+    pub fn compile(source: &str) -> Result<Self, ScriptError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(10_000);
+        engine.set_max_array_size(1_000);
+        engine.set_max_map_size(1_000);
+        engine.on_print(|text| say!("[rules] {}", text));
+        engine.on_debug(|text, _, _| say!("[rules] {}", text));
+
+        let ast = engine.compile(source).map_err(|e| ScriptError::Compile(e.to_string()))?;
+        engine.run_ast(&ast).map_err(|e| ScriptError::Runtime(e.to_string()))?;
+        Ok(HouseScript { engine, ast })
+    }
+
+    fn defines(&self, name: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == name)
+    }
+
+    /// Calls a hook without re-running the script's top-level statements.
+    fn call(&self, name: &str, args: impl FuncArgs) -> Result<Dynamic, String> {
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine
+            .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, name, args)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Registers the script's promotional bets as custom bets.
+    pub fn register_promotions(&self, wheel: &Wheel, registry: &mut BetRegistry) -> Result<(), ScriptError> {
+        if !self.defines("promotions") {
+            return Ok(());
+        }
+        let promotions = self.call("promotions", ()).map_err(ScriptError::Runtime)?;
+        let promotions = promotions
+            .try_cast::<Array>()
+            .ok_or_else(|| ScriptError::Promotion("promotions() must return an array".to_string()))?;
+        for promotion in promotions {
+            let bet = PromotionalBet::from_script(promotion, wheel)?;
+            registry.register(Box::new(bet)).map_err(|e| ScriptError::Promotion(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Runs `on_settle`, returning the adjusted payout. A script error is
+    /// reported and leaves the payout as it was.
+    pub fn adjust_payout(&self, wheel: &Wheel, bet: &Bet, pocket: PocketId, payout: Money, round: u32) -> Money {
+        if !self.defines("on_settle") {
+            return payout;
+        }
+        let landed = wheel.pocket(pocket);
+        let mut info = Map::new();
+        info.insert("kind".into(), bet.bet_type.to_string().into());
+        info.insert("amount".into(), amount(bet.amount));
+        info.insert("won".into(), (!payout.is_zero()).into());
+        info.insert("payout".into(), amount(payout));
+        info.insert("ticker".into(), landed.ticker.as_str().into());
+        info.insert("number".into(), (landed.number as i64).into());
+        info.insert("color".into(), landed.color.to_string().into());
+        info.insert("zero".into(), landed.is_zero().into());
+        info.insert("round".into(), (round as i64).into());
+        match self.call("on_settle", (info,)) {
+            Ok(value) if value.is_int() => value.as_int().map_or(payout, |v| Money::from_units(v.clamp(0, u32::MAX as i64) as u32)),
+            Ok(value) if value.is_float() => {
+                value.as_float().map_or(payout, |v| Money::from_f64(v.clamp(0.0, Money::MAX.as_f64())))
+            }
+            Ok(value) if value.is_unit() => payout,
+            Ok(value) => {
+                say!("[rules] on_settle returned {} instead of a number; ignored.", value.type_name());
+                payout
+            }
+            Err(e) => {
+                say!("[rules] on_settle failed: {}", e);
+                payout
+            }
+        }
+    }
+
+    /// Runs `on_round_end` and shows any message it returns.
+    pub fn round_ended(&self, round: u32, wagered: Money, returned: Money, balance: Money) {
+        if !self.defines("on_round_end") {
+            return;
+        }
+        let mut info = Map::new();
+        info.insert("round".into(), (round as i64).into());
+        info.insert("wagered".into(), amount(wagered));
+        info.insert("returned".into(), amount(returned));
+        info.insert("balance".into(), amount(balance));
+        match self.call("on_round_end", (info,)) {
+            Ok(value) if value.is_string() => say!("[rules] {}", value),
+            Ok(_) => {}
+            Err(e) => say!("[rules] on_round_end failed: {}", e),
+        }
+    }
+}
+
+/// An amount as a script sees it, in whole units.
+fn amount(money: Money) -> Dynamic {
+    match money.whole() {
+        Some(units) => (units as i64).into(),
+        None => money.as_f64().into(),
+    }
+}
+
+/// A bet defined by a rules script: a fixed set of pockets at fixed odds.
+struct PromotionalBet {
+    name: String,
+    pockets: PocketSet,
+    pays: u32,
+}
+
+impl PromotionalBet {
+    fn from_script(value: Dynamic, wheel: &Wheel) -> Result<Self, ScriptError> {
+        let invalid = |msg: &str| ScriptError::Promotion(msg.to_string());
+        let map = value.try_cast::<Map>().ok_or_else(|| invalid("each promotion must be a map"))?;
+        let name = map
+            .get("name")
+            .and_then(|v| v.clone().into_string().ok())
+            .ok_or_else(|| invalid("promotion needs a name"))?;
+        let pays = map
+            .get("pays")
+            .and_then(|v| v.as_int().ok())
+            .filter(|&p| p > 0 && p <= u32::MAX as i64)
+            .ok_or_else(|| ScriptError::Promotion(format!("'{}' needs positive whole-number odds in 'pays'", name)))?;
+        let mut pockets = PocketSet::new();
+        if let Some(tickers) = map.get("tickers") {
+            let tickers = tickers.clone().try_cast::<Array>().ok_or_else(|| invalid("'tickers' must be an array"))?;
+            for ticker in tickers {
+                let ticker = ticker.into_string().map_err(|_| invalid("tickers must be strings"))?;
+                let id = wheel
+                    .find_pocket(&ticker)
+                    .ok_or_else(|| ScriptError::Promotion(format!("'{}' names unknown ticker {}", name, ticker)))?;
+                pockets.insert(id);
+            }
+        }
+        if let Some(category) = map.get("category") {
+            let category = category.clone().into_string().map_err(|_| invalid("'category' must be a string"))?;
+            let id = wheel
+                .resolve_category(&category)
+                .map_err(|e| ScriptError::Promotion(format!("'{}' names an {}", name, e)))?;
+            for pocket in (0..wheel.get_all_pockets().len()).map(PocketId) {
+                if wheel.in_category(id, pocket) {
+                    pockets.insert(pocket);
+                }
+            }
+        }
+        if pockets.is_empty() {
+            return Err(ScriptError::Promotion(format!("'{}' covers no pockets; give 'tickers' or 'category'", name)));
+        }
+        Ok(PromotionalBet { name, pockets, pays: pays as u32 })
+    }
+}
+
+impl BetEvaluator for PromotionalBet {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn covers(&self, _wheel: &Wheel, pocket: PocketId) -> bool {
+        self.pockets.contains(pocket)
+    }
+
+    fn payout_multiplier(&self, _wheel: &Wheel) -> u32 {
+        self.pays
+    }
+}
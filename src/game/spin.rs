@@ -0,0 +1,366 @@
+// src/game/spin.rs
+
+//! How the winning pocket is chosen: a uniform draw, or a simple physical
+//! model of the ball.
+//!
+//! The physical model launches the ball around the track against a rotor
+//! turning the other way, slows it with friction and drag until it drops,
+//! lets it clip a deflector (a "diamond") and bounce between frets, and stops
+//! when it runs out of energy. It isn't a faithful simulation, but it picks
+//! the pocket from the ball's motion, which gives each spin a story ("bounced
+//! out of AAPL into MSFT"). A later bias model can adjust the parameters.
+//!
+//! Front ends that animate the wheel can observe a spin as a stream of
+//! [`SpinFrame`]s, one per pocket the ball passes over, with the time it gets
+//! there. Physical spins report the simulated motion; uniform spins get a
+//! decelerating run of a few laps that ends on the drawn pocket.
+//!
+//! Ordinary spins draw from a generator seeded afresh each time (see
+//! [`seeded_rng`]); the seed is recorded, so the spin can be redone later
+//! and checked against the pocket the game reported.
+
+use super::wheel::{PocketId, Wheel, WheelLike};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::f64::consts::TAU;
+
+/// Time step of the track integration, in seconds.
+const STEP: f64 = 0.01;
+/// Deflectors evenly spaced around the stator, as on a standard wheel.
+const DEFLECTORS: u32 = 8;
+/// Laps and length of the animation for spins that have no physical path.
+const ANIMATION_LAPS: usize = 3;
+const ANIMATION_SECONDS: f64 = 4.0;
+
+/// What the ball is doing when it passes a pocket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpinPhase {
+    /// Circling the track.
+    Track,
+    /// Falling from the track toward the pockets.
+    Falling,
+    /// Bouncing between pockets.
+    Bouncing,
+    /// At rest in the winning pocket; always the last frame.
+    Settled,
+}
+
+/// The ball reaching a pocket, for animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpinFrame {
+    pub pocket: PocketId,
+    /// Seconds since the ball was launched.
+    pub time: f64,
+    pub phase: SpinPhase,
+}
+
+/// Callback that receives each frame of a spin as it happens.
+pub type SpinObserver = Box<dyn FnMut(&SpinFrame)>;
+
+/// Frames of a decelerating run from `start` to `target`, for spins whose
+/// outcome was decided without a physical path.
+#[derive(Debug, Clone)]
+pub struct SpinAnimation {
+    pockets: usize,
+    start: usize,
+    distance: usize,
+    duration: f64,
+    passed: usize,
+}
+
+impl SpinAnimation {
+    /// Animates `laps` full laps plus however far `target` is from `start`,
+    /// slowing evenly to a stop after `duration` seconds.
+    pub fn new(wheel: &Wheel, start: PocketId, target: PocketId, laps: usize, duration: f64) -> Self {
+        let pockets = wheel.get_all_pockets().len();
+        let distance = laps * pockets + (target.0 + pockets - start.0) % pockets;
+        SpinAnimation { pockets, start: start.0, distance, duration, passed: 0 }
+    }
+}
+
+impl Iterator for SpinAnimation {
+    type Item = SpinFrame;
+
+    fn next(&mut self) -> Option<SpinFrame> {
+        if self.passed > self.distance {
+            return None;
+        }
+        let k = self.passed;
+        self.passed += 1;
+        // Under constant deceleration the ball has covered k of `distance`
+        // pockets once 1 - (1 - t/T)^2 = k / distance.
+        let progress = if self.distance == 0 { 1.0 } else { k as f64 / self.distance as f64 };
+        Some(SpinFrame {
+            pocket: PocketId((self.start + k) % self.pockets),
+            time: self.duration * (1.0 - (1.0 - progress).sqrt()),
+            phase: if k == self.distance { SpinPhase::Settled } else { SpinPhase::Track },
+        })
+    }
+}
+
+/// The generator behind a recorded spin seed, keyed with the seed's SHA-256.
+pub fn seeded_rng(seed: &str) -> StdRng {
+    StdRng::from_seed(Sha256::digest(seed.as_bytes()).into())
+}
+
+/// Which model picks the winning pocket.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SpinModel {
+    /// The wheel picks the pocket itself; on a plain [`Wheel`] every pocket
+    /// is equally likely.
+    #[default]
+    Uniform,
+    /// The pocket follows from a simulated ball.
+    Physics(PhysicsModel),
+}
+
+impl SpinModel {
+    /// Spins the wheel. Only the physics model produces a ball path.
+    pub fn spin<R: Rng>(&self, wheel: &dyn WheelLike, rng: &mut R) -> (PocketId, Option<BallPath>) {
+        self.spin_observed(wheel, rng, &mut |_| {})
+    }
+
+    /// Like [`SpinModel::spin`], reporting every pocket the ball passes to `observer`.
+    pub fn spin_observed<R: Rng>(
+        &self,
+        wheel: &dyn WheelLike,
+        rng: &mut R,
+        observer: &mut dyn FnMut(&SpinFrame),
+    ) -> (PocketId, Option<BallPath>) {
+        let layout = wheel.layout();
+        match self {
+            SpinModel::Uniform => {
+                let pocket = wheel.spin(rng);
+                let start = layout.spin_id_with(rng);
+                SpinAnimation::new(layout, start, pocket, ANIMATION_LAPS, ANIMATION_SECONDS).for_each(|f| observer(&f));
+                (pocket, None)
+            }
+            SpinModel::Physics(model) => {
+                let path = model.spin_observed(layout, rng, observer);
+                (path.pocket, Some(path))
+            }
+        }
+    }
+
+    /// Animation for a spin whose pocket was decided elsewhere, e.g. by a
+    /// committed seed.
+    pub fn animate_to<R: Rng + ?Sized>(wheel: &Wheel, target: PocketId, rng: &mut R) -> SpinAnimation {
+        SpinAnimation::new(wheel, wheel.spin_id_with(rng), target, ANIMATION_LAPS, ANIMATION_SECONDS)
+    }
+}
+
+/// Tuning for the physical model. Speeds are in radians per second.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicsModel {
+    /// Range the ball's launch speed is drawn from.
+    pub ball_speed: (f64, f64),
+    /// Range the rotor's speed is drawn from; it turns against the ball.
+    pub rotor_speed: (f64, f64),
+    /// Constant deceleration of the ball on the track (rolling friction).
+    pub friction: f64,
+    /// Deceleration proportional to the square of the ball's speed (air drag).
+    pub drag: f64,
+    /// Speed below which the ball leaves the track and falls inward.
+    pub drop_speed: f64,
+    /// Chance the falling ball clips a deflector.
+    pub deflector_chance: f64,
+    /// Share of speed kept after each bounce off a fret.
+    pub restitution: f64,
+    /// Speed below which the ball stays in its pocket.
+    pub settle_speed: f64,
+}
+
+impl Default for PhysicsModel {
+    fn default() -> Self {
+        PhysicsModel {
+            ball_speed: (18.0, 26.0),
+            rotor_speed: (2.0, 4.0),
+            friction: 0.5,
+            drag: 0.004,
+            drop_speed: 6.0,
+            deflector_chance: 0.7,
+            restitution: 0.45,
+            settle_speed: 0.6,
+        }
+    }
+}
+
+/// What the ball did on one physical spin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BallPath {
+    pub pocket: PocketId,
+    /// Laps the ball made around the track before dropping.
+    pub revolutions: f64,
+    /// Seconds from launch until the ball settled.
+    pub duration: f64,
+    pub hit_deflector: bool,
+    /// Pockets the ball landed in and bounced out of, in order.
+    pub bounced_out_of: Vec<PocketId>,
+}
+
+impl BallPath {
+    /// One-line description of the spin, naming any near misses.
+    pub fn narrative(&self, wheel: &Wheel) -> String {
+        let mut story = format!("The ball circled {:.1} times", self.revolutions);
+        if self.hit_deflector {
+            story.push_str(", clipped a diamond,");
+        }
+        if self.bounced_out_of.is_empty() {
+            story.push_str(" and dropped cleanly into ");
+        } else {
+            let tickers: Vec<&str> = self.bounced_out_of.iter().map(|&id| wheel.pocket(id).ticker.as_str()).collect();
+            story.push_str(&format!(" and bounced out of {} before settling in ", tickers.join(", ")));
+        }
+        story.push_str(wheel.pocket(self.pocket).ticker.as_str());
+        story.push('.');
+        if let Some(&last) = self.bounced_out_of.last()
+            && is_neighbour(wheel, last, self.pocket)
+        {
+            story.push_str(&format!(" So close for {}!", wheel.pocket(last).ticker));
+        }
+        story
+    }
+}
+
+/// Reports each pocket boundary the ball crosses, relative to the rotor.
+struct PocketTracker<'a> {
+    pockets: i64,
+    pocket_width: f64,
+    observer: &'a mut dyn FnMut(&SpinFrame),
+}
+
+impl PocketTracker<'_> {
+    /// Emits a frame for every pocket entered while the relative angle moves
+    /// from `from` to `to` between times `t0` and `t1`.
+    fn advance(&mut self, from: f64, to: f64, t0: f64, t1: f64, phase: SpinPhase) {
+        let k0 = (from / self.pocket_width).floor() as i64;
+        let k1 = (to / self.pocket_width).floor() as i64;
+        let step = if k1 >= k0 { 1 } else { -1 };
+        let mut k = k0;
+        while k != k1 {
+            k += step;
+            // Moving forward the ball enters pocket k at its lower edge; moving back, at its upper edge.
+            let boundary = if step > 0 { k } else { k + 1 } as f64 * self.pocket_width;
+            let time = t0 + (boundary - from) / (to - from) * (t1 - t0);
+            let pocket = PocketId(k.rem_euclid(self.pockets) as usize);
+            (self.observer)(&SpinFrame { pocket, time, phase });
+        }
+    }
+}
+
+/// True if two pockets sit next to each other on the wheel.
+fn is_neighbour(wheel: &Wheel, a: PocketId, b: PocketId) -> bool {
+    let n = wheel.get_all_pockets().len();
+    (a.0 + 1) % n == b.0 || (b.0 + 1) % n == a.0
+}
+
+impl PhysicsModel {
+    /// Checks that the parameters describe a ball that eventually stops.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, (low, high)) in [("ball_speed", self.ball_speed), ("rotor_speed", self.rotor_speed)] {
+            if !(low >= 0.0 && low <= high && high.is_finite()) {
+                return Err(format!("{} must be a range [low, high] with 0 <= low <= high", name));
+            }
+        }
+        if !(self.friction > 0.0 && self.drag >= 0.0) {
+            return Err("friction must be positive and drag non-negative".to_string());
+        }
+        if !(self.drop_speed > 0.0 && self.settle_speed > 0.0) {
+            return Err("drop_speed and settle_speed must be positive".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.deflector_chance) {
+            return Err("deflector_chance must be between 0 and 1".to_string());
+        }
+        if !(self.restitution > 0.0 && self.restitution < 1.0) {
+            return Err("restitution must be between 0 and 1 (exclusive)".to_string());
+        }
+        Ok(())
+    }
+
+    /// Simulates one spin. Pocket ids follow wheel order, so the pocket under
+    /// the ball is found from its angle relative to the rotor.
+    pub fn spin<R: Rng + ?Sized>(&self, wheel: &Wheel, rng: &mut R) -> BallPath {
+        self.spin_observed(wheel, rng, &mut |_| {})
+    }
+
+    /// Like [`PhysicsModel::spin`], reporting every pocket the ball passes to `observer`.
+    pub fn spin_observed<R: Rng + ?Sized>(
+        &self,
+        wheel: &Wheel,
+        rng: &mut R,
+        observer: &mut dyn FnMut(&SpinFrame),
+    ) -> BallPath {
+        let pockets = wheel.get_all_pockets().len();
+        let pocket_width = TAU / pockets as f64;
+        let mut tracker = PocketTracker { pockets: pockets as i64, pocket_width, observer };
+        let pocket_at = |relative: f64| PocketId(((relative.rem_euclid(TAU) / pocket_width) as usize).min(pockets - 1));
+
+        // On the track: the ball circles one way, the rotor the other.
+        let mut ball_angle = rng.gen_range(0.0..TAU);
+        let mut ball_speed = rng.gen_range(self.ball_speed.0..=self.ball_speed.1);
+        let mut rotor_angle = rng.gen_range(0.0..TAU);
+        let rotor_speed = rng.gen_range(self.rotor_speed.0..=self.rotor_speed.1);
+        let mut travelled = 0.0;
+        let mut time = 0.0;
+        while ball_speed > self.drop_speed {
+            let before = ball_angle - rotor_angle;
+            let step = ball_speed * STEP;
+            ball_angle += step;
+            travelled += step;
+            rotor_angle -= rotor_speed * STEP;
+            ball_speed -= (self.friction + self.drag * ball_speed * ball_speed) * STEP;
+            tracker.advance(before, ball_angle - rotor_angle, time, time + STEP, SpinPhase::Track);
+            time += STEP;
+        }
+
+        // Falling inward: the ball reaches the next deflector and may clip it,
+        // losing an unpredictable share of its speed.
+        let spacing = TAU / DEFLECTORS as f64;
+        let to_deflector = spacing - ball_angle.rem_euclid(spacing);
+        let fall_time = to_deflector / ball_speed;
+        let before = ball_angle - rotor_angle;
+        ball_angle += to_deflector;
+        rotor_angle -= rotor_speed * fall_time;
+        tracker.advance(before, ball_angle - rotor_angle, time, time + fall_time, SpinPhase::Falling);
+        time += fall_time;
+        let hit_deflector = rng.gen_bool(self.deflector_chance);
+        if hit_deflector {
+            ball_speed *= rng.gen_range(0.2..0.9);
+        }
+
+        // In the pocket area, the ball moves relative to the rotor and loses
+        // energy with each bounce, sometimes kicked back the way it came.
+        let mut relative = ball_angle - rotor_angle;
+        let mut relative_speed = ball_speed + rotor_speed;
+        let mut current = pocket_at(relative);
+        let mut bounced_out_of = Vec::new();
+        while relative_speed.abs() > self.settle_speed {
+            let flight = rng.gen_range(0.05..0.25);
+            let before = relative;
+            relative += relative_speed * flight;
+            tracker.advance(before, relative, time, time + flight, SpinPhase::Bouncing);
+            time += flight;
+            relative_speed *= self.restitution * rng.gen_range(0.6..1.0);
+            if rng.gen_bool(0.2) {
+                relative_speed = -relative_speed;
+            }
+            let landed = pocket_at(relative);
+            if landed != current {
+                bounced_out_of.push(current);
+                current = landed;
+            }
+        }
+
+        (tracker.observer)(&SpinFrame { pocket: current, time, phase: SpinPhase::Settled });
+        BallPath {
+            pocket: current,
+            revolutions: travelled / TAU,
+            duration: time,
+            hit_deflector,
+            bounced_out_of,
+        }
+    }
+}
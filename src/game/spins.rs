@@ -0,0 +1,79 @@
+// src/game/spins.rs
+
+//! Rounds as a sequence: [`Game::spins`] and [`Game::spin_stream`].
+
+use super::controller::{PlayerController, Turn};
+use super::{Game, RoundResult};
+use crate::console;
+
+/// Iterator over the rounds a strategy plays; see [`Game::spins`].
+pub struct Spins<'a, C> {
+    game: &'a mut Game,
+    strategy: C,
+    done: bool,
+}
+
+impl<'a, C: PlayerController> Spins<'a, C> {
+    pub(super) fn new(game: &'a mut Game, strategy: C) -> Self {
+        Spins { game, strategy, done: false }
+    }
+
+    /// The strategy, with whatever state it has built up.
+    pub fn strategy(&self) -> &C {
+        &self.strategy
+    }
+}
+
+impl<C: PlayerController> Iterator for Spins<'_, C> {
+    type Item = RoundResult;
+
+    fn next(&mut self) -> Option<RoundResult> {
+        if self.done {
+            return None;
+        }
+        let Spins { game, strategy, .. } = self;
+        let result = console::quietly(|| {
+            if strategy.place_bets(game) == Turn::Leave {
+                if !game.get_current_bets().is_empty() {
+                    game.clear_bets();
+                }
+                return None;
+            }
+            if !game.has_bets_in_play() {
+                return None;
+            }
+            let result = game.spin_round()?;
+            strategy.spin_settled(game.wheel(), result.pocket, result.total_returned());
+            Some(result)
+        });
+        if result.is_none() || !console::quietly(|| self.strategy.play_again(self.game)) {
+            self.done = true;
+        }
+        result
+    }
+}
+
+/// A [`Stream`](futures_core::Stream) of rounds; see [`Game::spin_stream`].
+#[cfg(feature = "async")]
+pub struct SpinStream<'a, C> {
+    spins: Spins<'a, C>,
+}
+
+#[cfg(feature = "async")]
+impl<'a, C> SpinStream<'a, C> {
+    pub(super) fn new(spins: Spins<'a, C>) -> Self {
+        SpinStream { spins }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<C: PlayerController + Unpin> futures_core::Stream for SpinStream<'_, C> {
+    type Item = RoundResult;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<RoundResult>> {
+        std::task::Poll::Ready(self.get_mut().spins.next())
+    }
+}
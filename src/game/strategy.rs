@@ -0,0 +1,236 @@
+// src/game/strategy.rs
+
+//! Betting strategies written down as settings rather than code.
+//!
+//! A strategy file names the bets to back, a base stake, how the stake moves
+//! after wins and losses, and when to walk away:
+//!
+//! ```toml
+//! name = "Red martingale"
+//! bets = ["red"]              # in the bet syntax, without an amount
+//! base_stake = 10             # staked on each bet
+//!
+//! [progression]
+//! on_loss = "double"          # "repeat" (the default), "reset", "double", "increase", or "decrease"
+//! on_win = "reset"
+//! max_stake = 640             # start over at base_stake rather than go past this
+//!
+//! [stop]
+//! max_rounds = 200
+//! take_profit = 100           # leave once this far ahead
+//! stop_loss = 300             # leave once this far behind
+//! ```
+//!
+//! A [`DeclarativeStrategy`] plays the file at a table like any other
+//! [`PlayerController`], and the simulator runs it without a table at all.
+
+use super::Game;
+use super::bets::{Bet, BetType};
+use super::controller::{PlayerController, Turn};
+use super::dsl::{self, ParseBetError};
+use super::money::Money;
+use super::rules::TableRules;
+use super::wheel::{PocketId, Wheel};
+use serde::Deserialize;
+
+/// How the stake changes after a round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeChange {
+    /// Keep the current stake.
+    #[default]
+    Repeat,
+    /// Go back to the base stake.
+    Reset,
+    Double,
+    /// Add one base stake (d'Alembert).
+    Increase,
+    /// Take off one base stake, but never go below it.
+    Decrease,
+}
+
+impl StakeChange {
+    pub fn apply(self, stake: Money, base: Money) -> Money {
+        match self {
+            StakeChange::Repeat => stake,
+            StakeChange::Reset => base,
+            StakeChange::Double => (stake * 2).min(Money::MAX),
+            StakeChange::Increase => (stake + base).min(Money::MAX),
+            StakeChange::Decrease => stake.saturating_sub(base).max(base),
+        }
+    }
+}
+
+/// The stake's response to winning and losing rounds. A round that breaks
+/// even leaves it alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Progression {
+    pub on_win: StakeChange,
+    pub on_loss: StakeChange,
+    /// Largest stake per bet; a change that would pass it resets to the base.
+    pub max_stake: Option<Money>,
+}
+
+/// When to leave the table, on top of running out of money.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StopConditions {
+    pub max_rounds: Option<u32>,
+    /// Profit over the starting balance that ends the session.
+    pub take_profit: Option<Money>,
+    /// Loss from the starting balance that ends the session.
+    pub stop_loss: Option<Money>,
+}
+
+/// A strategy as written in a strategy file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StrategySpec {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// What to back each round, e.g. `"red"` or `"straight NVDA"`.
+    pub bets: Vec<String>,
+    pub base_stake: Money,
+    #[serde(default)]
+    pub progression: Progression,
+    #[serde(default)]
+    pub stop: StopConditions,
+}
+
+impl StrategySpec {
+    /// Checks the settings that don't depend on a wheel.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bets.is_empty() {
+            return Err("bets must name at least one bet".to_string());
+        }
+        if self.base_stake <= Money::ZERO {
+            return Err("base_stake must be more than 0".to_string());
+        }
+        if let Some(max) = self.progression.max_stake
+            && max < self.base_stake
+        {
+            return Err(format!("max_stake ({}) is below base_stake ({})", max, self.base_stake));
+        }
+        Ok(())
+    }
+}
+
+/// A [`StrategySpec`] ready to play on a particular wheel.
+#[derive(Debug, Clone)]
+pub struct DeclarativeStrategy {
+    name: String,
+    bet_types: Vec<BetType>,
+    base: Money,
+    progression: Progression,
+    stop: StopConditions,
+    stake: Money,
+    /// Stake put on each bet last round, after the table rounded it.
+    placed: Money,
+    rounds: u32,
+    starting_balance: Option<Money>,
+}
+
+impl DeclarativeStrategy {
+    /// Resolves the spec's bets against `wheel`.
+    pub fn new(spec: &StrategySpec, wheel: &Wheel) -> Result<Self, ParseBetError> {
+        let bet_types = spec
+            .bets
+            .iter()
+            .map(|bet| dsl::parse_bet_kind(bet, wheel))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(DeclarativeStrategy {
+            name: spec.name.clone().unwrap_or_else(|| "strategy".to_string()),
+            bet_types,
+            base: spec.base_stake,
+            progression: spec.progression,
+            stop: spec.stop,
+            stake: spec.base_stake,
+            placed: spec.base_stake,
+            rounds: 0,
+            starting_balance: None,
+        })
+    }
+
+    /// Stops after `rounds` rounds if the spec doesn't stop sooner.
+    pub fn limit_rounds(&mut self, rounds: u32) {
+        self.stop.max_rounds = Some(self.stop.max_rounds.map_or(rounds, |max| max.min(rounds)));
+    }
+
+    pub fn bet_types(&self) -> &[BetType] {
+        &self.bet_types
+    }
+
+    /// Stake each bet gets next round.
+    pub fn stake(&self) -> Money {
+        self.stake
+    }
+
+    /// Decides whether to play a round with `balance` in hand, returning the
+    /// stake for each bet, or `None` to leave. The first call fixes the
+    /// starting balance the stop conditions measure from.
+    pub fn next_round(&mut self, balance: Money) -> Option<Money> {
+        self.next_stake(balance, |stake| stake)
+    }
+
+    /// [`next_round`](Self::next_round) at a table that takes only some
+    /// stakes: the stake is rounded to one `rules` takes before checking
+    /// it's affordable.
+    pub fn next_round_at(&mut self, balance: Money, rules: &TableRules) -> Option<Money> {
+        self.next_stake(balance, |stake| rules.round_stake(stake))
+    }
+
+    fn next_stake(&mut self, balance: Money, round: impl Fn(Money) -> Money) -> Option<Money> {
+        let start = *self.starting_balance.get_or_insert(balance);
+        let stop = self.stop;
+        if stop.max_rounds.is_some_and(|max| self.rounds >= max)
+            || stop.take_profit.is_some_and(|target| balance >= start + target)
+            || stop.stop_loss.is_some_and(|limit| balance + limit <= start)
+        {
+            return None;
+        }
+        let stake = round(self.stake);
+        let total = stake * self.bet_types.len() as u32;
+        if total > balance {
+            return None;
+        }
+        self.rounds += 1;
+        Some(stake)
+    }
+
+    /// Moves the stake after a round that staked `wagered` and returned
+    /// `returned`.
+    pub fn settled(&mut self, wagered: Money, returned: Money) {
+        let change = match returned.cmp(&wagered) {
+            std::cmp::Ordering::Greater => self.progression.on_win,
+            std::cmp::Ordering::Less => self.progression.on_loss,
+            std::cmp::Ordering::Equal => StakeChange::Repeat,
+        };
+        let next = change.apply(self.stake, self.base);
+        self.stake = match self.progression.max_stake {
+            Some(max) if next > max => self.base,
+            _ => next,
+        };
+    }
+}
+
+impl PlayerController for DeclarativeStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        let Some(stake) = self.next_round_at(game.get_player_balance(), game.rules()) else {
+            return Turn::Leave;
+        };
+        self.placed = stake;
+        for bet_type in &self.bet_types {
+            game.place_bet(Bet::new(bet_type.clone(), self.placed));
+        }
+        Turn::Play
+    }
+
+    fn spin_settled(&mut self, _wheel: &Wheel, _pocket: PocketId, returned: Money) {
+        self.settled(self.placed * self.bet_types.len() as u32, returned);
+    }
+}
@@ -0,0 +1,93 @@
+// src/game/streaks.rs
+
+//! Runs of the same color, parity, or category on consecutive spins.
+//!
+//! Streaks are shown because players enjoy them, not because they mean
+//! anything: every spin is independent, so a color that has hit eight times
+//! running is exactly as likely to hit next as it was before the run began.
+
+use super::category::CategoryId;
+use super::wheel::{Color, PocketId, Wheel};
+use crate::presentation;
+use std::fmt;
+
+/// Spins a streak must run before the table announces it, unless changed
+/// with [`Game::set_streak_alert`](super::Game::set_streak_alert).
+pub const DEFAULT_STREAK_ALERT: u32 = 5;
+
+/// What a streak is a run of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreakKind {
+    Color(Color),
+    /// Odd (`true`) or even numbers; a zero ends the run.
+    Odd(bool),
+    Category(CategoryId),
+}
+
+impl fmt::Display for StreakKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreakKind::Color(color) => write!(f, "{}", presentation::color(*color)),
+            StreakKind::Odd(true) => write!(f, "Odd"),
+            StreakKind::Odd(false) => write!(f, "Even"),
+            StreakKind::Category(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// A run still going as of the last spin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Streak {
+    pub kind: StreakKind,
+    /// Spins in a row, counting the last one.
+    pub length: u32,
+}
+
+impl fmt::Display for Streak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} has hit {} in a row", self.kind, self.length)
+    }
+}
+
+/// Every streak still running, updated spin by spin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreakTracker {
+    streaks: Vec<Streak>,
+}
+
+impl StreakTracker {
+    /// Extends the runs the pocket continues and ends the rest.
+    pub fn record(&mut self, wheel: &Wheel, pocket: PocketId) {
+        let pocket = wheel.pocket(pocket);
+        let mut kinds = vec![StreakKind::Color(pocket.color)];
+        if !pocket.is_zero() {
+            kinds.push(StreakKind::Odd(pocket.number % 2 == 1));
+        }
+        kinds.extend(pocket.categories.iter().map(|&id| StreakKind::Category(id)));
+
+        let previous = std::mem::take(&mut self.streaks);
+        self.streaks = kinds
+            .into_iter()
+            .map(|kind| {
+                let length = previous.iter().find(|s| s.kind == kind).map_or(0, |s| s.length);
+                Streak { kind, length: length + 1 }
+            })
+            .collect();
+    }
+
+    /// Runs still going, longest first.
+    pub fn current(&self) -> Vec<Streak> {
+        let mut streaks = self.streaks.clone();
+        streaks.sort_by_key(|s| std::cmp::Reverse(s.length));
+        streaks
+    }
+
+    /// Runs of at least `length` spins, longest first.
+    pub fn at_least(&self, length: u32) -> Vec<Streak> {
+        self.current().into_iter().filter(|s| s.length >= length).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.streaks.clear();
+    }
+}
@@ -0,0 +1,1496 @@
+// src/game/table.rs
+
+//! The [`Game`]: one player at one wheel, from placing bets to settling them.
+
+use super::bets::{Bet, BetType};
+use super::category::CategoryId;
+use super::controller::PlayerController;
+use super::comps::{self, CompError, CompReward};
+use super::currency::Amount;
+use super::custom::{BetEvaluator, BetRegistry, RegisterBetError};
+use super::events::{BalanceChangeReason, EventBus, EventSink, GameEvent};
+use super::entropy::{EntropyError, EntropySource};
+use super::fairness::{FairSpin, Reveal};
+use super::money::Money;
+use super::payout::PayoutTable;
+use super::player::{AuditEntry, ExposureLimit, Player};
+use super::rules::{LimitBreach, LossSettlement, MAX_RESPINS, TableRules};
+use super::spin::{self, SpinModel, SpinObserver};
+#[cfg(feature = "async")]
+use super::spins::SpinStream;
+use super::spins::Spins;
+use super::streaks::{DEFAULT_STREAK_ALERT, StreakTracker};
+use super::ticker::Ticker;
+use super::wheel::{PocketId, Wheel, WheelEdit, WheelEditError, WheelLike};
+use crate::presentation;
+use crate::say;
+use serde::Serialize;
+use std::fmt;
+
+pub struct Game {
+    wheel: Box<dyn WheelLike>,
+    player: Player,
+    current_bets: Vec<Bet>,
+    events: EventBus,
+    spin_model: SpinModel,
+    spin_observer: Option<SpinObserver>,
+    entropy: EntropySource,
+    fairness: Option<Fairness>,
+    last_spin: Option<LastSpin>,
+    /// Whether the last spin may be voided, see [`Game::allow_voids`].
+    voids: bool,
+    custom_bets: BetRegistry,
+    #[cfg(feature = "scripting")]
+    rules_script: Option<super::script::HouseScript>,
+    rules: TableRules,
+    /// Even-money bets held over from a zero under en prison.
+    imprisoned: Vec<Bet>,
+    /// Outside bets carried to a re-spin by the green-pocket rule.
+    respins: Vec<Bet>,
+    /// Bets of the last settled round, for rebets.
+    previous_bets: Vec<Bet>,
+    exposure: Option<ExposureLimit>,
+    streaks: StreakTracker,
+    /// Shortest streak announced after a spin; `None` keeps quiet.
+    streak_alert: Option<u32>,
+    comp_points: u64,
+    /// Cap of the round insurance bought with comp points, until it pays.
+    insurance: Option<Money>,
+    rebate_period: RebatePeriod,
+    wheel_edits: Vec<WheelEditEntry>,
+}
+
+/// A saved balance and set of standing bets, see [`Game::snapshot`].
+#[derive(Debug, Clone)]
+pub struct GameSnapshot {
+    balance: Money,
+    bets: Vec<Bet>,
+    imprisoned: Vec<Bet>,
+    respins: Vec<Bet>,
+}
+
+impl GameSnapshot {
+    pub fn balance(&self) -> Money {
+        self.balance
+    }
+
+    pub fn bets(&self) -> &[Bet] {
+        &self.bets
+    }
+
+    pub fn imprisoned(&self) -> &[Bet] {
+        &self.imprisoned
+    }
+}
+
+/// The most recent settlement, kept so it can be voided.
+struct LastSpin {
+    round: u32,
+    winning_id: PocketId,
+    bets: Vec<Bet>,
+    /// Bets that were in prison going into the spin.
+    imprisoned: Vec<Bet>,
+    /// Bets that were carried to this spin as a re-spin.
+    respins: Vec<Bet>,
+    returned: Money,
+    /// Streaks as they stood before the spin.
+    streaks: StreakTracker,
+    /// Comp points the round earned.
+    comps: u64,
+    /// Insurance the round used up: its cap and what it paid.
+    insurance: Option<(Money, Money)>,
+    /// The rebate period as it stood before the round, and any rebate the round paid.
+    rebate: (RebatePeriod, Money),
+}
+
+/// Net result of the loss-rebate period so far.
+#[derive(Debug, Clone, Copy, Default)]
+struct RebatePeriod {
+    /// Returned minus wagered.
+    net: Money,
+    rounds: u32,
+}
+
+/// Why a rebet was refused, see [`Game::rebet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebetError {
+    /// The table rules don't allow rebets.
+    NotAllowed,
+    /// No round has been settled yet.
+    NothingToRebet,
+}
+
+impl fmt::Display for RebetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RebetError::NotAllowed => write!(f, "this table doesn't allow rebets"),
+            RebetError::NothingToRebet => write!(f, "there is no previous round to repeat"),
+        }
+    }
+}
+
+impl std::error::Error for RebetError {}
+
+/// Why [`Game::play_round`] refused a round, or [`Game::try_place_bet`] a
+/// bet. Nothing is staked when it does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoundError {
+    /// Bets placed one at a time are still waiting for a spin.
+    BetsPending,
+    /// No bets were given and none are held in prison.
+    NoBets,
+    /// More bets than the table rules allow in one round.
+    TooManyBets { max: usize },
+    /// A custom bet that isn't registered at this table.
+    UnknownBet(String),
+    /// A stake outside the table limits.
+    OutsideLimits(LimitBreach),
+    /// The bets add up to more than the player has.
+    InsufficientFunds { stake: Amount, balance: Amount },
+    /// The bets add up to more of the bankroll than a blocking
+    /// [`ExposureLimit`] allows.
+    ExposureBlocked { stake: Amount, bankroll: Amount, percent: u32 },
+}
+
+impl fmt::Display for RoundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundError::BetsPending => write!(f, "bets are already on the table; spin or clear them first"),
+            RoundError::NoBets => write!(f, "no bets to play"),
+            RoundError::TooManyBets { max } => write!(f, "at most {} bets per round at this table", max),
+            RoundError::UnknownBet(name) => write!(f, "no custom bet named '{}' at this table", name),
+            RoundError::OutsideLimits(breach) => write!(f, "{}", breach),
+            RoundError::InsufficientFunds { stake, balance } => {
+                write!(f, "bets total {} but the balance is {}", stake, balance)
+            }
+            RoundError::ExposureBlocked { stake, bankroll, percent } => {
+                write!(f, "bets total {}, more than {}% of the {} bankroll", stake, percent, bankroll)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RoundError {}
+
+/// A spin that was voided by [`Game::void_last_spin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoidedSpin {
+    pub round: u32,
+    pub pocket: PocketId,
+    /// Winnings taken back from the player.
+    pub returned: Money,
+}
+
+/// How one spin went for the player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RoundResult {
+    pub round: u32,
+    #[serde(skip)]
+    pub pocket: PocketId,
+    pub ticker: String,
+    /// Stakes placed this round; bets held over in prison were counted when placed.
+    pub wagered: Money,
+    /// Everything paid back, stakes included.
+    pub returned: Money,
+    /// Balance once the round was settled.
+    pub balance: Money,
+    /// Re-spins the green-pocket rule played at once for the round's outside
+    /// bets, each a round of its own with nothing wagered.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub respins: Vec<RoundResult>,
+}
+
+impl RoundResult {
+    /// Everything paid back on the round and its re-spins.
+    pub fn total_returned(&self) -> Money {
+        self.returned + self.respins.iter().map(|respin| respin.returned).sum::<Money>()
+    }
+
+    /// Winnings minus stakes for the round, its re-spins included.
+    pub fn net(&self) -> Money {
+        self.total_returned() - self.wagered
+    }
+}
+
+/// Why the last spin can't be voided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoidError {
+    /// Nothing has been settled since the game started or was restored.
+    NoSpin,
+    /// New bets are on the table; clear them first.
+    BetsPlaced,
+    /// The table plays for keeps; only practice and croupier tables void spins.
+    NotAllowed,
+    /// Provably fair spins are committed to in advance and stand as dealt.
+    FairSpins,
+}
+
+impl fmt::Display for VoidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoidError::NoSpin => write!(f, "there is no settled spin to void"),
+            VoidError::BetsPlaced => write!(f, "clear the new bets before voiding the last spin"),
+            VoidError::NotAllowed => write!(f, "only practice and croupier tables can void a spin"),
+            VoidError::FairSpins => write!(f, "provably fair spins can't be voided"),
+        }
+    }
+}
+
+impl std::error::Error for VoidError {}
+
+/// Why a new wheel was refused mid-session, see [`Game::replace_wheel`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WheelChangeError {
+    /// Spins are provably fair, and the next one is committed on this wheel.
+    FairSpins,
+    /// A pocket of the wheel in play is gone from the new one, or holds
+    /// another stock there.
+    MissingPocket(String),
+    /// A standing bet could never win on the new wheel.
+    StrandedBet(BetType),
+}
+
+impl fmt::Display for WheelChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WheelChangeError::FairSpins => write!(f, "the next provably fair spin is already committed on this wheel"),
+            WheelChangeError::MissingPocket(pocket) => {
+                write!(f, "pocket {} isn't on the new wheel; pockets can be added mid-session, but not removed or changed", pocket)
+            }
+            WheelChangeError::StrandedBet(bet_type) => {
+                write!(f, "the {} bet on the table could never win on the new wheel; clear it first", bet_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WheelChangeError {}
+
+/// A change made to the wheel during play, see [`Game::edit_wheel`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WheelEditEntry {
+    /// How many entries the balance [audit log](Game::audit_log) had when
+    /// the edit was made, to place it among them.
+    pub after: u64,
+    /// The round that was next to be played.
+    pub round: u32,
+    #[serde(flatten)]
+    pub edit: WheelEdit,
+    /// Stakes handed back for bets the edit left unable to win.
+    pub refunded: Money,
+}
+
+/// Why a forced spin was refused, see [`Game::spin_with_outcome`].
+#[cfg(feature = "forced-outcome")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForcedOutcomeError {
+    /// The wheel has no pocket with this number.
+    UnknownPocket(u8),
+    /// Spins are provably fair, so their outcome can't be chosen.
+    FairSpins,
+}
+
+#[cfg(feature = "forced-outcome")]
+impl fmt::Display for ForcedOutcomeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ForcedOutcomeError::UnknownPocket(n) => write!(f, "no pocket numbered {}", n),
+            ForcedOutcomeError::FairSpins => write!(f, "provably fair spins can't be forced"),
+        }
+    }
+}
+
+#[cfg(feature = "forced-outcome")]
+impl std::error::Error for ForcedOutcomeError {}
+
+/// Commit–reveal state for provably fair play.
+struct Fairness {
+    client_seed: String,
+    /// The committed outcome of the next spin.
+    pending: FairSpin,
+    last_reveal: Option<Reveal>,
+}
+
+impl Game {
+    /// A game on the European wheel.
+    pub fn new(starting_balance: impl Into<Money>) -> Self {
+        Game::with_wheel(starting_balance, Box::new(Wheel::new()))
+    }
+
+    /// A game on any wheel: a built-in layout, a [`WeightedWheel`](super::wheel::WeightedWheel),
+    /// or a third-party [`WheelLike`].
+    pub fn with_wheel(starting_balance: impl Into<Money>, wheel: Box<dyn WheelLike>) -> Self {
+        Game {
+            player: Player::new(starting_balance.into()),
+            wheel,
+            current_bets: Vec::new(),
+            events: EventBus::new(1),
+            spin_model: SpinModel::default(),
+            spin_observer: None,
+            entropy: EntropySource::default(),
+            fairness: None,
+            last_spin: None,
+            voids: false,
+            custom_bets: BetRegistry::default(),
+            #[cfg(feature = "scripting")]
+            rules_script: None,
+            rules: TableRules::default(),
+            imprisoned: Vec::new(),
+            respins: Vec::new(),
+            previous_bets: Vec::new(),
+            exposure: None,
+            streaks: StreakTracker::default(),
+            streak_alert: Some(DEFAULT_STREAK_ALERT),
+            comp_points: 0,
+            insurance: None,
+            rebate_period: RebatePeriod::default(),
+            wheel_edits: Vec::new(),
+        }
+    }
+
+    /// Warns, or refuses, once a round's bets stake too much of the balance.
+    /// `None` turns the check off.
+    pub fn set_exposure_limit(&mut self, limit: Option<ExposureLimit>) {
+        self.exposure = limit;
+    }
+
+    /// Announces streaks of at least `length` spins after each spin; `None`
+    /// turns the announcements off. They are on by default.
+    pub fn set_streak_alert(&mut self, length: Option<u32>) {
+        self.streak_alert = length;
+    }
+
+    /// Color, parity, and category runs as of the last settled spin.
+    pub fn streaks(&self) -> &StreakTracker {
+        &self.streaks
+    }
+
+    /// Comp points banked so far, see [`comps`].
+    pub fn comp_points(&self) -> u64 {
+        self.comp_points
+    }
+
+    /// Starts the comp balance at `points`, such as those a profile carried over.
+    pub fn set_comp_points(&mut self, points: u64) {
+        self.comp_points = points;
+    }
+
+    /// Cap of the round insurance waiting for a losing round, if any.
+    pub fn insurance(&self) -> Option<Money> {
+        self.insurance
+    }
+
+    /// Adds money to the player's balance mid-session, such as a practice
+    /// table topping up its chips.
+    pub fn deposit(&mut self, amount: Money) {
+        if amount <= Money::ZERO {
+            return;
+        }
+        self.player.deposit(amount);
+        // A spin can't be voided once money has come on or off the table since.
+        self.last_spin = None;
+        emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Deposit, amount);
+    }
+
+    /// Adds chips bought with wallet cash; [`Wallet::buy_in`](super::wallet::Wallet::buy_in)
+    /// checks the table's minimum first.
+    pub fn buy_in(&mut self, amount: Money) {
+        if amount <= Money::ZERO {
+            return;
+        }
+        self.player.buy_in(amount);
+        self.last_spin = None;
+        emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::BuyIn, amount);
+    }
+
+    /// Takes every chip off the table and returns what they're worth. Bets
+    /// not yet spun are taken back first; bets held in prison or waiting on a
+    /// re-spin are given up.
+    pub fn cash_out(&mut self) -> Money {
+        if !self.current_bets.is_empty() {
+            self.clear_bets();
+        }
+        self.imprisoned.clear();
+        self.respins.clear();
+        self.last_spin = None;
+        let amount = self.player.cash_out();
+        if amount > Money::ZERO {
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::CashOut, -amount);
+        }
+        amount
+    }
+
+    /// Spends comp points on `reward`.
+    pub fn redeem_comps(&mut self, reward: CompReward) -> Result<(), CompError> {
+        let cost = reward.cost();
+        if cost > self.comp_points {
+            return Err(CompError::NotEnoughPoints { cost, points: self.comp_points });
+        }
+        if matches!(reward, CompReward::Insurance { .. }) && self.insurance.is_some() {
+            return Err(CompError::AlreadyInsured);
+        }
+        self.comp_points -= cost;
+        self.events.emit(|| GameEvent::CompRedeemed { reward, cost });
+        let points = self.comp_points;
+        self.events.emit(|| GameEvent::CompPoints { delta: -(cost as i64), points });
+        match reward {
+            CompReward::Credit { amount } => {
+                self.player.add_comp(amount.into());
+                emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Comp, amount.into());
+            }
+            CompReward::Insurance { cap } => {
+                self.insurance = Some(cap.into());
+                say!("Your next losing round is insured: half the loss back, up to {}.", self.money(cap));
+            }
+        }
+        Ok(())
+    }
+
+    /// Pays the loss rebate on the rounds since the last one, if the table
+    /// runs the promotion, and starts a new period. Call when the session
+    /// ends; periods of `every_rounds` are settled on their own. Returns the
+    /// amount paid.
+    pub fn settle_rebate(&mut self) -> Money {
+        let period = std::mem::take(&mut self.rebate_period);
+        let Some(rebate) = self.rules.rebate else { return Money::ZERO };
+        let amount = rebate.on(period.net);
+        if amount > Money::ZERO {
+            say!("Loss rebate: {}% of your {} net loss over {} round(s).", rebate.percent, self.money(-period.net), period.rounds);
+            self.player.add_rebate(amount);
+            self.events.emit(|| GameEvent::RebatePaid { loss: -period.net, amount });
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Rebate, amount);
+        }
+        amount
+    }
+
+    /// Pockets, categories, and payouts of the wheel in play.
+    pub fn wheel(&self) -> &Wheel {
+        self.wheel.layout()
+    }
+
+    /// The wheel in play, including how it spins.
+    pub fn wheel_variant(&self) -> &dyn WheelLike {
+        self.wheel.as_ref()
+    }
+
+    /// Swaps in another wheel between rounds, such as one with an extra
+    /// pocket or new categories. Every pocket in play must keep its number
+    /// and stock, and every bet on the table must still be able to win; bets
+    /// kept for a rebet that can't are dropped.
+    pub fn replace_wheel(&mut self, wheel: Box<dyn WheelLike>) -> Result<(), WheelChangeError> {
+        if self.fairness.is_some() {
+            return Err(WheelChangeError::FairSpins);
+        }
+        let layout = wheel.layout();
+        if let Some(pocket) = self
+            .wheel
+            .pockets()
+            .iter()
+            .find(|pocket| layout.get_pocket(pocket.number).is_none_or(|new| new.ticker != pocket.ticker))
+        {
+            return Err(WheelChangeError::MissingPocket(pocket.to_string()));
+        }
+        let can_win = |bet: &Bet| matches!(bet.bet_type, BetType::Custom(_)) || wheel.probability(&bet.bet_type) > 0.0;
+        if let Some(bet) = self.current_bets.iter().chain(&self.imprisoned).chain(&self.respins).find(|bet| !can_win(bet)) {
+            return Err(WheelChangeError::StrandedBet(bet.bet_type.clone()));
+        }
+        self.previous_bets.retain(|bet| can_win(bet));
+        if let Some(last) = self.last_spin.as_mut() {
+            let number = self.wheel.layout().pocket(last.winning_id).number;
+            last.winning_id = layout.id_of_number(number).expect("every pocket was found above");
+        }
+        self.wheel = wheel;
+        let name = self.wheel.name().to_string();
+        self.events.emit(|| GameEvent::WheelChanged { wheel: name });
+        Ok(())
+    }
+
+    /// Edits the wheel in play between rounds: adds, removes, or renames a
+    /// pocket, or changes its categories. Bets on a renamed pocket follow it.
+    /// Bets the edit leaves unable to win are refunded and returned; bets
+    /// kept for a rebet that can't are dropped. Each edit is kept in
+    /// [`wheel_edits`](Game::wheel_edits).
+    pub fn edit_wheel(&mut self, edit: WheelEdit) -> Result<Vec<Bet>, WheelEditError> {
+        if self.fairness.is_some() {
+            return Err(WheelEditError::FairSpins);
+        }
+        let layout = self.wheel.layout().edited(&edit)?;
+        let wheel = self.wheel.with_layout(layout).ok_or(WheelEditError::Unsupported)?;
+        if let WheelEdit::RenamePocket { ticker, .. } = &edit {
+            let old = self.wheel.layout().find_pocket(ticker).map(|id| self.wheel.layout().pocket(id));
+            let new = old.and_then(|old| wheel.layout().get_pocket(old.number));
+            if let (Some(old), Some(new)) = (old, new) {
+                let (from, to) = (old.ticker, new.ticker);
+                for bet in self.current_bets.iter_mut().chain(&mut self.imprisoned).chain(&mut self.respins).chain(&mut self.previous_bets) {
+                    rename_ticker(&mut bet.bet_type, from, to);
+                }
+            }
+        }
+
+        let can_win = |bet: &Bet| matches!(bet.bet_type, BetType::Custom(_)) || wheel.probability(&bet.bet_type) > 0.0;
+        let mut stranded = Vec::new();
+        for bets in [&mut self.current_bets, &mut self.imprisoned, &mut self.respins] {
+            let (keep, lose): (Vec<Bet>, Vec<Bet>) = bets.drain(..).partition(|bet| can_win(bet));
+            *bets = keep;
+            stranded.extend(lose);
+        }
+        self.previous_bets.retain(|bet| can_win(bet));
+        for bet in &stranded {
+            self.player.refund_bet(bet.amount);
+            let (bet_type, amount) = (bet.bet_type.clone(), bet.amount);
+            self.events.emit(|| GameEvent::BetRefunded { bet_type, amount });
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Refund, bet.amount);
+        }
+        // Its bets were placed on the old wheel, so the spin before an edit
+        // can't be voided.
+        self.last_spin = None;
+
+        self.wheel = wheel;
+        let refunded = stranded.iter().map(|bet| bet.amount).sum();
+        self.wheel_edits.push(WheelEditEntry {
+            after: self.player.audit_log().len() as u64,
+            round: self.events.round(),
+            edit: edit.clone(),
+            refunded,
+        });
+        self.events.emit(|| GameEvent::WheelEdited { edit, refunded });
+        Ok(stranded)
+    }
+
+    /// Every edit made to the wheel this game, in order.
+    pub fn wheel_edits(&self) -> &[WheelEditEntry] {
+        &self.wheel_edits
+    }
+
+    /// Chooses how spins pick the winning pocket. Provably fair spins ignore it,
+    /// since their outcome is fixed by the committed seed.
+    pub fn set_spin_model(&mut self, model: SpinModel) {
+        self.spin_model = model;
+    }
+
+    /// Chooses where spin seeds, and the server seeds of provably fair
+    /// spins, are drawn from. Fails if this build or machine lacks the source.
+    pub fn set_entropy_source(&mut self, source: EntropySource) -> Result<(), EntropyError> {
+        source.check()?;
+        self.entropy = source;
+        Ok(())
+    }
+
+    pub fn entropy_source(&self) -> EntropySource {
+        self.entropy
+    }
+
+    /// Registers a callback that sees every pocket the ball passes on each
+    /// spin, with timing hints, so front ends can animate the wheel.
+    pub fn set_spin_observer(&mut self, observer: SpinObserver) {
+        self.spin_observer = Some(observer);
+    }
+
+    /// Switches to provably fair spins: every spin's outcome is committed to
+    /// before bets are taken and its seed revealed after settlement.
+    pub fn enable_fairness(&mut self, client_seed: &str) {
+        let pending = FairSpin::with_entropy(client_seed, 1, self.entropy);
+        emit_commitment(&mut self.events, &pending);
+        self.fairness = Some(Fairness { client_seed: client_seed.to_string(), pending, last_reveal: None });
+    }
+
+    /// Commitment for the next spin, when fairness is enabled.
+    pub fn fairness_commitment(&self) -> Option<(u64, &str)> {
+        self.fairness.as_ref().map(|f| (f.pending.nonce(), f.pending.commitment()))
+    }
+
+    /// Seed reveal for the most recent fair spin.
+    pub fn last_reveal(&self) -> Option<&Reveal> {
+        self.fairness.as_ref().and_then(|f| f.last_reveal.as_ref())
+    }
+
+    /// Sets the house rules. Bets already on the table stand, and side bets
+    /// offered by earlier rules stay on offer.
+    pub fn set_rules(&mut self, rules: TableRules) {
+        for side_bet in &rules.side_bets {
+            if self.custom_bets.get(side_bet.name()).is_none() {
+                self.custom_bets.register(Box::new(*side_bet)).expect("the name is free");
+            }
+        }
+        self.player.set_currency(rules.currency.clone());
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> &TableRules {
+        &self.rules
+    }
+
+    /// `value` in the table's currency, to show to the player.
+    fn money(&self, value: impl Into<Money>) -> Amount {
+        self.rules.currency.amount(value)
+    }
+
+    /// Odds paid at this table: the rules' table if they set one, otherwise
+    /// the wheel's.
+    pub fn payouts(&self) -> &PayoutTable {
+        self.rules.payouts.as_ref().unwrap_or(self.wheel.layout().payouts())
+    }
+
+    /// Adds a custom bet type, placed as [`BetType::Custom`] with the
+    /// evaluator's name.
+    pub fn register_bet(&mut self, evaluator: Box<dyn BetEvaluator>) -> Result<(), RegisterBetError> {
+        self.custom_bets.register(evaluator)
+    }
+
+    /// Custom bet types registered with this game.
+    pub fn custom_bets(&self) -> &BetRegistry {
+        &self.custom_bets
+    }
+
+    /// Loads house rules written in Rhai: payout adjustments, promotional
+    /// bets, and round messages (see [`script`]). Replaces any earlier script,
+    /// though promotions it registered stay on offer.
+    #[cfg(feature = "scripting")]
+    pub fn load_rules_script(&mut self, source: &str) -> Result<(), super::script::ScriptError> {
+        let rules = super::script::HouseScript::compile(source)?;
+        rules.register_promotions(self.wheel.layout(), &mut self.custom_bets)?;
+        self.rules_script = Some(rules);
+        Ok(())
+    }
+
+    /// Registers a sink that will receive every subsequent game event.
+    pub fn add_event_sink(&mut self, sink: Box<dyn EventSink>) {
+        self.events.add_sink(sink);
+    }
+
+    pub fn get_player_balance(&self) -> Money {
+        self.player.balance()
+    }
+
+    /// Every change to the player's balance this game, with its cause. See
+    /// [`Player::audit_log`].
+    pub fn audit_log(&self) -> &[AuditEntry] {
+        self.player.audit_log()
+    }
+
+    pub fn place_bet(&mut self, bet: Bet) -> bool {
+        self.try_place_bet(bet).is_ok()
+    }
+
+    /// [`place_bet`](Self::place_bet), saying why a refused bet was refused.
+    pub fn try_place_bet(&mut self, mut bet: Bet) -> Result<(), RoundError> {
+        if let BetType::Custom(name) = &bet.bet_type {
+            match self.custom_bets.get(name) {
+                // Keep the registered spelling for display and history.
+                Some(evaluator) => bet.bet_type = BetType::Custom(evaluator.name().to_string()),
+                None => {
+                    say!("No custom bet named '{}' at this table.", name);
+                    return Err(RoundError::UnknownBet(name.clone()));
+                }
+            }
+        }
+        if let Err(breach) = self.rules.check_stake(bet.amount) {
+            say!("Bet refused: {}.", breach);
+            return Err(RoundError::OutsideLimits(breach));
+        }
+        if let Some(max) = self.rules.max_bets_per_round
+            && self.current_bets.len() >= max
+        {
+            say!("Table limit: at most {} bets per round.", max);
+            return Err(RoundError::TooManyBets { max });
+        }
+        if let Some(limit) = self.exposure
+            && bet.amount <= self.player.balance()
+        {
+            let staked = self.current_bets.iter().map(|b| b.amount).sum::<Money>() + bet.amount;
+            // The balance the round started with, before any of its bets.
+            let bankroll = self.player.balance() + staked - bet.amount;
+            if limit.exceeded_by(staked, bankroll) {
+                let share = staked.cents() * 100 / bankroll.cents().max(1);
+                if limit.block {
+                    say!("Bet refused: {} this round would be {}% of your {} bankroll (limit {}%).",
+                        self.money(staked), share, self.money(bankroll), limit.percent);
+                    return Err(RoundError::ExposureBlocked {
+                        stake: self.money(staked),
+                        bankroll: self.money(bankroll),
+                        percent: limit.percent,
+                    });
+                }
+                say!("Warning: {} this round is {}% of your {} bankroll (limit {}%).",
+                    self.money(staked), share, self.money(bankroll), limit.percent);
+            }
+        }
+        if self.player.place_bet(bet.amount) {
+            say!("Placing bet: {} for {}", bet.bet_type, self.money(bet.amount));
+            self.events.emit(|| GameEvent::BetPlaced {
+                bet_type: bet.bet_type.clone(),
+                amount: bet.amount,
+                note: bet.note.clone(),
+            });
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::BetPlaced, -bet.amount);
+            self.current_bets.push(bet);
+            Ok(())
+        } else {
+            Err(RoundError::InsufficientFunds { stake: self.money(bet.amount), balance: self.money(self.player.balance()) })
+        }
+    }
+
+    /// Places the previous round's bets again. Returns how many were placed,
+    /// which is fewer than last time if the balance runs short.
+    pub fn rebet(&mut self) -> Result<usize, RebetError> {
+        if !self.rules.rebet {
+            return Err(RebetError::NotAllowed);
+        }
+        if self.previous_bets.is_empty() {
+            return Err(RebetError::NothingToRebet);
+        }
+        let bets = self.previous_bets.clone();
+        Ok(bets.into_iter().take_while(|bet| self.place_bet(bet.clone())).count())
+    }
+
+    /// Attaches `note` to the `index`th bet standing this round, replacing
+    /// any note it had. False if there is no such bet.
+    pub fn note_bet(&mut self, index: usize, note: &str) -> bool {
+        let Some(bet) = self.current_bets.get_mut(index) else { return false };
+        *bet = bet.clone().with_note(note);
+        let note = bet.note.clone().unwrap_or_default();
+        self.events.emit(|| GameEvent::BetNoted { index, note });
+        true
+    }
+
+    /// Even-money bets held in prison for the next spin.
+    pub fn imprisoned_bets(&self) -> &[Bet] {
+        &self.imprisoned
+    }
+
+    /// True if the next spin has anything to settle.
+    pub fn has_bets_in_play(&self) -> bool {
+        !self.current_bets.is_empty() || !self.imprisoned.is_empty() || !self.respins.is_empty()
+    }
+
+    pub fn spin_wheel_and_resolve(&mut self) {
+        self.spin_round();
+    }
+
+    /// Spins and settles like [`Game::spin_wheel_and_resolve`], returning the
+    /// round with the re-spins it led to; `None` if nothing was in play.
+    pub(super) fn spin_round(&mut self) -> Option<RoundResult> {
+        if !self.has_bets_in_play() {
+            say!("No bets placed for this round.");
+            return None;
+        }
+        self.spin_once();
+        let mut round = self.last_round()?;
+        round.respins = self.respin_if_due();
+        Some(round)
+    }
+
+    /// Spins the wheel and settles whatever is in play, as one round.
+    fn spin_once(&mut self) {
+        say!("\nSpinning the Wall Street wheel...");
+        // Taken out while settling so the rest of the game can be borrowed.
+        let Some(mut fairness) = self.fairness.take() else {
+            let seed = self.entropy.random_seed();
+            let mut rng = spin::seeded_rng(&seed);
+            let (winning_id, path) = match self.spin_observer.as_mut() {
+                Some(observer) => self.spin_model.spin_observed(self.wheel.as_ref(), &mut rng, observer.as_mut()),
+                None => self.spin_model.spin(self.wheel.as_ref(), &mut rng),
+            };
+            if let Some(path) = path {
+                say!("{}", path.narrative(self.wheel.layout()));
+            }
+            self.events.emit(|| GameEvent::SpinSeeded { seed });
+            self.settle(winning_id);
+            self.events.next_round();
+            return;
+        };
+
+        let winning_id = fairness.pending.outcome(self.wheel.pockets().len());
+        if let Some(observer) = self.spin_observer.as_mut() {
+            SpinModel::animate_to(self.wheel.layout(), winning_id, &mut rand::thread_rng()).for_each(|f| observer(&f));
+        }
+        self.settle(winning_id);
+        let next = FairSpin::with_entropy(&fairness.client_seed, fairness.pending.nonce() + 1, self.entropy);
+        let reveal = std::mem::replace(&mut fairness.pending, next).reveal();
+        self.events.emit(|| GameEvent::SpinRevealed { reveal: reveal.clone() });
+        fairness.last_reveal = Some(reveal);
+        self.events.next_round();
+        emit_commitment(&mut self.events, &fairness.pending);
+        self.fairness = Some(fairness);
+    }
+
+    /// Spins again straight away, up to [`MAX_RESPINS`] times, while the
+    /// green-pocket rule carries bets to a re-spin. Each re-spin is a round
+    /// of its own, announced with a [`GameEvent::Respin`] naming the round
+    /// whose bets it settles. Returns the re-spins played.
+    fn respin_if_due(&mut self) -> Vec<RoundResult> {
+        let mut played = Vec::new();
+        let Some(of_round) = self.last_spin.as_ref().map(|last| last.round) else { return played };
+        for _ in 0..MAX_RESPINS {
+            if self.respins.is_empty() {
+                return played;
+            }
+            say!("\nGreen pocket! The outside bets ride a re-spin.");
+            self.events.emit(|| GameEvent::Respin { of_round });
+            self.spin_once();
+            played.extend(self.last_round());
+        }
+        if !self.respins.is_empty() {
+            say!("Green again! The outside bets wait for the next spin.");
+        }
+        played
+    }
+
+    /// **Rigged spin, for tests, tutorials, and demos only.** Spins the wheel
+    /// but always lands on the pocket numbered `pocket_number`, settling bets
+    /// and emitting events exactly like [`Game::spin_wheel_and_resolve`].
+    /// Only compiled with the `forced-outcome` feature, and refused while
+    /// provably fair spins are enabled.
+    #[cfg(feature = "forced-outcome")]
+    pub fn spin_with_outcome(&mut self, pocket_number: u8) -> Result<(), ForcedOutcomeError> {
+        if self.fairness.is_some() {
+            return Err(ForcedOutcomeError::FairSpins);
+        }
+        let winning_id = self.wheel.layout().id_of_number(pocket_number).ok_or(ForcedOutcomeError::UnknownPocket(pocket_number))?;
+        if !self.has_bets_in_play() {
+            say!("No bets placed for this round.");
+            return Ok(());
+        }
+        say!("\nSpinning the Wall Street wheel... (forced outcome)");
+        if let Some(observer) = self.spin_observer.as_mut() {
+            SpinModel::animate_to(self.wheel.layout(), winning_id, &mut rand::thread_rng()).for_each(|f| observer(&f));
+        }
+        self.settle(winning_id);
+        self.events.next_round();
+        // Only the forced spin is rigged; a re-spin it triggers is a real one.
+        self.respin_if_due();
+        Ok(())
+    }
+
+    /// Settles the current bets against a pocket produced outside the engine,
+    /// e.g. a physical wheel spun by a croupier. Bets the green-pocket rule
+    /// carries to a re-spin wait for the next pocket.
+    pub fn resolve_with_pocket(&mut self, winning_id: PocketId) {
+        if !self.has_bets_in_play() {
+            say!("No bets placed for this round.");
+            return;
+        }
+        self.settle(winning_id);
+        self.events.next_round();
+    }
+
+    fn settle(&mut self, winning_id: PocketId) {
+        let wheel = self.wheel.layout();
+        #[cfg(feature = "scripting")]
+        let (rules, round) = (self.rules_script.as_ref(), self.events.round());
+        let mut adjust = |_bet: &Bet, payout: Money| {
+            #[cfg(feature = "scripting")]
+            if let Some(rules) = rules {
+                return rules.adjust_payout(wheel, _bet, winning_id, payout, round);
+            }
+            payout
+        };
+        let mut payer = Payer {
+            wheel,
+            payouts: self.rules.payouts.as_ref().unwrap_or(wheel.payouts()),
+            rules: &self.rules,
+            custom_bets: &self.custom_bets,
+            adjust: &mut adjust,
+        };
+        let wagered: Money = self.current_bets.iter().map(|b| b.amount).sum();
+        let comps = wagered.round_down() as u64 * comps::POINTS_PER_DOLLAR;
+        if comps > 0 {
+            self.comp_points += comps;
+            let points = self.comp_points;
+            self.events.emit(|| GameEvent::CompPoints { delta: comps as i64, points });
+        }
+        let settled = settle_round(
+            &mut payer,
+            winning_id,
+            &self.current_bets,
+            &self.imprisoned,
+            &self.respins,
+            &mut self.player,
+            &mut self.events,
+        );
+        #[cfg(feature = "scripting")]
+        if let Some(rules) = &self.rules_script {
+            rules.round_ended(self.events.round(), wagered, settled.returned, self.player.balance());
+        }
+        let insurance = match self.insurance {
+            Some(cap) if settled.returned < wagered => {
+                self.insurance = None;
+                let paid = comps::insurance_payout(cap, wagered, settled.returned);
+                say!("Round insurance pays back {} of the loss.", self.money(paid));
+                if paid > Money::ZERO {
+                    self.player.add_comp(paid);
+                    emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Comp, paid);
+                }
+                Some((cap, paid))
+            }
+            _ => None,
+        };
+        let period = self.rebate_period;
+        self.rebate_period.net += settled.returned - wagered;
+        self.rebate_period.rounds += 1;
+        let rebate = match self.rules.rebate.and_then(|r| r.every_rounds) {
+            Some(every) if self.rebate_period.rounds >= every => self.settle_rebate(),
+            _ => Money::ZERO,
+        };
+        let wheel = self.wheel.layout();
+        let streaks = self.streaks.clone();
+        self.streaks.record(wheel, winning_id);
+        if let Some(length) = self.streak_alert {
+            let running = self.streaks.at_least(length);
+            if !running.is_empty() {
+                say!("Streaks (just for fun: past spins don't change the odds of the next one):");
+                for streak in running {
+                    say!("  {}", streak);
+                }
+            }
+        }
+        self.keep_last_spin(winning_id, settled, streaks, comps, insurance, (period, rebate));
+    }
+
+    /// Moves the settled bets aside so the spin can be voided later.
+    fn keep_last_spin(
+        &mut self,
+        winning_id: PocketId,
+        settled: Settled,
+        streaks: StreakTracker,
+        comps: u64,
+        insurance: Option<(Money, Money)>,
+        rebate: (RebatePeriod, Money),
+    ) {
+        let bets = std::mem::take(&mut self.current_bets);
+        let imprisoned = std::mem::replace(&mut self.imprisoned, settled.imprisoned);
+        let respins = std::mem::replace(&mut self.respins, settled.respins);
+        if !bets.is_empty() {
+            self.previous_bets = bets.clone();
+        }
+        self.last_spin = Some(LastSpin {
+            round: self.events.round(),
+            winning_id,
+            bets,
+            imprisoned,
+            respins,
+            returned: settled.returned,
+            streaks,
+            comps,
+            insurance,
+            rebate,
+        });
+    }
+
+    /// Lets [`Game::void_last_spin`] reverse spins. Off by default: a table
+    /// that plays for keeps, for a profile's bankroll, a challenge, or a time
+    /// attack, mustn't let the player take back the spins they lost.
+    pub fn allow_voids(&mut self, allowed: bool) {
+        self.voids = allowed;
+    }
+
+    /// Whether [`Game::void_last_spin`] may be tried at all.
+    pub fn voids_allowed(&self) -> bool {
+        self.voids && self.fairness.is_none()
+    }
+
+    /// Reverses the most recent settlement, as if the ball had never been
+    /// spun: winnings are taken back and the round's bets stand again. For
+    /// croupier corrections and practice play, once [`Game::allow_voids`]
+    /// turns it on; the reversal is emitted as a [`GameEvent::SpinVoided`]
+    /// so it shows up in the round history. A deposit, buy-in, or cash-out
+    /// since the spin leaves nothing to void.
+    pub fn void_last_spin(&mut self) -> Result<VoidedSpin, VoidError> {
+        if self.fairness.is_some() {
+            return Err(VoidError::FairSpins);
+        }
+        if !self.voids {
+            return Err(VoidError::NotAllowed);
+        }
+        if !self.current_bets.is_empty() {
+            return Err(VoidError::BetsPlaced);
+        }
+        let last = self.last_spin.take().ok_or(VoidError::NoSpin)?;
+        let taken = self.player.reverse_winnings(last.returned);
+        self.current_bets = last.bets;
+        self.imprisoned = last.imprisoned;
+        self.respins = last.respins;
+        self.streaks = last.streaks;
+        let ticker = self.wheel.layout().pocket(last.winning_id).ticker;
+        say!("Spin on {} in round {} voided; its bets stand again.", ticker, last.round);
+        self.events.emit(|| GameEvent::SpinVoided {
+            voided_round: last.round,
+            ticker: ticker.to_string(),
+            returned: last.returned,
+        });
+        if taken > Money::ZERO {
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Void, -taken);
+        }
+        if last.comps > 0 {
+            self.comp_points = self.comp_points.saturating_sub(last.comps);
+            let points = self.comp_points;
+            self.events.emit(|| GameEvent::CompPoints { delta: -(last.comps as i64), points });
+        }
+        if let Some((cap, paid)) = last.insurance {
+            // The insurance stands again for the replayed round.
+            self.insurance = Some(cap);
+            let taken = self.player.reverse_winnings(paid);
+            if taken > Money::ZERO {
+                emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Void, -taken);
+            }
+        }
+        let (period, rebate) = last.rebate;
+        self.rebate_period = period;
+        let taken = self.player.reverse_winnings(rebate);
+        if taken > Money::ZERO {
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Void, -taken);
+        }
+        Ok(VoidedSpin { round: last.round, pocket: last.winning_id, returned: last.returned })
+    }
+
+    /// Winning pocket and total returned on the most recent settled spin.
+    pub fn last_outcome(&self) -> Option<(PocketId, Money)> {
+        self.last_spin.as_ref().map(|last| (last.winning_id, last.returned))
+    }
+
+    /// The most recent settled spin in full; a re-spin is a spin of its own.
+    pub fn last_round(&self) -> Option<RoundResult> {
+        self.last_spin.as_ref().map(|last| RoundResult {
+            round: last.round,
+            pocket: last.winning_id,
+            ticker: self.wheel.layout().pocket(last.winning_id).ticker.to_string(),
+            wagered: last.bets.iter().map(|b| b.amount).sum(),
+            returned: last.returned,
+            balance: self.player.balance(),
+            respins: Vec::new(),
+        })
+    }
+
+    /// Places `bets`, spins, and settles in one call. The bets are checked
+    /// together first, so either all of them are played or none are. The
+    /// result is the round the bets were placed in, with any re-spins it
+    /// led to attached.
+    pub fn play_round(&mut self, bets: Vec<Bet>) -> Result<RoundResult, RoundError> {
+        if !self.current_bets.is_empty() {
+            return Err(RoundError::BetsPending);
+        }
+        if bets.is_empty() && !self.has_bets_in_play() {
+            return Err(RoundError::NoBets);
+        }
+        if let Some(max) = self.rules.max_bets_per_round
+            && bets.len() > max
+        {
+            return Err(RoundError::TooManyBets { max });
+        }
+        if let Some(bet) = bets.iter().find(|b| !self.custom_bets.knows(&b.bet_type)) {
+            return Err(RoundError::UnknownBet(bet.bet_type.to_string()));
+        }
+        if let Some(breach) = bets.iter().find_map(|b| self.rules.check_stake(b.amount).err()) {
+            return Err(RoundError::OutsideLimits(breach));
+        }
+        let stake: Money = bets.iter().map(|b| b.amount).sum();
+        if stake > self.player.balance() {
+            return Err(RoundError::InsufficientFunds {
+                stake: self.money(stake),
+                balance: self.money(self.player.balance()),
+            });
+        }
+        // `place_bet` checks the round's running total, which is largest
+        // with the last bet, so checking the whole stake is the same test.
+        if let Some(limit) = self.exposure.filter(|limit| limit.block)
+            && limit.exceeded_by(stake, self.player.balance())
+        {
+            return Err(RoundError::ExposureBlocked {
+                stake: self.money(stake),
+                bankroll: self.money(self.player.balance()),
+                percent: limit.percent,
+            });
+        }
+
+        for bet in bets {
+            self.place_bet(bet);
+        }
+        self.spin_round().ok_or(RoundError::NoBets)
+    }
+
+    /// Plays rounds for `strategy`, one per item, until it leaves or stops
+    /// betting. Table chatter is muted while the iterator runs.
+    ///
+    /// ```no_run
+    /// # use roulette_game::game::{Game, bets::create_red_bet, controller::FlatBettor};
+    /// let mut game = Game::new(1_000);
+    /// let results: Vec<_> = game.spins(FlatBettor::new(vec![create_red_bet(10)])).take(50).collect();
+    /// ```
+    pub fn spins<C: PlayerController>(&mut self, strategy: C) -> Spins<'_, C> {
+        Spins::new(self, strategy)
+    }
+
+    /// [`Game::spins`] as a [`Stream`](futures_core::Stream), for async
+    /// consumers. Each round is played as soon as it is polled.
+    #[cfg(feature = "async")]
+    pub fn spin_stream<C: PlayerController + Unpin>(&mut self, strategy: C) -> SpinStream<'_, C> {
+        SpinStream::new(self.spins(strategy))
+    }
+
+    /// Captures the balance and standing bets.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            balance: self.player.balance(),
+            bets: self.current_bets.clone(),
+            imprisoned: self.imprisoned.clone(),
+            respins: self.respins.clone(),
+        }
+    }
+
+    /// Puts the balance and standing bets back as they were in `snapshot`.
+    /// Round numbering carries on, and the last spin can no longer be voided.
+    pub fn restore(&mut self, snapshot: &GameSnapshot) {
+        let delta = snapshot.balance - self.player.balance();
+        self.player.restore_balance(snapshot.balance);
+        self.current_bets = snapshot.bets.clone();
+        self.imprisoned = snapshot.imprisoned.clone();
+        self.respins = snapshot.respins.clone();
+        self.last_spin = None;
+        if !delta.is_zero() {
+            emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Restore, delta);
+        }
+    }
+
+    pub fn clear_bets(&mut self) {
+        if self.current_bets.is_empty() {
+            say!("No bets to clear.");
+            return;
+        }
+        let total_refund: Money = self.current_bets.iter().map(|bet| bet.amount).sum();
+        self.player.refund_bet(total_refund);
+        self.current_bets.clear();
+        say!("All bets cleared and refunded.");
+        self.events.emit(|| GameEvent::BetsCleared { refunded: total_refund });
+        emit_balance_change(&mut self.events, &self.player, BalanceChangeReason::Refund, total_refund);
+    }
+
+    pub fn get_current_bets(&self) -> &[Bet] {
+        &self.current_bets
+    }
+}
+
+/// Points a bet on the pocket once called `from` at its new ticker `to`.
+fn rename_ticker(bet_type: &mut BetType, from: Ticker, to: Ticker) {
+    let rename = |ticker: &mut Ticker| {
+        if *ticker == from {
+            *ticker = to;
+        }
+    };
+    match bet_type {
+        BetType::StraightUp(ticker) => rename(ticker),
+        BetType::Split(first, second) => {
+            rename(first);
+            rename(second);
+        }
+        // A stock pocket's own category goes by its ticker, and was renamed with it.
+        BetType::Category(category) if category.name() == from.as_str() => *category = CategoryId::intern(to.as_str()),
+        _ => {}
+    }
+}
+
+fn emit_commitment(events: &mut EventBus, spin: &FairSpin) {
+    events.emit(|| GameEvent::SpinCommitted { nonce: spin.nonce(), commitment: spin.commitment().to_string() });
+}
+
+fn emit_balance_change(events: &mut EventBus, player: &Player, reason: BalanceChangeReason, delta: Money) {
+    let balance = player.balance();
+    events.emit(|| GameEvent::BalanceChange { reason, delta, balance });
+}
+
+/// Everything that decides what a bet pays, borrowed from the game.
+struct Payer<'a> {
+    wheel: &'a Wheel,
+    payouts: &'a PayoutTable,
+    rules: &'a TableRules,
+    custom_bets: &'a BetRegistry,
+    /// Last word on each payout, e.g. from a rules script.
+    adjust: &'a mut dyn FnMut(&Bet, Money) -> Money,
+}
+
+/// Result of settling a spin.
+struct Settled {
+    /// Total paid out, stakes included.
+    returned: Money,
+    /// Even-money bets sent to prison by this spin.
+    imprisoned: Vec<Bet>,
+    /// Outside bets carried to a re-spin by this spin.
+    respins: Vec<Bet>,
+}
+
+/// Settles `bets`, any `prisoners` held over from the previous zero, and any
+/// `respins` carried from it, against the winning pocket. What a bet that
+/// didn't win gets back is up to the rules' [`TableRules::settle_loss`]. Takes the game's parts separately so the
+/// pocket can be borrowed straight from the wheel. The caller clears the bets
+/// and advances the event round once it has emitted anything else for this
+/// spin.
+///
+/// A prisoner's or re-spun bet's stake was wagered in an earlier round, so
+/// its settlement is reported with an amount of 0 to keep round totals honest.
+fn settle_round(
+    payer: &mut Payer<'_>,
+    winning_id: PocketId,
+    bets: &[Bet],
+    prisoners: &[Bet],
+    respins: &[Bet],
+    player: &mut Player,
+    events: &mut EventBus,
+) -> Settled {
+    let wheel = payer.wheel;
+    let currency = payer.rules.currency.clone();
+    let winning_pocket = wheel.pocket(winning_id);
+    say!("------------------------------------");
+    say!(
+        ">>>>> The ball landed on: {} ({}, {}) <<<<<",
+        winning_pocket.ticker,
+        winning_pocket.display_name,
+        presentation::color(winning_pocket.color)
+    );
+    say!("Categories: {:?}", winning_pocket.categories);
+    say!("------------------------------------");
+    events.emit(|| GameEvent::Spin {
+        number: winning_pocket.number,
+        ticker: winning_pocket.ticker.to_string(),
+        color: winning_pocket.color,
+        categories: winning_pocket.categories.clone(),
+    });
+
+    let mut total_winnings = Money::ZERO;
+    let mut total_bet_amount = Money::ZERO;
+    let mut imprisoned = Vec::new();
+    let mut respun = Vec::new();
+    let mut total_surrendered = Money::ZERO;
+    let zero_hit = winning_pocket.is_zero();
+
+    for bet in prisoners {
+        let released = !zero_hit && bet.check_win(wheel, winning_id);
+        let payout = if released { bet.amount } else { Money::ZERO };
+        if released {
+            say!("  RELEASED! Bet on {} comes out of prison: {} stake returned.", bet.bet_type, currency.amount(bet.amount));
+            total_winnings += payout;
+        } else {
+            say!("  LOSE! Imprisoned bet on {} for {} lost.", bet.bet_type, currency.amount(bet.amount));
+        }
+        events.emit(|| GameEvent::Settlement {
+            bet_type: bet.bet_type.clone(),
+            amount: Money::ZERO,
+            won: released,
+            payout,
+            note: bet.note.clone(),
+        });
+    }
+
+    let staked = bets.iter().map(|bet| (bet, bet.amount));
+    for (bet, wagered) in staked.chain(respins.iter().map(|bet| (bet, Money::ZERO))) {
+        total_bet_amount += wagered;
+        let payout = (payer.adjust)(bet, payer.custom_bets.settle(bet, wheel, payer.payouts, winning_id));
+        let settlement = |won, payout| GameEvent::Settlement {
+            bet_type: bet.bet_type.clone(),
+            amount: wagered,
+            won,
+            payout,
+            note: bet.note.clone(),
+        };
+        if payout > Money::ZERO {
+            say!(
+                "  WIN! Bet on {} won! Payout: {} (includes {} stake)",
+                bet.bet_type, currency.amount(payout), currency.amount(bet.amount)
+            );
+            total_winnings += payout;
+            events.emit(|| settlement(true, payout));
+            continue;
+        }
+        let refund = match payer.rules.settle_loss(&bet.bet_type, zero_hit) {
+            LossSettlement::Lose => {
+                say!("  LOSE! Bet on {} for {} lost.", bet.bet_type, currency.amount(bet.amount));
+                Money::ZERO
+            }
+            LossSettlement::Imprison => {
+                say!("  PRISON! Bet on {} for {} stays on the table for the next spin.", bet.bet_type, currency.amount(bet.amount));
+                imprisoned.push(bet.clone());
+                Money::ZERO
+            }
+            LossSettlement::Respin => {
+                say!("  RE-SPIN! Bet on {} for {} rides the next spin.", bet.bet_type, currency.amount(bet.amount));
+                respun.push(bet.clone());
+                Money::ZERO
+            }
+            LossSettlement::Surrender => {
+                let half = bet.amount / 2;
+                say!("  SURRENDER! Bet on {} for {} lost, half refunded: {} back.",
+                    bet.bet_type, currency.amount(bet.amount), currency.amount(half));
+                total_surrendered += half;
+                events.emit(|| settlement(false, half));
+                events.emit(|| GameEvent::Surrendered { bet_type: bet.bet_type.clone(), amount: bet.amount, refunded: half });
+                total_winnings += half;
+                continue;
+            }
+            LossSettlement::HalfBack => {
+                let half = bet.amount / 2;
+                say!("  HALF BACK! Green pocket: bet on {} for {} lost, {} back.",
+                    bet.bet_type, currency.amount(bet.amount), currency.amount(half));
+                half
+            }
+            LossSettlement::Push => {
+                say!("  PUSH! Green pocket: bet on {} for {} stands off, stake returned.", bet.bet_type, currency.amount(bet.amount));
+                bet.amount
+            }
+        };
+        total_winnings += refund;
+        events.emit(|| settlement(false, refund));
+    }
+    if total_winnings > Money::ZERO {
+        player.add_winnings(total_winnings);
+        emit_balance_change(events, player, BalanceChangeReason::Winnings, total_winnings);
+    } else {
+        say!("No winning bets this round.");
+    }
+
+    say!("Round Summary:");
+    say!("  Total Wagered: {}", currency.amount(total_bet_amount));
+    say!("  Total Won (incl. stakes): {}", currency.amount(total_winnings));
+    if total_surrendered > Money::ZERO {
+        say!("  Of which surrender refunds: {}", currency.amount(total_surrendered));
+    }
+    say!("  Net Gain/Loss: {}", currency.amount(total_winnings - total_bet_amount));
+    say!("Current Balance: {}", currency.amount(player.balance()));
+
+    events.emit(|| GameEvent::RoundComplete {
+        wagered: total_bet_amount,
+        returned: total_winnings,
+        balance: player.balance(),
+    });
+    say!("\nBets cleared. Ready for the next round.");
+    Settled { returned: total_winnings, imprisoned, respins: respun }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::console;
+    use crate::game::rules::{GreenPocket, ZeroRule};
+    use crate::game::wheel::WeightedWheel;
+
+    /// A European wheel whose ball only ever finds the green pocket.
+    fn always_green() -> Box<dyn WheelLike> {
+        let layout = Wheel::european();
+        let zero = layout.zero_pockets()[0];
+        let weights = (0..layout.get_all_pockets().len()).map(|id| if id == zero.0 { 1.0 } else { 0.0 }).collect();
+        Box::new(WeightedWheel::new(layout, weights).unwrap())
+    }
+
+    fn pocket(game: &Game, number: u8) -> PocketId {
+        game.wheel().id_of_number(number).unwrap()
+    }
+
+    /// Places `bets` and settles them on the pocket numbered `number`.
+    fn settle_on(game: &mut Game, bets: &[Bet], number: u8) {
+        let pocket = pocket(game, number);
+        console::quietly(|| {
+            for bet in bets {
+                game.try_place_bet(bet.clone()).unwrap();
+            }
+            game.resolve_with_pocket(pocket);
+        });
+    }
+
+    fn respin_table() -> Game {
+        let mut game = Game::with_wheel(100u32, always_green());
+        game.set_rules(TableRules { green_pocket: GreenPocket::Respin, ..TableRules::default() });
+        game
+    }
+
+    #[test]
+    fn play_round_returns_the_original_round_with_its_respins() {
+        let mut game = respin_table();
+        let round = console::quietly(|| game.play_round(vec![Bet::new(BetType::Red, 10u32)])).unwrap();
+        assert_eq!(round.round, 1);
+        assert_eq!(round.wagered, Money::from_units(10));
+        assert_eq!(round.returned, Money::ZERO);
+        assert_eq!(round.respins.len(), MAX_RESPINS as usize);
+        for (i, respin) in round.respins.iter().enumerate() {
+            assert_eq!(respin.round, i as u32 + 2);
+            assert_eq!(respin.wagered, Money::ZERO);
+        }
+    }
+
+    #[test]
+    fn respins_stop_at_the_limit_and_ride_the_next_spin() {
+        let mut game = respin_table();
+        console::quietly(|| game.play_round(vec![Bet::new(BetType::Red, 10u32)])).unwrap();
+        assert!(game.has_bets_in_play());
+        assert_eq!(game.player.balance(), Money::from_units(90));
+    }
+
+    #[test]
+    fn settle_pays_the_winners_and_keeps_the_losers() {
+        let mut game = Game::new(100u32);
+        let one = game.wheel().pocket(pocket(&game, 1)).ticker;
+        settle_on(&mut game, &[Bet::new(BetType::StraightUp(one), 2u32), Bet::new(BetType::Black, 10u32)], 1);
+        assert_eq!(game.last_outcome(), Some((pocket(&game, 1), Money::from_units(72))));
+        assert_eq!(game.get_player_balance(), Money::from_units(160));
+        assert!(!game.has_bets_in_play());
+    }
+
+    #[test]
+    fn green_pocket_rules_decide_what_outside_bets_get_back() {
+        for (green_pocket, back) in [(GreenPocket::Lose, 0), (GreenPocket::HalfBack, 5), (GreenPocket::Push, 10)] {
+            let mut game = Game::new(100u32);
+            game.set_rules(TableRules { green_pocket, ..TableRules::default() });
+            settle_on(&mut game, &[Bet::new(BetType::Red, 10u32)], 0);
+            assert_eq!(game.get_player_balance(), Money::from_units(90 + back), "{:?}", green_pocket);
+        }
+    }
+
+    #[test]
+    fn imprisoned_bets_come_back_without_winnings() {
+        let mut game = Game::new(100u32);
+        game.set_rules(TableRules { zero_rule: ZeroRule::EnPrison, ..TableRules::default() });
+        settle_on(&mut game, &[Bet::new(BetType::Red, 10u32)], 0);
+        assert_eq!(game.imprisoned_bets().len(), 1);
+        settle_on(&mut game, &[], 1);
+        assert!(game.imprisoned_bets().is_empty());
+        assert_eq!(game.get_player_balance(), Money::from_units(100));
+    }
+
+    #[test]
+    fn bets_carried_to_a_respin_win_or_lose_on_the_next_pocket() {
+        let mut game = Game::new(100u32);
+        game.set_rules(TableRules { green_pocket: GreenPocket::Respin, ..TableRules::default() });
+        settle_on(&mut game, &[Bet::new(BetType::Red, 10u32), Bet::new(BetType::Black, 10u32)], 0);
+        assert!(game.has_bets_in_play());
+        assert_eq!(game.get_player_balance(), Money::from_units(80));
+        settle_on(&mut game, &[], 1);
+        assert!(!game.has_bets_in_play());
+        assert_eq!(game.last_outcome(), Some((pocket(&game, 1), Money::from_units(20))));
+        assert_eq!(game.get_player_balance(), Money::from_units(100));
+    }
+
+    #[test]
+    fn a_voided_spin_takes_back_its_winnings_and_its_bets_stand_again() {
+        let mut game = Game::new(100u32);
+        game.allow_voids(true);
+        settle_on(&mut game, &[Bet::new(BetType::Red, 10u32)], 1);
+        assert_eq!(game.get_player_balance(), Money::from_units(110));
+        let voided = console::quietly(|| game.void_last_spin()).unwrap();
+        assert_eq!((voided.round, voided.returned), (1, Money::from_units(20)));
+        assert_eq!(game.get_player_balance(), Money::from_units(90));
+        assert_eq!(game.get_current_bets().len(), 1);
+        assert_eq!(game.void_last_spin().err(), Some(VoidError::BetsPlaced));
+        console::quietly(|| game.resolve_with_pocket(pocket(&game, 2)));
+        assert_eq!(game.get_player_balance(), Money::from_units(90));
+    }
+
+    #[test]
+    fn voids_are_refused_unless_the_table_allows_them() {
+        let mut game = Game::new(100u32);
+        assert_eq!(game.void_last_spin().err(), Some(VoidError::NotAllowed));
+        game.allow_voids(true);
+        assert_eq!(game.void_last_spin().err(), Some(VoidError::NoSpin));
+        settle_on(&mut game, &[Bet::new(BetType::Red, 10u32)], 1);
+        console::quietly(|| game.deposit(Money::from_units(50)));
+        assert_eq!(game.void_last_spin().err(), Some(VoidError::NoSpin));
+        console::quietly(|| game.enable_fairness("client seed"));
+        assert!(!game.voids_allowed());
+        assert_eq!(game.void_last_spin().err(), Some(VoidError::FairSpins));
+    }
+}
@@ -0,0 +1,127 @@
+// src/game/ticker.rs
+
+//! Validated, interned stock tickers.
+//!
+//! A [`Ticker`] is checked when it's made: upper case, 1 to 10 characters of
+//! letters, digits, `.` and `-`, starting with a letter or digit, so
+//! `2222.SR` and `BRK-A` pass and `AA PL` doesn't. Like category names,
+//! tickers are interned once into a process-wide registry, which makes them
+//! `Copy` and comparing two of them an integer compare.
+//!
+//! A valid ticker may still not be on a given wheel; [`Wheel::ticker`]
+//! resolves one against the wheel's pockets.
+//!
+//! [`Wheel::ticker`]: super::wheel::Wheel::ticker
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use spin::{Mutex, Once};
+
+/// Longest ticker accepted, in characters.
+pub const MAX_TICKER_LEN: usize = 10;
+
+/// An interned, upper-case stock ticker such as `AAPL`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Ticker(u32);
+
+impl Ticker {
+    /// Reads a ticker in any case, upper-casing it.
+    pub fn parse(text: &str) -> Result<Ticker, InvalidTicker> {
+        let text = text.trim();
+        let valid = (1..=MAX_TICKER_LEN).contains(&text.len())
+            && text.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+        if !valid {
+            return Err(InvalidTicker(text.to_string()));
+        }
+        let mut registry = registry().lock();
+        Ok(registry.intern(&text.to_ascii_uppercase()))
+    }
+
+    pub fn as_str(self) -> &'static str {
+        let registry = registry().lock();
+        registry.names[self.0 as usize]
+    }
+}
+
+struct Registry {
+    names: Vec<&'static str>,
+    ids: BTreeMap<&'static str, Ticker>,
+}
+
+impl Registry {
+    fn intern(&mut self, name: &str) -> Ticker {
+        if let Some(&ticker) = self.ids.get(name) {
+            return ticker;
+        }
+        // Only valid tickers get here, and there are few of them.
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let ticker = Ticker(self.names.len() as u32);
+        self.names.push(name);
+        self.ids.insert(name, ticker);
+        ticker
+    }
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: Once<Mutex<Registry>> = Once::new();
+    REGISTRY.call_once(|| Mutex::new(Registry { names: Vec::new(), ids: BTreeMap::new() }))
+}
+
+impl fmt::Display for Ticker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for Ticker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl PartialEq<str> for Ticker {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Ticker {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl Serialize for Ticker {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Ticker {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ticker::parse(&text).map_err(D::Error::custom)
+    }
+}
+
+/// Text that isn't a well-formed ticker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidTicker(pub String);
+
+impl fmt::Display for InvalidTicker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' isn't a ticker (1 to {} letters, digits, '.' or '-')",
+            self.0, MAX_TICKER_LEN
+        )
+    }
+}
+
+impl core::error::Error for InvalidTicker {}
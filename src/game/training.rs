@@ -0,0 +1,87 @@
+// src/game/training.rs
+
+//! Bias-hunting practice: a wheel that secretly favours one pocket or a
+//! short sector of neighbours.
+//!
+//! Everything the table shows about odds (the odds screen, expected values,
+//! Kelly stakes) still describes a fair wheel, so the only way to find the
+//! bias is to watch where the ball lands. [`TrainingWheel::score`] grades the
+//! player's guess when the session ends.
+
+use super::wheel::{PocketId, WeightedWheel, Wheel, WheelLike};
+use rand::{Rng, RngCore};
+
+/// Longest sector the bias can cover.
+const MAX_SECTOR: usize = 5;
+
+/// A secretly biased wheel.
+pub struct TrainingWheel {
+    biased: WeightedWheel,
+    name: String,
+    favoured: Vec<PocketId>,
+    strength: f64,
+}
+
+impl TrainingWheel {
+    /// Picks a random pocket, or a sector of up to five neighbours, and makes
+    /// each of its pockets `strength` times as likely as any other.
+    pub fn random(layout: Wheel, strength: f64, rng: &mut impl Rng) -> Result<Self, String> {
+        if !(strength.is_finite() && strength > 1.0) {
+            return Err(format!("bias strength must be greater than 1, got {}", strength));
+        }
+        let pockets = layout.get_all_pockets().len();
+        let start = rng.gen_range(0..pockets);
+        let len = if rng.gen_bool(0.5) { 1 } else { rng.gen_range(2..=MAX_SECTOR) };
+        // Pockets are stored in wheel order, so a sector wraps round the end.
+        let favoured: Vec<PocketId> = (0..len).map(|i| PocketId((start + i) % pockets)).collect();
+        let mut weights = vec![1.0; pockets];
+        for id in &favoured {
+            weights[id.0] = strength;
+        }
+        let name = format!("{}-training", layout.name());
+        let biased = WeightedWheel::new(layout, weights)?;
+        Ok(TrainingWheel { biased, name, favoured, strength })
+    }
+
+    /// The pockets the wheel favours; the answer to the exercise.
+    pub fn favoured(&self) -> &[PocketId] {
+        &self.favoured
+    }
+
+    pub fn strength(&self) -> f64 {
+        self.strength
+    }
+
+    /// How well `guesses` match the favoured pockets; see [`TrainingWheel::score_guesses`].
+    pub fn score(&self, guesses: &[PocketId]) -> u32 {
+        Self::score_guesses(&self.favoured, guesses)
+    }
+
+    /// Scores `guesses` against `favoured` from 0 to 100: the share of
+    /// pockets in both out of all pockets in either, so naming every pocket
+    /// scores as badly as naming the wrong one.
+    pub fn score_guesses(favoured: &[PocketId], guesses: &[PocketId]) -> u32 {
+        let mut named: Vec<PocketId> = guesses.to_vec();
+        named.sort_unstable_by_key(|id| id.0);
+        named.dedup();
+        let hits = favoured.iter().filter(|id| named.contains(id)).count();
+        let union = favoured.len() + named.len() - hits;
+        (hits * 100 / union.max(1)) as u32
+    }
+}
+
+impl WheelLike for TrainingWheel {
+    fn layout(&self) -> &Wheel {
+        self.biased.layout()
+    }
+
+    fn spin(&self, rng: &mut dyn RngCore) -> PocketId {
+        self.biased.spin(rng)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    // The default, fair-wheel probability keeps the bias secret.
+}
@@ -0,0 +1,106 @@
+// src/game/wallet.rs
+
+//! Cash away from the table.
+//!
+//! A [`Wallet`] holds the player's cash, in as many currencies as they like.
+//! Sitting down at a [`Game`] buys in: cash in the table's
+//! [currency](super::rules::TableRules::currency) is changed into chips, at
+//! least the table's [buy-in minimum](super::rules::TableRules::buy_in_minimum).
+//! Leaving cashes out, changing every chip back. Both show in the game's
+//! audit log, as [`BalanceChangeReason::BuyIn`](super::events::BalanceChangeReason::BuyIn)
+//! and `CashOut`. One wallet can buy in at several tables at once.
+
+use super::Game;
+use super::currency::{Amount, Currency, ExchangeError, ExchangeRates};
+use super::money::Money;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Why a buy-in was refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuyInError {
+    /// Less than the table takes.
+    BelowMinimum { minimum: Amount },
+    /// More than the wallet holds in the table's currency.
+    NotEnoughCash { cash: Amount },
+}
+
+impl fmt::Display for BuyInError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuyInError::BelowMinimum { minimum } => write!(f, "this table's minimum buy-in is {}", minimum),
+            BuyInError::NotEnoughCash { cash } => write!(f, "the wallet only holds {}", cash),
+        }
+    }
+}
+
+impl std::error::Error for BuyInError {}
+
+/// The player's cash, away from any table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wallet {
+    cash: BTreeMap<Currency, Money>,
+}
+
+impl Wallet {
+    /// A wallet holding `cash` of one currency.
+    pub fn new(currency: Currency, cash: Money) -> Self {
+        Wallet { cash: BTreeMap::from([(currency, cash)]) }
+    }
+
+    /// Cash held in `currency`.
+    pub fn cash(&self, currency: &Currency) -> Money {
+        self.cash.get(currency).copied().unwrap_or_default()
+    }
+
+    /// Every currency held, with how much.
+    pub fn balances(&self) -> impl Iterator<Item = (&Currency, Money)> {
+        self.cash.iter().filter(|(_, cash)| **cash > Money::ZERO).map(|(currency, cash)| (currency, *cash))
+    }
+
+    /// Changes `amount` of `from` into `to` at `rates`. Returns what it came to.
+    pub fn exchange(
+        &mut self,
+        amount: Money,
+        from: &Currency,
+        to: &Currency,
+        rates: &ExchangeRates,
+    ) -> Result<Money, ExchangeError> {
+        let balance = self.cash(from);
+        if amount > balance {
+            return Err(ExchangeError::NotEnough { balance, currency: from.clone() });
+        }
+        let converted = rates.convert(amount, from, to)?;
+        let remaining = balance - amount;
+        let held = if from == to { remaining } else { self.cash(to) };
+        let held = held.checked_add(converted).ok_or_else(|| ExchangeError::TooMuch { currency: to.clone() })?;
+        self.cash.insert(from.clone(), remaining);
+        self.cash.insert(to.clone(), held);
+        Ok(converted)
+    }
+
+    /// Changes `amount` of cash into chips at `game`'s table.
+    pub fn buy_in(&mut self, game: &mut Game, amount: Money) -> Result<(), BuyInError> {
+        let currency = game.rules().currency.clone();
+        let minimum = game.rules().buy_in_minimum();
+        if amount < minimum {
+            return Err(BuyInError::BelowMinimum { minimum: currency.amount(minimum) });
+        }
+        let cash = self.cash(&currency);
+        if amount > cash {
+            return Err(BuyInError::NotEnoughCash { cash: currency.amount(cash) });
+        }
+        self.cash.insert(currency, cash - amount);
+        game.buy_in(amount);
+        Ok(())
+    }
+
+    /// Changes every chip at `game`'s table back into cash. Returns the
+    /// amount cashed out, in the table's currency.
+    pub fn cash_out(&mut self, game: &mut Game) -> Money {
+        let amount = game.cash_out();
+        let cash = self.cash.entry(game.rules().currency.clone()).or_default();
+        *cash = cash.checked_add(amount).unwrap_or(Money::MAX);
+        amount
+    }
+}
@@ -2,31 +2,68 @@
 
 //! Defines the roulette wheel structure, pockets, colors, and spinning logic.
 
-use rand::Rng;
-use std::collections::HashMap;
-use std::fmt;
+use super::bets::BetType;
+use super::category::{CategoryId, CategoryRegistry, UnknownCategory};
+use super::metadata::{self, MetadataWeighting, PocketMetadata};
+use super::payout::{PayoutReport, PayoutTable};
+use super::ticker::Ticker;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::{Rng, RngCore};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use serde::{Deserialize, Serialize};
 
 /// Represents the possible colors on a roulette wheel pocket.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Color {
     Red,
     Black,
     Green, // For zero
 }
 
+/// The three dozens, which group the stocks by investing style. Every
+/// pocket but the zeros belongs to one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Dozen {
+    Growth,
+    Value,
+    BlueChip,
+}
+
+impl Dozen {
+    pub const ALL: [Dozen; 3] = [Dozen::Growth, Dozen::Value, Dozen::BlueChip];
+}
+
+impl fmt::Display for Dozen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dozen::Growth => write!(f, "Growth Dozen"),
+            Dozen::Value => write!(f, "Value Dozen"),
+            Dozen::BlueChip => write!(f, "Blue Chip Dozen"),
+        }
+    }
+}
 
-mod stock_categories {
+pub(crate) mod stock_categories {
     pub const MAG7: &str = "Magnificent Seven";
     pub const TECH: &str = "Technology";
     pub const SP500_HEAVY_A: &str = "S&P 500 Heavy A";
-    pub const GROWTH_DOZEN_A: &str = "Growth Dozen A";
     pub const OIL_MAJOR: &str = "Oil & Gas Major";
     pub const ENERGY: &str = "Energy";
     pub const VALUE_FOCUS_B: &str = "Value Focus B";
-    pub const VALUE_DOZEN_B: &str = "Value Dozen B";
     pub const BIG_FINANCE: &str = "Big Finance";
     pub const FINANCIALS: &str = "Financials";
-    pub const BLUE_CHIP_DOZEN_C: &str = "Blue Chip Dozen C";
+    pub const LEGACY_TECH: &str = "Legacy Tech";
+    pub const TELECOM: &str = "Telecom";
+    pub const AUTOMOTIVE: &str = "Automotive";
+    pub const CONSUMER: &str = "Consumer";
+    pub const HEALTHCARE: &str = "Healthcare";
+    pub const INDUSTRIAL: &str = "Industrial";
     // Add other categories as needed...
 }
 
@@ -49,41 +86,304 @@ impl fmt::Display for Color {
 //    pub color: Color,
 //}
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Pocket {
-    pub ticker: String,
+    pub ticker: Ticker,
     pub display_name: String,
-    pub categories: Vec<String>,
-    /// The number displayed on the pocket (0-36).
+    pub categories: Vec<CategoryId>,
+    /// The dozen the pocket counts toward; `None` for the zeros.
+    pub dozen: Option<Dozen>,
+    pub metadata: PocketMetadata,
+    /// The number displayed on the pocket (0-36, or [`DOUBLE_ZERO`] for 00).
     pub number: u8,
     /// The color of the pocket.
     pub color: Color,
 }
 
+impl Pocket {
+    /// True for the green zero pockets, where outside bets lose.
+    pub fn is_zero(&self) -> bool {
+        self.color == Color::Green
+    }
+
+    /// The number as printed on the wheel ("00" for the double zero).
+    pub fn label(&self) -> String {
+        if self.number == DOUBLE_ZERO { "00".to_string() } else { self.number.to_string() }
+    }
+}
+
 impl fmt::Display for Pocket {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {} {}", self.number, self.color, self.ticker, self.display_name)
+        write!(f, "{} {} {} {}", self.label(), self.color, self.ticker, self.display_name)
+    }
+}
+
+/// Number stored for the American wheel's 00 pocket.
+pub const DOUBLE_ZERO: u8 = 37;
+
+/// Numbers colored red; every other non-zero number is black.
+const RED_NUMBERS: [u8; 18] = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
+
+/// European wheel order, starting from zero.
+const EUROPEAN_ORDER: [u8; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23,
+    10, 5, 24, 16, 33, 1, 20, 14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
+];
+
+/// American wheel order, starting from zero.
+const AMERICAN_ORDER: [u8; 38] = [
+    0, 28, 9, 26, 30, 11, 7, 20, 32, 17, 5, 22, 34, 15, 3, 24, 36, 13, 1,
+    DOUBLE_ZERO, 27, 10, 25, 29, 12, 8, 19, 31, 18, 6, 21, 33, 16, 4, 23, 35, 14, 2,
+];
+
+/// The sectors making up columns 1 to 3 on the full wheels, twelve stocks
+/// to a column so that 2 to 1 stays the fair price.
+const SECTOR_COLUMNS: &[(&str, &[&str])] = &[
+    ("Tech & Telecom", &[stock_categories::TECH, stock_categories::LEGACY_TECH, stock_categories::TELECOM]),
+    ("Energy, Finance & Autos", &[stock_categories::ENERGY, stock_categories::FINANCIALS, stock_categories::AUTOMOTIVE]),
+    ("Consumer, Health & Industry", &[stock_categories::CONSUMER, stock_categories::HEALTHCARE, stock_categories::INDUSTRIAL]),
+];
+
+/// Other names for the stock wheels' categories.
+const STOCK_CATEGORY_ALIASES: &[(&str, &[&str])] = &[
+    (stock_categories::MAG7, &["Mag 7", "Magnificent 7"]),
+    (stock_categories::TECH, &["Tech"]),
+    (stock_categories::SP500_HEAVY_A, &["S&P 500", "SP500"]),
+    (stock_categories::OIL_MAJOR, &["Oil", "Oil and Gas"]),
+    (stock_categories::BIG_FINANCE, &["Banks"]),
+    (stock_categories::FINANCIALS, &["Finance"]),
+    (stock_categories::HEALTHCARE, &["Health"]),
+    (stock_categories::AUTOMOTIVE, &["Autos", "Cars"]),
+    ("Dividend Aristocrats", &["Dividends", "Aristocrats"]),
+];
+
+/// The mini wheel's columns: its twelve stocks regrouped four to a column.
+const MINI_SECTOR_COLUMNS: &[(&str, &[&str])] = &[
+    ("Tech & Energy", &[stock_categories::TECH, stock_categories::LEGACY_TECH, stock_categories::ENERGY]),
+    ("Finance & Health", &[stock_categories::FINANCIALS, stock_categories::HEALTHCARE]),
+    ("Consumer & Autos", &[stock_categories::CONSUMER, stock_categories::AUTOMOTIVE]),
+];
+
+impl Color {
+    /// The color `number` has on a standard wheel.
+    pub fn of_number(number: u8) -> Color {
+        color_of(number)
+    }
+}
+
+fn color_of(number: u8) -> Color {
+    if number == 0 || number == DOUBLE_ZERO {
+        Color::Green
+    } else if RED_NUMBERS.contains(&number) {
+        Color::Red
+    } else {
+        Color::Black
     }
 }
 
-/// Represents the European roulette wheel.
+/// Index of a pocket within a [`Wheel`], cheap to copy and compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PocketId(pub usize);
+
+/// A set of pockets stored as a bitset, one bit per [`PocketId`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PocketSet {
+    words: Vec<u64>,
+}
+
+impl PocketSet {
+    pub fn new() -> Self {
+        PocketSet::default()
+    }
+
+    pub fn insert(&mut self, id: PocketId) {
+        let (word, bit) = (id.0 / 64, id.0 % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub fn contains(&self, id: PocketId) -> bool {
+        let (word, bit) = (id.0 / 64, id.0 % 64);
+        self.words.get(word).is_some_and(|w| w & (1 << bit) != 0)
+    }
+
+    /// Number of pockets in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Iterates over the pockets in the set in id order.
+    pub fn iter(&self) -> impl Iterator<Item = PocketId> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| PocketId(i * 64 + bit))
+        })
+    }
+}
+
+impl FromIterator<PocketId> for PocketSet {
+    fn from_iter<I: IntoIterator<Item = PocketId>>(iter: I) -> Self {
+        let mut set = PocketSet::new();
+        for id in iter {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+/// A column bet's group of sectors. A pocket in any of them is in the column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectorColumn {
+    pub name: String,
+    pub sectors: Vec<CategoryId>,
+    members: PocketSet,
+}
+
+impl SectorColumn {
+    /// The pockets the column covers.
+    pub fn members(&self) -> &PocketSet {
+        &self.members
+    }
+}
+
+/// A wheel layout: its pockets in wheel order, their categories, and the
+/// odds it pays. Spinning one gives every pocket the same chance; see
+/// [`WheelLike`] for wheels that spin differently.
+#[derive(Debug, Clone)]
 pub struct Wheel {
+    name: String,
     pockets: Vec<Pocket>,
-    pocket_map: HashMap<u8, Pocket>, // For quick lookup by number
+    pocket_map: BTreeMap<u8, PocketId>, // For quick lookup by number
+    category_index: BTreeMap<CategoryId, PocketSet>, // Pockets belonging to each category
+    category_registry: CategoryRegistry,
+    dozen_index: BTreeMap<Dozen, PocketSet>,
+    columns: Vec<SectorColumn>,
+    highest_number: u8,
+    payouts: PayoutTable,
 }
 
 impl Wheel {
     /// Creates a new European roulette wheel (0-36).
     pub fn new() -> Self {
-        let mut pockets = Vec::with_capacity(37);
-        let mut pocket_map = HashMap::with_capacity(37);
+        Self::european()
+    }
 
-        let red_numbers: [u8; 18] = [1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36];
-        let wheel_order: [u8; 37] = [
-            0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23,
-            10, 5, 24, 16, 33, 1, 20, 14, 31, 9, 22, 18, 29, 7, 28, 12, 35, 3, 26,
-        ];
+    /// The European wheel: 0-36 with a single zero.
+    pub fn european() -> Self {
+        Self::from_pockets("european", Self::stock_pockets(&EUROPEAN_ORDER), PayoutTable::STANDARD)
+            .with_columns(&Self::stock_columns(SECTOR_COLUMNS))
+            .with_category_aliases(STOCK_CATEGORY_ALIASES)
+    }
+
+    /// The American wheel: adds a 00 pocket (Market Surge) at the same odds,
+    /// nearly doubling the house edge.
+    pub fn american() -> Self {
+        Self::from_pockets("american", Self::stock_pockets(&AMERICAN_ORDER), PayoutTable::STANDARD)
+            .with_columns(&Self::stock_columns(SECTOR_COLUMNS))
+            .with_category_aliases(STOCK_CATEGORY_ALIASES)
+    }
+
+    /// The mini wheel: 0-12 only, with odds scaled down to match.
+    pub fn mini() -> Self {
+        let order: Vec<u8> = EUROPEAN_ORDER.iter().copied().filter(|&n| n <= 12).collect();
+        Self::from_pockets("mini", Self::stock_pockets(&order), PayoutTable::MINI)
+            .with_columns(&Self::stock_columns(MINI_SECTOR_COLUMNS))
+            .with_category_aliases(STOCK_CATEGORY_ALIASES)
+    }
+
+    /// Builds a wheel from pockets listed in wheel order. It has no columns
+    /// until [`with_columns`](Self::with_columns) gives it some.
+    pub fn from_pockets(name: &str, pockets: Vec<Pocket>, payouts: PayoutTable) -> Self {
+        let pocket_map = pockets.iter().enumerate().map(|(i, p)| (p.number, PocketId(i))).collect();
+        let category_index = Self::index_categories(&pockets);
+        let category_registry = CategoryRegistry::new(category_index.keys().copied());
+        let mut dozen_index: BTreeMap<Dozen, PocketSet> = BTreeMap::new();
+        for (i, pocket) in pockets.iter().enumerate() {
+            if let Some(dozen) = pocket.dozen {
+                dozen_index.entry(dozen).or_default().insert(PocketId(i));
+            }
+        }
+        let highest_number = pockets.iter().filter(|p| !p.is_zero()).map(|p| p.number).max().unwrap_or(0);
+
+        Wheel {
+            name: name.to_string(),
+            pockets,
+            pocket_map,
+            category_index,
+            category_registry,
+            dozen_index,
+            columns: Vec::new(),
+            highest_number,
+            payouts,
+        }
+    }
+
+    /// Pockets by category, counting the categories their metadata puts
+    /// them in.
+    fn index_categories(pockets: &[Pocket]) -> BTreeMap<CategoryId, PocketSet> {
+        let mut category_index: BTreeMap<CategoryId, PocketSet> = BTreeMap::new();
+        for (i, pocket) in pockets.iter().enumerate() {
+            let derived = pocket.metadata.categories().into_iter().map(CategoryId::intern);
+            for category in pocket.categories.iter().copied().chain(derived) {
+                category_index.entry(category).or_default().insert(PocketId(i));
+            }
+        }
+        category_index
+    }
+
+    /// Updates the metadata of the pockets named by ticker with the fields
+    /// each entry sets.
+    pub fn with_metadata(mut self, overrides: &BTreeMap<String, PocketMetadata>) -> Result<Self, String> {
+        if overrides.is_empty() {
+            return Ok(self);
+        }
+        for (ticker, metadata) in overrides {
+            let id = self.find_pocket(ticker).ok_or_else(|| format!("unknown ticker '{}'", ticker))?;
+            metadata.validate().map_err(|msg| format!("{}: {}", ticker, msg))?;
+            self.pockets[id.0].metadata.merge(metadata);
+        }
+        self.category_index = Self::index_categories(&self.pockets);
+        self.category_registry = self.category_registry.rebuilt(self.category_index.keys().copied());
+        Ok(self)
+    }
+
+    /// Gives categories other names to be found by, as `(name, aliases)`.
+    /// Categories this wheel doesn't have are skipped.
+    pub fn with_category_aliases(mut self, aliases: &[(&str, &[&str])]) -> Self {
+        for (name, aliases) in aliases {
+            if let Ok(id) = self.category_registry.resolve(name) {
+                for alias in *aliases {
+                    self.category_registry.add_alias(id, alias);
+                }
+            }
+        }
+        self
+    }
 
+    /// Sets the columns, 1 to 3 in order, each as a name and its sectors.
+    pub fn with_columns(mut self, columns: &[(&str, Vec<CategoryId>)]) -> Self {
+        self.columns = columns
+            .iter()
+            .map(|(name, sectors)| {
+                let members = sectors.iter().filter_map(|&sector| self.category_members(sector)).flat_map(PocketSet::iter).collect();
+                SectorColumn { name: name.to_string(), sectors: sectors.clone(), members }
+            })
+            .collect();
+        self
+    }
+
+    fn stock_columns(columns: &[(&'static str, &[&str])]) -> Vec<(&'static str, Vec<CategoryId>)> {
+        columns.iter().map(|(name, sectors)| (*name, sectors.iter().map(|&s| CategoryId::intern(s)).collect())).collect()
+    }
+
+    /// The stock pockets laid out in `order`. Every number keeps the stock it
+    /// has on the European wheel; 00 is the Market Surge.
+    fn stock_pockets(order: &[u8]) -> Vec<Pocket> {
         let pocket_defs = Self::get_pocket_definitions();
 
         // Ensure we have exactly 37 pockets
@@ -91,214 +391,232 @@ impl Wheel {
             panic!("Expected 37 pocket definitions, got {}", pocket_defs.len());
         }
 
-        for (i, &number) in wheel_order.iter().enumerate() {
-            let mut pocket = pocket_defs[i].clone();
-            pocket.number = number;
-            pocket.color = if number == 0 {
-                Color::Green
-            } else if red_numbers.contains(&number) {
-                Color::Red
-            } else {
-                Color::Black
-            };
-
-            pockets.push(pocket.clone());
-            pocket_map.insert(number, pocket);
-        }
+        let mut by_number: BTreeMap<u8, Pocket> = EUROPEAN_ORDER.iter().copied().zip(pocket_defs).collect();
+        by_number.insert(DOUBLE_ZERO, Pocket {
+            ticker: Ticker::parse("SRGE").expect("built-in tickers are valid"),
+            display_name: "Market Surge".to_string(),
+            categories: ["Market Surge", "SRGE"].iter().map(|&s| CategoryId::intern(s)).collect(),
+            dozen: None,
+            metadata: PocketMetadata::default(),
+            color: Color::Green,
+            number: DOUBLE_ZERO,
+        });
+        order
+            .iter()
+            .map(|&number| {
+                let mut pocket = by_number.remove(&number).expect("every number has a stock");
+                pocket.number = number;
+                pocket.color = color_of(number);
+                pocket
+            })
+            .collect()
+    }
+
+    /// Name of the layout, e.g. `european`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Highest pocket number, which splits Low from High (36 on a full wheel).
+    pub fn highest_number(&self) -> u8 {
+        self.highest_number
+    }
+
+    /// The odds this wheel pays.
+    pub fn payouts(&self) -> &PayoutTable {
+        &self.payouts
+    }
+
+    /// The green pockets, where every outside bet loses.
+    pub fn zero_pockets(&self) -> Vec<PocketId> {
+        (0..self.pockets.len()).map(PocketId).filter(|&id| self.pocket(id).is_zero()).collect()
+    }
 
-        Wheel { pockets, pocket_map }
+    /// Chance that a bet of this type wins on one spin, every pocket being
+    /// equally likely.
+    pub fn probability(&self, bet_type: &BetType) -> f64 {
+        let covered = (0..self.pockets.len()).filter(|&i| bet_type.covers(self, PocketId(i))).count();
+        covered as f64 / self.pockets.len() as f64
     }
 
     pub fn get_pocket_definitions() -> Vec<Pocket> {
-        let ticker_data: HashMap<&str, (&str, Vec<&str>)> = HashMap::from([
+        // Listed in wheel order: the first entry takes pocket 0, the next pocket 32, and so on.
+        // Ticker, then its name, dozen, and categories.
+        type Definition = (&'static str, (&'static str, Option<Dozen>, Vec<&'static str>));
+        let ticker_data: Vec<Definition> = vec![
+            // Green Space (pocket 0)
+            ("RCSN", ("Recession", None, vec![
+                "Recession", "Recession", "RCSN"
+            ])),
             // Magnificent Seven
-            ("AAPL", ("Apple Inc.", vec![
+            ("AAPL", ("Apple Inc.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "AAPL"
             ])),
-            ("MSFT", ("Microsoft Corp.", vec![
+            ("MSFT", ("Microsoft Corp.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "MSFT"
             ])),
-            ("GOOGL", ("Alphabet Inc.", vec![
+            ("GOOGL", ("Alphabet Inc.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "GOOGL"
             ])),
-            ("AMZN", ("Amazon.com Inc.", vec![
+            ("AMZN", ("Amazon.com Inc.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "AMZN"
             ])),
-            ("NVDA", ("NVIDIA Corp.", vec![
+            ("NVDA", ("NVIDIA Corp.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "NVDA"
             ])),
-            ("META", ("Meta Platforms Inc.", vec![
+            ("META", ("Meta Platforms Inc.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "META"
             ])),
-            ("TSLA", ("Tesla Inc.", vec![
+            ("TSLA", ("Tesla Inc.", Some(Dozen::Growth), vec![
                 stock_categories::MAG7, stock_categories::TECH,
                 stock_categories::SP500_HEAVY_A,
-                stock_categories::GROWTH_DOZEN_A,
                 "TSLA"
             ])),
     
             // Oil & Gas Majors
-            ("XOM", ("Exxon Mobil Corp.", vec![
+            ("XOM", ("Exxon Mobil Corp.", Some(Dozen::Value), vec![
                 stock_categories::OIL_MAJOR, stock_categories::ENERGY,
                 stock_categories::VALUE_FOCUS_B,
-                stock_categories::VALUE_DOZEN_B,
                 "XOM"
             ])),
-            ("CVX", ("Chevron Corp.", vec![
+            ("CVX", ("Chevron Corp.", Some(Dozen::Value), vec![
                 stock_categories::OIL_MAJOR, stock_categories::ENERGY,
                 stock_categories::VALUE_FOCUS_B,
-                stock_categories::VALUE_DOZEN_B,
                 "CVX"
             ])),
-            ("COP", ("ConocoPhillips", vec![
+            ("COP", ("ConocoPhillips", Some(Dozen::Value), vec![
                 stock_categories::OIL_MAJOR, stock_categories::ENERGY,
                 stock_categories::VALUE_FOCUS_B,
-                stock_categories::VALUE_DOZEN_B,
                 "COP"
             ])),
-            ("2222.SR", ("Saudi Aramco", vec![
+            ("2222.SR", ("Saudi Aramco", Some(Dozen::Value), vec![
                 stock_categories::OIL_MAJOR, stock_categories::ENERGY,
                 stock_categories::VALUE_FOCUS_B,
-                stock_categories::VALUE_DOZEN_B,
                 "2222.SR"
             ])),
-            ("PTR", ("PetroChina Co.", vec![
+            ("PTR", ("PetroChina Co.", Some(Dozen::Value), vec![
                 stock_categories::OIL_MAJOR, stock_categories::ENERGY,
                 stock_categories::VALUE_FOCUS_B,
-                stock_categories::VALUE_DOZEN_B,
                 "PTR"
             ])),
     
             // Big Finance (Banks & Payment Processors)
-            ("JPM", ("JPMorgan Chase & Co.", vec![
+            ("JPM", ("JPMorgan Chase & Co.", Some(Dozen::BlueChip), vec![
                 stock_categories::BIG_FINANCE, stock_categories::FINANCIALS,
-                stock_categories::BLUE_CHIP_DOZEN_C,
                 stock_categories::SP500_HEAVY_A,
                 "JPM"
             ])),
-            ("BRK-A", ("Berkshire Hathaway Inc.", vec![
+            ("BRK-A", ("Berkshire Hathaway Inc.", Some(Dozen::BlueChip), vec![
                 stock_categories::BIG_FINANCE, stock_categories::FINANCIALS,
-                stock_categories::BLUE_CHIP_DOZEN_C,
                 stock_categories::SP500_HEAVY_A,
                 "BRK-A"
             ])),
-            ("WFC", ("Wells Fargo & Co.", vec![
+            ("WFC", ("Wells Fargo & Co.", Some(Dozen::BlueChip), vec![
                 stock_categories::BIG_FINANCE, stock_categories::FINANCIALS,
-                stock_categories::BLUE_CHIP_DOZEN_C,
                 stock_categories::SP500_HEAVY_A,
                 "WFC"
             ])),
-            ("V", ("Visa Inc.", vec![
+            ("V", ("Visa Inc.", Some(Dozen::BlueChip), vec![
                 stock_categories::BIG_FINANCE, stock_categories::FINANCIALS,
-                stock_categories::BLUE_CHIP_DOZEN_C,
                 stock_categories::SP500_HEAVY_A,
                 "V"
             ])),
-            ("MA", ("Mastercard Inc.", vec![
+            ("MA", ("Mastercard Inc.", Some(Dozen::BlueChip), vec![
                 stock_categories::BIG_FINANCE, stock_categories::FINANCIALS,
-                stock_categories::BLUE_CHIP_DOZEN_C,
                 stock_categories::SP500_HEAVY_A,
                 "MA"
             ])),
     
             // Pharma/Healthcare
-            ("PFE", ("Pfizer Inc.", vec![
-                "Pharma", "Healthcare", "Dividend Aristocrats", "PFE"
+            ("PFE", ("Pfizer Inc.", Some(Dozen::Value), vec![
+                "Pharma", stock_categories::HEALTHCARE, "Dividend Aristocrats", "PFE"
             ])),
-            ("JNJ", ("Johnson & Johnson", vec![
-                "Pharma", "Healthcare", "Dividend Aristocrats", "JNJ"
+            ("JNJ", ("Johnson & Johnson", Some(Dozen::BlueChip), vec![
+                "Pharma", stock_categories::HEALTHCARE, "Dividend Aristocrats", "JNJ"
             ])),
-            ("UNH", ("UnitedHealth Group", vec![
-                "Pharma", "Healthcare", "Dividend Aristocrats", "UNH"
+            ("UNH", ("UnitedHealth Group", Some(Dozen::Growth), vec![
+                "Pharma", stock_categories::HEALTHCARE, "Dividend Aristocrats", "UNH"
             ])),
     
             // Industrial
-            ("GE", ("General Electric", vec![
-                "Industrial", "Dividend Aristocrats", "GE"
+            ("GE", ("General Electric", Some(Dozen::BlueChip), vec![
+                stock_categories::INDUSTRIAL, "Dividend Aristocrats", "GE"
             ])),
     
             // Legacy Tech
-            ("IBM", ("IBM Corp.", vec![
-                "Legacy Tech", "Dividend Aristocrats", "IBM"
+            ("IBM", ("IBM Corp.", Some(Dozen::Value), vec![
+                stock_categories::LEGACY_TECH, "Dividend Aristocrats", "IBM"
             ])),
-            ("INTC", ("Intel Corp.", vec![
-                "Legacy Tech", "Dividend Aristocrats", "INTC"
+            ("INTC", ("Intel Corp.", Some(Dozen::Value), vec![
+                stock_categories::LEGACY_TECH, "Dividend Aristocrats", "INTC"
             ])),
-            ("CSCO", ("Cisco Systems", vec![
-                "Legacy Tech", "Dividend Aristocrats", "CSCO"
+            ("CSCO", ("Cisco Systems", Some(Dozen::Growth), vec![
+                stock_categories::LEGACY_TECH, "Dividend Aristocrats", "CSCO"
             ])),
     
             // Telecom
-            ("T", ("AT&T Inc.", vec![
-                "Telecom", "Dividend Aristocrats", "T"
+            ("T", ("AT&T Inc.", Some(Dozen::Value), vec![
+                stock_categories::TELECOM, "Dividend Aristocrats", "T"
             ])),
-            ("VZ", ("Verizon Communications", vec![
-                "Telecom", "Dividend Aristocrats", "VZ"
+            ("VZ", ("Verizon Communications", Some(Dozen::Value), vec![
+                stock_categories::TELECOM, "Dividend Aristocrats", "VZ"
             ])),
     
             // Retail/Consumer
-            ("HD", ("Home Depot", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "HD"
+            ("HD", ("Home Depot", Some(Dozen::Growth), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "HD"
             ])),
-            ("WMT", ("Walmart Inc.", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "WMT"
+            ("WMT", ("Walmart Inc.", Some(Dozen::BlueChip), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "WMT"
             ])),
-            ("KO", ("Coca-Cola Co.", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "KO"
+            ("KO", ("Coca-Cola Co.", Some(Dozen::BlueChip), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "KO"
             ])),
-            ("PEP", ("PepsiCo Inc.", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "PEP"
+            ("PEP", ("PepsiCo Inc.", Some(Dozen::BlueChip), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "PEP"
             ])),
-            ("PG", ("Procter & Gamble", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "PG"
+            ("PG", ("Procter & Gamble", Some(Dozen::BlueChip), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "PG"
             ])),
-            ("MCD", ("McDonald's Corp.", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "MCD"
+            ("MCD", ("McDonald's Corp.", Some(Dozen::BlueChip), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "MCD"
             ])),
-            ("NKE", ("Nike Inc.", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "NKE"
+            ("NKE", ("Nike Inc.", Some(Dozen::Growth), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "NKE"
             ])),
-            ("COST", ("Costco Wholesale", vec![
-                "Retail", "Consumer", "Dividend Aristocrats", "COST"
+            ("COST", ("Costco Wholesale", Some(Dozen::Growth), vec![
+                "Retail", stock_categories::CONSUMER, "Dividend Aristocrats", "COST"
             ])),
     
             // Other Automotive
-            ("F", ("Ford Motor Co.", vec![
-                "Automotive", "Dividend Aristocrats", "F"
+            ("F", ("Ford Motor Co.", Some(Dozen::Value), vec![
+                stock_categories::AUTOMOTIVE, "Dividend Aristocrats", "F"
             ])),
-            ("GM", ("General Motors Co.", vec![
-                "Automotive", "Dividend Aristocrats", "GM"
+            ("GM", ("General Motors Co.", Some(Dozen::Value), vec![
+                stock_categories::AUTOMOTIVE, "Dividend Aristocrats", "GM"
             ])),
-            // Green Spaces 
-            ("RCSN", ("Recession", vec![
-                "Recession", "Recession", "RCSN"
-            ])),
-        ]);
+        ];
     
-        // Convert the hashmap entries into a Vec<Pocket>
-        ticker_data.into_iter().map(|(ticker, (display_name, categories))| {
+        // Convert the entries into a Vec<Pocket>
+        ticker_data.into_iter().map(|(ticker, (display_name, dozen, categories))| {
             Pocket {
-                ticker: ticker.to_string(),
+                ticker: Ticker::parse(ticker).expect("built-in tickers are valid"),
                 display_name: display_name.to_string(),
-                categories: categories.iter().map(|&s| s.to_string()).collect(),
+                categories: categories.iter().map(|&s| CategoryId::intern(s)).collect(),
+                dozen,
+                metadata: metadata::stock_metadata(ticker),
                 color: Color::Red,
                 number: 0,
             }
@@ -307,27 +625,419 @@ impl Wheel {
 
     /// Gets a pocket by its number.
     pub fn get_pocket(&self, number: u8) -> Option<&Pocket> {
-        self.pocket_map.get(&number)
+        self.pocket_map.get(&number).map(|&id| self.pocket(id))
+    }
+
+    /// Gets a pocket by its id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` did not come from this wheel.
+    pub fn pocket(&self, id: PocketId) -> &Pocket {
+        &self.pockets[id.0]
+    }
+
+    /// Gets the id of the pocket with the given number.
+    pub fn id_of_number(&self, number: u8) -> Option<PocketId> {
+        self.pocket_map.get(&number).copied()
+    }
+
+    /// Gets the id of the pocket with the given ticker.
+    pub fn id_of_ticker(&self, ticker: Ticker) -> Option<PocketId> {
+        self.pockets.iter().position(|p| p.ticker == ticker).map(PocketId)
+    }
+
+    /// Reads a ticker typed in any case and checks the wheel has it.
+    pub fn ticker(&self, text: &str) -> Option<Ticker> {
+        let ticker = Ticker::parse(text).ok()?;
+        self.id_of_ticker(ticker).map(|_| ticker)
+    }
+
+    /// Looks up a pocket by its number or (case-insensitive) ticker.
+    pub fn find_pocket(&self, query: &str) -> Option<PocketId> {
+        let query = query.trim();
+        if query == "00" {
+            return self.id_of_number(DOUBLE_ZERO);
+        }
+        match query.parse::<u8>() {
+            Ok(number) => self.id_of_number(number),
+            Err(_) => self.id_of_ticker(Ticker::parse(query).ok()?),
+        }
+    }
+
+    /// Returns the pockets tagged with a category, if any pocket has it.
+    pub fn category_members(&self, category: CategoryId) -> Option<&PocketSet> {
+        self.category_index.get(&category)
+    }
+
+    /// Every category used on this wheel, sorted by name.
+    pub fn categories(&self) -> Vec<CategoryId> {
+        let mut ids: Vec<CategoryId> = self.category_index.keys().copied().collect();
+        ids.sort_by_key(|id| id.name());
+        ids
+    }
+
+    /// Works out the expected return and house edge of every bet this wheel
+    /// offers under `table`, flagging any that pay back more than they take.
+    pub fn verify_payouts(&self, table: &PayoutTable) -> PayoutReport {
+        PayoutReport::for_wheel(self, table)
+    }
+
+    /// The pockets in a dozen, if any pocket is.
+    pub fn dozen_members(&self, dozen: Dozen) -> Option<&PocketSet> {
+        self.dozen_index.get(&dozen)
+    }
+
+    /// Returns true if the pocket is in the dozen.
+    pub fn in_dozen(&self, dozen: Dozen, id: PocketId) -> bool {
+        self.dozen_members(dozen).is_some_and(|set| set.contains(id))
+    }
+
+    /// The column bets, 1 to 3 in order.
+    pub fn columns(&self) -> &[SectorColumn] {
+        &self.columns
+    }
+
+    /// Column `number`, counting from 1.
+    pub fn column(&self, number: u8) -> Option<&SectorColumn> {
+        self.columns.get(usize::from(number).checked_sub(1)?)
+    }
+
+    /// Returns true if the pocket is in column `number`.
+    pub fn in_column(&self, number: u8, id: PocketId) -> bool {
+        self.column(number).is_some_and(|column| column.members.contains(id))
+    }
+
+    /// The categories on this wheel, with their aliases.
+    pub fn category_registry(&self) -> &CategoryRegistry {
+        &self.category_registry
+    }
+
+    /// Resolves a category name or alias to its id, in any case, suggesting
+    /// a close match when there is none.
+    pub fn resolve_category(&self, name: &str) -> Result<CategoryId, UnknownCategory> {
+        self.category_registry.resolve(name)
+    }
+
+    /// Resolves a category name to its id, if some pocket on this wheel has it.
+    pub fn category_id(&self, name: &str) -> Option<CategoryId> {
+        self.resolve_category(name).ok()
+    }
+
+    /// Returns true if the pocket is tagged with the category.
+    pub fn in_category(&self, category: CategoryId, id: PocketId) -> bool {
+        self.category_members(category).is_some_and(|set| set.contains(id))
     }
 
     /// Simulates spinning the wheel and returns the winning pocket.
-    pub fn spin(&self) -> Pocket {
-        let mut rng = rand::thread_rng();
-        // Generate a random index from 0 to 36 (inclusive)
-        let winning_index = rng.gen_range(0..self.pockets.len());
-        // Return a copy of the winning pocket
-        self.pockets[winning_index].clone()
+    #[cfg(feature = "std")]
+    pub fn spin(&self) -> &Pocket {
+        self.pocket(self.spin_id())
+    }
+
+    /// Simulates spinning the wheel and returns the id of the winning pocket.
+    #[cfg(feature = "std")]
+    pub fn spin_id(&self) -> PocketId {
+        self.spin_id_with(&mut rand::thread_rng())
+    }
+
+    /// Spins using the given random number generator, e.g. a seeded one for
+    /// reproducible simulations.
+    pub fn spin_id_with<R: Rng + ?Sized>(&self, rng: &mut R) -> PocketId {
+        PocketId(rng.gen_range(0..self.pockets.len()))
     }
 
     /// Returns a slice of all pockets on the wheel.
     pub fn get_all_pockets(&self) -> &[Pocket] {
         &self.pockets
     }
+
+    /// This wheel with one edit made, keeping its name, payouts, columns,
+    /// and category aliases.
+    pub fn edited(&self, edit: &WheelEdit) -> Result<Wheel, WheelEditError> {
+        let find = |query: &str| self.find_pocket(query).ok_or_else(|| WheelEditError::UnknownPocket(query.to_string()));
+        let parse = |text: &str| Ticker::parse(text).map_err(|e| WheelEditError::InvalidTicker(e.to_string()));
+        let mut pockets = self.pockets.clone();
+        match edit {
+            WheelEdit::AddPocket { number, ticker, name } => {
+                let ticker = parse(ticker)?;
+                if self.pocket_map.contains_key(number) {
+                    return Err(WheelEditError::NumberTaken(*number));
+                }
+                if self.id_of_ticker(ticker).is_some() {
+                    return Err(WheelEditError::TickerTaken(ticker.to_string()));
+                }
+                pockets.push(Pocket {
+                    ticker,
+                    display_name: name.clone(),
+                    categories: Vec::new(),
+                    dozen: None,
+                    metadata: PocketMetadata::default(),
+                    color: color_of(*number),
+                    number: *number,
+                });
+            }
+            WheelEdit::RemovePocket { ticker } => {
+                let id = find(ticker)?;
+                if pockets.len() == 1 {
+                    return Err(WheelEditError::LastPocket);
+                }
+                pockets.remove(id.0);
+            }
+            WheelEdit::RenamePocket { ticker, new_ticker, name } => {
+                let id = find(ticker)?;
+                let renamed = parse(new_ticker)?;
+                if self.id_of_ticker(renamed).is_some_and(|other| other != id) {
+                    return Err(WheelEditError::TickerTaken(renamed.to_string()));
+                }
+                let pocket = &mut pockets[id.0];
+                // Stock pockets are a category of their own, under their ticker.
+                let own = CategoryId::intern(pocket.ticker.as_str());
+                for category in pocket.categories.iter_mut().filter(|category| **category == own) {
+                    *category = CategoryId::intern(renamed.as_str());
+                }
+                pocket.ticker = renamed;
+                pocket.display_name = name.clone();
+            }
+            WheelEdit::AddCategory { ticker, category } => {
+                let id = find(ticker)?;
+                let category = self.category_id(category).unwrap_or_else(|| CategoryId::intern(category.trim()));
+                if self.in_category(category, id) {
+                    return Err(WheelEditError::AlreadyInCategory(self.pockets[id.0].ticker.to_string(), category.name().to_string()));
+                }
+                pockets[id.0].categories.push(category);
+            }
+            WheelEdit::RemoveCategory { ticker, category } => {
+                let id = find(ticker)?;
+                let ticker = self.pockets[id.0].ticker.to_string();
+                let not_in = || WheelEditError::NotInCategory(ticker.clone(), category.trim().to_string());
+                let category = self.category_id(category).ok_or_else(not_in)?;
+                let position = pockets[id.0].categories.iter().position(|&c| c == category).ok_or_else(not_in)?;
+                pockets[id.0].categories.remove(position);
+            }
+        }
+        let columns: Vec<(&str, Vec<CategoryId>)> =
+            self.columns.iter().map(|column| (column.name.as_str(), column.sectors.clone())).collect();
+        let mut wheel = Wheel::from_pockets(&self.name, pockets, self.payouts.clone()).with_columns(&columns);
+        wheel.category_registry = self.category_registry.rebuilt(wheel.category_index.keys().copied());
+        Ok(wheel)
+    }
+}
+
+/// One change to a wheel's pockets, see [`Wheel::edited`]. Pockets are
+/// named by ticker or number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "edit", rename_all = "snake_case")]
+pub enum WheelEdit {
+    /// Adds a pocket after the last one, colored as its number is on a
+    /// standard wheel and in no category yet.
+    AddPocket { number: u8, ticker: String, name: String },
+    RemovePocket { ticker: String },
+    /// Gives a pocket another ticker and display name.
+    RenamePocket { ticker: String, new_ticker: String, name: String },
+    /// Puts a pocket in a category, which is new if no pocket was in it.
+    AddCategory { ticker: String, category: String },
+    /// Takes a pocket out of a category it was put in. Categories that come
+    /// from a pocket's metadata stay.
+    RemoveCategory { ticker: String, category: String },
+}
+
+impl fmt::Display for WheelEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WheelEdit::AddPocket { number, ticker, name } => write!(f, "added pocket {} {} ({})", number, ticker, name),
+            WheelEdit::RemovePocket { ticker } => write!(f, "removed pocket {}", ticker),
+            WheelEdit::RenamePocket { ticker, new_ticker, name } => write!(f, "renamed {} to {} ({})", ticker, new_ticker, name),
+            WheelEdit::AddCategory { ticker, category } => write!(f, "put {} in {}", ticker, category),
+            WheelEdit::RemoveCategory { ticker, category } => write!(f, "took {} out of {}", ticker, category),
+        }
+    }
+}
+
+/// Why a [`WheelEdit`] couldn't be made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WheelEditError {
+    UnknownPocket(String),
+    InvalidTicker(String),
+    TickerTaken(String),
+    NumberTaken(u8),
+    /// A wheel needs at least one pocket.
+    LastPocket,
+    /// The pocket and category, by name.
+    AlreadyInCategory(String, String),
+    NotInCategory(String, String),
+    /// Spins are provably fair, and the next one is committed on this wheel.
+    FairSpins,
+    /// The wheel in play can't spin an edited layout, see
+    /// [`WheelLike::with_layout`].
+    Unsupported,
+}
+
+impl fmt::Display for WheelEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WheelEditError::UnknownPocket(query) => write!(f, "no pocket matches '{}'", query),
+            WheelEditError::InvalidTicker(reason) => write!(f, "{}", reason),
+            WheelEditError::TickerTaken(ticker) => write!(f, "another pocket is already {}", ticker),
+            WheelEditError::NumberTaken(number) => write!(f, "there is already a pocket numbered {}", number),
+            WheelEditError::LastPocket => write!(f, "the wheel can't lose its last pocket"),
+            WheelEditError::AlreadyInCategory(ticker, category) => write!(f, "{} is already in {}", ticker, category),
+            WheelEditError::NotInCategory(ticker, category) => {
+                write!(f, "{} wasn't put in a category called '{}'", ticker, category)
+            }
+            WheelEditError::FairSpins => write!(f, "the next provably fair spin is already committed on this wheel"),
+            WheelEditError::Unsupported => write!(f, "this wheel can't be edited while it's in play"),
+        }
+    }
 }
 
+impl core::error::Error for WheelEditError {}
+
 // Default implementation for convenience
 impl Default for Wheel {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Anything a [`Game`](super::Game) can be played on: a layout of pockets
+/// plus a way of picking the winning one. Implement this for wheels that
+/// spin differently from a fair [`Wheel`].
+pub trait WheelLike: Send + Sync {
+    /// The pockets, categories, and payouts bets are settled against.
+    fn layout(&self) -> &Wheel;
+
+    /// Draws the winning pocket.
+    fn spin(&self, rng: &mut dyn RngCore) -> PocketId;
+
+    fn pockets(&self) -> &[Pocket] {
+        self.layout().get_all_pockets()
+    }
+
+    /// The green pockets, where every outside bet loses.
+    fn zero_pockets(&self) -> Vec<PocketId> {
+        self.layout().zero_pockets()
+    }
+
+    /// Name recorded in round history.
+    fn name(&self) -> &str {
+        self.layout().name()
+    }
+
+    /// Chance that a bet of this type wins on one spin of this wheel.
+    fn probability(&self, bet_type: &BetType) -> f64 {
+        self.layout().probability(bet_type)
+    }
+
+    /// The same kind of wheel with an [edited](Wheel::edited) layout, or
+    /// `None` if it can't take one.
+    fn with_layout(&self, layout: Wheel) -> Option<Box<dyn WheelLike>> {
+        let _ = layout;
+        None
+    }
+}
+
+impl WheelLike for Wheel {
+    fn layout(&self) -> &Wheel {
+        self
+    }
+
+    fn spin(&self, rng: &mut dyn RngCore) -> PocketId {
+        self.spin_id_with(rng)
+    }
+
+    fn with_layout(&self, layout: Wheel) -> Option<Box<dyn WheelLike>> {
+        Some(Box::new(layout))
+    }
+}
+
+/// A wheel whose pockets come up with unequal odds, e.g. to model a worn
+/// or tilted wheel.
+#[derive(Debug, Clone)]
+pub struct WeightedWheel {
+    layout: Wheel,
+    name: String,
+    weights: Vec<f64>,
+    distribution: WeightedIndex<f64>,
+}
+
+impl WeightedWheel {
+    /// Weights one pocket per entry, in pocket id order.
+    pub fn new(layout: Wheel, weights: Vec<f64>) -> Result<Self, String> {
+        if weights.len() != layout.get_all_pockets().len() {
+            return Err(format!(
+                "expected {} weights, one per pocket, got {}",
+                layout.get_all_pockets().len(),
+                weights.len()
+            ));
+        }
+        let distribution = WeightedIndex::new(&weights).map_err(|e| format!("invalid weights: {}", e))?;
+        let name = format!("{}-weighted", layout.name());
+        Ok(WeightedWheel { layout, name, weights, distribution })
+    }
+
+    /// Weights pockets by ticker; pockets not listed weigh 1.
+    pub fn from_ticker_weights(layout: Wheel, weights: &BTreeMap<String, f64>) -> Result<Self, String> {
+        for ticker in weights.keys() {
+            if layout.find_pocket(ticker).is_none() {
+                return Err(format!("unknown ticker '{}' in weights", ticker));
+            }
+        }
+        let per_pocket = layout
+            .get_all_pockets()
+            .iter()
+            .map(|p| {
+                weights
+                    .iter()
+                    .find(|(ticker, _)| ticker.eq_ignore_ascii_case(p.ticker.as_str()))
+                    .map_or(1.0, |(_, &w)| w)
+            })
+            .collect();
+        WeightedWheel::new(layout, per_pocket)
+    }
+
+    /// Weights every pocket by a field of its metadata.
+    pub fn from_metadata(layout: Wheel, weighting: MetadataWeighting) -> Result<Self, String> {
+        let per_pocket = layout.get_all_pockets().iter().map(|p| weighting.weight(&p.metadata)).collect();
+        WeightedWheel::new(layout, per_pocket)
+    }
+
+    /// Chance of each pocket coming up, in pocket id order.
+    pub fn probabilities(&self) -> Vec<f64> {
+        let total: f64 = self.weights.iter().sum();
+        self.weights.iter().map(|w| w / total).collect()
+    }
+}
+
+impl WheelLike for WeightedWheel {
+    fn layout(&self) -> &Wheel {
+        &self.layout
+    }
+
+    fn spin(&self, rng: &mut dyn RngCore) -> PocketId {
+        PocketId(self.distribution.sample(rng))
+    }
+
+    fn probability(&self, bet_type: &BetType) -> f64 {
+        let total: f64 = self.weights.iter().sum();
+        let covered: f64 = (0..self.weights.len())
+            .filter(|&i| bet_type.covers(&self.layout, PocketId(i)))
+            .map(|i| self.weights[i])
+            .sum();
+        covered / total
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Pockets keep their weights by number; new ones weigh 1.
+    fn with_layout(&self, layout: Wheel) -> Option<Box<dyn WheelLike>> {
+        let weights = layout
+            .get_all_pockets()
+            .iter()
+            .map(|pocket| self.layout.id_of_number(pocket.number).map_or(1.0, |id| self.weights[id.0]))
+            .collect();
+        Some(Box::new(WeightedWheel::new(layout, weights).ok()?))
+    }
+}
@@ -0,0 +1,241 @@
+// src/gui/board.rs
+
+//! The betting board: a cell for every bet on offer, where chips are dropped.
+//!
+//! The numbers sit in three rows as on a casino layout, with the green
+//! pockets to their left. Below them come the dozens, the even-money bets,
+//! the wheel's sector columns, and its categories.
+
+use crate::{draw_chip_sized, pocket_fill};
+use macroquad::prelude::*;
+use roulette_game::game::bets::{Bet, BetType};
+use roulette_game::game::money::Money;
+use roulette_game::game::wheel::{Color as PocketColor, Wheel};
+use roulette_game::presentation;
+use std::collections::BTreeMap;
+
+const LEFT: f32 = 640.0;
+const TOP: f32 = 70.0;
+const CELL_W: f32 = 48.0;
+const CELL_H: f32 = 54.0;
+const ROW_H: f32 = 36.0;
+const GAP: f32 = 6.0;
+/// How close to the line between two numbers a chip must land to split them.
+const SPLIT_MARGIN: f32 = 8.0;
+const BOARD_CHIP_RADIUS: f32 = 13.0;
+
+/// Where on the board a chip was dropped.
+pub struct Target {
+    pub bet_type: BetType,
+}
+
+struct Cell {
+    rect: Rect,
+    bet_type: BetType,
+    label: String,
+    /// A second line, such as the ticker under a number.
+    detail: Option<String>,
+    fill: Color,
+}
+
+pub struct Board {
+    cells: Vec<Cell>,
+    /// Index into `cells` of each number, by grid column and row.
+    grid: BTreeMap<(usize, usize), usize>,
+}
+
+impl Board {
+    pub fn new(wheel: &Wheel) -> Self {
+        let mut board = Board { cells: Vec::new(), grid: BTreeMap::new() };
+        let palette = presentation::palette();
+        let grid_left = LEFT + CELL_W;
+        let grid_columns = (wheel.highest_number() / 3) as usize;
+        let grid_width = grid_columns as f32 * CELL_W;
+
+        let zeros = wheel.zero_pockets();
+        let zero_h = CELL_H * 3.0 / zeros.len().max(1) as f32;
+        for (i, &id) in zeros.iter().enumerate() {
+            let pocket = wheel.pocket(id);
+            board.cells.push(Cell {
+                rect: Rect::new(LEFT, TOP + i as f32 * zero_h, CELL_W, zero_h),
+                bet_type: BetType::StraightUp(pocket.ticker),
+                label: palette.mark(pocket.color, &pocket.label()),
+                detail: Some(pocket.ticker.to_string()),
+                fill: pocket_fill(pocket.color),
+            });
+        }
+        for column in 0..grid_columns {
+            for row in 0..3 {
+                let number = (column * 3 + (3 - row)) as u8;
+                let Some(pocket) = wheel.get_pocket(number) else { continue };
+                board.grid.insert((column, row), board.cells.len());
+                board.cells.push(Cell {
+                    rect: Rect::new(grid_left + column as f32 * CELL_W, TOP + row as f32 * CELL_H, CELL_W, CELL_H),
+                    bet_type: BetType::StraightUp(pocket.ticker),
+                    label: palette.mark(pocket.color, &pocket.label()),
+                    detail: Some(pocket.ticker.to_string()),
+                    fill: pocket_fill(pocket.color),
+                });
+            }
+        }
+
+        let mut y = TOP + 3.0 * CELL_H + GAP;
+        let dozens = [BetType::GrowthDozen, BetType::ValueDozen, BetType::BlueChipDozen];
+        board.add_row(y, grid_left, grid_width, dozens.into_iter().map(|bet| (bet.to_string(), bet)));
+        y += ROW_H + GAP;
+        let even_money = [
+            ("Low", BetType::Low),
+            ("Even", BetType::Even),
+            ("Red", BetType::Red),
+            ("Black", BetType::Black),
+            ("Odd", BetType::Odd),
+            ("High", BetType::High),
+        ];
+        board.add_row(y, grid_left, grid_width, even_money.into_iter().map(|(label, bet)| (label.to_string(), bet)));
+        for cell in board.cells.iter_mut().rev().take(6) {
+            let color = match cell.bet_type {
+                BetType::Red => PocketColor::Red,
+                BetType::Black => PocketColor::Black,
+                _ => continue,
+            };
+            cell.fill = pocket_fill(color);
+            cell.label = palette.mark(color, &cell.label);
+        }
+        y += ROW_H + GAP;
+        let columns = (1..=3u8).filter_map(|n| wheel.column(n).map(|column| (column.name.to_string(), BetType::Column(n))));
+        board.add_row(y, grid_left, grid_width, columns);
+        y += ROW_H + GAP;
+
+        // Categories that cover a single stock are its straight-up bet by
+        // another name, so only the wider ones get a cell.
+        let categories = wheel
+            .categories()
+            .into_iter()
+            .filter(|&id| wheel.category_members(id).is_some_and(|members| members.len() > 1))
+            .filter(|&id| !wheel.zero_pockets().iter().any(|&zero| wheel.in_category(id, zero)));
+        let right = grid_left + grid_width;
+        let mut x = LEFT;
+        for id in categories {
+            let label = id.name().to_string();
+            let width = measure_text(&label, None, 16, 1.0).width + 14.0;
+            if x + width > right {
+                x = LEFT;
+                y += 28.0;
+            }
+            if y + 24.0 > 500.0 {
+                break;
+            }
+            board.cells.push(Cell {
+                rect: Rect::new(x, y, width, 24.0),
+                bet_type: BetType::Category(id),
+                label,
+                detail: None,
+                fill: Color::from_rgba(20, 80, 50, 255),
+            });
+            x += width + 4.0;
+        }
+        board
+    }
+
+    /// Lays `bets` out side by side across `width`.
+    fn add_row(&mut self, y: f32, left: f32, width: f32, bets: impl Iterator<Item = (String, BetType)>) {
+        let bets: Vec<_> = bets.collect();
+        let cell_w = width / bets.len().max(1) as f32;
+        for (i, (label, bet_type)) in bets.into_iter().enumerate() {
+            self.cells.push(Cell {
+                rect: Rect::new(left + i as f32 * cell_w, y, cell_w, ROW_H),
+                bet_type,
+                label,
+                detail: None,
+                fill: Color::from_rgba(20, 80, 50, 255),
+            });
+        }
+    }
+
+    /// The bet a chip dropped at `point` makes. Near the line between two
+    /// numbers it's a split.
+    pub fn target_at(&self, point: Vec2) -> Option<Target> {
+        if let Some(bet_type) = self.split_at(point) {
+            return Some(Target { bet_type });
+        }
+        let cell = self.cells.iter().find(|cell| cell.rect.contains(point))?;
+        Some(Target { bet_type: cell.bet_type.clone() })
+    }
+
+    fn split_at(&self, point: Vec2) -> Option<BetType> {
+        let (&(column, row), &index) = self.grid.iter().find(|&(_, &index)| self.cells[index].rect.contains(point))?;
+        let rect = self.cells[index].rect;
+        let neighbour = if point.x - rect.left() < SPLIT_MARGIN && column > 0 {
+            (column - 1, row)
+        } else if rect.right() - point.x < SPLIT_MARGIN {
+            (column + 1, row)
+        } else if point.y - rect.top() < SPLIT_MARGIN && row > 0 {
+            (column, row - 1)
+        } else if rect.bottom() - point.y < SPLIT_MARGIN {
+            (column, row + 1)
+        } else {
+            return None;
+        };
+        let other = self.grid.get(&neighbour)?;
+        match (&self.cells[index].bet_type, &self.cells[*other].bet_type) {
+            (BetType::StraightUp(a), BetType::StraightUp(b)) => Some(BetType::Split(*a, *b)),
+            _ => None,
+        }
+    }
+
+    /// Where chips on `bet_type` are stacked: the middle of its cell, or the
+    /// line between the two numbers of a split.
+    fn anchor(&self, bet_type: &BetType) -> Option<Vec2> {
+        let center_of = |bet: &BetType| self.cells.iter().find(|cell| cell.bet_type == *bet).map(|cell| cell.rect.center());
+        match bet_type {
+            BetType::Split(a, b) => {
+                let a = center_of(&BetType::StraightUp(*a))?;
+                let b = center_of(&BetType::StraightUp(*b))?;
+                Some((a + b) / 2.0)
+            }
+            other => center_of(other),
+        }
+    }
+
+    pub fn draw(&self, bets: &[Bet], spinning: bool) {
+        let hovered = (!spinning).then(|| self.target_at(Vec2::from(mouse_position()))).flatten();
+        for cell in &self.cells {
+            let rect = cell.rect;
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, cell.fill);
+            let highlight = hovered.as_ref().is_some_and(|target| target.bet_type == cell.bet_type);
+            let (thickness, edge) = if highlight { (3.0, GOLD) } else { (1.0, Color::from_rgba(220, 220, 200, 255)) };
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, thickness, edge);
+            let font_size = if cell.detail.is_some() { 20 } else { 16 };
+            let size = measure_text(&cell.label, None, font_size, 1.0);
+            let label_y = match cell.detail {
+                Some(_) => rect.y + rect.h * 0.42,
+                None => rect.center().y + size.offset_y / 2.0,
+            };
+            draw_text(&cell.label, rect.center().x - size.width / 2.0, label_y, font_size as f32, WHITE);
+            if let Some(detail) = &cell.detail {
+                let size = measure_text(detail, None, 13, 1.0);
+                draw_text(detail, rect.center().x - size.width / 2.0, rect.y + rect.h * 0.8, 13.0, LIGHTGRAY);
+            }
+        }
+        if let Some(BetType::Split(a, b)) = hovered.map(|target| target.bet_type)
+            && let Some(at) = self.anchor(&BetType::Split(a, b))
+        {
+            draw_circle_lines(at.x, at.y, BOARD_CHIP_RADIUS + 2.0, 2.0, GOLD);
+        }
+
+        // Several bets of one kind stack into one pile showing their total.
+        let mut piles: Vec<(&BetType, Money)> = Vec::new();
+        for bet in bets {
+            match piles.iter_mut().find(|(bet_type, _)| **bet_type == bet.bet_type) {
+                Some((_, total)) => *total += bet.amount,
+                None => piles.push((&bet.bet_type, bet.amount)),
+            }
+        }
+        for (bet_type, total) in piles {
+            if let Some(at) = self.anchor(bet_type) {
+                draw_circle(at.x + 2.0, at.y + 3.0, BOARD_CHIP_RADIUS, Color::from_rgba(0, 0, 0, 90));
+                draw_chip_sized(at, BOARD_CHIP_RADIUS, total, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+// src/gui/main.rs
+
+//! Wall Street Roulette in a window (requires the `gui` feature).
+//!
+//! A showcase client for the library: the wheel and betting board are drawn
+//! with macroquad, and every bet, spin, and payout goes through [`Game`]
+//! exactly as it does in the terminal game. Drag a chip from the tray onto
+//! the board, or pick a chip and click, then press Spin. Dropping a chip on
+//! the line between two numbers makes a split.
+//!
+//! ```text
+//! roulette_gui [--wheel european|american|mini] [--balance <AMOUNT>]
+//!              [--palette classic|color_blind|high_contrast] [--color-symbols]
+//! ```
+
+mod board;
+mod wheel;
+
+use board::{Board, Target};
+use macroquad::prelude::*;
+use roulette_game::console;
+use roulette_game::game::Game;
+use roulette_game::game::bets::Bet;
+use roulette_game::game::currency::Currency;
+use roulette_game::game::events::{EventSink, GameEvent};
+use roulette_game::game::money::Money;
+use roulette_game::game::wheel::{Color as PocketColor, Wheel};
+use roulette_game::presentation::{self, Palette, Scheme};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wheel::WheelView;
+
+const DEFAULT_BALANCE: Money = Money::from_units(1_000);
+/// Chips offered when the table doesn't set its own.
+const DEFAULT_CHIPS: [u32; 5] = [1, 5, 25, 100, 500];
+/// Most lines kept in the round log.
+const LOG_LINES: usize = 9;
+
+fn window_conf() -> Conf {
+    Conf {
+        window_title: "Wall Street Roulette".to_string(),
+        window_width: 1280,
+        window_height: 800,
+        window_resizable: false,
+        ..Default::default()
+    }
+}
+
+/// Keeps the game's events so the window can report how each bet settled.
+#[derive(Default)]
+struct EventLog {
+    events: Vec<GameEvent>,
+}
+
+impl EventSink for EventLog {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        self.events.push(event.clone());
+    }
+}
+
+/// A chip being dragged from the tray.
+struct Drag {
+    value: u32,
+}
+
+struct App {
+    game: Game,
+    board: Board,
+    view: WheelView,
+    events: Rc<RefCell<EventLog>>,
+    chips: Vec<u32>,
+    selected_chip: u32,
+    drag: Option<Drag>,
+    /// The balance on show, which holds back a spin's winnings until the ball lands.
+    shown_balance: Money,
+    status: String,
+    log: Vec<String>,
+    /// Log lines waiting for the ball to land.
+    pending_log: Vec<String>,
+}
+
+impl App {
+    fn new(wheel: Wheel, balance: Money) -> Self {
+        let board = Board::new(&wheel);
+        let view = WheelView::new(&wheel);
+        let mut game = Game::with_wheel(balance, Box::new(wheel));
+        let events = Rc::new(RefCell::new(EventLog::default()));
+        game.add_event_sink(Box::new(events.clone()));
+        let chips = game.rules().chips.as_ref().map_or(DEFAULT_CHIPS.to_vec(), |set| set.denominations().to_vec());
+        let selected_chip = chips[0];
+        App {
+            shown_balance: game.get_player_balance(),
+            game,
+            board,
+            view,
+            events,
+            chips,
+            selected_chip,
+            drag: None,
+            status: "Drag a chip onto the board, then press Spin.".to_string(),
+            log: Vec::new(),
+            pending_log: Vec::new(),
+        }
+    }
+
+    fn update(&mut self) {
+        let dt = get_frame_time();
+        if self.view.update(dt) {
+            self.land();
+        }
+        let spinning = self.view.is_spinning();
+        let mouse = Vec2::from(mouse_position());
+
+        if is_mouse_button_pressed(MouseButton::Left) {
+            if let Some(value) = self.chip_at(mouse) {
+                self.selected_chip = value;
+                self.drag = Some(Drag { value });
+            } else if !spinning {
+                match self.button_at(mouse) {
+                    Some(Button::Spin) => self.spin(),
+                    Some(Button::Clear) => self.clear(),
+                    Some(Button::Rebet) => self.rebet(),
+                    None => {}
+                }
+            }
+        }
+        if is_mouse_button_released(MouseButton::Left) {
+            let dragged = self.drag.take();
+            if !spinning && self.chip_at(mouse).is_none() {
+                let value = dragged.map_or(self.selected_chip, |drag| drag.value);
+                if let Some(target) = self.board.target_at(mouse) {
+                    self.place(target, value);
+                }
+            }
+        }
+    }
+
+    fn place(&mut self, target: Target, value: u32) {
+        let placed = console::quietly(|| self.game.place_bet(Bet::new(target.bet_type.clone(), value)));
+        let chip = self.game.rules().currency.amount(value);
+        if placed {
+            self.shown_balance = self.game.get_player_balance();
+            self.status = format!("{} on {}.", chip, target.bet_type);
+        } else if Money::from(value) > self.game.get_player_balance() {
+            self.status = format!("Not enough left for a {} chip.", chip);
+        } else {
+            self.status = format!("The table won't take {} on {}.", chip, target.bet_type);
+        }
+    }
+
+    fn clear(&mut self) {
+        if self.game.get_current_bets().is_empty() {
+            return;
+        }
+        console::quietly(|| self.game.clear_bets());
+        self.shown_balance = self.game.get_player_balance();
+        self.events.borrow_mut().events.clear();
+        self.status = "Bets taken back.".to_string();
+    }
+
+    fn rebet(&mut self) {
+        match console::quietly(|| self.game.rebet()) {
+            Ok(placed) => {
+                self.shown_balance = self.game.get_player_balance();
+                self.status = format!("{} bet(s) placed again.", placed);
+            }
+            Err(e) => self.status = format!("Can't rebet: {}.", e),
+        }
+        self.events.borrow_mut().events.clear();
+    }
+
+    /// Settles the round at once and sets the ball rolling towards the
+    /// pocket it landed on; the result shows once the animation ends.
+    fn spin(&mut self) {
+        if !self.game.has_bets_in_play() {
+            self.status = "Place a bet first.".to_string();
+            return;
+        }
+        self.events.borrow_mut().events.clear();
+        console::quietly(|| self.game.spin_wheel_and_resolve());
+        let Some((pocket, _)) = self.game.last_outcome() else {
+            return;
+        };
+        let events = std::mem::take(&mut self.events.borrow_mut().events);
+        let currency = &self.game.rules().currency;
+        self.pending_log = events.iter().filter_map(|event| describe(event, currency)).collect();
+        self.view.spin_to(pocket);
+        self.status = "No more bets!".to_string();
+    }
+
+    fn land(&mut self) {
+        self.shown_balance = self.game.get_player_balance();
+        if let Some((pocket, returned)) = self.game.last_outcome() {
+            let pocket = self.game.wheel().pocket(pocket);
+            self.status = match returned {
+                Money::ZERO => format!("{} ({}). No win this time.", pocket.ticker, pocket.display_name),
+                _ => format!("{} ({}). {} back!", pocket.ticker, pocket.display_name, self.game.rules().currency.amount(returned)),
+            };
+        }
+        self.log.append(&mut self.pending_log);
+        let excess = self.log.len().saturating_sub(LOG_LINES);
+        self.log.drain(..excess);
+    }
+
+    fn chip_at(&self, point: Vec2) -> Option<u32> {
+        self.chips.iter().enumerate().find_map(|(i, &value)| (chip_center(i).distance(point) <= CHIP_RADIUS).then_some(value))
+    }
+
+    fn button_at(&self, point: Vec2) -> Option<Button> {
+        Button::ALL.into_iter().find(|button| button.rect().contains(point))
+    }
+
+    fn draw(&self) {
+        clear_background(Color::from_rgba(12, 60, 36, 255));
+        draw_text("Wall Street Roulette", 24.0, 40.0, 36.0, GOLD);
+        let currency = &self.game.rules().currency;
+        draw_text(&format!("Balance: {}", currency.amount(self.shown_balance)), 640.0, 40.0, 30.0, WHITE);
+        let staked: Money = self.game.get_current_bets().iter().map(|bet| bet.amount).sum();
+        if !staked.is_zero() {
+            draw_text(&format!("On the table: {}", currency.amount(staked)), 920.0, 40.0, 24.0, LIGHTGRAY);
+        }
+
+        self.view.draw(self.game.wheel());
+        self.board.draw(self.game.get_current_bets(), self.view.is_spinning());
+
+        for (i, &value) in self.chips.iter().enumerate() {
+            draw_chip(chip_center(i), value, value == self.selected_chip);
+        }
+        for button in Button::ALL {
+            let rect = button.rect();
+            let fill = if self.view.is_spinning() { DARKGRAY } else { Color::from_rgba(120, 30, 30, 255) };
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+            draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 2.0, GOLD);
+            let size = measure_text(button.label(), None, 26, 1.0);
+            draw_text(button.label(), rect.center().x - size.width / 2.0, rect.center().y + 8.0, 26.0, WHITE);
+        }
+
+        draw_text(&self.status, 24.0, 770.0, 24.0, WHITE);
+        for (i, line) in self.log.iter().enumerate() {
+            draw_text(line, 900.0, 610.0 + i as f32 * 20.0, 18.0, LIGHTGRAY);
+        }
+
+        if let Some(drag) = &self.drag {
+            draw_chip(Vec2::from(mouse_position()), drag.value, true);
+        }
+    }
+}
+
+/// One line of the round log, for the events worth reporting, with amounts
+/// in `currency`.
+fn describe(event: &GameEvent, currency: &Currency) -> Option<String> {
+    match event {
+        GameEvent::Spin { ticker, number, .. } => Some(format!("Ball lands on {} ({})", ticker, number)),
+        GameEvent::Settlement { bet_type, won: true, payout, .. } => Some(format!("  {}: paid {}", bet_type, currency.amount(*payout))),
+        GameEvent::Settlement { bet_type, payout: Money::ZERO, .. } => Some(format!("  {}: lost", bet_type)),
+        GameEvent::Settlement { bet_type, payout, .. } => Some(format!("  {}: {} back", bet_type, currency.amount(*payout))),
+        GameEvent::RoundComplete { wagered, returned, .. } => {
+            Some(format!("Wagered {}, returned {}", currency.amount(*wagered), currency.amount(*returned)))
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Button {
+    Spin,
+    Clear,
+    Rebet,
+}
+
+impl Button {
+    const ALL: [Button; 3] = [Button::Spin, Button::Clear, Button::Rebet];
+
+    fn label(self) -> &'static str {
+        match self {
+            Button::Spin => "Spin",
+            Button::Clear => "Clear",
+            Button::Rebet => "Rebet",
+        }
+    }
+
+    fn rect(self) -> Rect {
+        let index = Button::ALL.iter().position(|&b| b == self).unwrap_or(0);
+        Rect::new(930.0 + index as f32 * 112.0, 532.0, 100.0, 48.0)
+    }
+}
+
+const CHIP_RADIUS: f32 = 24.0;
+
+fn chip_center(index: usize) -> Vec2 {
+    vec2(660.0 + index as f32 * 56.0, 556.0)
+}
+
+/// The colour of a chip worth `value`, as on a casino table.
+fn chip_color(value: u32) -> Color {
+    match value {
+        0..=1 => Color::from_rgba(235, 235, 235, 255),
+        2..=5 => Color::from_rgba(200, 30, 30, 255),
+        6..=25 => Color::from_rgba(30, 140, 60, 255),
+        26..=100 => Color::from_rgba(25, 25, 25, 255),
+        101..=500 => Color::from_rgba(110, 40, 150, 255),
+        _ => Color::from_rgba(230, 130, 20, 255),
+    }
+}
+
+pub(crate) fn draw_chip(center: Vec2, value: u32, highlighted: bool) {
+    draw_chip_sized(center, CHIP_RADIUS, Money::from(value), highlighted);
+}
+
+/// A chip worth `value`; a pile of bets with cents shows them on its face.
+pub(crate) fn draw_chip_sized(center: Vec2, radius: f32, value: Money, highlighted: bool) {
+    let fill = chip_color(value.round_up());
+    draw_circle(center.x, center.y, radius, fill);
+    draw_circle_lines(center.x, center.y, radius * 0.75, 2.0, WHITE);
+    if highlighted {
+        draw_circle_lines(center.x, center.y, radius + 3.0, 3.0, GOLD);
+    }
+    let text = match value.whole() {
+        Some(units) => units.to_string(),
+        None => format!("{:.2}", value.as_f64()),
+    };
+    let font_size = (radius * 0.8) as u16;
+    let size = measure_text(&text, None, font_size, 1.0);
+    let ink = if value <= Money::from_units(1) { BLACK } else { WHITE };
+    draw_text(&text, center.x - size.width / 2.0, center.y + size.offset_y / 2.0, font_size as f32, ink);
+}
+
+/// A pocket's colour as the palette paints it.
+pub(crate) fn pocket_fill(color: PocketColor) -> Color {
+    let presentation::Rgb(r, g, b) = presentation::palette().rgb(color);
+    Color::from_rgba(r, g, b, 255)
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: roulette_gui [--wheel european|american|mini] [--balance <AMOUNT>] [--palette classic|color_blind|high_contrast] [--color-symbols]"
+    );
+    std::process::exit(2);
+}
+
+fn parse_args() -> (Wheel, Money) {
+    let mut wheel = Wheel::european();
+    let mut balance = DEFAULT_BALANCE;
+    let mut palette = Palette::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--wheel" => {
+                wheel = match args.next().as_deref() {
+                    Some("european") => Wheel::european(),
+                    Some("american") => Wheel::american(),
+                    Some("mini") => Wheel::mini(),
+                    _ => usage(),
+                }
+            }
+            "--balance" => {
+                balance = args.next().and_then(|amount| amount.parse().ok()).filter(|amount: &Money| !amount.is_zero()).unwrap_or_else(|| usage())
+            }
+            "--palette" => palette.scheme = args.next().as_deref().and_then(Scheme::parse).unwrap_or_else(|| usage()),
+            "--color-symbols" => palette.symbols = true,
+            _ => usage(),
+        }
+    }
+    presentation::set_palette(palette);
+    (wheel, balance)
+}
+
+#[macroquad::main(window_conf)]
+async fn main() {
+    let (wheel, balance) = parse_args();
+    let mut app = App::new(wheel, balance);
+    loop {
+        app.update();
+        app.draw();
+        next_frame().await;
+    }
+}
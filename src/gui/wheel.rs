@@ -0,0 +1,157 @@
+// src/gui/wheel.rs
+
+//! The spinning wheel.
+//!
+//! The game settles a spin before the ball is drawn moving, so the animation
+//! knows where it ends: the wheel turns one way and the ball the other, both
+//! slowing down, and the ball drops into the winning pocket as they stop.
+
+use crate::pocket_fill;
+use macroquad::prelude::*;
+use roulette_game::game::wheel::{PocketId, Wheel};
+use roulette_game::presentation;
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+const CENTER: Vec2 = vec2(320.0, 410.0);
+const BOWL_RADIUS: f32 = 300.0;
+const RIM_RADIUS: f32 = 282.0;
+const POCKET_OUTER: f32 = 262.0;
+const POCKET_INNER: f32 = 180.0;
+const HUB_RADIUS: f32 = 90.0;
+/// Where the ball rests in a pocket.
+const BALL_REST: f32 = 245.0;
+const BALL_RADIUS: f32 = 8.0;
+/// Seconds from the throw until the ball settles.
+const SPIN_SECONDS: f32 = 5.0;
+/// Share of the spin the ball spends on the rim before it drops.
+const ON_RIM: f32 = 0.7;
+/// Turns of the wheel while idle, per second.
+const IDLE_TURNS: f32 = 0.02;
+
+struct Throw {
+    elapsed: f32,
+    wheel_from: f32,
+    wheel_to: f32,
+    ball_from: f32,
+    ball_to: f32,
+}
+
+pub struct WheelView {
+    /// Angle of pocket 0's leading edge, in radians.
+    rotation: f32,
+    /// The ball's angle around the wheel.
+    ball: f32,
+    /// The pocket the ball sits in, once one has been spun.
+    resting: Option<usize>,
+    throw: Option<Throw>,
+    pockets: usize,
+}
+
+impl WheelView {
+    pub fn new(wheel: &Wheel) -> Self {
+        WheelView { rotation: -FRAC_PI_2, ball: -FRAC_PI_2, resting: None, throw: None, pockets: wheel.get_all_pockets().len() }
+    }
+
+    fn segment(&self) -> f32 {
+        TAU / self.pockets as f32
+    }
+
+    pub fn is_spinning(&self) -> bool {
+        self.throw.is_some()
+    }
+
+    /// Throws the ball so it comes to rest in `pocket`.
+    pub fn spin_to(&mut self, pocket: PocketId) {
+        let wheel_to = self.rotation + 2.0 * TAU + rand::gen_range(0.0, TAU);
+        let landing = wheel_to + (pocket.0 as f32 + 0.5) * self.segment();
+        // The ball runs the other way, at least five turns.
+        let turns = ((landing - self.ball + 5.0 * TAU) / TAU).ceil();
+        self.throw = Some(Throw {
+            elapsed: 0.0,
+            wheel_from: self.rotation,
+            wheel_to,
+            ball_from: self.ball,
+            ball_to: landing - turns * TAU,
+        });
+        self.resting = Some(pocket.0);
+    }
+
+    /// Moves the animation on by `dt` seconds. True on the frame the ball settles.
+    pub fn update(&mut self, dt: f32) -> bool {
+        let Some(throw) = &mut self.throw else {
+            self.rotation += IDLE_TURNS * TAU * dt;
+            if let Some(pocket) = self.resting {
+                self.ball = self.rotation + (pocket as f32 + 0.5) * self.segment();
+            }
+            return false;
+        };
+        throw.elapsed = (throw.elapsed + dt).min(SPIN_SECONDS);
+        let t = ease_out(throw.elapsed / SPIN_SECONDS);
+        self.rotation = throw.wheel_from + (throw.wheel_to - throw.wheel_from) * t;
+        self.ball = throw.ball_from + (throw.ball_to - throw.ball_from) * t;
+        if throw.elapsed >= SPIN_SECONDS {
+            self.throw = None;
+            return true;
+        }
+        false
+    }
+
+    /// How far from the centre the ball is: on the rim, then dropping in.
+    fn ball_radius(&self) -> f32 {
+        match &self.throw {
+            Some(throw) => {
+                let progress = throw.elapsed / SPIN_SECONDS;
+                if progress < ON_RIM {
+                    RIM_RADIUS - BALL_RADIUS
+                } else {
+                    let drop = (progress - ON_RIM) / (1.0 - ON_RIM);
+                    RIM_RADIUS - BALL_RADIUS + (BALL_REST - RIM_RADIUS + BALL_RADIUS) * drop
+                }
+            }
+            None => BALL_REST,
+        }
+    }
+
+    pub fn draw(&self, wheel: &Wheel) {
+        draw_circle(CENTER.x, CENTER.y, BOWL_RADIUS, Color::from_rgba(90, 50, 20, 255));
+        draw_circle(CENTER.x, CENTER.y, RIM_RADIUS, Color::from_rgba(60, 32, 12, 255));
+        let segment = self.segment();
+        let palette = presentation::palette();
+        for (i, pocket) in wheel.get_all_pockets().iter().enumerate() {
+            let start = self.rotation + i as f32 * segment;
+            let end = start + segment;
+            let fill = pocket_fill(pocket.color);
+            let corner = |radius: f32, angle: f32| CENTER + Vec2::from_angle(angle) * radius;
+            let (a, b) = (corner(POCKET_INNER, start), corner(POCKET_OUTER, start));
+            let (c, d) = (corner(POCKET_OUTER, end), corner(POCKET_INNER, end));
+            draw_triangle(a, b, c, fill);
+            draw_triangle(a, c, d, fill);
+            draw_line(a.x, a.y, b.x, b.y, 1.0, GOLD);
+
+            // Labels read outwards along the middle of the pocket.
+            let middle = start + segment / 2.0;
+            let params = |font_size: u16| TextParams { font_size, rotation: middle, color: WHITE, ..Default::default() };
+            let at = corner(POCKET_INNER + 8.0, middle - segment * 0.15);
+            draw_text_ex(pocket.ticker.as_str(), at.x, at.y, params(14));
+            let at = corner(POCKET_OUTER - 22.0, middle - segment * 0.15);
+            draw_text_ex(&palette.mark(pocket.color, &pocket.label()), at.x, at.y, params(14));
+        }
+        draw_circle_lines(CENTER.x, CENTER.y, POCKET_OUTER, 2.0, GOLD);
+        draw_circle(CENTER.x, CENTER.y, POCKET_INNER, Color::from_rgba(110, 65, 25, 255));
+        draw_circle(CENTER.x, CENTER.y, HUB_RADIUS, Color::from_rgba(200, 160, 60, 255));
+        for spoke in 0..4 {
+            let angle = self.rotation + spoke as f32 * FRAC_PI_2;
+            let tip = CENTER + Vec2::from_angle(angle) * (HUB_RADIUS + 40.0);
+            draw_line(CENTER.x, CENTER.y, tip.x, tip.y, 6.0, Color::from_rgba(220, 190, 90, 255));
+        }
+
+        let ball = CENTER + Vec2::from_angle(self.ball) * self.ball_radius();
+        draw_circle(ball.x + 2.0, ball.y + 2.0, BALL_RADIUS, Color::from_rgba(0, 0, 0, 90));
+        draw_circle(ball.x, ball.y, BALL_RADIUS, Color::from_rgba(245, 245, 240, 255));
+    }
+}
+
+/// Fast at first, coasting to a stop.
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
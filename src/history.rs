@@ -0,0 +1,737 @@
+// src/history.rs
+
+//! Round history: records every settled round and aggregates it across sessions.
+//! [`History::query`] picks out rounds and bets and sums them up,
+//! [`SessionReport`] sets sessions side by side, and [`hand`] writes rounds
+//! out as hand histories.
+
+mod compare;
+pub mod hand;
+mod query;
+
+pub use compare::SessionReport;
+pub use query::{Filter, GroupBy, History, OUTCOMES, Query, format_date, parse_date};
+
+use crate::game::bets::BetType;
+use crate::game::currency::{Currency, ExchangeError, ExchangeRates};
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use crate::game::rules::Difficulty;
+use crate::game::wheel::{Color, Wheel};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One bet as it was settled.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BetRecord {
+    pub bet_type: BetType,
+    pub amount: Money,
+    /// Total returned to the player, including the stake (0 for a loss).
+    pub payout: Money,
+    /// The player's note on why they made the bet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl BetRecord {
+    /// How the bet came out: `win`, `loss`, `push`, `surrender` (part of the
+    /// stake back), or `released` (a stake held in prison handed back).
+    pub fn outcome(&self) -> &'static str {
+        match self.payout {
+            payout if payout.is_zero() => "loss",
+            _ if self.amount.is_zero() => "released",
+            payout if payout > self.amount => "win",
+            payout if payout == self.amount => "push",
+            _ => "surrender",
+        }
+    }
+}
+
+/// Everything that happened in one round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundRecord {
+    pub session: String,
+    pub round: u32,
+    /// Seconds since the Unix epoch when the round was settled.
+    pub timestamp: u64,
+    /// Which wheel the round was played on.
+    pub wheel: String,
+    /// Who chose the bets (`manual` for interactive play).
+    pub strategy: String,
+    /// The profile the round was played under, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    pub number: u8,
+    pub ticker: String,
+    pub color: Color,
+    /// The seed the spin was drawn from, when the game drew it itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    pub bets: Vec<BetRecord>,
+    pub balance_after: Money,
+    /// The currency the table played in; rounds recorded before tables had
+    /// one were played in dollars.
+    #[serde(default, skip_serializing_if = "Currency::is_usd")]
+    pub currency: Currency,
+    /// The round whose outside bets this re-spin settled, when the
+    /// green-pocket rule carried them here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respin_of: Option<u32>,
+    /// Marks a later entry recording that this round's spin was voided. The
+    /// original entry stays in the file; both are left out of statistics.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub voided: bool,
+}
+
+impl RoundRecord {
+    pub fn wagered(&self) -> Money {
+        self.bets.iter().map(|b| b.amount).sum()
+    }
+
+    pub fn returned(&self) -> Money {
+        self.bets.iter().map(|b| b.payout).sum()
+    }
+
+    /// The record with every amount converted into `currency`, rounded down
+    /// to the cent, or `None` if `rates` has no rate for the record's own.
+    pub fn in_currency(&self, currency: &Currency, rates: &ExchangeRates) -> Option<RoundRecord> {
+        if &self.currency == currency {
+            return Some(self.clone());
+        }
+        let convert = |amount: Money| match rates.convert(amount, &self.currency, currency) {
+            Ok(converted) => Some(converted),
+            Err(ExchangeError::TooSmall { .. }) => Some(Money::ZERO),
+            Err(_) => None,
+        };
+        let mut record = self.clone();
+        for bet in &mut record.bets {
+            bet.amount = convert(bet.amount)?;
+            bet.payout = convert(bet.payout)?;
+        }
+        record.balance_after = convert(record.balance_after)?;
+        record.currency = currency.clone();
+        Some(record)
+    }
+}
+
+/// The records in one currency, so rounds from tables in different
+/// currencies can be added up, and how many were left out for want of a
+/// rate in `rates`.
+pub fn in_currency(records: &[RoundRecord], currency: &Currency, rates: &ExchangeRates) -> (Vec<RoundRecord>, usize) {
+    let converted: Vec<RoundRecord> = records.iter().filter_map(|record| record.in_currency(currency, rates)).collect();
+    let left_out = records.len() - converted.len();
+    (converted, left_out)
+}
+
+/// Append-only store of round records, one JSON object per line.
+#[derive(Debug, Clone)]
+pub struct RoundStore {
+    path: PathBuf,
+}
+
+impl RoundStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        RoundStore { path: path.into() }
+    }
+
+    /// The default location: `rounds.ndjson` inside the [data directory](crate::data_dir).
+    pub fn default_location() -> Self {
+        RoundStore::new(crate::data_dir().join("rounds.ndjson"))
+    }
+
+    /// Where practice rounds go, kept apart so they never mix with real play.
+    pub fn practice_location() -> Self {
+        RoundStore::new(crate::data_dir().join("practice-rounds.ndjson"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, record: &RoundRecord) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let line = serde_json::to_string(record).map_err(io::Error::other)?;
+        writeln!(file, "{}", line)
+    }
+
+    /// Reads every stored round. Lines that fail to parse are skipped.
+    pub fn load_all(&self) -> io::Result<Vec<RoundRecord>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(record) = serde_json::from_str(&line?) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Returns an identifier unique to this process run.
+pub fn new_session_id() -> String {
+    format!("{}-{}", unix_now(), std::process::id())
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// An [`EventSink`] that assembles each round's events into a [`RoundRecord`]
+/// and appends it to a [`RoundStore`].
+pub struct HistoryRecorder {
+    store: RoundStore,
+    session: String,
+    wheel: String,
+    strategy: String,
+    profile: Option<String>,
+    currency: Currency,
+    pending: Option<RoundRecord>,
+    /// Seed of the spin about to be reported.
+    seed: Option<String>,
+    /// The round the spin about to be reported re-spins for.
+    respin_of: Option<u32>,
+    /// The last round written, in case its spin is voided.
+    last: Option<RoundRecord>,
+}
+
+impl HistoryRecorder {
+    pub fn new(store: RoundStore, wheel: &str, strategy: &str) -> Self {
+        HistoryRecorder {
+            store,
+            session: new_session_id(),
+            wheel: wheel.to_string(),
+            strategy: strategy.to_string(),
+            profile: None,
+            currency: Currency::usd(),
+            pending: None,
+            seed: None,
+            respin_of: None,
+            last: None,
+        }
+    }
+
+    /// Marks every round as played under `profile`.
+    pub fn with_profile(mut self, profile: &str) -> Self {
+        self.profile = Some(profile.to_string());
+        self
+    }
+
+    /// Marks every round as played in `currency`; dollars unless set.
+    pub fn with_currency(mut self, currency: &Currency) -> Self {
+        self.currency = currency.clone();
+        self
+    }
+}
+
+impl EventSink for HistoryRecorder {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::Spin { number, ticker, color, .. } => {
+                self.pending = Some(RoundRecord {
+                    session: self.session.clone(),
+                    round,
+                    timestamp: unix_now(),
+                    wheel: self.wheel.clone(),
+                    strategy: self.strategy.clone(),
+                    profile: self.profile.clone(),
+                    number: *number,
+                    ticker: ticker.clone(),
+                    color: *color,
+                    seed: self.seed.take(),
+                    bets: Vec::new(),
+                    balance_after: Money::ZERO,
+                    currency: self.currency.clone(),
+                    respin_of: self.respin_of.take(),
+                    voided: false,
+                });
+            }
+            GameEvent::SpinSeeded { seed } => self.seed = Some(seed.clone()),
+            GameEvent::Respin { of_round } => self.respin_of = Some(*of_round),
+            GameEvent::WheelChanged { wheel } => self.wheel = wheel.clone(),
+            GameEvent::Settlement { bet_type, amount, payout, note, .. } => {
+                if let Some(record) = self.pending.as_mut() {
+                    record.bets.push(BetRecord {
+                        bet_type: bet_type.clone(),
+                        amount: *amount,
+                        payout: *payout,
+                        note: note.clone(),
+                    });
+                }
+            }
+            GameEvent::RoundComplete { balance, .. } => {
+                if let Some(mut record) = self.pending.take() {
+                    record.balance_after = *balance;
+                    if let Err(e) = self.store.append(&record) {
+                        crate::console::warn(format_args!("Could not record round history: {}", e));
+                    }
+                    self.last = Some(record);
+                }
+            }
+            GameEvent::SpinVoided { voided_round, .. } => {
+                if let Some(mut record) = self.last.take().filter(|r| r.round == *voided_round) {
+                    record.voided = true;
+                    record.timestamp = unix_now();
+                    if let Err(e) = self.store.append(&record) {
+                        crate::console::warn(format_args!("Could not record voided round: {}", e));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Wagered/returned totals for one group of bets or rounds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub count: u32,
+    pub wagered: Money,
+    pub returned: Money,
+}
+
+impl Totals {
+    pub fn net(&self) -> Money {
+        self.returned - self.wagered
+    }
+
+    /// Return on investment as a percentage of the amount wagered.
+    pub fn roi_percent(&self) -> f64 {
+        if self.wagered.is_zero() {
+            0.0
+        } else {
+            self.net().as_f64() / self.wagered.as_f64() * 100.0
+        }
+    }
+
+    fn add(&mut self, wagered: Money, returned: Money) {
+        self.count += 1;
+        self.wagered += wagered;
+        self.returned += returned;
+    }
+}
+
+/// Performance aggregated over every recorded round.
+#[derive(Debug, Clone, Default)]
+pub struct AllTimeStats {
+    pub sessions: usize,
+    pub rounds: Totals,
+    pub by_bet_type: BTreeMap<String, Totals>,
+    pub by_wheel: BTreeMap<String, Totals>,
+    pub by_strategy: BTreeMap<String, Totals>,
+}
+
+impl AllTimeStats {
+    /// Aggregates the records, leaving out rounds whose spin was voided.
+    /// They must all be in one currency; see [`in_currency`].
+    pub fn from_records(records: &[RoundRecord]) -> Self {
+        let sessions: std::collections::HashSet<&str> = records.iter().map(|r| r.session.as_str()).collect();
+        AllTimeStats { sessions: sessions.len(), ..AllTimeStats::from_query(&Query::new(records)) }
+    }
+
+    /// Aggregates what `query` matches.
+    pub fn from_query(query: &Query) -> Self {
+        AllTimeStats {
+            sessions: query.sessions(),
+            rounds: query.round_totals(),
+            by_bet_type: query.group_by(GroupBy::BetType),
+            by_wheel: query.group_by(GroupBy::Wheel),
+            by_strategy: query.group_by(GroupBy::Strategy),
+        }
+    }
+}
+
+/// Version of the [`StatsExport`] layout; bumped whenever a field changes
+/// meaning or goes away.
+pub const STATS_SCHEMA_VERSION: u32 = 1;
+
+/// Statistics over every recorded round in a stable JSON layout for
+/// dashboards. Voided spins are left out of everything but `sessions`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsExport {
+    pub schema_version: u32,
+    /// The currency every amount is in.
+    pub currency: Currency,
+    pub sessions: usize,
+    pub rounds: TotalsExport,
+    pub by_bet_type: BTreeMap<String, TotalsExport>,
+    pub by_wheel: BTreeMap<String, TotalsExport>,
+    pub by_strategy: BTreeMap<String, TotalsExport>,
+    /// Times each ticker came up.
+    pub pocket_frequencies: BTreeMap<String, u64>,
+    pub color_frequencies: BTreeMap<String, u64>,
+    pub streaks: Streaks,
+    /// Balance after every round, oldest first.
+    pub balance_series: Vec<BalancePoint>,
+}
+
+/// [`Totals`] with the derived figures spelled out.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct TotalsExport {
+    pub count: u32,
+    pub wagered: Money,
+    pub returned: Money,
+    pub net: Money,
+    pub roi_percent: f64,
+}
+
+impl From<&Totals> for TotalsExport {
+    fn from(totals: &Totals) -> Self {
+        TotalsExport {
+            count: totals.count,
+            wagered: totals.wagered,
+            returned: totals.returned,
+            net: totals.net(),
+            roi_percent: totals.roi_percent(),
+        }
+    }
+}
+
+/// Longest runs of rounds within a session. A round that breaks even ends
+/// both winning and losing runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Streaks {
+    pub longest_winning: u32,
+    pub longest_losing: u32,
+    /// Longest run of spins landing on the same color.
+    pub longest_same_color: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BalancePoint {
+    pub session: String,
+    pub round: u32,
+    pub balance: Money,
+}
+
+impl StatsExport {
+    /// Statistics over `records`, which must all be in `currency`; see
+    /// [`in_currency`].
+    pub fn from_records(records: &[RoundRecord], currency: &Currency) -> Self {
+        let stats = AllTimeStats::from_records(records);
+        let export_all =
+            |groups: &BTreeMap<String, Totals>| groups.iter().map(|(name, t)| (name.clone(), t.into())).collect();
+        let mut export = StatsExport {
+            schema_version: STATS_SCHEMA_VERSION,
+            currency: currency.clone(),
+            sessions: stats.sessions,
+            rounds: (&stats.rounds).into(),
+            by_bet_type: export_all(&stats.by_bet_type),
+            by_wheel: export_all(&stats.by_wheel),
+            by_strategy: export_all(&stats.by_strategy),
+            pocket_frequencies: BTreeMap::new(),
+            color_frequencies: BTreeMap::new(),
+            streaks: Streaks::default(),
+            balance_series: Vec::new(),
+        };
+
+        // (session, current winning run, losing run, color run, last color)
+        let mut run: Option<(&str, u32, u32, u32, Color)> = None;
+        for record in without_voided(records) {
+            *export.pocket_frequencies.entry(record.ticker.clone()).or_default() += 1;
+            *export.color_frequencies.entry(record.color.to_string()).or_default() += 1;
+            export.balance_series.push(BalancePoint {
+                session: record.session.clone(),
+                round: record.round,
+                balance: record.balance_after,
+            });
+
+            let (mut wins, mut losses, mut colors) = match run {
+                Some((session, wins, losses, colors, color)) if session == record.session => {
+                    (wins, losses, if color == record.color { colors } else { 0 })
+                }
+                _ => (0, 0, 0),
+            };
+            match record.returned().cmp(&record.wagered()) {
+                std::cmp::Ordering::Greater => (wins, losses) = (wins + 1, 0),
+                std::cmp::Ordering::Less => (wins, losses) = (0, losses + 1),
+                std::cmp::Ordering::Equal => (wins, losses) = (0, 0),
+            }
+            colors += 1;
+            let streaks = &mut export.streaks;
+            streaks.longest_winning = streaks.longest_winning.max(wins);
+            streaks.longest_losing = streaks.longest_losing.max(losses);
+            streaks.longest_same_color = streaks.longest_same_color.max(colors);
+            run = Some((record.session.as_str(), wins, losses, colors, record.color));
+        }
+        export
+    }
+}
+
+/// The records whose spins stood: both entries for a voided spin are left out.
+pub fn without_voided(records: &[RoundRecord]) -> impl Iterator<Item = &RoundRecord> {
+    let voided: std::collections::HashSet<(&str, u32)> =
+        records.iter().filter(|r| r.voided).map(|r| (r.session.as_str(), r.round)).collect();
+    records.iter().filter(move |r| !voided.contains(&(r.session.as_str(), r.round)))
+}
+
+/// The records from `wheel`, or a biased or training variant of it, whose
+/// spins stood.
+pub fn spins_on_wheel<'a>(records: &'a [RoundRecord], wheel: &str) -> impl Iterator<Item = &'a RoundRecord> {
+    let variant = format!("{}-", wheel);
+    let wheel = wheel.to_string();
+    without_voided(records).filter(move |r| r.wheel == wheel || r.wheel.starts_with(&variant))
+}
+
+/// How long a pocket or category has gone without coming up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drought {
+    pub label: String,
+    /// Spins since it last hit, or `None` if it never has.
+    pub spins_since: Option<u64>,
+    /// Average spins between hits on a fair wheel.
+    pub expected_gap: f64,
+}
+
+/// Droughts for every pocket and every category of more than one pocket,
+/// longest first, from the recorded spins on `wheel`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Droughts {
+    pub spins: u64,
+    pub pockets: Vec<Drought>,
+    pub categories: Vec<Drought>,
+}
+
+impl Droughts {
+    pub fn from_records(records: &[RoundRecord], wheel: &Wheel) -> Self {
+        // Index of the most recent spin on each pocket.
+        let mut last_hit: Vec<Option<u64>> = vec![None; wheel.get_all_pockets().len()];
+        let mut spins = 0;
+        for record in spins_on_wheel(records, wheel.name()) {
+            if let Some(id) = wheel.id_of_number(record.number) {
+                last_hit[id.0] = Some(spins);
+            }
+            spins += 1;
+        }
+        let since = |index: Option<u64>| index.map(|i| spins - 1 - i);
+        let pocket_count = last_hit.len() as f64;
+
+        let mut pockets: Vec<Drought> = wheel
+            .get_all_pockets()
+            .iter()
+            .zip(&last_hit)
+            .map(|(pocket, &hit)| Drought {
+                label: pocket.ticker.to_string(),
+                spins_since: since(hit),
+                expected_gap: pocket_count,
+            })
+            .collect();
+        let mut categories: Vec<Drought> = wheel
+            .categories()
+            .into_iter()
+            .filter_map(|id| {
+                let members = wheel.category_members(id)?;
+                if members.len() < 2 {
+                    return None;
+                }
+                let hit = members.iter().filter_map(|m| last_hit[m.0]).max();
+                Some(Drought {
+                    label: id.to_string(),
+                    spins_since: since(hit),
+                    expected_gap: pocket_count / members.len() as f64,
+                })
+            })
+            .collect();
+        // Never-hit first, then the longest gaps.
+        let gap = |d: &Drought| std::cmp::Reverse(d.spins_since.unwrap_or(u64::MAX));
+        pockets.sort_by_key(gap);
+        categories.sort_by_key(gap);
+        Droughts { spins, pockets, categories }
+    }
+}
+
+/// Writes one CSV row per settled bet, with a header row, leaving out voided
+/// spins. Amounts are plain decimals in the round's `currency`;
+/// `balance_after` is the balance once the whole round was settled.
+pub fn write_csv(records: &[RoundRecord], mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "session,round,pocket,bet_type,stake,outcome,payout,balance_after,currency,note")?;
+    for record in without_voided(records) {
+        for bet in &record.bets {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{},{},{},{}",
+                csv_field(&record.session),
+                record.round,
+                csv_field(&record.ticker),
+                csv_field(&bet.bet_type.to_string()),
+                csv_amount(bet.amount),
+                bet.outcome(),
+                csv_amount(bet.payout),
+                csv_amount(record.balance_after),
+                record.currency,
+                csv_field(bet.note.as_deref().unwrap_or_default())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// An amount as a spreadsheet reads it: `10.00`, `-0.50`.
+fn csv_amount(amount: Money) -> String {
+    let cents = amount.cents();
+    let sign = if cents < 0 { "-" } else { "" };
+    format!("{}{}.{:02}", sign, cents.abs() / 100, cents.abs() % 100)
+}
+
+/// Quotes a field if it holds a comma, quote, or line break.
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+/// Bet results for the session in progress, collected from the game's events.
+///
+/// Hand it to the game wrapped in `Rc<RefCell<_>>` and keep a clone to read
+/// from.
+#[derive(Debug, Clone, Default)]
+pub struct SessionStats {
+    settled: Vec<(u32, BetRecord)>,
+    /// Balance at the end of each round.
+    balances: Vec<(u32, Money)>,
+    voided: Vec<u32>,
+    rebates: Vec<(u32, Money)>,
+    difficulty: Difficulty,
+}
+
+impl SessionStats {
+    /// Stats for a session played at `difficulty`.
+    pub fn at_difficulty(difficulty: Difficulty) -> Self {
+        SessionStats { difficulty, ..SessionStats::default() }
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Wagered and returned per kind of bet, leaving out voided spins.
+    /// Bets held in prison count once, in the round they were placed.
+    pub fn by_bet_type(&self) -> BTreeMap<String, Totals> {
+        let mut groups: BTreeMap<String, Totals> = BTreeMap::new();
+        for (round, bet) in &self.settled {
+            if self.voided.contains(round) {
+                continue;
+            }
+            let totals = groups.entry(bet_type_group(&bet.bet_type)).or_default();
+            if bet.amount > Money::ZERO {
+                totals.count += 1;
+            }
+            totals.wagered += bet.amount;
+            totals.returned += bet.payout;
+        }
+        groups
+    }
+
+    /// Balance after each round whose spin stood, oldest first.
+    pub fn balance_series(&self) -> Vec<Money> {
+        self.balances
+            .iter()
+            .filter(|(round, _)| !self.voided.contains(round))
+            .map(|&(_, balance)| balance)
+            .collect()
+    }
+
+    /// Total paid by the loss-rebate promotion.
+    pub fn rebates_paid(&self) -> Money {
+        self.rebates.iter().filter(|(round, _)| !self.voided.contains(round)).map(|&(_, amount)| amount).sum()
+    }
+}
+
+impl EventSink for SessionStats {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::Settlement { bet_type, amount, payout, note, .. } => {
+                let bet = BetRecord { bet_type: bet_type.clone(), amount: *amount, payout: *payout, note: note.clone() };
+                self.settled.push((round, bet));
+            }
+            GameEvent::RoundComplete { balance, .. } => self.balances.push((round, *balance)),
+            GameEvent::SpinVoided { voided_round, .. } => self.voided.push(*voided_round),
+            GameEvent::RebatePaid { amount, .. } => self.rebates.push((round, *amount)),
+            _ => {}
+        }
+    }
+}
+
+/// Groups bets by kind, ignoring the specific ticker or category chosen.
+pub fn bet_type_group(bet_type: &BetType) -> String {
+    match bet_type {
+        BetType::StraightUp(_) => "Straight Up".to_string(),
+        BetType::Split(_, _) => "Split".to_string(),
+        BetType::Category(_) => "Category".to_string(),
+        BetType::Column(_) => "Column".to_string(),
+        BetType::Custom(_) => "Custom".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(session: &str, currency: &str, stake: u32, payout: u32) -> RoundRecord {
+        RoundRecord {
+            session: session.to_string(),
+            round: 1,
+            timestamp: 0,
+            wheel: "european".to_string(),
+            strategy: "manual".to_string(),
+            profile: None,
+            number: 1,
+            ticker: "AAPL".to_string(),
+            color: Color::Red,
+            seed: None,
+            bets: vec![BetRecord {
+                bet_type: BetType::Red,
+                amount: Money::from_units(stake),
+                payout: Money::from_units(payout),
+                note: None,
+            }],
+            balance_after: Money::from_units(100),
+            currency: Currency::parse(currency).unwrap(),
+            respin_of: None,
+            voided: false,
+        }
+    }
+
+    #[test]
+    fn rounds_are_converted_before_they_are_added_up() {
+        let rates = ExchangeRates::default();
+        let records = [round("a", "USD", 10, 20), round("b", "EUR", 92, 0)];
+        let (records, left_out) = in_currency(&records, &Currency::usd(), &rates);
+        assert_eq!(left_out, 0);
+        assert_eq!(records[1].wagered(), Money::from_units(100));
+        assert_eq!(records[1].balance_after, Money::from_cents(10_869));
+        let stats = AllTimeStats::from_records(&records);
+        assert_eq!(stats.rounds.wagered, Money::from_units(110));
+        assert_eq!(stats.rounds.returned, Money::from_units(20));
+    }
+
+    #[test]
+    fn csv_amounts_are_plain_decimals_beside_their_currency() {
+        let mut out = Vec::new();
+        write_csv(&[round("a", "EUR", 10, 0)], &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().nth(1), Some("a,1,AAPL,Red,10.00,loss,0.00,100.00,EUR,"));
+        assert_eq!(csv_amount(Money::from_cents(-50)), "-0.50");
+    }
+
+    #[test]
+    fn rounds_without_a_rate_are_left_out() {
+        let records = [round("a", "USD", 10, 20), round("b", "GBP", 10, 0)];
+        let (records, left_out) = in_currency(&records, &Currency::usd(), &ExchangeRates::default());
+        assert_eq!(left_out, 1);
+        assert_eq!(records.len(), 1);
+    }
+}
@@ -0,0 +1,102 @@
+// src/history/compare.rs
+
+//! Figures for setting sessions side by side: one session's own, or the
+//! average session's over many.
+
+use super::{RoundRecord, bet_type_group, without_voided};
+use crate::game::money::Money;
+use std::collections::BTreeMap;
+
+/// How a session went, or how the average session went when built from
+/// several. Amounts and counts are per session; rates are over every round.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionReport {
+    /// How many sessions the figures are averaged over.
+    pub sessions: usize,
+    pub rounds: f64,
+    pub wagered: f64,
+    pub net: f64,
+    /// Net as a percentage of everything wagered.
+    pub roi_percent: f64,
+    /// Variance of a round's net result.
+    pub variance: f64,
+    /// Percentage of the amount wagered that went on each kind of bet.
+    pub bet_mix: BTreeMap<String, f64>,
+    /// Deepest fall from the session's peak balance.
+    pub max_drawdown: f64,
+    /// Most rounds in a row spent below the session's peak balance.
+    pub longest_drawdown: f64,
+}
+
+impl SessionReport {
+    /// Figures for the sessions in `records`, averaged if there are several.
+    /// `None` if no spin among them stood.
+    pub fn from_records(records: &[RoundRecord]) -> Option<Self> {
+        let mut by_session: BTreeMap<&str, Vec<&RoundRecord>> = BTreeMap::new();
+        for record in without_voided(records) {
+            by_session.entry(&record.session).or_default().push(record);
+        }
+        if by_session.is_empty() {
+            return None;
+        }
+        let rounds: Vec<&RoundRecord> = by_session.values().flatten().copied().collect();
+        let wagered = rounds.iter().map(|r| r.wagered()).sum::<Money>().as_f64();
+        let returned = rounds.iter().map(|r| r.returned()).sum::<Money>().as_f64();
+        let nets: Vec<f64> = rounds.iter().map(|r| (r.returned() - r.wagered()).as_f64()).collect();
+        let mean = nets.iter().sum::<f64>() / nets.len() as f64;
+        let variance = nets.iter().map(|net| (net - mean).powi(2)).sum::<f64>() / nets.len() as f64;
+
+        let mut staked: BTreeMap<String, Money> = BTreeMap::new();
+        for bet in rounds.iter().flat_map(|r| &r.bets) {
+            *staked.entry(bet_type_group(&bet.bet_type)).or_default() += bet.amount;
+        }
+        let bet_mix = staked
+            .into_iter()
+            .filter(|&(_, amount)| amount > Money::ZERO)
+            .map(|(kind, amount)| (kind, amount.as_f64() / wagered * 100.0))
+            .collect();
+
+        let (deepest, longest) = by_session
+            .values()
+            .map(|session| drawdowns(session))
+            .fold((Money::ZERO, 0), |(deepest, longest), (d, l)| (deepest + d, longest + l as u64));
+        let sessions = by_session.len();
+        let per_session = |total: f64| total / sessions as f64;
+        Some(SessionReport {
+            sessions,
+            rounds: per_session(rounds.len() as f64),
+            wagered: per_session(wagered),
+            net: per_session(returned - wagered),
+            roi_percent: if wagered == 0.0 { 0.0 } else { (returned - wagered) / wagered * 100.0 },
+            variance,
+            bet_mix,
+            max_drawdown: per_session(deepest.as_f64()),
+            longest_drawdown: per_session(longest as f64),
+        })
+    }
+
+    /// Standard deviation of a round's net result.
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// The deepest fall from the peak balance over one session's rounds, and the
+/// most rounds in a row spent below it. The peak starts at the balance the
+/// first round began with.
+fn drawdowns(rounds: &[&RoundRecord]) -> (Money, u32) {
+    let Some(first) = rounds.first() else { return (Money::ZERO, 0) };
+    let mut peak = (first.balance_after + first.wagered()).saturating_sub(first.returned());
+    let (mut deepest, mut longest, mut run) = (Money::ZERO, 0, 0);
+    for record in rounds {
+        if record.balance_after >= peak {
+            peak = record.balance_after;
+            run = 0;
+        } else {
+            run += 1;
+            deepest = deepest.max(peak - record.balance_after);
+            longest = longest.max(run);
+        }
+    }
+    (deepest, longest)
+}
@@ -0,0 +1,524 @@
+// src/history/hand.rs
+
+//! Hand histories: recorded rounds as plain text, the way poker sites write
+//! them, for pasting into a forum post or a bug report.
+//!
+//! ```text
+//! Roulette Hand #1792152608-351/3: european wheel, 2026-10-16 08:30:08 UTC
+//! Strategy: manual
+//! Seed: 9f2c41d0a7b3e5f8
+//! *** BETS ***
+//! straight AAPL 5
+//! red 10  # hedging the MAG7 bet
+//! *** SPIN ***
+//! 13 JPM Red
+//! *** SETTLEMENT ***
+//! straight AAPL 5: loss, paid $0
+//! red 10: win, paid $20
+//! *** SUMMARY ***
+//! Wagered $15, returned $20, net +$5. Balance $510.
+//! ```
+//!
+//! A hand from a table in another currency names it in a `Currency:` line
+//! under the strategy, and writes its amounts in it (`€20`, `40 MBTC`).
+//! Bets are written in the [bet syntax](crate::game::dsl) of `--bet` and
+//! strategy files. A bet held over from the round before (out of prison, or
+//! riding a re-spin) has no stake of its own this round and shows as `0`;
+//! a re-spin's hand also names the hand it re-spins for, in a
+//! `Re-spin of: #<session>/<round>` line under the seed.
+//!
+//! [`read_hands`] reads the text back, and [`Hand::check`] settles each hand
+//! again with the engine's own math, so a disputed payout can be checked from
+//! the text alone.
+
+use super::{BetRecord, RoundRecord, format_date, parse_date, without_voided};
+use crate::game::bets::{Bet, BetType};
+use crate::game::currency::{Amount, Currency};
+use crate::game::dsl;
+use crate::game::money::Money;
+use crate::game::wheel::{Color, Wheel};
+use std::fmt;
+use std::io::{self, Write};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// How every hand's first line begins.
+const HEADER: &str = "Roulette Hand #";
+
+/// Writes one round as a hand history.
+pub fn write_round(record: &RoundRecord, mut out: impl Write) -> io::Result<()> {
+    writeln!(
+        out,
+        "{}{}/{}: {} wheel, {}",
+        HEADER,
+        record.session,
+        record.round,
+        record.wheel,
+        format_timestamp(record.timestamp)
+    )?;
+    writeln!(out, "Strategy: {}", record.strategy)?;
+    if !record.currency.is_usd() {
+        writeln!(out, "Currency: {}", record.currency)?;
+    }
+    if let Some(seed) = &record.seed {
+        writeln!(out, "Seed: {}", seed)?;
+    }
+    if let Some(round) = record.respin_of {
+        writeln!(out, "Re-spin of: #{}/{}", record.session, round)?;
+    }
+    writeln!(out, "*** BETS ***")?;
+    for bet in record.bets.iter().filter(|bet| bet.amount > Money::ZERO) {
+        match &bet.note {
+            Some(note) => writeln!(out, "{}  # {}", bet_text(bet), note)?,
+            None => writeln!(out, "{}", bet_text(bet))?,
+        }
+    }
+    writeln!(out, "*** SPIN ***")?;
+    writeln!(out, "{} {} {}", record.number, record.ticker, record.color)?;
+    writeln!(out, "*** SETTLEMENT ***")?;
+    let money = |amount: Money| record.currency.amount(amount);
+    for bet in &record.bets {
+        writeln!(out, "{}: {}, paid {}", bet_text(bet), bet.outcome(), money(bet.payout))?;
+    }
+    writeln!(out, "*** SUMMARY ***")?;
+    writeln!(
+        out,
+        "Wagered {}, returned {}, net {:+}. Balance {}.",
+        money(record.wagered()),
+        money(record.returned()),
+        money(record.returned() - record.wagered()),
+        money(record.balance_after)
+    )
+}
+
+/// Writes every round whose spin stood, a blank line between hands.
+pub fn write_hands(records: &[RoundRecord], mut out: impl Write) -> io::Result<()> {
+    for (i, record) in without_voided(records).enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        write_round(record, &mut out)?;
+    }
+    Ok(())
+}
+
+/// Why hand-history text could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseHandError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseHandError {}
+
+/// A hand's summary line as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub wagered: Money,
+    pub returned: Money,
+    pub net: Money,
+    pub currency: Currency,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let money = |amount: Money| self.currency.amount(amount);
+        write!(f, "wagered {}, returned {}, net {:+}", money(self.wagered), money(self.returned), money(self.net))
+    }
+}
+
+/// A hand read back from its text.
+#[derive(Debug, Clone)]
+pub struct Hand {
+    /// The round as the text records it.
+    pub record: RoundRecord,
+    /// Each bet's outcome as its settlement line names it, in the order of
+    /// `record.bets`.
+    pub outcomes: Vec<String>,
+    pub summary: Summary,
+}
+
+impl Hand {
+    /// `session/round`, as the header names the hand.
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.record.session, self.record.round)
+    }
+
+    /// Settles the hand again on `wheel` and lists everything the text gets
+    /// wrong: a spin that isn't on the wheel, a payout the engine wouldn't
+    /// make, or a summary the bets don't add up to. A losing bet may get
+    /// back whatever some table rule gives, since the text doesn't say which
+    /// rules were in force; custom bets aren't checked.
+    pub fn check(&self, wheel: &Wheel) -> Vec<Discrepancy> {
+        let record = &self.record;
+        let money = |amount: Money| record.currency.amount(amount);
+        let hand = self.id();
+        let mut found = Vec::new();
+        let pocket = wheel.id_of_number(record.number);
+        match pocket.map(|id| wheel.pocket(id)) {
+            Some(actual) if actual.ticker.to_string() == record.ticker && actual.color == record.color => {}
+            actual => found.push(Discrepancy::Spin {
+                hand: hand.clone(),
+                recorded: format!("{} {} {}", record.number, record.ticker, record.color),
+                actual: actual.map(|p| format!("{} {} {}", p.number, p.ticker, p.color)),
+            }),
+        }
+        for (bet, outcome) in record.bets.iter().zip(&self.outcomes) {
+            if outcome != bet.outcome() {
+                found.push(Discrepancy::Outcome {
+                    hand: hand.clone(),
+                    bet: bet_text(bet),
+                    recorded: outcome.clone(),
+                    actual: bet.outcome(),
+                });
+            }
+            let Some(id) = pocket.filter(|_| !matches!(bet.bet_type, BetType::Custom(_))) else { continue };
+            if !bet.bet_type.covers(wheel, id) {
+                if !refund_allowed(bet, wheel.pocket(id).is_zero()) {
+                    found.push(Discrepancy::Unearned { hand: hand.clone(), bet: bet_text(bet), paid: money(bet.payout) });
+                }
+                continue;
+            }
+            // A carried bet's stake was placed in an earlier round, so what it
+            // pays can't be worked out from this one.
+            if bet.amount.is_zero() {
+                continue;
+            }
+            let expected = Bet::new(bet.bet_type.clone(), bet.amount).payout_with(wheel.payouts());
+            if bet.payout != expected {
+                let (paid, expected) = (money(bet.payout), money(expected));
+                found.push(Discrepancy::Payout { hand: hand.clone(), bet: bet_text(bet), paid, expected });
+            }
+        }
+        let actual = Summary {
+            wagered: record.wagered(),
+            returned: record.returned(),
+            net: record.returned() - record.wagered(),
+            currency: record.currency.clone(),
+        };
+        if self.summary != actual {
+            found.push(Discrepancy::Summary { hand, recorded: self.summary.clone(), actual });
+        }
+        found
+    }
+}
+
+/// Something a hand history records that the engine disagrees with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// The spin line doesn't match the wheel's pocket with that number, or
+    /// the wheel has no such pocket (`actual` is `None`).
+    Spin { hand: String, recorded: String, actual: Option<String> },
+    /// A settlement line names an outcome its payout doesn't give.
+    Outcome { hand: String, bet: String, recorded: String, actual: &'static str },
+    /// A winning bet paid other than the payout table says.
+    Payout { hand: String, bet: String, paid: Amount, expected: Amount },
+    /// A bet that didn't cover the pocket got back more than any rule returns.
+    Unearned { hand: String, bet: String, paid: Amount },
+    /// The summary line doesn't add up to the settlement lines.
+    Summary { hand: String, recorded: Summary, actual: Summary },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Discrepancy::Spin { hand, recorded, actual: Some(actual) } => {
+                write!(f, "hand {}: the spin reads `{}`, but that pocket is `{}`", hand, recorded, actual)
+            }
+            Discrepancy::Spin { hand, recorded, actual: None } => {
+                write!(f, "hand {}: the spin `{}` is not on this wheel", hand, recorded)
+            }
+            Discrepancy::Outcome { hand, bet, recorded, actual } => {
+                write!(f, "hand {}: `{}` is marked {}, but what it paid makes it a {}", hand, bet, recorded, actual)
+            }
+            Discrepancy::Payout { hand, bet, paid, expected } => {
+                write!(f, "hand {}: `{}` won and pays {}, but was paid {}", hand, bet, expected, paid)
+            }
+            Discrepancy::Unearned { hand, bet, paid } => {
+                write!(f, "hand {}: `{}` didn't cover the pocket, and no table rule gives {} back", hand, bet, paid)
+            }
+            Discrepancy::Summary { hand, recorded, actual } => {
+                write!(f, "hand {}: the summary says {}, but the bets come to {}", hand, recorded, actual)
+            }
+        }
+    }
+}
+
+/// Reads every hand in `text`, skipping whatever comes before the first
+/// header and the blank lines between hands. `wheels` gives the wheel each
+/// header names (`european`, `mini-training`), for reading its bets.
+pub fn read_hands(text: &str, wheels: impl Fn(&str) -> Option<Wheel>) -> Result<Vec<Hand>, ParseHandError> {
+    let mut hands = Vec::new();
+    let mut lines: Vec<(usize, &str)> = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with(HEADER) && !lines.is_empty() {
+            hands.push(read_hand(&lines, &wheels)?);
+            lines.clear();
+        }
+        if !line.is_empty() && (line.starts_with(HEADER) || !lines.is_empty()) {
+            lines.push((i + 1, line));
+        }
+    }
+    if !lines.is_empty() {
+        hands.push(read_hand(&lines, &wheels)?);
+    }
+    Ok(hands)
+}
+
+/// Reads one hand from its numbered lines, the header first.
+fn read_hand(lines: &[(usize, &str)], wheels: &impl Fn(&str) -> Option<Wheel>) -> Result<Hand, ParseHandError> {
+    let error = |line, message| ParseHandError { line, message };
+    let (&(first, header), body) = lines.split_first().expect("a hand starts with its header");
+    let (session, round, wheel_name, timestamp) =
+        parse_header(header).ok_or_else(|| error(first, format!("unreadable hand header `{}`", header)))?;
+    let wheel = wheels(&wheel_name).ok_or_else(|| error(first, format!("unknown wheel `{}`", wheel_name)))?;
+
+    let mut strategy = None;
+    let mut seed = None;
+    let mut respin_of = None;
+    let mut currency = Currency::usd();
+    let mut placed: Vec<Bet> = Vec::new();
+    let mut spin = None;
+    let mut settled: Vec<(BetRecord, String)> = Vec::new();
+    let mut summary = None;
+    let mut section = "";
+    for &(number, line) in body {
+        if let Some(name) = line.strip_prefix("*** ").and_then(|rest| rest.strip_suffix(" ***")) {
+            section = match name {
+                "BETS" | "SPIN" | "SETTLEMENT" | "SUMMARY" => name,
+                _ => return Err(error(number, format!("unknown section `{}`", name))),
+            };
+            continue;
+        }
+        let read = match section {
+            "" => {
+                if let Some(name) = line.strip_prefix("Strategy: ") {
+                    strategy = Some(name.to_string());
+                } else if let Some(code) = line.strip_prefix("Currency: ") {
+                    match Currency::parse(code) {
+                        Some(code) => currency = code,
+                        None => return Err(error(number, format!("unknown currency `{}`", code))),
+                    }
+                } else if let Some(value) = line.strip_prefix("Seed: ") {
+                    seed = Some(value.to_string());
+                } else if let Some(hand) = line.strip_prefix("Re-spin of: #") {
+                    let round = hand.strip_prefix(session.as_str()).and_then(|rest| rest.strip_prefix('/'));
+                    match round.and_then(|round| round.parse().ok()) {
+                        Some(round) => respin_of = Some(round),
+                        None => return Err(error(number, format!("unreadable re-spin line `{}`", line))),
+                    }
+                } else {
+                    return Err(error(number, format!("unexpected line `{}`", line)));
+                }
+                Ok(())
+            }
+            "BETS" => dsl::parse_bet_line(line, &wheel).map(|bet| placed.push(bet)).map_err(|e| format!("`{}`: {}", line, e)),
+            "SPIN" if spin.is_some() => Err("a second spin".to_string()),
+            "SPIN" => parse_spin(line).map(|read| spin = Some(read)).ok_or_else(|| format!("unreadable spin `{}`", line)),
+            "SETTLEMENT" => parse_settlement(line, &wheel, &currency).map(|read| settled.push(read)),
+            _ if summary.is_some() => Err(format!("unexpected line `{}`", line)),
+            _ => parse_summary(line, &currency)
+                .map(|read| summary = Some(read))
+                .ok_or_else(|| format!("unreadable summary `{}`", line)),
+        };
+        read.map_err(|message| error(number, message))?;
+    }
+
+    let strategy = strategy.ok_or_else(|| error(first, "the hand has no strategy line".to_string()))?;
+    let (number, ticker, color) = spin.ok_or_else(|| error(first, "the hand has no spin".to_string()))?;
+    let (summary, balance_after) = summary.ok_or_else(|| error(first, "the hand has no summary".to_string()))?;
+    // Every staked settlement is a bet from the bets section, in the same order.
+    let mut placed = placed.into_iter();
+    for (bet, _) in settled.iter_mut().filter(|(bet, _)| bet.amount > Money::ZERO) {
+        match placed.next() {
+            Some(placed) if placed.bet_type == bet.bet_type && placed.amount == bet.amount => bet.note = placed.note,
+            _ => return Err(error(first, format!("`{}` is settled but was never placed", bet_text(bet)))),
+        }
+    }
+    if let Some(unsettled) = placed.next() {
+        return Err(error(first, format!("`{}` is placed but never settled", dsl::format_bet(&unsettled))));
+    }
+    let (bets, outcomes) = settled.into_iter().unzip();
+    let record = RoundRecord {
+        session,
+        round,
+        timestamp,
+        wheel: wheel_name,
+        strategy,
+        profile: None,
+        number,
+        ticker,
+        color,
+        seed,
+        bets,
+        balance_after,
+        currency,
+        respin_of,
+        voided: false,
+    };
+    Ok(Hand { record, outcomes, summary })
+}
+
+/// The session, round, wheel, and time a header line gives.
+fn parse_header(line: &str) -> Option<(String, u32, String, u64)> {
+    let (id, rest) = line.strip_prefix(HEADER)?.split_once(": ")?;
+    let (session, round) = id.rsplit_once('/')?;
+    let (wheel, time) = rest.split_once(" wheel, ")?;
+    let (date, clock) = time.strip_suffix(" UTC")?.split_once(' ')?;
+    let mut fields = clock.splitn(3, ':').map(|field| field.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (fields.next()??, fields.next()??, fields.next()??);
+    if hours >= 24 || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    let timestamp = parse_date(date)? + hours * 3600 + minutes * 60 + seconds;
+    Some((session.to_string(), round.parse().ok()?, wheel.to_string(), timestamp))
+}
+
+/// `13 JPM Red` as number, ticker, and color.
+fn parse_spin(line: &str) -> Option<(u8, String, Color)> {
+    let [number, ticker, color] = line.split_whitespace().collect::<Vec<_>>()[..] else { return None };
+    let color = [Color::Red, Color::Black, Color::Green].into_iter().find(|c| c.to_string() == color)?;
+    Some((number.parse().ok()?, ticker.to_string(), color))
+}
+
+/// `red 10: win, paid $20` as the bet and the outcome it names. The amount
+/// may be `0`, which the bet syntax otherwise refuses.
+fn parse_settlement(line: &str, wheel: &Wheel, currency: &Currency) -> Result<(BetRecord, String), String> {
+    let unreadable = || format!("unreadable settlement `{}`", line);
+    let (bet, result) = line.rsplit_once(": ").ok_or_else(unreadable)?;
+    let (outcome, payout) = result.split_once(", paid ").ok_or_else(unreadable)?;
+    let payout = parse_amount(payout, currency).ok_or_else(unreadable)?;
+    let (kind, amount) = bet.rsplit_once(' ').ok_or_else(unreadable)?;
+    let amount = amount.parse().map_err(|_| unreadable())?;
+    let bet_type = dsl::parse_bet_kind(kind, wheel).map_err(|e| format!("`{}`: {}", bet, e))?;
+    Ok((BetRecord { bet_type, amount, payout, note: None }, outcome.to_string()))
+}
+
+/// `Wagered $15, returned $20, net +$5. Balance $510.` as the summary and
+/// the balance.
+fn parse_summary(line: &str, currency: &Currency) -> Option<(Summary, Money)> {
+    let (wagered, rest) = line.strip_prefix("Wagered ")?.split_once(", returned ")?;
+    let (returned, rest) = rest.split_once(", net ")?;
+    let (net, balance) = rest.split_once(". Balance ")?;
+    let summary = Summary {
+        wagered: parse_amount(wagered, currency)?,
+        returned: parse_amount(returned, currency)?,
+        net: parse_amount(net, currency)?,
+        currency: currency.clone(),
+    };
+    Some((summary, parse_amount(balance.strip_suffix('.')?, currency)?))
+}
+
+/// An amount as [`Amount`] writes it in `currency`: `$12.50`, `+€5`, or
+/// `-40 MBTC`.
+fn parse_amount(text: &str, currency: &Currency) -> Option<Money> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let digits = match currency.sign() {
+        Some(sign) => text.strip_prefix(sign)?,
+        None => text.strip_suffix(currency.code())?.strip_suffix(' ')?,
+    };
+    let amount: Money = digits.parse().ok()?;
+    Some(if negative { -amount } else { amount })
+}
+
+/// Whether some table rule gives `bet` back what it was paid though it
+/// didn't cover the pocket: nothing, half back or a push for an outside bet
+/// on a green pocket, or half back for a surrendered even-money bet. A bet
+/// riding a re-spin may land green again, on a stake this round doesn't show.
+fn refund_allowed(bet: &BetRecord, zero_hit: bool) -> bool {
+    let green = zero_hit && bet.bet_type.is_outside();
+    match bet.payout {
+        payout if payout.is_zero() => true,
+        _ if bet.amount.is_zero() => green,
+        payout if payout == bet.amount / 2 => green || bet.bet_type.is_even_money(),
+        payout => payout == bet.amount && green,
+    }
+}
+
+fn bet_text(bet: &BetRecord) -> String {
+    dsl::format_bet(&Bet { bet_type: bet.bet_type.clone(), amount: bet.amount, note: None })
+}
+
+/// `timestamp` as a UTC date and time.
+fn format_timestamp(timestamp: u64) -> String {
+    let seconds = timestamp % SECONDS_PER_DAY;
+    format!(
+        "{} {:02}:{:02}:{:02} UTC",
+        format_date(timestamp),
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(currency: &str) -> RoundRecord {
+        let wheel = Wheel::european();
+        let pocket = wheel.get_pocket(13).unwrap();
+        RoundRecord {
+            session: "1792152608-351".to_string(),
+            round: 3,
+            timestamp: 1_792_152_608,
+            wheel: "european".to_string(),
+            strategy: "manual".to_string(),
+            profile: None,
+            number: pocket.number,
+            ticker: pocket.ticker.to_string(),
+            color: pocket.color,
+            seed: None,
+            bets: vec![
+                BetRecord { bet_type: BetType::Black, amount: Money::from_units(10), payout: Money::from_units(20), note: None },
+                BetRecord { bet_type: BetType::Red, amount: Money::from_cents(550), payout: Money::ZERO, note: None },
+            ],
+            balance_after: Money::from_cents(51_050),
+            currency: Currency::parse(currency).unwrap(),
+            respin_of: None,
+            voided: false,
+        }
+    }
+
+    fn round_trip(record: &RoundRecord) -> (String, Hand) {
+        let mut text = Vec::new();
+        write_round(record, &mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        let mut hands = read_hands(&text, |_| Some(Wheel::european())).unwrap();
+        (text, hands.remove(0))
+    }
+
+    #[test]
+    fn hands_are_written_and_read_in_their_currency() {
+        for (code, net) in [("EUR", "net +€4.50"), ("MBTC", "net +4.50 MBTC")] {
+            let record = record(code);
+            let (text, hand) = round_trip(&record);
+            assert!(text.contains(&format!("Currency: {}\n", code)), "{}", text);
+            assert!(text.contains(net), "{}", text);
+            assert!(!text.contains('$'), "{}", text);
+            assert_eq!(hand.record.currency, record.currency);
+            let amounts = |record: &RoundRecord| record.bets.iter().map(|bet| (bet.amount, bet.payout)).collect::<Vec<_>>();
+            assert_eq!(amounts(&hand.record), amounts(&record));
+            assert_eq!(hand.record.balance_after, record.balance_after);
+            assert_eq!(hand.check(&Wheel::european()), Vec::new());
+        }
+    }
+
+    #[test]
+    fn dollar_hands_name_no_currency() {
+        let (text, hand) = round_trip(&record("USD"));
+        assert!(!text.contains("Currency:"), "{}", text);
+        assert!(text.contains("Wagered $15.50, returned $20, net +$4.50. Balance $510.50."), "{}", text);
+        assert!(hand.record.currency.is_usd());
+    }
+}
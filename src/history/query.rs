@@ -0,0 +1,298 @@
+// src/history/query.rs
+
+//! Filtering and aggregating recorded rounds without walking the raw records.
+//!
+//! ```no_run
+//! use roulette_game::history::{GroupBy, History, RoundStore};
+//!
+//! let history = History::load(&RoundStore::default_location())?;
+//! let red_wins = history.query().bet_type("Red").outcome("win").since(1_790_000_000);
+//! println!("{} winning red bets", red_wins.totals().count);
+//! for (wheel, totals) in red_wins.group_by(GroupBy::Wheel) {
+//!     println!("{}: {:+}", wheel, totals.net());
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use super::{BetRecord, RoundRecord, RoundStore, Totals, bet_type_group, without_voided};
+use crate::game::money::Money;
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::ops::RangeInclusive;
+
+/// How each settled bet can come out; see [`BetRecord::outcome`].
+pub const OUTCOMES: [&str; 5] = ["win", "loss", "push", "surrender", "released"];
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Every recorded round, loaded once to be [queried](History::query).
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    records: Vec<RoundRecord>,
+}
+
+impl History {
+    pub fn new(records: Vec<RoundRecord>) -> Self {
+        History { records }
+    }
+
+    /// Reads every round in `store`.
+    pub fn load(store: &RoundStore) -> io::Result<Self> {
+        store.load_all().map(History::new)
+    }
+
+    pub fn records(&self) -> &[RoundRecord] {
+        &self.records
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Every round whose spin stood, to be narrowed down with filters.
+    pub fn query(&self) -> Query<'_> {
+        Query::new(&self.records)
+    }
+}
+
+/// What a [`Query`] keeps. Unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// A kind of bet as [`bet_type_group`] names it (`Straight Up`,
+    /// `Red`), or one bet exactly (`AAPL`); either way without regard to case.
+    pub bet_type: Option<String>,
+    /// One of [`OUTCOMES`].
+    pub outcome: Option<String>,
+    /// The ticker the ball landed on.
+    pub ticker: Option<String>,
+    pub session: Option<String>,
+    /// The profile the rounds were played under.
+    pub profile: Option<String>,
+    /// Round numbers within their sessions.
+    pub rounds: Option<RangeInclusive<u32>>,
+    /// Rounds settled at or after this time, in seconds since the Unix epoch.
+    pub since: Option<u64>,
+    /// Rounds settled before this time.
+    pub until: Option<u64>,
+}
+
+impl Filter {
+    /// Whether anything looks at single bets rather than whole rounds.
+    fn filters_bets(&self) -> bool {
+        self.bet_type.is_some() || self.outcome.is_some()
+    }
+
+    fn matches_round(&self, record: &RoundRecord) -> bool {
+        self.ticker.as_ref().is_none_or(|ticker| record.ticker.eq_ignore_ascii_case(ticker))
+            && self.session.as_ref().is_none_or(|session| &record.session == session)
+            && self.profile.as_ref().is_none_or(|profile| record.profile.as_ref() == Some(profile))
+            && self.rounds.as_ref().is_none_or(|rounds| rounds.contains(&record.round))
+            && self.since.is_none_or(|since| record.timestamp >= since)
+            && self.until.is_none_or(|until| record.timestamp < until)
+    }
+
+    fn matches_bet(&self, bet: &BetRecord) -> bool {
+        self.bet_type.as_ref().is_none_or(|wanted| {
+            bet_type_group(&bet.bet_type).eq_ignore_ascii_case(wanted) || bet.bet_type.to_string().eq_ignore_ascii_case(wanted)
+        }) && self.outcome.as_ref().is_none_or(|outcome| bet.outcome().eq_ignore_ascii_case(outcome))
+    }
+}
+
+/// What to group a query's results by. Grouping by something a bet has
+/// ([`BetType`](GroupBy::BetType), [`Outcome`](GroupBy::Outcome)) counts
+/// bets; grouping by something the round has counts rounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    BetType,
+    Outcome,
+    Ticker,
+    Wheel,
+    Strategy,
+    Session,
+    /// The UTC day the round was settled, as `YYYY-MM-DD`.
+    Day,
+}
+
+/// Recorded rounds narrowed down by filters, with the figures over them.
+/// Voided spins never match.
+#[derive(Debug, Clone)]
+pub struct Query<'a> {
+    records: &'a [RoundRecord],
+    filter: Filter,
+}
+
+impl<'a> Query<'a> {
+    /// A query over `records`, for callers holding them already.
+    pub fn new(records: &'a [RoundRecord]) -> Self {
+        Query { records, filter: Filter::default() }
+    }
+
+    /// Replaces every filter with `filter`.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn bet_type(mut self, bet_type: &str) -> Self {
+        self.filter.bet_type = Some(bet_type.to_string());
+        self
+    }
+
+    pub fn outcome(mut self, outcome: &str) -> Self {
+        self.filter.outcome = Some(outcome.to_string());
+        self
+    }
+
+    pub fn ticker(mut self, ticker: &str) -> Self {
+        self.filter.ticker = Some(ticker.to_string());
+        self
+    }
+
+    pub fn session(mut self, session: &str) -> Self {
+        self.filter.session = Some(session.to_string());
+        self
+    }
+
+    pub fn profile(mut self, profile: &str) -> Self {
+        self.filter.profile = Some(profile.to_string());
+        self
+    }
+
+    pub fn rounds(mut self, rounds: RangeInclusive<u32>) -> Self {
+        self.filter.rounds = Some(rounds);
+        self
+    }
+
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.filter.since = Some(timestamp);
+        self
+    }
+
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.filter.until = Some(timestamp);
+        self
+    }
+
+    /// The rounds that match, oldest first. With a bet filter, only rounds
+    /// with at least one matching bet.
+    pub fn matching_rounds(&self) -> impl Iterator<Item = &'a RoundRecord> + '_ {
+        without_voided(self.records).filter(|record| {
+            self.filter.matches_round(record)
+                && (!self.filter.filters_bets() || record.bets.iter().any(|bet| self.filter.matches_bet(bet)))
+        })
+    }
+
+    /// The bets that match, each with its round.
+    pub fn matching_bets(&self) -> impl Iterator<Item = (&'a RoundRecord, &'a BetRecord)> + '_ {
+        self.matching_rounds()
+            .flat_map(|record| record.bets.iter().map(move |bet| (record, bet)))
+            .filter(|(_, bet)| self.filter.matches_bet(bet))
+    }
+
+    /// How many sessions the matching rounds come from.
+    pub fn sessions(&self) -> usize {
+        self.matching_rounds().map(|record| record.session.as_str()).collect::<HashSet<_>>().len()
+    }
+
+    /// Wagered and returned over the matching bets, counting bets.
+    pub fn totals(&self) -> Totals {
+        let mut totals = Totals::default();
+        for (_, bet) in self.matching_bets() {
+            totals.add(bet.amount, bet.payout);
+        }
+        totals
+    }
+
+    /// Wagered and returned over the matching bets, counting rounds.
+    pub fn round_totals(&self) -> Totals {
+        let mut totals = Totals::default();
+        for record in self.matching_rounds() {
+            let (wagered, returned) = self.matching_sums(record);
+            totals.add(wagered, returned);
+        }
+        totals
+    }
+
+    /// [`totals`](Query::totals) or [`round_totals`](Query::round_totals)
+    /// for each group; see [`GroupBy`].
+    pub fn group_by(&self, key: GroupBy) -> BTreeMap<String, Totals> {
+        let mut groups: BTreeMap<String, Totals> = BTreeMap::new();
+        match key {
+            GroupBy::BetType | GroupBy::Outcome => {
+                for (_, bet) in self.matching_bets() {
+                    let name = match key {
+                        GroupBy::BetType => bet_type_group(&bet.bet_type),
+                        _ => bet.outcome().to_string(),
+                    };
+                    groups.entry(name).or_default().add(bet.amount, bet.payout);
+                }
+            }
+            _ => {
+                for record in self.matching_rounds() {
+                    let name = match key {
+                        GroupBy::Ticker => record.ticker.clone(),
+                        GroupBy::Wheel => record.wheel.clone(),
+                        GroupBy::Strategy => record.strategy.clone(),
+                        GroupBy::Session => record.session.clone(),
+                        _ => format_date(record.timestamp),
+                    };
+                    let (wagered, returned) = self.matching_sums(record);
+                    groups.entry(name).or_default().add(wagered, returned);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Wagered and returned by the round's matching bets.
+    fn matching_sums(&self, record: &RoundRecord) -> (Money, Money) {
+        record
+            .bets
+            .iter()
+            .filter(|bet| self.filter.matches_bet(bet))
+            .fold((Money::ZERO, Money::ZERO), |(wagered, returned), bet| (wagered + bet.amount, returned + bet.payout))
+    }
+}
+
+/// The start of a UTC day written `YYYY-MM-DD`, in seconds since the Unix
+/// epoch.
+pub fn parse_date(text: &str) -> Option<u64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+    // Days from 1970-01-01, counting years from March so leap days come last.
+    let (y, m) = if month <= 2 { (year - 1, month + 9) } else { (year, month - 3) };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    u64::try_from(days).ok().map(|days| days * SECONDS_PER_DAY)
+}
+
+/// The UTC day `timestamp` falls on, as `YYYY-MM-DD`.
+pub fn format_date(timestamp: u64) -> String {
+    let days = (timestamp / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let m = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * m + 2) / 5 + 1;
+    let month = if m < 10 { m + 3 } else { m - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
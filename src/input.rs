@@ -0,0 +1,150 @@
+// src/input.rs
+
+//! Lines typed at the terminal, read on a background thread.
+//!
+//! Every prompt reads through here rather than from stdin directly, so a
+//! caller can stop waiting: a betting countdown ([`set_deadline`]) ends the
+//! wait when time runs out, and turbo spins look for a keypress with
+//! [`poll_line`] between spins. Lines arrive in the order they were typed
+//! whoever asks for them.
+
+use crate::say;
+use std::io::{self, BufRead, Read};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds left at which a countdown warns the player.
+const WARNINGS: [u64; 3] = [30, 10, 5];
+
+struct Source {
+    /// `None` marks the end of input.
+    lines: Receiver<Option<String>>,
+    closed: bool,
+    deadline: Option<Instant>,
+    /// The next warning still to give, as an index into `WARNINGS`.
+    warned: usize,
+}
+
+fn source() -> &'static Mutex<Source> {
+    static SOURCE: OnceLock<Mutex<Source>> = OnceLock::new();
+    SOURCE.get_or_init(|| {
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            let stdin = io::stdin();
+            loop {
+                let mut line = String::new();
+                let line = match stdin.lock().read_line(&mut line) {
+                    Ok(0) | Err(_) => None,
+                    Ok(_) => Some(line),
+                };
+                let done = line.is_none();
+                if sender.send(line).is_err() || done {
+                    return;
+                }
+            }
+        });
+        Mutex::new(Source { lines, closed: false, deadline: None, warned: 0 })
+    })
+}
+
+fn lock() -> std::sync::MutexGuard<'static, Source> {
+    source().lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Waits for the next line, with its line ending. `None` once input has
+/// ended, and an empty line if the deadline passes first.
+pub fn read_line() -> Option<String> {
+    let mut source = lock();
+    if source.closed {
+        return None;
+    }
+    let line = loop {
+        let Some(deadline) = source.deadline else {
+            break source.lines.recv().ok().flatten();
+        };
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Some(String::new());
+        }
+        if let Some(&seconds) = WARNINGS.get(source.warned)
+            && left <= Duration::from_secs(seconds)
+        {
+            source.warned += 1;
+            say!("\n({} seconds left to bet)", left.as_secs_f64().ceil());
+            continue;
+        }
+        // Wake for the next warning, if it comes before the deadline.
+        let next_warning = WARNINGS.get(source.warned).map(|&seconds| left.saturating_sub(Duration::from_secs(seconds)));
+        match source.lines.recv_timeout(next_warning.unwrap_or(left).max(Duration::from_millis(1))) {
+            Ok(line) => break line,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break None,
+        }
+    };
+    source.closed = line.is_none();
+    line
+}
+
+/// A line typed since it was last asked for, without waiting; `Some` with
+/// an empty line once input has ended.
+pub fn poll_line() -> Option<String> {
+    let mut source = lock();
+    if source.closed {
+        return Some(String::new());
+    }
+    match source.lines.try_recv() {
+        Ok(Some(line)) => Some(line),
+        Ok(None) | Err(TryRecvError::Disconnected) => {
+            source.closed = true;
+            Some(String::new())
+        }
+        Err(TryRecvError::Empty) => None,
+    }
+}
+
+/// Gives up waiting for lines at `deadline`, or never with `None`.
+pub fn set_deadline(deadline: Option<Instant>) {
+    let mut source = lock();
+    source.deadline = deadline;
+    let left = deadline.map_or(Duration::MAX, |deadline| deadline.saturating_duration_since(Instant::now()));
+    // Warnings already due when the countdown starts aren't worth giving.
+    source.warned = WARNINGS.iter().take_while(|&&seconds| left <= Duration::from_secs(seconds)).count();
+}
+
+/// Time before the deadline, if one is set.
+pub fn time_left() -> Option<Duration> {
+    lock().deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Lines from [`read_line`] as a reader, for code that takes a `BufRead`.
+#[derive(Debug, Default)]
+pub struct Lines {
+    line: Vec<u8>,
+    read: usize,
+}
+
+impl Read for Lines {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Lines {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.read == self.line.len() {
+            self.line = read_line().unwrap_or_default().into_bytes();
+            self.read = 0;
+        }
+        Ok(&self.line[self.read..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.read = (self.read + amount).min(self.line.len());
+    }
+}
@@ -0,0 +1,214 @@
+// src/integrity.rs
+
+//! Tamper detection for files the game keeps between sessions.
+//!
+//! Each machine gets a random key in the [data directory](crate::data_dir).
+//! Profiles carry an HMAC of their contents under that key, and line-based
+//! logs (the session journal and the audit log) carry a MAC on every line
+//! chained to the line before, so editing, inserting, or deleting a line
+//! breaks every MAC after it. Anyone who reads the key can still forge a
+//! file; the point is that editing a balance by hand no longer goes unnoticed.
+
+use crate::game::fairness::to_hex;
+use hmac::{Hmac, KeyInit, Mac};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+const KEY_LEN: usize = 32;
+
+/// Name of the MAC field added to each line of a chained log.
+pub const MAC_FIELD: &str = "mac";
+
+/// The outcome of checking a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Integrity {
+    Verified,
+    /// The file carries no MACs, e.g. it predates tamper detection.
+    Unsigned,
+    /// The file is signed but this machine has no key to check it with.
+    NoKey,
+    Tampered,
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Integrity::Verified => write!(f, "verified"),
+            Integrity::Unsigned => write!(f, "unsigned (saved before tamper detection)"),
+            Integrity::NoKey => write!(f, "can't be checked: the integrity key is missing"),
+            Integrity::Tampered => write!(f, "TAMPERED: edited outside the game"),
+        }
+    }
+}
+
+/// The machine's signing key.
+#[derive(Clone)]
+pub struct IntegrityKey([u8; KEY_LEN]);
+
+impl fmt::Debug for IntegrityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "IntegrityKey(..)")
+    }
+}
+
+impl IntegrityKey {
+    /// The default location: `integrity.key` inside the data directory.
+    pub fn default_location() -> PathBuf {
+        crate::data_dir().join("integrity.key")
+    }
+
+    /// Reads the key at `path`, or `None` if there is none yet.
+    pub fn load(path: &Path) -> io::Result<Option<Self>> {
+        match fs::read(path) {
+            Ok(bytes) => {
+                let key = bytes
+                    .try_into()
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "integrity key has the wrong length"))?;
+                Ok(Some(IntegrityKey(key)))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads the key at `path`, creating a random one on first use.
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        if let Some(key) = Self::load(path)? {
+            return Ok(key);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut key = [0u8; KEY_LEN];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut key);
+        match OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut file) => {
+                file.write_all(&key)?;
+                Ok(IntegrityKey(key))
+            }
+            // Another process created it first; use theirs.
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                Self::load(path)?.ok_or_else(|| io::Error::other("integrity key vanished"))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Hex HMAC-SHA256 of `bytes`.
+    pub fn sign(&self, bytes: &[u8]) -> String {
+        let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(&self.0).expect("HMAC takes keys of any length");
+        mac.update(bytes);
+        to_hex(&mac.finalize().into_bytes())
+    }
+
+    pub fn verify(&self, bytes: &[u8], signature: &str) -> bool {
+        self.sign(bytes).eq_ignore_ascii_case(signature)
+    }
+}
+
+/// Signs the lines of an NDJSON log, each MAC covering the line and the MAC
+/// before it.
+#[derive(Debug, Clone)]
+pub struct MacChain {
+    key: IntegrityKey,
+    last: String,
+}
+
+impl MacChain {
+    pub fn new(key: IntegrityKey) -> Self {
+        MacChain { key, last: String::new() }
+    }
+
+    fn next(&mut self, line: &str) -> String {
+        self.last = self.key.sign(format!("{}{}", self.last, line).as_bytes());
+        self.last.clone()
+    }
+
+    /// Serializes `record` as one JSON line with its MAC added.
+    pub fn seal(&mut self, record: &impl Serialize) -> serde_json::Result<String> {
+        let mut value = serde_json::to_value(record)?;
+        let mac = self.next(&value.to_string());
+        if let Value::Object(map) = &mut value {
+            map.insert(MAC_FIELD.to_string(), Value::String(mac));
+        }
+        Ok(value.to_string())
+    }
+}
+
+/// Checks the MAC chain of an NDJSON log. A line that isn't JSON at all
+/// (such as one cut off by a crash) is skipped. The game signs every line
+/// it writes once the machine has a key, so with `key` given an unsigned
+/// line is tampered; only a log from a machine without one is unsigned.
+pub fn check_log(key: Option<&IntegrityKey>, reader: impl BufRead) -> io::Result<Integrity> {
+    let mut chain = key.cloned().map(MacChain::new);
+    let mut signed = false;
+    let mut unsigned = false;
+    for line in reader.lines() {
+        let Ok(mut value) = serde_json::from_str::<Value>(&line?) else { continue };
+        let mac = value.as_object_mut().and_then(|map| map.remove(MAC_FIELD));
+        match (mac, chain.as_mut()) {
+            (None, _) => unsigned = true,
+            (Some(_), None) => signed = true,
+            (Some(mac), Some(chain)) => {
+                signed = true;
+                if mac.as_str() != Some(chain.next(&value.to_string()).as_str()) {
+                    return Ok(Integrity::Tampered);
+                }
+            }
+        }
+    }
+    Ok(match (signed, unsigned, chain.is_some()) {
+        // Some lines signed and some not: lines were added by hand.
+        (true, true, _) => Integrity::Tampered,
+        // The MACs were stripped.
+        (false, true, true) => Integrity::Tampered,
+        (true, false, false) => Integrity::NoKey,
+        (true, false, true) => Integrity::Verified,
+        (false, _, _) => Integrity::Unsigned,
+    })
+}
+
+/// Checks the log at `path` with this machine's key.
+pub fn check_log_file(path: &Path) -> io::Result<Integrity> {
+    let key = IntegrityKey::load(&IntegrityKey::default_location())?;
+    check_log(key.as_ref(), io::BufReader::new(fs::File::open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn signed_log(key: &IntegrityKey) -> String {
+        let mut chain = MacChain::new(key.clone());
+        let lines: Vec<String> = (1..=3).map(|round| chain.seal(&json!({ "round": round })).unwrap()).collect();
+        lines.join("\n")
+    }
+
+    #[test]
+    fn signed_log_verifies() {
+        let key = IntegrityKey([7; KEY_LEN]);
+        assert_eq!(check_log(Some(&key), signed_log(&key).as_bytes()).unwrap(), Integrity::Verified);
+        assert_eq!(check_log(None, signed_log(&key).as_bytes()).unwrap(), Integrity::NoKey);
+    }
+
+    #[test]
+    fn edited_line_is_tampered() {
+        let key = IntegrityKey([7; KEY_LEN]);
+        let log = signed_log(&key).replacen("\"round\":2", "\"round\":9", 1);
+        assert_eq!(check_log(Some(&key), log.as_bytes()).unwrap(), Integrity::Tampered);
+    }
+
+    #[test]
+    fn stripped_macs_are_tampered_once_there_is_a_key() {
+        let key = IntegrityKey([7; KEY_LEN]);
+        let log = "{\"round\":1}\n{\"round\":2}\n";
+        assert_eq!(check_log(Some(&key), log.as_bytes()).unwrap(), Integrity::Tampered);
+        assert_eq!(check_log(None, log.as_bytes()).unwrap(), Integrity::Unsigned);
+    }
+}
@@ -0,0 +1,211 @@
+// src/journal.rs
+
+//! Write-ahead session journal, so a crash or power cut doesn't cost the
+//! player their balance.
+//!
+//! The journal holds the session's opening balance and then every game event
+//! as it happens. Each event is written out before the game moves on, and
+//! the file is synced to disk at the end of every round. A clean exit closes
+//! the journal; a journal that was never closed belongs to an interrupted
+//! session, and [`find_interrupted`] works out what it was worth and which
+//! bets were still standing, so the session can be resumed. Every line
+//! carries a [chained MAC](crate::integrity), so a journal edited to recover
+//! a bigger balance is caught.
+
+use crate::game::bets::Bet;
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use crate::game::rules::Difficulty;
+use crate::integrity::{self, Integrity, IntegrityKey, MacChain};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// One line of the journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "entry", rename_all = "snake_case")]
+pub enum JournalEntry {
+    Start {
+        balance: Money,
+        wheel: String,
+        #[serde(default)]
+        difficulty: Difficulty,
+        /// Id of the table played at, if one was chosen.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        table: Option<String>,
+        /// Name of the profile played as, if any.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        profile: Option<String>,
+    },
+    Event { round: u32, event: GameEvent },
+    /// The session ended normally with this balance.
+    End { balance: Money },
+}
+
+/// An [`EventSink`] appending every event to the journal file.
+pub struct Journal {
+    file: File,
+    path: PathBuf,
+    chain: Option<MacChain>,
+}
+
+impl Journal {
+    /// The default location: `session.journal` inside the [data directory](crate::data_dir).
+    pub fn default_location() -> PathBuf {
+        crate::data_dir().join("session.journal")
+    }
+
+    /// Starts a new journal at `path`, replacing any old one.
+    pub fn start(
+        path: impl Into<PathBuf>,
+        balance: Money,
+        wheel: &str,
+        difficulty: Difficulty,
+        table: Option<&str>,
+        profile: Option<&str>,
+    ) -> io::Result<Journal> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(&path)?;
+        let chain = match IntegrityKey::load_or_create(&IntegrityKey::default_location()) {
+            Ok(key) => Some(MacChain::new(key)),
+            Err(e) => {
+                crate::console::warn(format_args!("Can't sign the session journal: {}", e));
+                None
+            }
+        };
+        let mut journal = Journal { file, path, chain };
+        journal.append(&JournalEntry::Start {
+            balance,
+            wheel: wheel.to_string(),
+            difficulty,
+            table: table.map(str::to_string),
+            profile: profile.map(str::to_string),
+        }, true)?;
+        Ok(journal)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Closes the journal: the session ended normally with `balance`.
+    pub fn finish(&mut self, balance: Money) -> io::Result<()> {
+        self.append(&JournalEntry::End { balance }, true)
+    }
+
+    fn append(&mut self, entry: &JournalEntry, sync: bool) -> io::Result<()> {
+        let line = match self.chain.as_mut() {
+            Some(chain) => chain.seal(entry),
+            None => serde_json::to_string(entry),
+        }
+        .map_err(io::Error::other)?;
+        writeln!(self.file, "{}", line)?;
+        if sync { self.file.sync_data() } else { Ok(()) }
+    }
+}
+
+impl EventSink for Journal {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        let end_of_round = matches!(event, GameEvent::RoundComplete { .. });
+        if let Err(e) = self.append(&JournalEntry::Event { round, event: event.clone() }, end_of_round) {
+            crate::console::warn(format_args!("Could not write session journal {}: {}", self.path.display(), e));
+        }
+    }
+}
+
+/// What an interrupted session left behind.
+#[derive(Debug, Clone)]
+pub struct InterruptedSession {
+    pub wheel: String,
+    /// The profile the session was played as, if any.
+    pub profile: Option<String>,
+    /// Rounds settled (and not voided) before the interruption.
+    pub rounds: u32,
+    /// Balance after the last recorded change.
+    pub balance: Money,
+    /// Bets that were placed but never spun. A resumed session puts them
+    /// back on the table.
+    pub standing: Vec<Bet>,
+    /// Whether the journal is as the game wrote it.
+    pub integrity: Integrity,
+}
+
+impl InterruptedSession {
+    /// Stakes on the standing bets.
+    pub fn unspun(&self) -> Money {
+        self.standing.iter().map(|bet| bet.amount).sum()
+    }
+
+    /// The balance to carry on with, once the standing bets' stakes are
+    /// returned.
+    pub fn recovered_balance(&self) -> Money {
+        self.balance + self.unspun()
+    }
+}
+
+/// Reads the journal at `path` and returns the session it records if that
+/// session never ended. Lines that don't parse (such as one cut off mid-write)
+/// are skipped.
+pub fn find_interrupted(path: &Path) -> io::Result<Option<InterruptedSession>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let mut session: Option<InterruptedSession> = None;
+    let mut last_bets = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let Ok(entry) = serde_json::from_str::<JournalEntry>(&line?) else { continue };
+        match entry {
+            JournalEntry::Start { balance, wheel, profile, .. } => {
+                session = Some(InterruptedSession {
+                    wheel,
+                    profile,
+                    rounds: 0,
+                    balance,
+                    standing: Vec::new(),
+                    integrity: Integrity::Unsigned,
+                });
+            }
+            JournalEntry::End { .. } => session = None,
+            JournalEntry::Event { event, .. } => {
+                let Some(session) = session.as_mut() else { continue };
+                match event {
+                    GameEvent::BetPlaced { bet_type, amount, note } => {
+                        session.standing.push(Bet { bet_type, amount, note });
+                    }
+                    GameEvent::BetNoted { index, note } => {
+                        if let Some(bet) = session.standing.get_mut(index) {
+                            bet.note = Some(note).filter(|note| !note.is_empty());
+                        }
+                    }
+                    GameEvent::BetsCleared { .. } => session.standing.clear(),
+                    GameEvent::BetRefunded { bet_type, amount } => {
+                        if let Some(i) = session.standing.iter().position(|bet| bet.bet_type == bet_type && bet.amount == amount) {
+                            session.standing.remove(i);
+                        }
+                    }
+                    GameEvent::RoundComplete { .. } => {
+                        session.rounds += 1;
+                        last_bets = std::mem::take(&mut session.standing);
+                    }
+                    // The voided round's bets stand again, waiting for a spin.
+                    GameEvent::SpinVoided { .. } => {
+                        session.rounds = session.rounds.saturating_sub(1);
+                        session.standing = last_bets.clone();
+                    }
+                    GameEvent::BalanceChange { balance, .. } => session.balance = balance,
+                    _ => {}
+                }
+            }
+        }
+    }
+    if let Some(session) = session.as_mut() {
+        session.integrity = integrity::check_log_file(path)?;
+    }
+    Ok(session)
+}
@@ -0,0 +1,77 @@
+// src/lib.rs
+
+//! Wall Street Roulette engine: wheel, bets, players, and game flow.
+//!
+//! With default features off the crate is `no_std` (it still needs `alloc`)
+//! and offers the wheel, bets, and payouts: enough to drive a physical wheel
+//! from a microcontroller with whatever RNG the board provides.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+#[cfg(feature = "cli")]
+pub mod accessibility;
+pub mod analysis;
+#[cfg(feature = "std")]
+pub mod bench;
+#[cfg(feature = "cli")]
+pub mod campaign;
+#[cfg(feature = "cli")]
+pub mod challenge;
+#[cfg(feature = "charts")]
+pub mod charts;
+#[cfg(feature = "cli")]
+pub mod config;
+pub mod console;
+pub mod game;
+#[cfg(feature = "std")]
+pub mod history;
+#[cfg(feature = "cli")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod integrity;
+#[cfg(feature = "std")]
+pub mod journal;
+#[cfg(feature = "cli")]
+pub mod machine;
+#[cfg(feature = "std")]
+pub mod optimize;
+#[cfg(feature = "std")]
+pub mod presentation;
+#[cfg(feature = "std")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod renderer;
+#[cfg(feature = "std")]
+pub mod replay;
+#[cfg(feature = "std")]
+pub mod selftest;
+#[cfg(feature = "std")]
+pub mod simulation;
+#[cfg(feature = "cli")]
+pub mod spectate;
+#[cfg(feature = "audio")]
+pub mod sound;
+#[cfg(feature = "cli")]
+pub mod tables;
+#[cfg(feature = "std")]
+pub mod time_attack;
+#[cfg(feature = "cli")]
+pub mod turbo;
+#[cfg(feature = "std")]
+pub mod tutorial;
+#[cfg(feature = "cli")]
+pub mod wheel_file;
+#[cfg(feature = "notifications")]
+pub mod notifications;
+
+/// Directory for persistent data (profiles, round history): `$HOME/.roulette`.
+#[cfg(feature = "std")]
+pub fn data_dir() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    home.join(".roulette")
+}
@@ -0,0 +1,189 @@
+// src/machine.rs
+
+//! The machine protocol: the game driven a line at a time by a script.
+//!
+//! When stdin isn't a terminal the game drops its menus and prompts. It reads
+//! one command per line and answers each with exactly one line of JSON on
+//! stdout; everything written for people is switched off, so a test harness
+//! or wrapper script can read the answers as they come.
+//!
+//! | Command | Does |
+//! |---|---|
+//! | `bet <BET>` | places a bet in the [bet syntax](crate::game::dsl), with an optional `# note` |
+//! | `bets` | lists the bets on the table, and any held in prison |
+//! | `clear` | takes back every bet |
+//! | `rebet` | places last round's bets again |
+//! | `balance` | reports the balance |
+//! | `spin` | spins and settles the bets |
+//! | `help` | lists the commands |
+//! | `quit` | takes back any bets and leaves the table |
+//!
+//! Every answer has `"ok"`. When it is `false`, `"error"` says why and
+//! nothing changed. Otherwise most answers carry the `"balance"`, and `spin`
+//! adds where the ball landed, the round's totals, and its
+//! [events](crate::game::events) from the spin on:
+//!
+//! ```text
+//! bet red 10
+//! {"balance":990,"bet":"red 10","ok":true}
+//! spin
+//! {"balance":1010,"color":"Red","events":[...],"number":3,"ok":true,"returned":20,"round":1,"ticker":"AAPL","wagered":10}
+//! ```
+//!
+//! Blank lines are skipped. The end of input leaves the table like `quit`,
+//! without an answer. A spin can also end the session, by emptying the
+//! balance, deciding a challenge, or running out the clock; the game then
+//! exits after the spin's answer.
+
+use crate::game::Game;
+use crate::game::bets::Bet;
+use crate::game::controller::{PlayerController, Turn};
+use crate::game::dsl;
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use crate::game::wheel::{PocketId, Wheel};
+use crate::input;
+use serde_json::{Value, json};
+use std::cell::RefCell;
+use std::io::Write;
+use std::rc::Rc;
+
+const COMMANDS: [&str; 8] = ["bet", "bets", "clear", "rebet", "balance", "spin", "help", "quit"];
+
+/// The events of the round being spun, kept for its answer.
+#[derive(Default)]
+struct RoundEvents {
+    round: u32,
+    events: Vec<GameEvent>,
+}
+
+impl EventSink for RoundEvents {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        self.round = round;
+        self.events.push(event.clone());
+    }
+}
+
+/// A seat played over the machine protocol; see the [module docs](self).
+pub struct MachinePlayer<W> {
+    output: W,
+    round: Rc<RefCell<RoundEvents>>,
+}
+
+impl<W: Write> MachinePlayer<W> {
+    /// Takes a seat at `game`, answering on `output`.
+    pub fn new(game: &mut Game, output: W) -> Self {
+        let round = Rc::new(RefCell::new(RoundEvents::default()));
+        game.add_event_sink(Box::new(round.clone()));
+        MachinePlayer { output, round }
+    }
+
+    fn answer(&mut self, response: Value) {
+        // A reader that has gone away finds out at the next command.
+        let _ = writeln!(self.output, "{}", response);
+        let _ = self.output.flush();
+    }
+
+    fn error(&mut self, message: impl ToString) {
+        self.answer(json!({ "ok": false, "error": message.to_string() }));
+    }
+
+    fn place(&mut self, game: &mut Game, spec: &str) {
+        let bet = match dsl::parse_bet_line(spec, game.wheel()) {
+            Ok(bet) => bet,
+            Err(e) => return self.error(e),
+        };
+        let placed = format_line(&bet);
+        match game.try_place_bet(bet) {
+            Ok(()) => self.answer(json!({ "ok": true, "bet": placed, "balance": game.get_player_balance() })),
+            Err(e) => self.error(e),
+        }
+    }
+
+    /// Answers commands until one spins or leaves.
+    fn read_commands(&mut self, game: &mut Game) -> Turn {
+        while let Some(line) = input::read_line() {
+            let line = line.trim();
+            let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match command {
+                "" => {}
+                "bet" => self.place(game, rest),
+                "bets" => {
+                    let bets: Vec<String> = game.get_current_bets().iter().map(format_line).collect();
+                    let imprisoned: Vec<String> = game.imprisoned_bets().iter().map(format_line).collect();
+                    self.answer(json!({ "ok": true, "bets": bets, "imprisoned": imprisoned }));
+                }
+                "clear" => {
+                    if !game.get_current_bets().is_empty() {
+                        game.clear_bets();
+                    }
+                    self.answer(json!({ "ok": true, "balance": game.get_player_balance() }));
+                }
+                "rebet" => match game.rebet() {
+                    Ok(placed) => self.answer(json!({ "ok": true, "placed": placed, "balance": game.get_player_balance() })),
+                    Err(e) => self.error(e),
+                },
+                "balance" => self.answer(json!({ "ok": true, "balance": game.get_player_balance() })),
+                "spin" if !game.has_bets_in_play() => self.error("no bets to spin for"),
+                "spin" => {
+                    self.round.borrow_mut().events.clear();
+                    return Turn::Play;
+                }
+                "help" => self.answer(json!({ "ok": true, "commands": COMMANDS })),
+                "quit" => {
+                    if !game.get_current_bets().is_empty() {
+                        game.clear_bets();
+                    }
+                    self.answer(json!({ "ok": true, "balance": game.get_player_balance() }));
+                    return Turn::Leave;
+                }
+                _ => self.error(format!("unknown command '{}'", command)),
+            }
+        }
+        Turn::Leave
+    }
+}
+
+/// A bet as a line of a bets file, note and all.
+fn format_line(bet: &Bet) -> String {
+    match &bet.note {
+        Some(note) => format!("{} # {}", dsl::format_bet(bet), note),
+        None => dsl::format_bet(bet),
+    }
+}
+
+impl<W: Write> PlayerController for MachinePlayer<W> {
+    fn name(&self) -> &str {
+        "machine"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        self.read_commands(game)
+    }
+
+    fn spin_settled(&mut self, _wheel: &Wheel, _pocket: PocketId, _returned: Money) {
+        let RoundEvents { round, events } = std::mem::take(&mut *self.round.borrow_mut());
+        let mut answer = json!({ "ok": true, "round": round });
+        for event in &events {
+            match event {
+                GameEvent::Spin { number, ticker, color, .. } => {
+                    answer["number"] = json!(number);
+                    answer["ticker"] = json!(ticker);
+                    answer["color"] = json!(color);
+                }
+                GameEvent::RoundComplete { wagered, returned, balance } => {
+                    answer["wagered"] = json!(wagered);
+                    answer["returned"] = json!(returned);
+                    answer["balance"] = json!(balance);
+                }
+                _ => {}
+            }
+        }
+        answer["events"] = json!(events);
+        self.answer(answer);
+    }
+
+    fn play_again(&mut self, _game: &mut Game) -> bool {
+        true
+    }
+}
@@ -0,0 +1,81 @@
+// src/notifications.rs
+
+//! Desktop notifications for notable game events (requires the `notifications` feature).
+
+use crate::game::currency::Currency;
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use notify_rust::Notification;
+
+/// When a notification should fire.
+#[derive(Debug, Clone, Copy)]
+pub struct NotificationThresholds {
+    /// Notify when a single bet's net profit reaches this amount.
+    pub big_win: Money,
+    /// Notify when a bet paying at least this multiplier wins (35 = straight up).
+    pub jackpot_multiplier: u32,
+    /// Notify when the balance is wiped out at the end of a round.
+    pub bankruptcy: bool,
+}
+
+impl Default for NotificationThresholds {
+    fn default() -> Self {
+        NotificationThresholds { big_win: Money::from_units(500), jackpot_multiplier: 35, bankruptcy: true }
+    }
+}
+
+/// An [`EventSink`] that raises desktop notifications for big wins, jackpots, and bankruptcy.
+pub struct NotificationSink {
+    thresholds: NotificationThresholds,
+    /// What the table plays in, for the amounts in the notifications.
+    currency: Currency,
+    reported_failure: bool,
+}
+
+impl NotificationSink {
+    pub fn new(thresholds: NotificationThresholds, currency: Currency) -> Self {
+        NotificationSink { thresholds, currency, reported_failure: false }
+    }
+
+    fn notify(&mut self, summary: &str, body: &str) {
+        let result = Notification::new()
+            .appname("Wall Street Roulette")
+            .summary(summary)
+            .body(body)
+            .show();
+        // Only complain once; a missing notification daemon shouldn't spam the session.
+        if let Err(e) = result
+            && !self.reported_failure
+        {
+            crate::console::warn(format_args!("Desktop notifications unavailable: {}", e));
+            self.reported_failure = true;
+        }
+    }
+}
+
+impl EventSink for NotificationSink {
+    fn record(&mut self, round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::Settlement { bet_type, amount, won: true, payout, .. } => {
+                let profit = payout.saturating_sub(*amount);
+                // Odds paid, recovered from the payout so wheels with their own tables count too.
+                let odds = payout.cents() / amount.cents().max(1) - 1;
+                if odds >= self.thresholds.jackpot_multiplier as i64 {
+                    self.notify(
+                        "Jackpot!",
+                        &format!("Round {}: {} paid {}", round, bet_type, self.currency.amount(*payout)),
+                    );
+                } else if profit >= self.thresholds.big_win {
+                    self.notify(
+                        "Big win",
+                        &format!("Round {}: {} won {} profit", round, bet_type, self.currency.amount(profit)),
+                    );
+                }
+            }
+            GameEvent::RoundComplete { balance, .. } if balance.is_zero() && self.thresholds.bankruptcy => {
+                self.notify("Bankrupt", &format!("Balance wiped out in round {}", round));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,154 @@
+// src/optimize.rs
+
+//! Grid search over a [strategy file's](crate::game::strategy) numbers.
+//!
+//! Every combination of base stake, stake cap, and stop-loss is simulated
+//! with the same seed, so candidates face the same spins and differ only in
+//! how they bet. The results come back ranked by an [`Objective`], each with
+//! the simulation report that holds its risk figures.
+
+use crate::game::dsl::ParseBetError;
+use crate::game::money::Money;
+use crate::game::strategy::{DeclarativeStrategy, StrategySpec};
+use crate::game::wheel::WheelLike;
+use crate::simulation::{self, SimulationConfig, SimulationError, SimulationReport};
+use std::fmt;
+
+/// Values to try for each parameter. An empty list keeps the spec's own
+/// value; `None` in a list means no cap or no stop-loss.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchSpace {
+    pub base_stakes: Vec<Money>,
+    pub max_stakes: Vec<Option<Money>>,
+    pub stop_losses: Vec<Option<Money>>,
+}
+
+impl SearchSpace {
+    /// Every combination, applied to `spec`. Combinations the spec rejects,
+    /// such as a cap below the base stake, are left out.
+    pub fn candidates(&self, spec: &StrategySpec) -> Vec<StrategySpec> {
+        let or_current =
+            |values: &[Money], current: Money| if values.is_empty() { vec![current] } else { values.to_vec() };
+        let or_current_opt = |values: &[Option<Money>], current: Option<Money>| {
+            if values.is_empty() { vec![current] } else { values.to_vec() }
+        };
+
+        let mut candidates = Vec::new();
+        for base in or_current(&self.base_stakes, spec.base_stake) {
+            for max in or_current_opt(&self.max_stakes, spec.progression.max_stake) {
+                for stop_loss in or_current_opt(&self.stop_losses, spec.stop.stop_loss) {
+                    let mut candidate = spec.clone();
+                    candidate.base_stake = base;
+                    candidate.progression.max_stake = max;
+                    candidate.stop.stop_loss = stop_loss;
+                    if candidate.validate().is_ok() {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+
+/// What makes one candidate better than another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Objective {
+    /// Highest mean final balance.
+    #[default]
+    MeanBalance,
+    /// Most trials finishing ahead.
+    ChanceAhead,
+    /// Fewest trials going bust.
+    LowestBust,
+}
+
+impl Objective {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "mean" => Some(Objective::MeanBalance),
+            "ahead" => Some(Objective::ChanceAhead),
+            "bust" => Some(Objective::LowestBust),
+            _ => None,
+        }
+    }
+
+    /// Higher is better.
+    fn score(self, report: &SimulationReport) -> f64 {
+        match self {
+            Objective::MeanBalance => report.mean_final_balance,
+            Objective::ChanceAhead => report.ahead_rate_percent(),
+            Objective::LowestBust => -report.bust_rate_percent(),
+        }
+    }
+}
+
+/// Why a search could not run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptimizeError {
+    /// No combination of the given values makes a valid strategy.
+    NoCandidates,
+    Bet(ParseBetError),
+    Simulation(SimulationError),
+}
+
+impl fmt::Display for OptimizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptimizeError::NoCandidates => write!(f, "no combination of the given values is a valid strategy"),
+            OptimizeError::Bet(e) => write!(f, "invalid bet in strategy: {}", e),
+            OptimizeError::Simulation(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for OptimizeError {}
+
+/// One simulated combination.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub spec: StrategySpec,
+    pub report: SimulationReport,
+}
+
+/// Simulates every candidate in `space` and returns them best first.
+pub fn grid_search(
+    wheel: &dyn WheelLike,
+    spec: &StrategySpec,
+    space: &SearchSpace,
+    objective: Objective,
+    config: &SimulationConfig,
+) -> Result<Vec<Candidate>, OptimizeError> {
+    let specs = space.candidates(spec);
+    if specs.is_empty() {
+        return Err(OptimizeError::NoCandidates);
+    }
+    let mut results = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let strategy = DeclarativeStrategy::new(&spec, wheel.layout()).map_err(OptimizeError::Bet)?;
+        let report =
+            simulation::simulate_strategy(wheel, &strategy, config).map_err(OptimizeError::Simulation)?;
+        results.push(Candidate { spec, report });
+    }
+    results.sort_by(|a, b| objective.score(&b.report).total_cmp(&objective.score(&a.report)));
+    Ok(results)
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dollars = |v: Option<Money>| v.map_or("none".to_string(), |v| v.to_string());
+        let report = &self.report;
+        write!(
+            f,
+            "{:>6} {:>7} {:>10} {:>11.2} {:>8} {:>7} {:>6.1}% {:>6.1}%",
+            self.spec.base_stake,
+            dollars(self.spec.progression.max_stake),
+            dollars(self.spec.stop.stop_loss),
+            report.mean_final_balance,
+            report.median_final_balance,
+            report.min_final_balance,
+            report.ahead_rate_percent(),
+            report.bust_rate_percent(),
+        )
+    }
+}
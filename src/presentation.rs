@@ -0,0 +1,178 @@
+// src/presentation.rs
+
+//! How pocket colours are shown, for every view at once.
+//!
+//! Red, black, and green are hard to tell apart with the common kinds of
+//! colour blindness, so the [`Palette`] can swap them for colours that stay
+//! distinct, take exact colours from the config, and put a letter before
+//! each colour name (`[R] Red`) so nothing rests on the colour alone. The
+//! terminal prints colours with [`color`], as far as the
+//! [renderer](crate::renderer) allows; the GUI fills with [`Palette::rgb`].
+//! Both read the palette set here with [`set_palette`].
+//!
+//! ```toml
+//! [palette]
+//! scheme = "color_blind"   # or "high_contrast", or "classic" (the default)
+//! symbols = true           # R, B, and G before colour names and numbers
+//! red = "#D55E00"          # exact colours, over the scheme's
+//! ```
+
+use crate::game::wheel::Color;
+use crate::renderer::{self, Renderer};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+static PALETTE: RwLock<Palette> = RwLock::new(Palette::CLASSIC);
+
+/// A built-in set of pocket colours.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scheme {
+    /// Casino red, black, and green.
+    #[default]
+    Classic,
+    /// Vermilion, black, and blue from the Okabe-Ito palette, apart under
+    /// red-green and blue-yellow colour blindness alike.
+    #[serde(alias = "colorblind")]
+    ColorBlind,
+    /// Yellow, black, and blue at full strength, for low vision.
+    HighContrast,
+}
+
+impl Scheme {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().replace('-', "_").as_str() {
+            "classic" => Some(Scheme::Classic),
+            "color_blind" | "colorblind" => Some(Scheme::ColorBlind),
+            "high_contrast" => Some(Scheme::HighContrast),
+            _ => None,
+        }
+    }
+
+    fn rgb(self, color: Color) -> Rgb {
+        match (self, color) {
+            (Scheme::Classic, Color::Red) => Rgb(180, 25, 30),
+            (Scheme::Classic, Color::Black) => Rgb(25, 25, 25),
+            (Scheme::Classic, Color::Green) => Rgb(20, 120, 60),
+            (Scheme::ColorBlind, Color::Red) => Rgb(213, 94, 0),
+            (Scheme::ColorBlind, Color::Black) => Rgb(25, 25, 25),
+            (Scheme::ColorBlind, Color::Green) => Rgb(0, 114, 178),
+            (Scheme::HighContrast, Color::Red) => Rgb(240, 200, 0),
+            (Scheme::HighContrast, Color::Black) => Rgb(0, 0, 0),
+            (Scheme::HighContrast, Color::Green) => Rgb(0, 70, 255),
+        }
+    }
+}
+
+/// A colour as red, green, and blue, written `#RRGGBB` in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub fn parse(text: &str) -> Option<Rgb> {
+        let hex = text.trim().strip_prefix('#')?;
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |at: usize| u8::from_str_radix(&hex[at..at + 2], 16).ok();
+        Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.0, self.1, self.2)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Rgb::parse(&text).ok_or_else(|| serde::de::Error::custom(format!("'{}' isn't a colour like \"#D55E00\"", text)))
+    }
+}
+
+/// How pocket colours look, the `[palette]` table of the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Palette {
+    pub scheme: Scheme,
+    /// Mark colours with a letter as well: `[R] Red`, and `R 7` on the board.
+    pub symbols: bool,
+    pub red: Option<Rgb>,
+    pub black: Option<Rgb>,
+    pub green: Option<Rgb>,
+}
+
+impl Palette {
+    pub const CLASSIC: Palette = Palette { scheme: Scheme::Classic, symbols: false, red: None, black: None, green: None };
+
+    pub fn rgb(&self, color: Color) -> Rgb {
+        let chosen = match color {
+            Color::Red => self.red,
+            Color::Black => self.black,
+            Color::Green => self.green,
+        };
+        chosen.unwrap_or_else(|| self.scheme.rgb(color))
+    }
+
+    /// `label` with the colour's letter in front, if the palette uses them.
+    pub fn mark(&self, color: Color, label: &str) -> String {
+        if self.symbols { format!("{} {}", symbol(color), label) } else { label.to_string() }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::CLASSIC
+    }
+}
+
+/// The letter that stands for `color`.
+pub fn symbol(color: Color) -> char {
+    match color {
+        Color::Red => 'R',
+        Color::Black => 'B',
+        Color::Green => 'G',
+    }
+}
+
+/// Shows colours with `palette` from now on.
+pub fn set_palette(palette: Palette) {
+    *PALETTE.write().unwrap_or_else(|e| e.into_inner()) = palette;
+}
+
+pub fn palette() -> Palette {
+    *PALETTE.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// `color`'s name as the palette shows it, for printing.
+pub fn color(color: Color) -> ColorName {
+    ColorName { color, palette: palette(), renderer: renderer::current() }
+}
+
+/// A colour name ready to print; see [`color`]. Padding such as `{:<9}` counts
+/// only the visible text.
+#[derive(Clone)]
+pub struct ColorName {
+    color: Color,
+    palette: Palette,
+    renderer: Arc<dyn Renderer>,
+}
+
+impl fmt::Display for ColorName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.color.to_string();
+        let text = if self.palette.symbols { format!("[{}] {}", symbol(self.color), name) } else { name };
+        let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+        let (before, after) = match f.align() {
+            Some(fmt::Alignment::Right) => (padding, 0),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            _ => (0, padding),
+        };
+        write!(f, "{:before$}", "")?;
+        f.write_str(&self.renderer.swatch(&text, self.palette.rgb(self.color)))?;
+        write!(f, "{:after$}", "")
+    }
+}
@@ -0,0 +1,90 @@
+// src/profile/crypt.rs
+
+//! Passphrase encryption for profile files.
+//!
+//! An encrypted file is a short header, a random salt and nonce, and the
+//! profile JSON sealed with ChaCha20-Poly1305 under a key stretched from the
+//! passphrase with Argon2. The authentication tag means a wrong passphrase
+//! and an edited file are both caught on load.
+
+use super::BackendError;
+use std::fmt;
+
+/// First bytes of every encrypted profile file.
+const MAGIC: &[u8] = b"ROULETTE-ENCRYPTED-1\n";
+#[cfg(feature = "encryption")]
+const SALT_LEN: usize = 16;
+#[cfg(feature = "encryption")]
+const NONCE_LEN: usize = 12;
+
+/// A profile passphrase. Kept out of `Debug` output.
+#[derive(Clone)]
+pub struct Passphrase(#[cfg_attr(not(feature = "encryption"), allow(dead_code))] String);
+
+impl Passphrase {
+    pub fn new(passphrase: impl Into<String>) -> Self {
+        Passphrase(passphrase.into())
+    }
+}
+
+impl fmt::Debug for Passphrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Passphrase(..)")
+    }
+}
+
+pub(super) fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+#[cfg(feature = "encryption")]
+fn cipher(passphrase: &Passphrase, salt: &[u8]) -> Result<chacha20poly1305::ChaCha20Poly1305, BackendError> {
+    use chacha20poly1305::KeyInit;
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.0.as_bytes(), salt, &mut key)
+        .map_err(|e| BackendError::Other(format!("can't derive key: {}", e)))?;
+    Ok(chacha20poly1305::ChaCha20Poly1305::new(&key.into()))
+}
+
+#[cfg(feature = "encryption")]
+pub(super) fn seal(plaintext: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, BackendError> {
+    use chacha20poly1305::aead::Aead;
+    use rand::RngCore;
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let sealed = cipher(passphrase, &salt)?
+        .encrypt(&nonce.into(), plaintext)
+        .map_err(|_| BackendError::Other("encryption failed".into()))?;
+    Ok([MAGIC, &salt, &nonce, &sealed].concat())
+}
+
+#[cfg(feature = "encryption")]
+pub(super) fn open(bytes: &[u8], passphrase: &Passphrase) -> Result<Vec<u8>, BackendError> {
+    use chacha20poly1305::aead::Aead;
+    let body = &bytes[MAGIC.len()..];
+    if body.len() < SALT_LEN + NONCE_LEN {
+        return Err(BackendError::BadPassphrase);
+    }
+    let (salt, rest) = body.split_at(SALT_LEN);
+    let (nonce, sealed) = rest.split_at(NONCE_LEN);
+    let nonce: [u8; NONCE_LEN] = nonce.try_into().expect("split at NONCE_LEN");
+    cipher(passphrase, salt)?.decrypt(&nonce.into(), sealed).map_err(|_| BackendError::BadPassphrase)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unsupported() -> BackendError {
+    BackendError::Other("this build has no profile encryption support (enable the `encryption` feature)".into())
+}
+
+#[cfg(not(feature = "encryption"))]
+pub(super) fn seal(_plaintext: &[u8], _passphrase: &Passphrase) -> Result<Vec<u8>, BackendError> {
+    Err(unsupported())
+}
+
+#[cfg(not(feature = "encryption"))]
+pub(super) fn open(_bytes: &[u8], _passphrase: &Passphrase) -> Result<Vec<u8>, BackendError> {
+    Err(unsupported())
+}
@@ -0,0 +1,263 @@
+// src/profile/file.rs
+
+//! Profiles stored as one JSON file each inside a local directory.
+//!
+//! Given a passphrase, the backend encrypts the files it writes (see
+//! [`crypt`](super::crypt)) and reads encrypted and plain files alike.
+//! Every file is signed with the machine's integrity key; reading one whose
+//! signature doesn't match flags the profile as tampered.
+
+use super::crypt::{self, Passphrase};
+use super::{BackendError, Profile, ProfileBackend};
+use crate::integrity::{Integrity, IntegrityKey};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for another process to release a profile lock.
+const LOCK_ATTEMPTS: u32 = 50;
+const LOCK_RETRY_DELAY: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    dir: PathBuf,
+    passphrase: Option<Passphrase>,
+}
+
+impl FileBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileBackend { dir: dir.into(), passphrase: None }
+    }
+
+    /// The default location: `profiles` inside the [data directory](crate::data_dir).
+    pub fn default_location() -> Self {
+        FileBackend::new(crate::data_dir().join("profiles"))
+    }
+
+    /// Encrypts saved profiles with `passphrase` and uses it to open
+    /// encrypted ones.
+    pub fn with_passphrase(mut self, passphrase: Passphrase) -> Self {
+        self.passphrase = Some(passphrase);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether `name`'s profile is stored encrypted.
+    pub fn is_encrypted(&self, name: &str) -> bool {
+        fs::read(self.path_for(name)).is_ok_and(|bytes| crypt::is_encrypted(&bytes))
+    }
+
+    /// `name`'s file. A name that isn't filesystem-safe is made safe and
+    /// given a hash of itself after a dot, which no safe name contains, so
+    /// names that clean up alike (`a b`, `a.b`, `a_b`) never share a file.
+    fn path_for(&self, name: &str) -> PathBuf {
+        let file = safe_name(name);
+        if file == name {
+            return self.dir.join(format!("{}.json", file));
+        }
+        let hash: String = Sha256::digest(name.as_bytes())[..4].iter().map(|b| format!("{:02x}", b)).collect();
+        self.dir.join(format!("{}.{}.json", file, hash))
+    }
+
+    /// Reads `name`'s profile and the file it's in. Names that needed making
+    /// safe used to be stored without the hash; such a file still counts if
+    /// the profile in it has this name, and the next save moves it.
+    fn read_named(&self, name: &str) -> Result<Option<(Profile, PathBuf)>, BackendError> {
+        let path = self.path_for(name);
+        if let Some(profile) = self.read(&path)? {
+            return Ok(Some((profile, path)));
+        }
+        let old = self.dir.join(format!("{}.json", safe_name(name)));
+        if old == path {
+            return Ok(None);
+        }
+        Ok(self.read(&old)?.filter(|profile| profile.name == name).map(|profile| (profile, old)))
+    }
+
+    fn read(&self, path: &Path) -> Result<Option<Profile>, BackendError> {
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut profile: Profile = if crypt::is_encrypted(&bytes) {
+            let passphrase = self.passphrase.as_ref().ok_or(BackendError::Encrypted)?;
+            serde_json::from_slice(&crypt::open(&bytes, passphrase)?)?
+        } else {
+            serde_json::from_slice(&bytes)?
+        };
+        let key = IntegrityKey::load(&IntegrityKey::default_location())?;
+        if profile.check(key.as_ref()) == Integrity::Tampered {
+            profile.tampered = true;
+        }
+        Ok(Some(profile))
+    }
+}
+
+/// `name` with everything but ASCII letters, digits, `-` and `_` turned
+/// into `_`.
+fn safe_name(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// Exclusive advisory lock on one profile, taken on a `.lock` file beside it
+/// and released on drop. The OS releases it when its process dies, so a
+/// crash never leaves a stale lock. The file itself is left in place:
+/// removing it would let a waiting process lock a file no longer there.
+struct LockFile(File);
+
+impl LockFile {
+    fn acquire(path: PathBuf) -> io::Result<Self> {
+        let file = OpenOptions::new().write(true).create(true).truncate(false).open(&path)?;
+        for _ in 0..LOCK_ATTEMPTS {
+            match file.try_lock() {
+                Ok(()) => return Ok(LockFile(file)),
+                Err(TryLockError::WouldBlock) => thread::sleep(LOCK_RETRY_DELAY),
+                Err(TryLockError::Error(e)) => return Err(e),
+            }
+        }
+        Err(io::Error::new(io::ErrorKind::WouldBlock, format!("{} is locked", path.display())))
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = self.0.unlock();
+    }
+}
+
+impl ProfileBackend for FileBackend {
+    fn load(&self, name: &str) -> Result<Option<Profile>, BackendError> {
+        Ok(self.read_named(name)?.map(|(profile, _)| profile))
+    }
+
+    fn save(&self, profile: &mut Profile) -> Result<(), BackendError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path_for(&profile.name);
+        let _lock = LockFile::acquire(path.with_extension("lock"))?;
+
+        let stored = self.read_named(&profile.name)?;
+        let stored_version = stored.as_ref().map(|(p, _)| p.version).unwrap_or(0);
+        if stored_version != profile.version {
+            return Err(BackendError::Conflict { stored_version });
+        }
+
+        let mut next = profile.clone();
+        next.version += 1;
+        next.sign(&IntegrityKey::load_or_create(&IntegrityKey::default_location())?);
+        // Write then rename so readers never see a half-written file.
+        let tmp = path.with_extension("json.tmp");
+        let json = serde_json::to_vec_pretty(&next)?;
+        let contents = match &self.passphrase {
+            Some(passphrase) => crypt::seal(&json, passphrase)?,
+            None => json,
+        };
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, &path)?;
+        if let Some((_, old)) = stored
+            && old != path
+        {
+            fs::remove_file(old)?;
+        }
+        profile.version = next.version;
+        Ok(())
+    }
+
+    /// Unreadable files, including ones encrypted under another passphrase,
+    /// are skipped.
+    fn list(&self) -> Result<Vec<Profile>, BackendError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json")
+                && let Ok(Some(profile)) = self.read(&path)
+            {
+                profiles.push(profile);
+            }
+        }
+        Ok(profiles)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, BackendError> {
+        let path = self.path_for(name);
+        let lock = path.with_extension("lock");
+        let path = match self.read_named(name) {
+            Ok(Some((_, found))) => found,
+            // Ours even if it can't be opened, say without the passphrase.
+            _ if path.exists() => path,
+            _ => return Ok(false),
+        };
+        let _lock = LockFile::acquire(lock)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.dir.display().to_string()
+    }
+
+    fn signs(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("roulette-file-test-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn names_that_clean_up_alike_get_their_own_files() {
+        let backend = FileBackend::new("profiles");
+        assert_eq!(backend.path_for("ada_1"), Path::new("profiles/ada_1.json"));
+        let paths = ["a b", "a.b", "a_b", "a/b"].map(|name| backend.path_for(name));
+        for (i, path) in paths.iter().enumerate() {
+            assert!(paths[i + 1..].iter().all(|other| other != path), "{:?}", paths);
+        }
+    }
+
+    #[test]
+    fn profiles_saved_under_the_old_cleaned_name_still_load() {
+        let dir = scratch("legacy");
+        let backend = FileBackend::new(&dir);
+        fs::write(dir.join("a_b.json"), serde_json::to_vec(&Profile::new("a b")).unwrap()).unwrap();
+        assert_eq!(backend.load("a b").unwrap().map(|profile| profile.name), Some("a b".to_string()));
+        assert!(backend.load("a.b").unwrap().is_none());
+        assert!(backend.delete("a b").unwrap());
+        assert!(!dir.join("a_b.json").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn a_lock_left_behind_does_not_block_and_a_held_one_does() {
+        let dir = scratch("lock");
+        let path = dir.join("ada.lock");
+        fs::write(&path, b"").unwrap();
+        let held = LockFile::acquire(path.clone()).unwrap();
+        let error = LockFile::acquire(path.clone()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+        drop(held);
+        assert!(LockFile::acquire(path).is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,87 @@
+// src/profile/http.rs
+
+//! Profiles synced with a remote server over HTTP (requires the `http` feature).
+//!
+//! The server is expected to expose:
+//!
+//! * `GET  {base}/profiles` — JSON array of profiles
+//! * `GET  {base}/profiles/{name}` — one profile, or 404
+//! * `PUT  {base}/profiles/{name}` — store a profile; the `If-Match` header
+//!   carries the version being replaced, and a stale version yields 409 or 412
+//! * `DELETE {base}/profiles/{name}` — remove a profile, or 404
+
+use super::{BackendError, Profile, ProfileBackend};
+
+pub struct HttpBackend {
+    base_url: String,
+}
+
+impl HttpBackend {
+    pub fn new(base_url: &str) -> Self {
+        HttpBackend { base_url: base_url.trim_end_matches('/').to_string() }
+    }
+
+    fn profile_url(&self, name: &str) -> String {
+        let encoded: String = name
+            .bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' => (b as char).to_string(),
+                _ => format!("%{:02X}", b),
+            })
+            .collect();
+        format!("{}/profiles/{}", self.base_url, encoded)
+    }
+}
+
+impl From<ureq::Error> for BackendError {
+    fn from(e: ureq::Error) -> Self {
+        BackendError::Other(format!("http: {}", e))
+    }
+}
+
+impl ProfileBackend for HttpBackend {
+    fn load(&self, name: &str) -> Result<Option<Profile>, BackendError> {
+        match ureq::get(&self.profile_url(name)).call() {
+            Ok(mut response) => Ok(Some(serde_json::from_str(&response.body_mut().read_to_string()?)?)),
+            Err(ureq::Error::StatusCode(404)) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, profile: &mut Profile) -> Result<(), BackendError> {
+        let mut next = profile.clone();
+        next.version += 1;
+        let result = ureq::put(&self.profile_url(&profile.name))
+            .header("If-Match", profile.version.to_string())
+            .content_type("application/json")
+            .send(serde_json::to_string(&next)?);
+        match result {
+            Ok(_) => {
+                profile.version = next.version;
+                Ok(())
+            }
+            Err(ureq::Error::StatusCode(409 | 412)) => {
+                let stored_version = self.load(&profile.name)?.map(|p| p.version).unwrap_or(0);
+                Err(BackendError::Conflict { stored_version })
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<Profile>, BackendError> {
+        let mut response = ureq::get(&format!("{}/profiles", self.base_url)).call()?;
+        Ok(serde_json::from_str(&response.body_mut().read_to_string()?)?)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, BackendError> {
+        match ureq::delete(&self.profile_url(name)).call() {
+            Ok(_) => Ok(true),
+            Err(ureq::Error::StatusCode(404)) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.base_url.clone()
+    }
+}
@@ -0,0 +1,755 @@
+// src/profile/mod.rs
+
+//! Persistent player profiles with lifetime statistics and a leaderboard.
+//!
+//! Profiles live behind a [`ProfileBackend`] so the same player can share
+//! balances and stats between machines. Saves are optimistic: each profile
+//! carries a version, and a save against a stale version fails with
+//! [`BackendError::Conflict`] so the caller can merge and retry.
+//!
+//! Profiles also carry the player's [experience and level](progression) and
+//! bankroll: a session with a profile starts from the balance the last one
+//! ended on, plus a [daily bonus](DAILY_BONUS) on the first session of the
+//! day, so a player who went broke can come back tomorrow. The bankroll is
+//! in dollars; money in other [currencies](crate::game::currency) is kept
+//! beside it.
+//!
+//! Local profile files are [signed](crate::integrity); one that fails its
+//! check is flagged as tampered and kept off the leaderboard for good. The
+//! leaderboard also looks through the [round history](crate::history) for
+//! what each profile's sessions show.
+
+mod crypt;
+mod file;
+#[cfg(feature = "http")]
+mod http;
+pub mod progression;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use crypt::Passphrase;
+pub use file::FileBackend;
+#[cfg(feature = "http")]
+pub use http::HttpBackend;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteBackend;
+
+use crate::game::currency::{Currency, ExchangeError, ExchangeRates};
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use crate::history::{GroupBy, Query, RoundRecord};
+use crate::integrity::{Integrity, IntegrityKey};
+use crate::say;
+use progression::Progress;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+
+/// Bankroll added on a profile's first session of each day (UTC).
+pub const DAILY_BONUS: Money = Money::from_units(100);
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Fewest rounds a session needs to rank by its return on investment, so a
+/// lucky first spin doesn't top the board.
+pub const MIN_ROI_ROUNDS: u32 = 10;
+
+/// Lifetime statistics accumulated across every session of a profile.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LifetimeStats {
+    /// Highest balance ever held.
+    pub peak_balance: Money,
+    /// Largest net gain in a single round.
+    pub biggest_win: Money,
+    /// Most rounds played in a single session.
+    pub rounds_survived: u32,
+    /// Total rounds played across all sessions.
+    pub total_rounds: u32,
+    /// Number of sessions started.
+    pub sessions: u32,
+    /// Experience earned, see [`progression`].
+    #[serde(default)]
+    pub xp: u64,
+    /// Comp points not yet redeemed, see [`comps`](crate::game::comps).
+    #[serde(default)]
+    pub comp_points: u64,
+    /// Best [time attack](crate::time_attack) score, once one has been played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_time_attack: Option<Money>,
+    /// Ids of the [challenges](crate::challenge) completed.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub challenges: BTreeSet<String>,
+    /// How far into the [campaign](crate::campaign) the profile is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub campaign: Option<CampaignProgress>,
+}
+
+/// A stage of the campaign and the bankroll to play it with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    /// Index of the stage to play next.
+    pub stage: u32,
+    pub bankroll: Money,
+}
+
+impl LifetimeStats {
+    /// Re-applies the progress made since `base` on top of `remote`.
+    /// Counters are added, records keep the best value.
+    pub fn rebase(&self, base: &LifetimeStats, remote: &LifetimeStats) -> LifetimeStats {
+        LifetimeStats {
+            peak_balance: remote.peak_balance.max(self.peak_balance),
+            biggest_win: remote.biggest_win.max(self.biggest_win),
+            rounds_survived: remote.rounds_survived.max(self.rounds_survived),
+            total_rounds: remote.total_rounds + self.total_rounds.saturating_sub(base.total_rounds),
+            sessions: remote.sessions + self.sessions.saturating_sub(base.sessions),
+            xp: remote.xp + self.xp.saturating_sub(base.xp),
+            // Points go down as well as up, so apply the net change.
+            comp_points: (remote.comp_points + self.comp_points).saturating_sub(base.comp_points),
+            best_time_attack: remote.best_time_attack.max(self.best_time_attack),
+            challenges: remote.challenges.union(&self.challenges).cloned().collect(),
+            // Progress isn't additive; the session that moved it last wins.
+            campaign: if self.campaign != base.campaign { self.campaign } else { remote.campaign },
+        }
+    }
+}
+
+/// A named player profile as stored by a backend.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Incremented by the backend on every successful save; 0 means never saved.
+    #[serde(default)]
+    pub version: u64,
+    pub lifetime: LifetimeStats,
+    /// Balance the last session ended on; `None` until a session is played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bankroll: Option<Money>,
+    /// Money held in currencies other than dollars.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub balances: BTreeMap<Currency, Money>,
+    /// Start of the last session, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_played: Option<u64>,
+    /// HMAC of the rest of the profile, see [`Profile::sign`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// Set once the profile has been caught edited outside the game.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tampered: bool,
+    /// The daily bonus [`check_in`](Profile::check_in) added to the bankroll,
+    /// until a save makes it the profile's; see [`Profile::rebase`].
+    #[serde(skip)]
+    pending_bonus: Money,
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Self {
+        Profile {
+            name: name.to_string(),
+            version: 0,
+            lifetime: LifetimeStats::default(),
+            bankroll: None,
+            balances: BTreeMap::new(),
+            last_played: None,
+            signature: None,
+            tampered: false,
+            pending_bonus: Money::ZERO,
+        }
+    }
+
+    /// Records a session starting now and returns the daily bonus it earns:
+    /// [`DAILY_BONUS`] for a returning profile that last played on an
+    /// earlier day, otherwise nothing. The bonus is added to the bankroll, but
+    /// another session can still claim it first; see [`rebase`](Self::rebase).
+    pub fn check_in(&mut self) -> Money {
+        let now = crate::history::unix_now();
+        let last = self.last_played.replace(now);
+        let Some(bankroll) = self.bankroll else { return Money::ZERO };
+        match last {
+            Some(last) if last / SECONDS_PER_DAY >= now / SECONDS_PER_DAY => Money::ZERO,
+            _ => {
+                self.bankroll = Some(bankroll.checked_add(DAILY_BONUS).unwrap_or(Money::MAX));
+                self.pending_bonus = DAILY_BONUS;
+                DAILY_BONUS
+            }
+        }
+    }
+
+    /// Re-applies the changes made since `base` on top of `remote`, a newer
+    /// save of the same profile. Stats merge as [`LifetimeStats::rebase`]
+    /// has them, and each balance moves by as much as it moved here. A daily
+    /// bonus not yet saved is dropped if `remote` checked in on the same day,
+    /// since that session was first to claim it.
+    pub fn rebase(&self, base: &Profile, remote: &Profile) -> Profile {
+        let currencies: BTreeSet<&Currency> = self.balances.keys().chain(remote.balances.keys()).collect();
+        let balances = currencies
+            .into_iter()
+            .filter_map(|currency| {
+                let moved = |profile: &Profile| profile.balances.get(currency).copied();
+                let amount = rebase_amount(moved(self), moved(base), moved(remote))?;
+                Some((currency.clone(), amount))
+            })
+            .collect();
+        let mut merged = Profile {
+            version: remote.version,
+            lifetime: self.lifetime.rebase(&base.lifetime, &remote.lifetime),
+            bankroll: rebase_amount(self.bankroll, base.bankroll, remote.bankroll),
+            balances,
+            last_played: remote.last_played.max(self.last_played),
+            tampered: self.tampered || remote.tampered,
+            ..self.clone()
+        };
+        // The bonus was due because the profile hadn't been played today
+        // when this session loaded it, so a check-in today is someone else's.
+        let claimed_elsewhere = remote
+            .last_played
+            .zip(self.last_played)
+            .is_some_and(|(theirs, ours)| theirs / SECONDS_PER_DAY >= ours / SECONDS_PER_DAY);
+        if merged.pending_bonus > Money::ZERO && claimed_elsewhere {
+            merged.bankroll = merged.bankroll.map(|bankroll| bankroll.saturating_sub(merged.pending_bonus));
+            merged.pending_bonus = Money::ZERO;
+        }
+        merged
+    }
+
+    /// Money held in `currency`: the bankroll for dollars. `None` if there
+    /// has never been any.
+    pub fn balance(&self, currency: &Currency) -> Option<Money> {
+        if currency.is_usd() { self.bankroll } else { self.balances.get(currency).copied() }
+    }
+
+    pub fn set_balance(&mut self, currency: &Currency, amount: Money) {
+        if currency.is_usd() {
+            self.bankroll = Some(amount);
+        } else {
+            self.balances.insert(currency.clone(), amount);
+        }
+    }
+
+    /// Changes `amount` of `from` into `to` at `rates`. Returns what it came to.
+    pub fn exchange(
+        &mut self,
+        amount: Money,
+        from: &Currency,
+        to: &Currency,
+        rates: &ExchangeRates,
+    ) -> Result<Money, ExchangeError> {
+        let balance = self.balance(from).unwrap_or_default();
+        if amount > balance {
+            return Err(ExchangeError::NotEnough { balance, currency: from.clone() });
+        }
+        let converted = rates.convert(amount, from, to)?;
+        let remaining = balance - amount;
+        let held = if from == to { remaining } else { self.balance(to).unwrap_or_default() };
+        let held = held.checked_add(converted).ok_or_else(|| ExchangeError::TooMuch { currency: to.clone() })?;
+        self.set_balance(from, remaining);
+        self.set_balance(to, held);
+        Ok(converted)
+    }
+
+    /// Whole days since the last session started, or `None` if there hasn't
+    /// been one.
+    pub fn days_since_played(&self) -> Option<u64> {
+        let last = self.last_played?;
+        Some(crate::history::unix_now().saturating_sub(last) / SECONDS_PER_DAY)
+    }
+
+    /// The player's level, from the XP earned so far.
+    pub fn progress(&self) -> Progress {
+        Progress::from_xp(self.lifetime.xp)
+    }
+
+    fn signed_bytes(&self) -> Vec<u8> {
+        let unsigned = Profile { signature: None, ..self.clone() };
+        serde_json::to_vec(&unsigned).expect("profiles are always serializable")
+    }
+
+    pub fn sign(&mut self, key: &IntegrityKey) {
+        self.signature = Some(key.sign(&self.signed_bytes()));
+    }
+
+    /// Checks the signature against `key`. A profile already flagged as
+    /// tampered stays tampered. Every save signs the profile and makes the
+    /// machine's key, so a profile without a signature is tampered too once
+    /// it has been saved or the key exists: only one from before signing
+    /// counts as unsigned.
+    pub fn check(&self, key: Option<&IntegrityKey>) -> Integrity {
+        match (&self.signature, key) {
+            _ if self.tampered => Integrity::Tampered,
+            (None, Some(_)) => Integrity::Tampered,
+            (None, None) if self.version > 0 => Integrity::Tampered,
+            (None, None) => Integrity::Unsigned,
+            (Some(_), None) => Integrity::NoKey,
+            (Some(signature), Some(key)) if key.verify(&self.signed_bytes(), signature) => Integrity::Verified,
+            (Some(_), Some(_)) => Integrity::Tampered,
+        }
+    }
+}
+
+/// `remote` moved by what `local` moved since `base`, or just `remote` if
+/// `local` didn't move.
+fn rebase_amount(local: Option<Money>, base: Option<Money>, remote: Option<Money>) -> Option<Money> {
+    if local == base {
+        return remote;
+    }
+    let moved = local.unwrap_or_default() - base.unwrap_or_default();
+    Some((remote.unwrap_or_default() + moved).clamp(Money::ZERO, Money::MAX))
+}
+
+/// Errors raised by a [`ProfileBackend`].
+#[derive(Debug)]
+pub enum BackendError {
+    /// Someone else saved the profile since it was loaded.
+    Conflict { stored_version: u64 },
+    /// The profile is encrypted and no passphrase was given.
+    Encrypted,
+    /// The passphrase is wrong, or the encrypted file was altered.
+    BadPassphrase,
+    Io(io::Error),
+    Other(String),
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackendError::Conflict { stored_version } => {
+                write!(f, "profile was changed elsewhere (stored version {})", stored_version)
+            }
+            BackendError::Encrypted => write!(f, "profile is encrypted; a passphrase is needed"),
+            BackendError::BadPassphrase => write!(f, "wrong passphrase, or the profile file was altered"),
+            BackendError::Io(e) => write!(f, "{}", e),
+            BackendError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+impl From<io::Error> for BackendError {
+    fn from(e: io::Error) -> Self {
+        BackendError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for BackendError {
+    fn from(e: serde_json::Error) -> Self {
+        BackendError::Other(format!("invalid profile data: {}", e))
+    }
+}
+
+/// Where profiles are stored: a local directory, a database, or a remote server.
+pub trait ProfileBackend {
+    /// Loads a profile, or `None` if it has never been saved.
+    fn load(&self, name: &str) -> Result<Option<Profile>, BackendError>;
+
+    /// Saves the profile if the stored version still equals `profile.version`,
+    /// then bumps `profile.version` to the new stored version.
+    fn save(&self, profile: &mut Profile) -> Result<(), BackendError>;
+
+    /// Loads every stored profile.
+    fn list(&self) -> Result<Vec<Profile>, BackendError>;
+
+    /// Removes a profile for good. Returns whether there was one to remove.
+    fn delete(&self, name: &str) -> Result<bool, BackendError>;
+
+    /// Human-readable location, for messages.
+    fn describe(&self) -> String;
+
+    /// Whether saved profiles carry a [signature](Profile::sign) to check.
+    fn signs(&self) -> bool {
+        false
+    }
+
+    /// Loads a profile, or returns a fresh one if it doesn't exist yet.
+    fn load_or_create(&self, name: &str) -> Result<Profile, BackendError> {
+        Ok(self.load(name)?.unwrap_or_else(|| Profile::new(name)))
+    }
+}
+
+/// Opens a backend from a spec string: a directory path, `file:DIR`,
+/// `sqlite:PATH`, or an `http://`/`https://` base URL. Only directories can
+/// be encrypted with a `passphrase`.
+pub fn open_backend(spec: &str, passphrase: Option<Passphrase>) -> Result<Box<dyn ProfileBackend>, BackendError> {
+    let is_file = !(spec.starts_with("http://") || spec.starts_with("https://") || spec.starts_with("sqlite:"));
+    if passphrase.is_some() && !is_file {
+        return Err(BackendError::Other("only file profile stores can be encrypted".into()));
+    }
+    if spec.starts_with("http://") || spec.starts_with("https://") {
+        #[cfg(feature = "http")]
+        return Ok(Box::new(HttpBackend::new(spec)));
+        #[cfg(not(feature = "http"))]
+        return Err(BackendError::Other("this build has no HTTP profile support (enable the `http` feature)".into()));
+    }
+    if let Some(path) = spec.strip_prefix("sqlite:") {
+        #[cfg(feature = "sqlite")]
+        return Ok(Box::new(SqliteBackend::open(path)?));
+        #[cfg(not(feature = "sqlite"))]
+        {
+            let _ = path;
+            return Err(BackendError::Other("this build has no SQLite profile support (enable the `sqlite` feature)".into()));
+        }
+    }
+    let dir = spec.strip_prefix("file:").unwrap_or(spec);
+    let backend = FileBackend::new(dir);
+    Ok(Box::new(match passphrase {
+        Some(passphrase) => backend.with_passphrase(passphrase),
+        None => backend,
+    }))
+}
+
+/// What the leaderboard is ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardMetric {
+    PeakBalance,
+    BiggestWin,
+    RoundsSurvived,
+    TimeAttack,
+    /// Best return on investment over a single session of at least
+    /// [`MIN_ROI_ROUNDS`] rounds.
+    SessionRoi,
+}
+
+impl LeaderboardMetric {
+    pub const ALL: [LeaderboardMetric; 5] = [
+        LeaderboardMetric::PeakBalance,
+        LeaderboardMetric::BiggestWin,
+        LeaderboardMetric::RoundsSurvived,
+        LeaderboardMetric::TimeAttack,
+        LeaderboardMetric::SessionRoi,
+    ];
+
+    /// Parses a metric name as used on the command line.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "peak" | "peak-balance" => Some(LeaderboardMetric::PeakBalance),
+            "win" | "biggest-win" => Some(LeaderboardMetric::BiggestWin),
+            "rounds" | "rounds-survived" => Some(LeaderboardMetric::RoundsSurvived),
+            "attack" | "time-attack" => Some(LeaderboardMetric::TimeAttack),
+            "roi" | "session-roi" => Some(LeaderboardMetric::SessionRoi),
+            _ => None,
+        }
+    }
+
+    /// The profile's standing, or `None` if it has none yet (no time attack
+    /// played, or no session long enough to rank by ROI). Peak balance and
+    /// rounds survived are the better of the profile's own record and what
+    /// its rounds in `history` show; session ROI comes from `history` alone,
+    /// in hundredths of a percent. Amounts of money are in cents.
+    pub fn value(&self, profile: &Profile, history: &[RoundRecord]) -> Option<i64> {
+        let stats = &profile.lifetime;
+        let rounds = Query::new(history).profile(&profile.name);
+        match self {
+            LeaderboardMetric::PeakBalance => {
+                let peak = rounds.matching_rounds().map(|record| record.balance_after).max().unwrap_or_default();
+                Some(stats.peak_balance.max(peak).cents())
+            }
+            LeaderboardMetric::BiggestWin => Some(stats.biggest_win.cents()),
+            LeaderboardMetric::RoundsSurvived => {
+                let longest = rounds.group_by(GroupBy::Session).values().map(|session| session.count).max().unwrap_or(0);
+                Some(stats.rounds_survived.max(longest) as i64)
+            }
+            LeaderboardMetric::TimeAttack => stats.best_time_attack.map(Money::cents),
+            LeaderboardMetric::SessionRoi => rounds
+                .group_by(GroupBy::Session)
+                .values()
+                .filter(|session| session.count >= MIN_ROI_ROUNDS && session.wagered > Money::ZERO)
+                .map(|session| (session.roi_percent() * 100.0).round() as i64)
+                .max(),
+        }
+    }
+
+    /// A [`value`](Self::value) as the board shows it.
+    pub fn format(&self, value: i64) -> String {
+        match self {
+            LeaderboardMetric::SessionRoi => format!("{:+.2}%", value as f64 / 100.0),
+            LeaderboardMetric::RoundsSurvived => value.to_string(),
+            _ => Money::from_cents(value).to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LeaderboardMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaderboardMetric::PeakBalance => write!(f, "Peak Balance"),
+            LeaderboardMetric::BiggestWin => write!(f, "Biggest Win"),
+            LeaderboardMetric::RoundsSurvived => write!(f, "Rounds Survived"),
+            LeaderboardMetric::TimeAttack => write!(f, "Time Attack Score"),
+            LeaderboardMetric::SessionRoi => write!(f, "Best Session ROI"),
+        }
+    }
+}
+
+/// A ranking, and the profiles refused a place on it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub ranking: Vec<(String, i64)>,
+    /// Profiles flagged as tampered.
+    pub rejected: Vec<String>,
+}
+
+/// Ranks all profiles in a backend by the given metric, best first, leaving
+/// out tampered ones. `history` is the recorded rounds the metric may be
+/// worked out from.
+pub fn leaderboard(
+    backend: &dyn ProfileBackend,
+    metric: LeaderboardMetric,
+    history: &[RoundRecord],
+) -> Result<Leaderboard, BackendError> {
+    let (tampered, honest): (Vec<Profile>, Vec<Profile>) = backend.list()?.into_iter().partition(|p| p.tampered);
+    let mut ranking: Vec<(String, i64)> =
+        honest.into_iter().filter_map(|p| metric.value(&p, history).map(|value| (p.name, value))).collect();
+    ranking.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut rejected: Vec<String> = tampered.into_iter().map(|p| p.name).collect();
+    rejected.sort();
+    Ok(Leaderboard { ranking, rejected })
+}
+
+/// How many times a conflicting save is merged and retried before giving up.
+const MAX_SAVE_ATTEMPTS: usize = 3;
+
+/// An [`EventSink`] that folds game events into a profile's lifetime stats
+/// and saves the profile after every round, merging with concurrent sessions.
+pub struct ProfileTracker {
+    backend: Box<dyn ProfileBackend>,
+    profile: Profile,
+    /// The profile as of the last successful save, or as loaded before
+    /// that, which conflicting saves are rebased from.
+    synced: Profile,
+    session_rounds: u32,
+    /// XP the last round earned, taken back if its spin is voided.
+    last_round_xp: u64,
+    /// Whether the session's balance is the profile's bankroll; not so in a challenge.
+    carries_bankroll: bool,
+    /// Cash kept in the wallet rather than brought to the table, counted in
+    /// the bankroll along with the balance.
+    cash_off_table: Money,
+    /// What other sessions' saves, merged in on conflicts, have added to
+    /// the bankroll since this one started, or taken from it.
+    merged_in: Money,
+    /// What the session's balance is counted in.
+    currency: Currency,
+}
+
+impl ProfileTracker {
+    /// Starts a new session for the profile, played in `currency`, counting
+    /// the starting balance toward its peak.
+    pub fn start_session(
+        backend: Box<dyn ProfileBackend>,
+        profile: Profile,
+        currency: Currency,
+        starting_balance: Money,
+    ) -> Self {
+        ProfileTracker::start(backend, profile, starting_balance, Some(currency))
+    }
+
+    /// Starts a challenge session. The challenge brings its own balance, so
+    /// the profile's bankroll is left as it was.
+    pub fn start_challenge(backend: Box<dyn ProfileBackend>, profile: Profile, starting_balance: Money) -> Self {
+        ProfileTracker::start(backend, profile, starting_balance, None)
+    }
+
+    /// `currency` is the session's when its balance is the bankroll.
+    fn start(
+        backend: Box<dyn ProfileBackend>,
+        mut profile: Profile,
+        starting_balance: Money,
+        currency: Option<Currency>,
+    ) -> Self {
+        // The profile as stored: a daily bonus isn't its until it's saved.
+        let mut synced = profile.clone();
+        synced.bankroll = synced.bankroll.map(|bankroll| bankroll.saturating_sub(profile.pending_bonus));
+        synced.pending_bonus = Money::ZERO;
+        let carries_bankroll = currency.is_some();
+        if let Some(currency) = &currency {
+            profile.set_balance(currency, starting_balance);
+        }
+        profile.lifetime.sessions += 1;
+        profile.lifetime.peak_balance = profile.lifetime.peak_balance.max(starting_balance);
+        let mut tracker =
+            ProfileTracker {
+            backend,
+            profile,
+            synced,
+            session_rounds: 0,
+            last_round_xp: 0,
+            carries_bankroll,
+            cash_off_table: Money::ZERO,
+            merged_in: Money::ZERO,
+            currency: currency.unwrap_or_else(Currency::usd),
+        };
+        tracker.save();
+        tracker
+    }
+
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Records a time attack score. Returns true if it beats the profile's best.
+    pub fn record_time_attack(&mut self, score: Money) -> bool {
+        let best = &mut self.profile.lifetime.best_time_attack;
+        if best.is_some_and(|best| best >= score) {
+            return false;
+        }
+        *best = Some(score);
+        self.save();
+        true
+    }
+
+    /// Records a completed challenge. Returns true the first time it's completed.
+    pub fn record_challenge(&mut self, id: &str) -> bool {
+        if !self.profile.lifetime.challenges.insert(id.to_string()) {
+            return false;
+        }
+        self.save();
+        true
+    }
+
+    /// Saves where the profile stands in the campaign; `None` starts it over.
+    pub fn set_campaign(&mut self, progress: Option<CampaignProgress>) {
+        self.profile.lifetime.campaign = progress;
+        self.save();
+    }
+
+    /// Sets the cash the player holds away from the table. From the next
+    /// balance change on, the bankroll is that plus the table balance.
+    pub fn set_cash_off_table(&mut self, cash: Money) {
+        self.cash_off_table = cash;
+    }
+
+    /// Saves the profile with the session's closing balance as its bankroll.
+    pub fn finish(&mut self, balance: Money) {
+        if self.carries_bankroll {
+            self.profile.set_balance(&self.currency, self.bankroll_holding(balance));
+        }
+        self.save();
+    }
+
+    /// The bankroll when the session holds `amount`, counting what other
+    /// sessions have done to it meanwhile.
+    fn bankroll_holding(&self, amount: Money) -> Money {
+        (amount + self.merged_in).clamp(Money::ZERO, Money::MAX)
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = self.try_save() {
+            crate::console::warn(format_args!("Could not save profile {}: {}", self.profile.name, e));
+        }
+    }
+
+    fn try_save(&mut self) -> Result<(), BackendError> {
+        for _ in 0..MAX_SAVE_ATTEMPTS {
+            match self.backend.save(&mut self.profile) {
+                Ok(()) => {
+                    self.profile.pending_bonus = Money::ZERO;
+                    self.synced = self.profile.clone();
+                    return Ok(());
+                }
+                Err(BackendError::Conflict { .. }) => {
+                    // Another session saved first: replay our progress on top of theirs.
+                    let remote = self.backend.load_or_create(&self.profile.name)?;
+                    let merged = self.profile.rebase(&self.synced, &remote);
+                    if self.profile.pending_bonus > merged.pending_bonus {
+                        say!("Today's daily bonus was already claimed in another session; {} taken back.", self.profile.pending_bonus);
+                    }
+                    if self.carries_bankroll {
+                        let moved = |profile: &Profile| profile.balance(&self.currency).unwrap_or_default();
+                        self.merged_in += moved(&merged) - moved(&self.profile);
+                    }
+                    self.profile = merged;
+                    self.synced = remote;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(BackendError::Other("gave up after repeated save conflicts".into()))
+    }
+}
+
+impl EventSink for ProfileTracker {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        let stats = &mut self.profile.lifetime;
+        match event {
+            GameEvent::BalanceChange { balance, .. } => {
+                stats.peak_balance = stats.peak_balance.max(*balance);
+                if self.carries_bankroll {
+                    let bankroll = self.bankroll_holding(*balance + self.cash_off_table);
+                    self.profile.set_balance(&self.currency, bankroll);
+                }
+            }
+            GameEvent::RoundComplete { wagered, returned, .. } => {
+                self.session_rounds += 1;
+                stats.total_rounds += 1;
+                stats.rounds_survived = stats.rounds_survived.max(self.session_rounds);
+                stats.biggest_win = stats.biggest_win.max(returned.saturating_sub(*wagered));
+                let before = self.profile.progress();
+                self.last_round_xp = progression::round_xp(*wagered, *returned);
+                self.profile.lifetime.xp += self.last_round_xp;
+                announce_progress(before, self.profile.progress());
+                self.save();
+            }
+            GameEvent::SpinVoided { .. } => {
+                // The round no longer counts; records like peak balance stand.
+                self.session_rounds = self.session_rounds.saturating_sub(1);
+                stats.total_rounds = stats.total_rounds.saturating_sub(1);
+                stats.xp = stats.xp.saturating_sub(std::mem::take(&mut self.last_round_xp));
+                self.save();
+            }
+            GameEvent::CompPoints { delta, points } => {
+                stats.comp_points = *points;
+                // Points earned are saved with their round; spent ones right away.
+                if *delta < 0 {
+                    self.save();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn announce_progress(before: Progress, after: Progress) {
+    if after.level <= before.level {
+        return;
+    }
+    say!("Level up! You are now level {}.", after.level);
+    let rank = after.rank();
+    if rank != before.rank() {
+        say!("New title: {}.", rank);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn key() -> IntegrityKey {
+        let path = std::env::temp_dir().join(format!("roulette-profile-test-{}.key", std::process::id()));
+        fs::write(&path, [3u8; 32]).unwrap();
+        let key = IntegrityKey::load(&path).unwrap().unwrap();
+        let _ = fs::remove_file(&path);
+        key
+    }
+
+    #[test]
+    fn signed_profile_verifies_until_edited() {
+        let key = key();
+        let mut profile = Profile::new("ada");
+        profile.version = 3;
+        profile.sign(&key);
+        assert_eq!(profile.check(Some(&key)), Integrity::Verified);
+        profile.bankroll = Some(Money::from_units(1_000_000));
+        assert_eq!(profile.check(Some(&key)), Integrity::Tampered);
+    }
+
+    #[test]
+    fn missing_signature_is_tampered_unless_legacy() {
+        let mut profile = Profile::new("ada");
+        assert_eq!(profile.check(None), Integrity::Unsigned);
+        assert_eq!(profile.check(Some(&key())), Integrity::Tampered);
+        profile.version = 1;
+        assert_eq!(profile.check(None), Integrity::Tampered);
+    }
+}
@@ -0,0 +1,92 @@
+// src/profile/progression.rs
+
+//! Experience and levels.
+//!
+//! Every round played earns experience (XP), and a round that comes out
+//! ahead earns a bonus on top. The level follows from the XP total, each
+//! level costing more than the last. Reaching a new rank unlocks a title and
+//! raises the default limit on bets per round, which applies unless the
+//! table rules set one.
+
+use crate::game::money::Money;
+use std::fmt;
+
+/// XP for every round played.
+pub const ROUND_XP: u64 = 10;
+/// Extra XP for a round that returned more than was wagered.
+pub const WIN_XP: u64 = 25;
+/// Level `n` takes `LEVEL_STEP * n * (n - 1)` XP in total.
+const LEVEL_STEP: u64 = 50;
+
+/// A title and the table limit that comes with it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rank {
+    /// Level the rank is reached at.
+    pub level: u32,
+    pub title: &'static str,
+    /// Default bets-per-round limit; `None` for no limit.
+    pub max_bets_per_round: Option<usize>,
+}
+
+/// Every rank, lowest first.
+pub const RANKS: &[Rank] = &[
+    Rank { level: 1, title: "Intern", max_bets_per_round: Some(5) },
+    Rank { level: 3, title: "Day Trader", max_bets_per_round: Some(8) },
+    Rank { level: 5, title: "Analyst", max_bets_per_round: Some(12) },
+    Rank { level: 8, title: "Portfolio Manager", max_bets_per_round: Some(16) },
+    Rank { level: 12, title: "Hedge Fund Manager", max_bets_per_round: Some(24) },
+    Rank { level: 20, title: "Market Maker", max_bets_per_round: None },
+];
+
+/// XP earned by a round that staked `wagered` and paid back `returned`.
+pub fn round_xp(wagered: Money, returned: Money) -> u64 {
+    if returned > wagered { ROUND_XP + WIN_XP } else { ROUND_XP }
+}
+
+/// Total XP needed to reach `level`.
+pub fn xp_for_level(level: u32) -> u64 {
+    let level = level.max(1) as u64;
+    LEVEL_STEP * level * (level - 1)
+}
+
+/// A player's standing: XP so far and the level it buys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub xp: u64,
+    pub level: u32,
+}
+
+impl Progress {
+    pub fn from_xp(xp: u64) -> Self {
+        let mut level = 1;
+        while xp >= xp_for_level(level + 1) {
+            level += 1;
+        }
+        Progress { xp, level }
+    }
+
+    /// The highest rank reached.
+    pub fn rank(&self) -> &'static Rank {
+        RANKS.iter().rev().find(|rank| rank.level <= self.level).unwrap_or(&RANKS[0])
+    }
+
+    /// Total XP at which the next level is reached.
+    pub fn next_level_xp(&self) -> u64 {
+        xp_for_level(self.level + 1)
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Level {} {} ({}/{} XP)", self.level, self.rank().title, self.xp, self.next_level_xp())
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max_bets_per_round {
+            Some(max) => write!(f, "{} (up to {} bets per round)", self.title, max),
+            None => write!(f, "{} (no limit on bets per round)", self.title),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// src/profile/sqlite.rs
+
+//! Profiles stored in a SQLite database (requires the `sqlite` feature).
+
+use super::{BackendError, LifetimeStats, Profile, ProfileBackend};
+use crate::game::money::Money;
+use rusqlite::{Connection, OptionalExtension, params};
+use std::path::{Path, PathBuf};
+
+/// A `profiles` row: name, version, stats JSON, bankroll, last played, and
+/// balances in other currencies as JSON.
+type ProfileRow = (String, i64, String, Option<f64>, Option<i64>, Option<String>);
+
+pub struct SqliteBackend {
+    path: PathBuf,
+    conn: Connection,
+}
+
+impl From<rusqlite::Error> for BackendError {
+    fn from(e: rusqlite::Error) -> Self {
+        BackendError::Other(format!("sqlite: {}", e))
+    }
+}
+
+impl SqliteBackend {
+    /// Opens (and if needed creates) the profile database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BackendError> {
+        let conn = Connection::open(path.as_ref())?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS profiles (
+                name    TEXT PRIMARY KEY,
+                version INTEGER NOT NULL,
+                stats   TEXT NOT NULL
+            )",
+            [],
+        )?;
+        // Columns added since the table was first created.
+        for (column, kind) in [("bankroll", "INTEGER"), ("last_played", "INTEGER"), ("balances", "TEXT")] {
+            let present = conn
+                .prepare("SELECT 1 FROM pragma_table_info('profiles') WHERE name = ?1")?
+                .exists(params![column])?;
+            if !present {
+                conn.execute(&format!("ALTER TABLE profiles ADD COLUMN {} {}", column, kind), [])?;
+            }
+        }
+        Ok(SqliteBackend { path: path.as_ref().to_path_buf(), conn })
+    }
+
+    fn read_row(row: &rusqlite::Row) -> rusqlite::Result<ProfileRow> {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+    }
+
+    fn row_to_profile(
+        (name, version, stats, bankroll, last_played, balances): ProfileRow,
+    ) -> Result<Profile, BackendError> {
+        let lifetime: LifetimeStats = serde_json::from_str(&stats)?;
+        let balances = match balances {
+            Some(json) => serde_json::from_str(&json)?,
+            None => Default::default(),
+        };
+        Ok(Profile {
+            version: version as u64,
+            lifetime,
+            bankroll: bankroll.map(Money::from_f64),
+            balances,
+            last_played: last_played.map(|t| t as u64),
+            ..Profile::new(&name)
+        })
+    }
+}
+
+impl ProfileBackend for SqliteBackend {
+    fn load(&self, name: &str) -> Result<Option<Profile>, BackendError> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT name, version, stats, bankroll, last_played, balances FROM profiles WHERE name = ?1",
+                params![name],
+                Self::read_row,
+            )
+            .optional()?;
+        row.map(Self::row_to_profile).transpose()
+    }
+
+    fn save(&self, profile: &mut Profile) -> Result<(), BackendError> {
+        let stats = serde_json::to_string(&profile.lifetime)?;
+        // Whole units, so SQLite keeps whole bankrolls as integers, as they always were.
+        let bankroll = profile.bankroll.map(Money::as_f64);
+        let last_played = profile.last_played.map(|t| t as i64);
+        let balances = serde_json::to_string(&profile.balances)?;
+        let next = profile.version + 1;
+        // Compare-and-swap on the version column.
+        let changed = if profile.version == 0 {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO profiles (name, version, stats, bankroll, last_played, balances) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![profile.name, next as i64, stats, bankroll, last_played, balances],
+            )?
+        } else {
+            self.conn.execute(
+                "UPDATE profiles SET version = ?2, stats = ?3, bankroll = ?5, last_played = ?6, balances = ?7 WHERE name = ?1 AND version = ?4",
+                params![profile.name, next as i64, stats, profile.version as i64, bankroll, last_played, balances],
+            )?
+        };
+        if changed == 0 {
+            let stored_version = self.load(&profile.name)?.map(|p| p.version).unwrap_or(0);
+            return Err(BackendError::Conflict { stored_version });
+        }
+        profile.version = next;
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<Profile>, BackendError> {
+        let mut stmt = self.conn.prepare("SELECT name, version, stats, bankroll, last_played, balances FROM profiles")?;
+        let rows = stmt.query_map([], Self::read_row)?;
+        let mut profiles = Vec::new();
+        for row in rows {
+            profiles.push(Self::row_to_profile(row?)?);
+        }
+        Ok(profiles)
+    }
+
+    fn delete(&self, name: &str) -> Result<bool, BackendError> {
+        Ok(self.conn.execute("DELETE FROM profiles WHERE name = ?1", params![name])? > 0)
+    }
+
+    fn describe(&self) -> String {
+        format!("sqlite:{}", self.path.display())
+    }
+}
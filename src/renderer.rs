@@ -0,0 +1,169 @@
+// src/renderer.rs
+
+//! What the terminal can show, decided once at startup.
+//!
+//! Game screens are written as plain text with [`say!`](crate::say), and
+//! colour through [`presentation::color`](crate::presentation::color); the
+//! current [`Renderer`] turns that into what the terminal understands. A dumb
+//! terminal, a pipe, or an old Windows console gets [`Plain`] text, most
+//! terminals get [`Ansi`] colours, and an emulator with full colour and
+//! Unicode gets the [`Tui`] touches as well. [`RendererKind::detect`] picks
+//! one from the environment; `--renderer` or `renderer` in the config pick
+//! one outright.
+
+use crate::presentation::Rgb;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::env;
+use std::sync::{Arc, RwLock};
+
+static CURRENT: RwLock<Option<Arc<dyn Renderer>>> = RwLock::new(None);
+
+/// Turns the game's text into output for one kind of terminal.
+pub trait Renderer: Send + Sync {
+    /// `text` on a background of `swatch`, as colour names are shown.
+    fn swatch(&self, text: &str, swatch: Rgb) -> String {
+        let _ = swatch;
+        text.to_string()
+    }
+
+    /// Goes back to the start of the line so the next text replaces it, as
+    /// the spin animation does.
+    fn rewrite_line(&self) -> &'static str {
+        "\r"
+    }
+
+    /// Last changes to text about to be written.
+    fn finish<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Text exactly as the game writes it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plain;
+
+impl Renderer for Plain {}
+
+/// 256-colour escape codes, which nearly every terminal since xterm takes,
+/// including Windows consoles that understand escapes at all.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ansi;
+
+impl Renderer for Ansi {
+    fn swatch(&self, text: &str, swatch: Rgb) -> String {
+        let ink = if is_light(swatch) { 16 } else { 231 };
+        format!("\u{1b}[48;5;{}m\u{1b}[38;5;{}m{}\u{1b}[0m", nearest_256(swatch), ink, text)
+    }
+
+    fn rewrite_line(&self) -> &'static str {
+        "\r\u{1b}[2K"
+    }
+}
+
+/// For emulators with 24-bit colour and Unicode: exact colours, and rules
+/// of dashes and equals signs drawn as lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Tui;
+
+impl Renderer for Tui {
+    fn swatch(&self, text: &str, swatch: Rgb) -> String {
+        let Rgb(r, g, b) = swatch;
+        let ink = if is_light(swatch) { "0;0;0" } else { "255;255;255" };
+        format!("\u{1b}[48;2;{};{};{}m\u{1b}[38;2;{}m{}\u{1b}[0m", r, g, b, ink, text)
+    }
+
+    fn rewrite_line(&self) -> &'static str {
+        "\r\u{1b}[2K"
+    }
+
+    fn finish<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !text.contains("---") && !text.contains("===") {
+            return Cow::Borrowed(text);
+        }
+        let mut drawn = String::with_capacity(text.len() * 2);
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let mut run = 1;
+            while matches!(c, '-' | '=') && chars.peek() == Some(&c) {
+                chars.next();
+                run += 1;
+            }
+            // Shorter runs are text: `--animate`, `-5`.
+            let c = match c {
+                '-' if run >= 3 => '─',
+                '=' if run >= 3 => '═',
+                other => other,
+            };
+            drawn.extend(std::iter::repeat_n(c, run));
+        }
+        Cow::Owned(drawn)
+    }
+}
+
+/// Black text reads better than white on this colour.
+fn is_light(Rgb(r, g, b): Rgb) -> bool {
+    299 * r as u32 + 587 * g as u32 + 114 * b as u32 > 140_000
+}
+
+/// The closest colour in the 6x6x6 cube of the 256-colour palette.
+fn nearest_256(Rgb(r, g, b): Rgb) -> u8 {
+    let level = |channel: u8| (channel as u16 * 5 + 127) / 255;
+    (16 + 36 * level(r) + 6 * level(g) + level(b)) as u8
+}
+
+/// The renderers on offer, as named on the command line and in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RendererKind {
+    Plain,
+    Ansi,
+    Tui,
+}
+
+impl RendererKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "plain" => Some(RendererKind::Plain),
+            "ansi" => Some(RendererKind::Ansi),
+            "tui" => Some(RendererKind::Tui),
+            _ => None,
+        }
+    }
+
+    /// The best renderer for output going to a terminal (`terminal`) or not,
+    /// judged from the usual environment variables.
+    pub fn detect(terminal: bool) -> Self {
+        let var = |name: &str| env::var(name).unwrap_or_default();
+        if !terminal || env::var_os("NO_COLOR").is_some() || var("TERM") == "dumb" {
+            return RendererKind::Plain;
+        }
+        // The classic Windows console prints escape codes as they are; its
+        // successors announce themselves.
+        if cfg!(windows) && var("TERM").is_empty() && env::var_os("WT_SESSION").is_none() && var("ConEmuANSI") != "ON" {
+            return RendererKind::Plain;
+        }
+        let truecolor = matches!(var("COLORTERM").as_str(), "truecolor" | "24bit");
+        let locale = [var("LC_ALL"), var("LC_CTYPE"), var("LANG")].into_iter().find(|value| !value.is_empty());
+        let unicode = cfg!(windows) || locale.is_some_and(|locale| locale.to_uppercase().contains("UTF-8"));
+        if truecolor && unicode { RendererKind::Tui } else { RendererKind::Ansi }
+    }
+
+    pub fn build(self) -> Arc<dyn Renderer> {
+        match self {
+            RendererKind::Plain => Arc::new(Plain),
+            RendererKind::Ansi => Arc::new(Ansi),
+            RendererKind::Tui => Arc::new(Tui),
+        }
+    }
+}
+
+/// Renders everything from now on with `renderer`.
+pub fn set_renderer(renderer: Arc<dyn Renderer>) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Some(renderer);
+}
+
+/// The renderer in use; [`Plain`] until one is set.
+pub fn current() -> Arc<dyn Renderer> {
+    CURRENT.read().unwrap_or_else(|e| e.into_inner()).clone().unwrap_or_else(|| Arc::new(Plain))
+}
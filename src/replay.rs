@@ -0,0 +1,368 @@
+// src/replay.rs
+
+//! Re-running a recorded session to check its settlements.
+//!
+//! A recording is either a [session journal](crate::journal) or the NDJSON
+//! stream written by `--events`. Replaying feeds the recorded bets and spins
+//! to a fresh [`Game`] and compares every settlement the game works out with
+//! the one that was recorded. Spins from provably fair mode carry their
+//! seeds, so those are checked against their commitments as well.
+//!
+//! [`verify_spins`] checks the spins alone: every ordinary spin records the
+//! seed it was drawn from, and redoing the draw must land on the same pocket.
+
+use crate::game::Game;
+use crate::game::bets::Bet;
+use crate::game::events::{BalanceChangeReason, EventSink, GameEvent};
+use crate::game::rules::Difficulty;
+use crate::game::fairness::FairnessError;
+use crate::game::money::Money;
+use crate::game::spin::{self, SpinModel};
+use crate::game::wheel::{PocketId, WheelLike};
+use crate::journal::JournalEntry;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::rc::Rc;
+
+/// A line of an `--events` stream.
+#[derive(Deserialize)]
+struct StampedEvent {
+    round: u32,
+    #[serde(flatten)]
+    event: GameEvent,
+}
+
+/// Why a recording could not be read.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    /// A line is neither a journal entry nor an event.
+    Parse { line: usize, message: String },
+    /// The recording doesn't say what the session started with.
+    NoStartingBalance,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(f, "{}", e),
+            ReplayError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            ReplayError::NoStartingBalance => write!(f, "the recording has no starting balance"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+/// A session as it was recorded.
+#[derive(Debug, Clone)]
+pub struct RecordedSession {
+    pub starting_balance: Money,
+    /// The wheel's name, when the recording is a journal.
+    pub wheel: Option<String>,
+    /// The table's difficulty, when the recording is a journal.
+    pub difficulty: Option<Difficulty>,
+    /// Id of the table it was played at, when the journal names one.
+    pub table: Option<String>,
+    /// Every event with the round it belongs to.
+    pub events: Vec<(u32, GameEvent)>,
+}
+
+/// Reads a journal or an `--events` stream. A journal holding several
+/// sessions yields the last one.
+pub fn read_session(reader: impl BufRead) -> Result<RecordedSession, ReplayError> {
+    let mut starting_balance = None;
+    let mut wheel = None;
+    let mut difficulty = None;
+    let mut table = None;
+    let mut events = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(entry) = serde_json::from_str::<JournalEntry>(&line) {
+            match entry {
+                JournalEntry::Start { balance, wheel: name, difficulty: level, table: id, .. } => {
+                    starting_balance = Some(balance);
+                    wheel = Some(name);
+                    difficulty = Some(level);
+                    table = id;
+                    events.clear();
+                }
+                JournalEntry::Event { round, event } => events.push((round, event)),
+                JournalEntry::End { .. } => {}
+            }
+            continue;
+        }
+        let stamped: StampedEvent = serde_json::from_str(&line)
+            .map_err(|e| ReplayError::Parse { line: index + 1, message: e.to_string() })?;
+        events.push((stamped.round, stamped.event));
+    }
+    // An event stream has no header; the first balance change tells us where it began.
+    let starting_balance = starting_balance
+        .or_else(|| {
+            events.iter().find_map(|(_, event)| match event {
+                GameEvent::BalanceChange { delta, balance, .. } => Some(*balance - *delta).filter(|b| !b.is_negative()),
+                _ => None,
+            })
+        })
+        .ok_or(ReplayError::NoStartingBalance)?;
+    Ok(RecordedSession { starting_balance, wheel, difficulty, table, events })
+}
+
+/// Where the replay and the recording disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Discrepancy {
+    /// The game settled a bet or a round differently, or not at all.
+    Settlement { round: u32, recorded: GameEvent, replayed: Option<GameEvent> },
+    /// The game produced a settlement the recording doesn't have.
+    Unrecorded { round: u32, replayed: GameEvent },
+    /// The recorded pocket isn't on the wheel.
+    UnknownPocket { round: u32, ticker: String },
+    /// A revealed seed doesn't match its commitment or the recorded pocket.
+    UnfairSpin { round: u32, error: FairnessError },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = |event: &GameEvent| serde_json::to_string(event).unwrap_or_default();
+        match self {
+            Discrepancy::Settlement { round, recorded, replayed: Some(replayed) } => {
+                write!(f, "round {}: recorded {} but replay gave {}", round, json(recorded), json(replayed))
+            }
+            Discrepancy::Settlement { round, recorded, replayed: None } => {
+                write!(f, "round {}: recorded {} but replay settled nothing", round, json(recorded))
+            }
+            Discrepancy::Unrecorded { round, replayed } => {
+                write!(f, "round {}: replay gave {} which was never recorded", round, json(replayed))
+            }
+            Discrepancy::UnknownPocket { round, ticker } => {
+                write!(f, "round {}: pocket {} is not on this wheel", round, ticker)
+            }
+            Discrepancy::UnfairSpin { round, error } => write!(f, "round {}: {}", round, error),
+        }
+    }
+}
+
+/// Settlement events, the ones a replay must reproduce.
+fn is_settlement(event: &GameEvent) -> bool {
+    matches!(
+        event,
+        GameEvent::Settlement { .. } | GameEvent::Surrendered { .. } | GameEvent::RoundComplete { .. }
+    )
+}
+
+/// Collects the replaying game's settlements.
+#[derive(Default)]
+struct Settlements(VecDeque<GameEvent>);
+
+impl EventSink for Settlements {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        if is_settlement(event) {
+            self.0.push_back(event.clone());
+        }
+    }
+}
+
+/// A game being driven by a recording, one event at a time.
+pub struct Replay {
+    game: Game,
+    replayed: Rc<RefCell<Settlements>>,
+    last_pocket: Option<PocketId>,
+    rounds: u32,
+    discrepancies: Vec<Discrepancy>,
+}
+
+impl Replay {
+    /// Replays into `game`, which should start with the recorded balance
+    /// and have the recorded wheel and table rules.
+    pub fn new(mut game: Game) -> Self {
+        // Spins voided at the recorded table are voided again here.
+        game.allow_voids(true);
+        let replayed = Rc::new(RefCell::new(Settlements::default()));
+        game.add_event_sink(Box::new(replayed.clone()));
+        Replay { game, replayed, last_pocket: None, rounds: 0, discrepancies: Vec::new() }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Rounds settled so far.
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn discrepancies(&self) -> &[Discrepancy] {
+        &self.discrepancies
+    }
+
+    /// Applies one recorded event. Returns `true` when it completed a round.
+    pub fn apply(&mut self, round: u32, event: &GameEvent) -> bool {
+        match event {
+            GameEvent::BetPlaced { bet_type, amount, note } => {
+                let bet = Bet::new(bet_type.clone(), *amount);
+                self.game.place_bet(match note {
+                    Some(note) => bet.with_note(note),
+                    None => bet,
+                });
+            }
+            GameEvent::BetNoted { index, note } => {
+                self.game.note_bet(*index, note);
+            }
+            GameEvent::BetsCleared { .. } => self.game.clear_bets(),
+            GameEvent::Spin { ticker, .. } => match self.game.wheel().find_pocket(ticker) {
+                Some(pocket) => {
+                    self.last_pocket = Some(pocket);
+                    self.game.resolve_with_pocket(pocket);
+                }
+                None => self.discrepancies.push(Discrepancy::UnknownPocket { round, ticker: ticker.clone() }),
+            },
+            GameEvent::SpinVoided { .. } if self.game.void_last_spin().is_ok() => {
+                self.rounds = self.rounds.saturating_sub(1);
+            }
+            GameEvent::BalanceChange { reason: BalanceChangeReason::Deposit, delta, .. } => {
+                self.game.deposit((*delta).max(Money::ZERO));
+            }
+            GameEvent::BalanceChange { reason: BalanceChangeReason::BuyIn, delta, .. } => {
+                self.game.buy_in((*delta).max(Money::ZERO));
+            }
+            GameEvent::BalanceChange { reason: BalanceChangeReason::CashOut, .. } => {
+                self.game.cash_out();
+            }
+            GameEvent::CompRedeemed { reward, cost } => {
+                // The recording doesn't carry the points the session began
+                // with, so take it that there were enough.
+                let points = self.game.comp_points().max(*cost);
+                self.game.set_comp_points(points);
+                let _ = self.game.redeem_comps(*reward);
+            }
+            // A rebate every so many rounds was already paid by the spin that
+            // closed its period, leaving nothing to settle here; any other
+            // was paid as the session ended.
+            GameEvent::RebatePaid { .. } => {
+                self.game.settle_rebate();
+            }
+            GameEvent::SpinRevealed { reveal } => {
+                if let Some(pocket) = self.last_pocket
+                    && let Err(error) = reveal.verify_outcome(self.game.wheel().get_all_pockets().len(), pocket)
+                {
+                    self.discrepancies.push(Discrepancy::UnfairSpin { round, error });
+                }
+            }
+            recorded if is_settlement(recorded) => {
+                let replayed = self.replayed.borrow_mut().0.pop_front();
+                if replayed.as_ref() != Some(recorded) {
+                    self.discrepancies.push(Discrepancy::Settlement { round, recorded: recorded.clone(), replayed });
+                }
+                if matches!(recorded, GameEvent::RoundComplete { .. }) {
+                    // Whatever the replay settled beyond the recording belongs to this round.
+                    let extra: Vec<GameEvent> = self.replayed.borrow_mut().0.drain(..).collect();
+                    self.discrepancies.extend(extra.into_iter().map(|replayed| Discrepancy::Unrecorded { round, replayed }));
+                    self.rounds += 1;
+                    return true;
+                }
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+/// A spin whose seed gives a different pocket from the one recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpinMismatch {
+    pub round: u32,
+    pub seed: String,
+    pub recorded: String,
+    /// What the seed gives, by ticker.
+    pub recomputed: String,
+}
+
+impl fmt::Display for SpinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "round {}: seed {} gives {}, but {} was recorded",
+            self.round, self.seed, self.recomputed, self.recorded
+        )
+    }
+}
+
+/// What [`verify_spins`] found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpinAudit {
+    /// Seeded spins that came out the same.
+    pub verified: u32,
+    /// Provably fair spins whose reveal checked out.
+    pub fair: u32,
+    /// Spins with nothing to check them by, such as croupier-entered ones.
+    pub unseeded: u32,
+    pub mismatches: Vec<SpinMismatch>,
+    pub unfair: Vec<(u32, FairnessError)>,
+}
+
+impl SpinAudit {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty() && self.unfair.is_empty()
+    }
+}
+
+/// Redoes every seeded spin in `session` on `wheel` with `model`, which
+/// must be the wheel and spin model the session was played with, and checks
+/// provably fair spins against their reveals.
+pub fn verify_spins(session: &RecordedSession, wheel: &dyn WheelLike, model: &SpinModel) -> SpinAudit {
+    let mut audit = SpinAudit::default();
+    let mut seed: Option<&str> = None;
+    // An unseeded spin, waiting to see if a reveal follows.
+    let mut unseeded: Option<(u32, PocketId)> = None;
+    for (round, event) in &session.events {
+        match event {
+            GameEvent::SpinSeeded { seed: recorded } => seed = Some(recorded),
+            GameEvent::Spin { ticker, .. } => {
+                if unseeded.take().is_some() {
+                    audit.unseeded += 1;
+                }
+                let Some(pocket) = wheel.layout().find_pocket(ticker) else { continue };
+                let Some(seed) = seed.take() else {
+                    unseeded = Some((*round, pocket));
+                    continue;
+                };
+                let (recomputed, _) = model.spin(wheel, &mut spin::seeded_rng(seed));
+                if recomputed == pocket {
+                    audit.verified += 1;
+                } else {
+                    audit.mismatches.push(SpinMismatch {
+                        round: *round,
+                        seed: seed.to_string(),
+                        recorded: ticker.clone(),
+                        recomputed: wheel.layout().pocket(recomputed).ticker.to_string(),
+                    });
+                }
+            }
+            GameEvent::SpinRevealed { reveal } => {
+                if let Some((round, pocket)) = unseeded.take() {
+                    match reveal.verify_outcome(wheel.pockets().len(), pocket) {
+                        Ok(()) => audit.fair += 1,
+                        Err(e) => audit.unfair.push((round, e)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    if unseeded.is_some() {
+        audit.unseeded += 1;
+    }
+    audit
+}
@@ -0,0 +1,153 @@
+// src/selftest.rs
+
+//! The `selftest` command: spin the wheel a great many times and check the
+//! results against the odds it's documented to have.
+//!
+//! Three checks, each of which a rigged or broken wheel would fail:
+//!
+//! * the pocket frequencies fit the wheel's odds (a chi-square test; a
+//!   weighted wheel is held to its weights, not to an even spread);
+//! * one spin says nothing about the next (lag-1 serial correlation);
+//! * the same seed gives the same spins, as seeded play promises.
+//!
+//! Without a fixed seed every spin is drawn the way the game draws it: a
+//! fresh seed from the configured [entropy source](EntropySource), fed to
+//! [`seeded_rng`]. The test then covers the entropy source as well.
+
+use crate::analysis::bias::BiasReport;
+use crate::game::bets::BetType;
+use crate::game::entropy::EntropySource;
+use crate::game::spin::{SpinModel, seeded_rng};
+use crate::game::wheel::{PocketId, WheelLike};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fmt;
+
+pub const DEFAULT_SPINS: u64 = 100_000;
+
+/// Seed for the repeatability check when none is given.
+const REPEAT_SEED: u64 = 0x5E1F;
+const REPEAT_SPINS: usize = 1_000;
+
+/// Below this p-value the frequencies fail.
+const FIT_ALPHA: f64 = 0.001;
+/// Serial correlation beyond this many standard errors fails (the same 0.1% level).
+const CORRELATION_LIMIT: f64 = 3.29;
+
+/// Everything the self-test found.
+#[derive(Debug, Clone)]
+pub struct SelfTestReport {
+    pub wheel: String,
+    pub model: &'static str,
+    /// Where the spins' seeds came from; `None` when run from a fixed seed.
+    pub entropy: Option<EntropySource>,
+    pub fit: BiasReport,
+    /// Correlation between each spin's pocket and the next one's.
+    pub serial_correlation: f64,
+    pub repeat_seed: u64,
+    /// Whether two runs from `repeat_seed` gave the same spins.
+    pub repeatable: bool,
+}
+
+impl SelfTestReport {
+    /// Serial correlation in standard errors; about ±1 for independent spins.
+    pub fn correlation_z(&self) -> f64 {
+        self.serial_correlation * (self.fit.spins as f64).sqrt()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.fit.p_value >= FIT_ALPHA && self.correlation_z().abs() < CORRELATION_LIMIT && self.repeatable
+    }
+}
+
+/// Spins `wheel` with `model` `spins` times, from `seed` if given and
+/// otherwise with a fresh seed from `entropy` for every spin.
+pub fn run(wheel: &dyn WheelLike, model: &SpinModel, spins: u64, seed: Option<u64>, entropy: EntropySource) -> SelfTestReport {
+    let mut fixed = seed.map(StdRng::seed_from_u64);
+    let mut counts = vec![0u64; wheel.pockets().len()];
+    let mut sequence = Vec::with_capacity(spins as usize);
+    for _ in 0..spins {
+        let (PocketId(id), _) = match fixed.as_mut() {
+            Some(rng) => model.spin(wheel, rng),
+            None => model.spin(wheel, &mut seeded_rng(&entropy.random_seed())),
+        };
+        counts[id] += 1;
+        sequence.push(id as f64);
+    }
+    let expected = wheel
+        .pockets()
+        .iter()
+        .zip(counts)
+        .map(|(pocket, observed)| {
+            let share = wheel.probability(&BetType::StraightUp(pocket.ticker));
+            (pocket.ticker.to_string(), observed, share)
+        })
+        .collect();
+
+    let repeat_seed = seed.unwrap_or(REPEAT_SEED);
+    let run_seeded = || {
+        let mut rng = StdRng::seed_from_u64(repeat_seed);
+        (0..REPEAT_SPINS).map(|_| model.spin(wheel, &mut rng).0).collect::<Vec<_>>()
+    };
+
+    SelfTestReport {
+        wheel: wheel.name().to_string(),
+        model: match model {
+            SpinModel::Uniform => "uniform",
+            SpinModel::Physics(_) => "physics",
+        },
+        entropy: seed.is_none().then_some(entropy),
+        fit: BiasReport::against(expected),
+        serial_correlation: lag_one_correlation(&sequence),
+        repeat_seed,
+        repeatable: run_seeded() == run_seeded(),
+    }
+}
+
+fn lag_one_correlation(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance: f64 = values.iter().map(|v| (v - mean).powi(2)).sum();
+    if variance == 0.0 {
+        return 0.0;
+    }
+    let covariance: f64 = values.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum();
+    covariance / variance
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fit = &self.fit;
+        writeln!(f, "=== Wheel Self-Test ({} wheel, {} spin model, {} spins) ===", self.wheel, self.model, fit.spins)?;
+        if let Some(entropy) = self.entropy {
+            writeln!(f, "Seeds from: {}", entropy)?;
+        }
+        writeln!(f, "{:<8} {:>10} {:>12} {:>8}", "Pocket", "Observed", "Expected", "sd")?;
+        for pocket in &fit.pockets {
+            writeln!(f, "{:<8} {:>10} {:>12.1} {:>+8.2}", pocket.label, pocket.observed, pocket.expected, pocket.z_score)?;
+        }
+        writeln!(
+            f,
+            "Chi-square:   {:.2} with {} degrees of freedom, p-value {:.4}",
+            fit.chi_square, fit.degrees_of_freedom, fit.p_value
+        )?;
+        writeln!(f, "Serial correlation (lag 1): {:+.4} ({:+.2} standard errors)", self.serial_correlation, self.correlation_z())?;
+        writeln!(
+            f,
+            "Same seed, same spins: {} (seed {}, {} spins twice)",
+            if self.repeatable { "yes" } else { "NO" },
+            self.repeat_seed,
+            REPEAT_SPINS
+        )?;
+        if !fit.is_reliable() {
+            writeln!(f, "Note: too few spins for the frequency test to be reliable; try more with --spins.")?;
+        }
+        if self.passed() {
+            write!(f, "Result: PASS. Nothing here suggests the wheel departs from its documented odds.")
+        } else {
+            write!(f, "Result: FAIL. The spins don't match the wheel's documented behaviour.")
+        }
+    }
+}
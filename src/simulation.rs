@@ -0,0 +1,490 @@
+// src/simulation.rs
+
+//! Monte Carlo simulation of a fixed bet layout, or of a
+//! [strategy file](crate::game::strategy), over many independent trials.
+//!
+//! Each trial starts a fresh bankroll and replays the layout round after round
+//! until it runs out of rounds or can no longer cover the stake. A strategy
+//! also leaves when its own stop conditions say so. Bets are paid and their
+//! misses settled by the table's [rules](crate::game::rules): its payouts,
+//! and what la partage, en prison, surrender, or a re-spin hand back when a
+//! bet misses. Trials run on
+//! every core when the `parallel` feature is enabled. Each trial seeds its own
+//! RNG from the master seed and its index, so a report depends only on the
+//! seed and never on how trials were scheduled across threads.
+
+use crate::game::bets::{Bet, BetType};
+use crate::game::compiled::{MAX_COMPILED_POCKETS, coverage_mask};
+use crate::game::money::Money;
+use crate::game::payout::PayoutTable;
+use crate::game::rules::{LossSettlement, MAX_RESPINS, TableRules};
+use crate::game::strategy::DeclarativeStrategy;
+use crate::game::wheel::{PocketId, WheelLike};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+
+/// How many trials to run and how long each one lasts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationConfig {
+    pub trials: u64,
+    /// Rounds played per trial unless the bankroll runs out first.
+    pub rounds_per_trial: u32,
+    pub starting_balance: Money,
+    /// Master seed; the same seed always produces the same report.
+    pub seed: u64,
+    /// The rules of the table being simulated, difficulty already applied.
+    pub rules: TableRules,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            trials: 10_000,
+            rounds_per_trial: 100,
+            starting_balance: Money::from_units(1000),
+            seed: 0,
+            rules: TableRules::default(),
+        }
+    }
+}
+
+/// Why a simulation could not be started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SimulationError {
+    /// The layout has no bets to play.
+    NoBets,
+    /// The wheel has more pockets than a compiled layout can describe.
+    WheelTooLarge(usize),
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::NoBets => write!(f, "no bets to simulate"),
+            SimulationError::WheelTooLarge(pockets) => write!(
+                f,
+                "wheel has {} pockets; simulation supports at most {}",
+                pockets, MAX_COMPILED_POCKETS
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// Outcome of a single trial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrialResult {
+    pub final_balance: Money,
+    pub peak_balance: Money,
+    pub rounds_played: u32,
+    /// True if the trial stopped early because it couldn't cover the layout
+    /// (or a strategy's next stake).
+    pub busted: bool,
+    pub wagered: Money,
+    pub returned: Money,
+}
+
+/// Aggregate results over every trial.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationReport {
+    pub trials: u64,
+    /// Total spins across all trials.
+    pub spins: u64,
+    pub busted: u64,
+    /// Trials that finished above the starting balance.
+    pub ahead: u64,
+    /// Totals across every trial, in whole units like the mean below: added
+    /// up as [`Money`] they could run past [`Money::MAX`].
+    pub wagered: f64,
+    pub returned: f64,
+    /// House edge the layout should give on this wheel, as a percentage.
+    pub expected_edge_percent: f64,
+    pub starting_balance: Money,
+    pub mean_final_balance: f64,
+    pub median_final_balance: Money,
+    pub min_final_balance: Money,
+    pub max_final_balance: Money,
+    pub max_peak_balance: Money,
+}
+
+impl SimulationReport {
+    /// Net result for the players across all trials.
+    pub fn net(&self) -> f64 {
+        self.returned - self.wagered
+    }
+
+    /// House edge observed in the simulation, as a percentage of money wagered.
+    pub fn realized_edge_percent(&self) -> f64 {
+        if self.wagered == 0.0 { 0.0 } else { -self.net() * 100.0 / self.wagered }
+    }
+
+    /// Share of trials that ran out of money, as a percentage.
+    pub fn bust_rate_percent(&self) -> f64 {
+        if self.trials == 0 {
+            0.0
+        } else {
+            self.busted as f64 * 100.0 / self.trials as f64
+        }
+    }
+
+    /// Share of trials that finished ahead, as a percentage.
+    pub fn ahead_rate_percent(&self) -> f64 {
+        if self.trials == 0 {
+            0.0
+        } else {
+            self.ahead as f64 * 100.0 / self.trials as f64
+        }
+    }
+
+    /// Merges per-trial results, which must be in trial order.
+    fn from_trials(results: &[TrialResult], starting_balance: Money, expected_edge_percent: f64) -> SimulationReport {
+        let mut finals: Vec<Money> = results.iter().map(|r| r.final_balance).collect();
+        finals.sort_unstable();
+        let trials = results.len() as u64;
+        let total_final: f64 = finals.iter().map(|balance| balance.as_f64()).sum();
+        SimulationReport {
+            trials,
+            spins: results.iter().map(|r| r.rounds_played as u64).sum(),
+            busted: results.iter().filter(|r| r.busted).count() as u64,
+            ahead: results.iter().filter(|r| r.final_balance > starting_balance).count() as u64,
+            wagered: results.iter().map(|r| r.wagered.as_f64()).sum(),
+            returned: results.iter().map(|r| r.returned.as_f64()).sum(),
+            expected_edge_percent,
+            starting_balance,
+            mean_final_balance: if trials == 0 { 0.0 } else { total_final / trials as f64 },
+            median_final_balance: finals.get(finals.len() / 2).copied().unwrap_or_default(),
+            min_final_balance: finals.first().copied().unwrap_or_default(),
+            max_final_balance: finals.last().copied().unwrap_or_default(),
+            max_peak_balance: results.iter().map(|r| r.peak_balance).max().unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Simulation Report ===")?;
+        writeln!(f, "Trials:               {}", self.trials)?;
+        writeln!(f, "Spins:                {}", self.spins)?;
+        writeln!(f, "Starting balance:     {}", self.starting_balance)?;
+        writeln!(f, "Mean final balance:   ${:.2}", self.mean_final_balance)?;
+        writeln!(f, "Median final balance: {}", self.median_final_balance)?;
+        writeln!(f, "Final balance range:  {} - {}", self.min_final_balance, self.max_final_balance)?;
+        writeln!(f, "Highest peak:         {}", self.max_peak_balance)?;
+        writeln!(f, "Busted:               {} ({:.2}%)", self.busted, self.bust_rate_percent())?;
+        writeln!(f, "Ended ahead:          {} ({:.2}%)", self.ahead, self.ahead_rate_percent())?;
+        writeln!(f, "Total wagered:        ${:.2}", self.wagered)?;
+        writeln!(f, "Total returned:       ${:.2}", self.returned)?;
+        writeln!(f, "Realized house edge:  {:.3}%", self.realized_edge_percent())?;
+        write!(f, "Expected house edge:  {:.3}%", self.expected_edge_percent)
+    }
+}
+
+/// Runs `config.trials` trials of `bets` on `wheel` and merges the results.
+pub fn simulate(wheel: &dyn WheelLike, bets: &[Bet], config: &SimulationConfig) -> Result<SimulationReport, SimulationError> {
+    if bets.is_empty() {
+        return Err(SimulationError::NoBets);
+    }
+    let layout = RuledLayout::compile(bets.iter().map(|bet| &bet.bet_type), wheel, &config.rules)?;
+    let stakes: Vec<Money> = bets.iter().map(|bet| bet.amount).collect();
+
+    let results = run_trials(config, |rng| run_trial(wheel, &layout, &stakes, config, rng));
+    let edge = expected_edge_percent(wheel, bets, &config.rules);
+    Ok(SimulationReport::from_trials(&results, config.starting_balance, edge))
+}
+
+/// Runs `config.trials` trials of `strategy`, each from its initial state.
+///
+/// The strategy's bets are compiled once and staked at whatever it picks
+/// each round, since every payout is proportional to its stake.
+pub fn simulate_strategy(
+    wheel: &dyn WheelLike,
+    strategy: &DeclarativeStrategy,
+    config: &SimulationConfig,
+) -> Result<SimulationReport, SimulationError> {
+    let unit_bets: Vec<Bet> = strategy.bet_types().iter().map(|t| Bet::new(t.clone(), 1)).collect();
+    if unit_bets.is_empty() {
+        return Err(SimulationError::NoBets);
+    }
+    let layout = RuledLayout::compile(strategy.bet_types().iter(), wheel, &config.rules)?;
+
+    let results = run_trials(config, |rng| run_strategy_trial(wheel, &layout, strategy.clone(), config, rng));
+    let edge = expected_edge_percent(wheel, &unit_bets, &config.rules);
+    Ok(SimulationReport::from_trials(&results, config.starting_balance, edge))
+}
+
+/// A layout's bets reduced to the pockets they cover, what they pay under
+/// the table's rules, and how those rules settle them when they miss.
+struct RuledLayout {
+    bets: Vec<RuledBet>,
+    /// Bit `i` is set when `PocketId(i)` is green.
+    zeros: u64,
+}
+
+struct RuledBet {
+    /// Bit `i` is set when the bet wins on `PocketId(i)`.
+    mask: u64,
+    multiplier: u32,
+    /// How a miss is settled on a green pocket, and on any other.
+    on_zero: LossSettlement,
+    elsewhere: LossSettlement,
+}
+
+/// Bets carried from one spin to the next by the rules, as an index into the
+/// layout and the stake they were placed with.
+#[derive(Default)]
+struct Held {
+    prisoners: Vec<(usize, Money)>,
+    respins: Vec<(usize, Money)>,
+}
+
+impl RuledLayout {
+    fn compile<'a>(
+        bet_types: impl Iterator<Item = &'a BetType>,
+        wheel: &dyn WheelLike,
+        rules: &TableRules,
+    ) -> Result<RuledLayout, SimulationError> {
+        let layout = wheel.layout();
+        let payouts = rules.payouts.as_ref().unwrap_or(layout.payouts());
+        let bets = bet_types
+            .map(|bet_type| {
+                Some(RuledBet {
+                    mask: coverage_mask(bet_type, layout)?,
+                    multiplier: payouts.multiplier(bet_type),
+                    on_zero: rules.settle_loss(bet_type, true),
+                    elsewhere: rules.settle_loss(bet_type, false),
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(SimulationError::WheelTooLarge(wheel.pockets().len()))?;
+        let zeros = wheel.zero_pockets().iter().fold(0u64, |mask, id| mask | (1 << id.0));
+        Ok(RuledLayout { bets, zeros })
+    }
+
+    /// Plays one round with each bet staked at `stake(index)`, along with
+    /// whatever `held` carries in, then any re-spins the rules call for.
+    /// Returns everything paid back, stakes included.
+    fn play(&self, wheel: &dyn WheelLike, rng: &mut StdRng, stake: impl Fn(usize) -> Money, held: &mut Held) -> Money {
+        let placed: Vec<(usize, Money)> = (0..self.bets.len()).map(|i| (i, stake(i))).collect();
+        let mut returned = self.settle(wheel.spin(rng), placed, held);
+        for _ in 0..MAX_RESPINS {
+            if held.respins.is_empty() {
+                break;
+            }
+            returned += self.settle(wheel.spin(rng), Vec::new(), held);
+        }
+        returned
+    }
+
+    /// Settles `placed` and everything `held` against one pocket, the way
+    /// the game's own settlement does.
+    fn settle(&self, pocket: PocketId, placed: Vec<(usize, Money)>, held: &mut Held) -> Money {
+        let zero = (self.zeros >> pocket.0) & 1 == 1;
+        let wins = |i: usize| (self.bets[i].mask >> pocket.0) & 1 == 1;
+        let mut returned = Money::ZERO;
+        for (i, stake) in std::mem::take(&mut held.prisoners) {
+            if !zero && wins(i) {
+                returned += stake;
+            }
+        }
+        let riding = std::mem::take(&mut held.respins);
+        for (i, stake) in placed.into_iter().chain(riding) {
+            let bet = &self.bets[i];
+            if wins(i) {
+                returned += stake * bet.multiplier + stake;
+                continue;
+            }
+            returned += match if zero { bet.on_zero } else { bet.elsewhere } {
+                LossSettlement::Lose => Money::ZERO,
+                LossSettlement::Imprison => {
+                    held.prisoners.push((i, stake));
+                    Money::ZERO
+                }
+                LossSettlement::Respin => {
+                    held.respins.push((i, stake));
+                    Money::ZERO
+                }
+                LossSettlement::Surrender | LossSettlement::HalfBack => stake / 2,
+                LossSettlement::Push => stake,
+            };
+        }
+        returned
+    }
+}
+
+/// Runs every trial with its own seeded RNG, returning results in trial order.
+fn run_trials<F>(config: &SimulationConfig, trial: F) -> Vec<TrialResult>
+where
+    F: Fn(&mut StdRng) -> TrialResult + Sync,
+{
+    let run = |index: u64| trial(&mut StdRng::seed_from_u64(trial_seed(config.seed, index)));
+    // Both paths collect in trial order, so the merge is deterministic.
+    #[cfg(feature = "parallel")]
+    let results = (0..config.trials).into_par_iter().map(run).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results = (0..config.trials).map(run).collect();
+    results
+}
+
+/// The layout's house edge from each bet's chance of winning on `wheel`,
+/// and what the table's rules hand back when it misses.
+fn expected_edge_percent(wheel: &dyn WheelLike, bets: &[Bet], rules: &TableRules) -> f64 {
+    let payouts = rules.payouts.as_ref().unwrap_or(wheel.layout().payouts());
+    let stake: f64 = bets.iter().map(|b| b.amount.as_f64()).sum();
+    let expected: f64 = bets.iter().map(|b| b.amount.as_f64() * expected_return(&b.bet_type, wheel, rules, payouts)).sum();
+    (1.0 - expected / stake) * 100.0
+}
+
+/// Average returned per unit staked on a bet, stake included, under `rules`.
+fn expected_return(bet_type: &BetType, wheel: &dyn WheelLike, rules: &TableRules, payouts: &PayoutTable) -> f64 {
+    let layout = wheel.layout();
+    let win = wheel.probability(bet_type);
+    let zero_miss: f64 = wheel
+        .zero_pockets()
+        .into_iter()
+        .filter(|&id| !bet_type.covers(layout, id))
+        .map(|id| wheel.probability(&BetType::StraightUp(layout.pocket(id).ticker)))
+        .sum();
+    let elsewhere = 1.0 - win - zero_miss;
+    let back = |settlement| match settlement {
+        LossSettlement::Lose | LossSettlement::Respin => 0.0,
+        // Only even-money bets go to prison, and they never cover a zero.
+        LossSettlement::Imprison => win,
+        LossSettlement::Surrender | LossSettlement::HalfBack => 0.5,
+        LossSettlement::Push => 1.0,
+    };
+    let paid = win * (payouts.multiplier(bet_type) as f64 + 1.0) + elsewhere * back(rules.settle_loss(bet_type, false));
+    match rules.settle_loss(bet_type, true) {
+        // A re-spin plays the bet again for nothing until it lands off green.
+        LossSettlement::Respin => paid / (1.0 - zero_miss),
+        on_zero => paid + zero_miss * back(on_zero),
+    }
+}
+
+fn run_trial(
+    wheel: &dyn WheelLike,
+    layout: &RuledLayout,
+    stakes: &[Money],
+    config: &SimulationConfig,
+    rng: &mut StdRng,
+) -> TrialResult {
+    let stake: Money = stakes.iter().sum();
+    let mut held = Held::default();
+    let mut result = TrialResult {
+        final_balance: config.starting_balance,
+        peak_balance: config.starting_balance,
+        rounds_played: 0,
+        busted: false,
+        wagered: Money::ZERO,
+        returned: Money::ZERO,
+    };
+    while result.rounds_played < config.rounds_per_trial {
+        if result.final_balance < stake {
+            result.busted = true;
+            break;
+        }
+        let returned = layout.play(wheel, rng, |i| stakes[i], &mut held);
+        result.final_balance = result.final_balance - stake + returned;
+        result.peak_balance = result.peak_balance.max(result.final_balance);
+        result.rounds_played += 1;
+        result.wagered += stake;
+        result.returned += returned;
+    }
+    result
+}
+
+fn run_strategy_trial(
+    wheel: &dyn WheelLike,
+    layout: &RuledLayout,
+    mut strategy: DeclarativeStrategy,
+    config: &SimulationConfig,
+    rng: &mut StdRng,
+) -> TrialResult {
+    let mut result = TrialResult {
+        final_balance: config.starting_balance,
+        peak_balance: config.starting_balance,
+        rounds_played: 0,
+        busted: false,
+        wagered: Money::ZERO,
+        returned: Money::ZERO,
+    };
+    let bets = layout.bets.len() as u32;
+    let mut held = Held::default();
+    while result.rounds_played < config.rounds_per_trial {
+        let Some(stake) = strategy.next_round(result.final_balance) else {
+            result.busted = strategy.stake() * bets > result.final_balance;
+            break;
+        };
+        let wagered = stake * bets;
+        let returned = layout.play(wheel, rng, |_| stake, &mut held);
+        strategy.settled(wagered, returned);
+        result.final_balance = result.final_balance - wagered + returned;
+        result.peak_balance = result.peak_balance.max(result.final_balance);
+        result.rounds_played += 1;
+        result.wagered += wagered;
+        result.returned += returned;
+    }
+    result
+}
+
+/// Derives an independent seed for one trial from the master seed (SplitMix64).
+fn trial_seed(master: u64, trial: u64) -> u64 {
+    let mut z = master.wrapping_add(trial.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::rules::GreenPocket;
+    use crate::game::wheel::{WeightedWheel, Wheel};
+
+    fn rules(green_pocket: GreenPocket) -> TableRules {
+        TableRules { green_pocket, ..TableRules::default() }
+    }
+
+    fn red() -> Vec<Bet> {
+        vec![Bet::new(BetType::Red, 10u32)]
+    }
+
+    #[test]
+    fn expected_edge_counts_what_the_rules_hand_back() {
+        let wheel = Wheel::european();
+        let edge = |green_pocket| expected_edge_percent(&wheel, &red(), &rules(green_pocket));
+        assert!((edge(GreenPocket::Lose) - 100.0 / 37.0).abs() < 1e-9);
+        assert!((edge(GreenPocket::HalfBack) - 50.0 / 37.0).abs() < 1e-9);
+        assert!(edge(GreenPocket::Push).abs() < 1e-9);
+        assert!(edge(GreenPocket::Respin).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expected_edge_uses_the_tables_payouts() {
+        let wheel = Wheel::european();
+        let straight = vec![Bet::new(BetType::StraightUp(wheel.get_all_pockets()[1].ticker), 1u32)];
+        let mut payouts = wheel.payouts().clone();
+        payouts.straight_up = 36;
+        let rules = TableRules { payouts: Some(payouts), ..TableRules::default() };
+        assert!(expected_edge_percent(&wheel, &straight, &rules).abs() < 1e-9);
+    }
+
+    #[test]
+    fn trials_settle_misses_by_the_green_pocket_rule() {
+        let layout = Wheel::european();
+        let zero = layout.zero_pockets()[0];
+        let weights = (0..layout.get_all_pockets().len()).map(|id| if id == zero.0 { 1.0 } else { 0.0 }).collect();
+        let wheel = WeightedWheel::new(layout, weights).unwrap();
+        let config = |green_pocket| SimulationConfig { trials: 4, rounds_per_trial: 10, rules: rules(green_pocket), ..SimulationConfig::default() };
+
+        let push = simulate(&wheel, &red(), &config(GreenPocket::Push)).unwrap();
+        assert_eq!(push.returned, push.wagered);
+        let half_back = simulate(&wheel, &red(), &config(GreenPocket::HalfBack)).unwrap();
+        assert_eq!(half_back.returned, half_back.wagered / 2.0);
+        let lose = simulate(&wheel, &red(), &config(GreenPocket::Lose)).unwrap();
+        assert_eq!(lose.returned, 0.0);
+    }
+}
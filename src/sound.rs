@@ -0,0 +1,102 @@
+// src/sound.rs
+
+//! Sound effects for play at the table (requires the `audio` feature).
+//!
+//! [`SoundSink`] is an [`EventSink`] like any other, so builds without the
+//! feature, such as a server or WASM embedding, leave it out with nothing
+//! else to change. The sounds are synthesized rather than loaded from files:
+//! the ball spinning down, its click into a pocket, a chime for a winning
+//! round, and a rising run of notes when a bet pays 35 to 1 or more.
+
+use crate::game::events::{EventSink, GameEvent};
+use rodio::source::{SineWave, chirp};
+use rodio::{OutputStream, OutputStreamBuilder, Sink, Source, StreamError};
+use std::time::Duration;
+
+/// Odds (to 1) at which a win counts as a jackpot: a straight-up bet.
+const JACKPOT_ODDS: u32 = 35;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// One of the game's sounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cue {
+    Spin,
+    Landing,
+    Win,
+    Jackpot,
+}
+
+impl Cue {
+    fn source(self) -> Box<dyn Source + Send> {
+        match self {
+            Cue::Spin => Box::new(chirp(SAMPLE_RATE, 900.0, 250.0, Duration::from_millis(1200)).amplify(0.25)),
+            Cue::Landing => Box::new(tone(1800.0, 60)),
+            Cue::Win => Box::new(notes(&[659.25, 880.0], 160)),
+            Cue::Jackpot => Box::new(notes(&[523.25, 659.25, 783.99, 1046.5], 140)),
+        }
+    }
+}
+
+/// A sine tone of `millis` length that fades out to avoid a click at the end.
+fn tone(frequency: f32, millis: u64) -> impl Source + Send {
+    let length = Duration::from_millis(millis);
+    SineWave::new(frequency).take_duration(length).fade_out(length).amplify(0.4)
+}
+
+/// Tones played one after another.
+fn notes(frequencies: &[f32], millis: u64) -> impl Source + Send {
+    let (queue, output) = rodio::queue::queue(false);
+    for &frequency in frequencies {
+        queue.append(tone(frequency, millis));
+    }
+    output
+}
+
+/// An [`EventSink`] that plays a sound for the spin, the landing, and a
+/// winning round.
+pub struct SoundSink {
+    // Playback stops when the stream is dropped, so it's kept alongside the sink.
+    _stream: OutputStream,
+    sink: Sink,
+    /// The best win seen in the round so far, played once it's settled.
+    best_win: Option<Cue>,
+}
+
+impl SoundSink {
+    /// Opens the default audio device, playing at `volume` (0 to 1).
+    pub fn open(volume: f32) -> Result<Self, StreamError> {
+        let mut stream = OutputStreamBuilder::open_default_stream()?;
+        stream.log_on_drop(false);
+        let sink = Sink::connect_new(stream.mixer());
+        sink.set_volume(volume);
+        Ok(SoundSink { _stream: stream, sink, best_win: None })
+    }
+
+    pub fn play(&self, cue: Cue) {
+        self.sink.append(cue.source());
+    }
+}
+
+impl EventSink for SoundSink {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::Spin { .. } => {
+                self.play(Cue::Spin);
+                self.play(Cue::Landing);
+            }
+            GameEvent::Settlement { amount, won: true, payout, .. } => {
+                // Taken from the payout rather than the bet type, as custom payout tables change it.
+                let odds = payout.cents() / amount.cents().max(1) - 1;
+                let cue = if odds >= JACKPOT_ODDS as i64 { Cue::Jackpot } else { Cue::Win };
+                self.best_win = self.best_win.max(Some(cue));
+            }
+            GameEvent::RoundComplete { .. } => {
+                if let Some(cue) = self.best_win.take() {
+                    self.play(cue);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,134 @@
+// src/spectate.rs
+
+//! Spectating: watch a betting bot play, a round at a time, at a pace a
+//! person can follow.
+//!
+//! [`Spectator`] wraps the bot's controller and waits between rounds,
+//! reading commands typed in the meantime, each followed by Enter: Enter
+//! alone pauses and resumes, `n` plays one more round while paused, `f`
+//! turns fast-forward on and off, and `q` stops watching. The bot bets just
+//! as it would with nobody looking.
+
+use crate::game::Game;
+use crate::game::controller::{PlayerController, Turn};
+use crate::game::money::Money;
+use crate::game::wheel::{PocketId, Wheel};
+use crate::{input, say};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Pause between rounds unless another is asked for.
+pub const DEFAULT_SPECTATE_DELAY: Duration = Duration::from_millis(1500);
+
+/// How often the pause looks for a command.
+const KEY_POLL: Duration = Duration::from_millis(50);
+
+const COMMANDS: &str = "Enter pauses, f fast-forwards, q stops watching.";
+
+/// Plays a bot's rounds out slowly enough to watch; see the
+/// [module docs](self).
+pub struct Spectator {
+    inner: Box<dyn PlayerController>,
+    delay: Duration,
+    paused: bool,
+    fast: bool,
+    /// False once input has ended and no more commands can come.
+    listening: bool,
+    started: bool,
+}
+
+impl Spectator {
+    /// Watches `inner` play.
+    pub fn new(inner: Box<dyn PlayerController>) -> Self {
+        Spectator { inner, delay: DEFAULT_SPECTATE_DELAY, paused: false, fast: false, listening: true, started: false }
+    }
+
+    /// Pauses `delay` between rounds; fast-forward skips the pause.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    fn pace(&self) -> Duration {
+        if self.fast { Duration::ZERO } else { self.delay }
+    }
+
+    /// The next command typed, trimmed and lowercased, waiting for one while
+    /// paused.
+    fn next_command(&mut self) -> Option<String> {
+        if !self.listening {
+            return None;
+        }
+        let line = if self.paused { input::read_line().unwrap_or_default() } else { input::poll_line()? };
+        if line.is_empty() {
+            // Nothing more can be typed, so nobody could resume a pause.
+            self.listening = false;
+            self.paused = false;
+            return None;
+        }
+        Some(line.trim().to_lowercase())
+    }
+
+    /// Waits out the pause after a round, following any commands. False to
+    /// stop watching.
+    fn wait(&mut self) -> bool {
+        let mut until = Instant::now() + self.pace();
+        loop {
+            match self.next_command().as_deref() {
+                Some("q") => return false,
+                Some("f") => {
+                    self.fast = !self.fast;
+                    say!("Fast-forward {}.", if self.fast { "on" } else { "off" });
+                    until = Instant::now() + self.pace();
+                }
+                Some("") if self.paused => {
+                    self.paused = false;
+                    say!("Resumed.");
+                    return true;
+                }
+                Some("") => {
+                    self.paused = true;
+                    say!("Paused. Enter resumes, n plays one round, q stops watching.");
+                }
+                Some("n") if self.paused => return true,
+                Some(_) => say!("{}", COMMANDS),
+                None => {
+                    let left = until.saturating_duration_since(Instant::now());
+                    if left.is_zero() {
+                        return true;
+                    }
+                    thread::sleep(left.min(KEY_POLL));
+                }
+            }
+        }
+    }
+}
+
+impl PlayerController for Spectator {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        if !self.started {
+            self.started = true;
+            say!("Spectating {}. {}", self.inner.name(), COMMANDS);
+        }
+        self.inner.place_bets(game)
+    }
+
+    fn spin_settled(&mut self, wheel: &Wheel, pocket: PocketId, returned: Money) {
+        self.inner.spin_settled(wheel, pocket, returned);
+    }
+
+    fn play_again(&mut self, game: &mut Game) -> bool {
+        if !self.inner.play_again(game) {
+            return false;
+        }
+        if self.wait() {
+            return true;
+        }
+        say!("Stopped watching.");
+        false
+    }
+}
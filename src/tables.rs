@@ -0,0 +1,251 @@
+// src/tables.rs
+
+//! The tables a player can sit at.
+//!
+//! Every table plays by its own [`TableRules`], which take the place of the
+//! config's `[rules]`. The main floor is open to everyone; the VIP tables
+//! have higher limits and more side bets, and open once a profile's bankroll
+//! or level is high enough. The tables live in `tables/` and are compiled in.
+//! A [`TableManager`] keeps a player at several of them at once.
+//!
+//! ```toml
+//! name = "High Roller Room"
+//! description = "Bigger stakes and the Black Swan side bet."
+//!
+//! [unlock]                 # either one opens the table
+//! bankroll = 5000
+//! level = 8
+//!
+//! [rules]                  # as in the config
+//! min_bet = 25
+//! side_bets = ["black_swan"]
+//! ```
+
+use crate::game::Game;
+use crate::game::custom::BetEvaluator;
+use crate::game::money::Money;
+use crate::game::rules::TableRules;
+use crate::game::wallet::{BuyInError, Wallet};
+use crate::profile::Profile;
+use serde::Deserialize;
+use std::fmt;
+
+const BUILT_IN: &[(&str, &str)] = &[
+    ("main-floor", include_str!("../tables/main-floor.toml")),
+    ("high-roller", include_str!("../tables/high-roller.toml")),
+    ("private-salon", include_str!("../tables/private-salon.toml")),
+];
+
+/// What opens a table. Reaching either threshold is enough; a table with
+/// neither is open to all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Unlock {
+    pub bankroll: Option<Money>,
+    pub level: Option<u32>,
+}
+
+impl Unlock {
+    pub fn is_met(&self, bankroll: Money, level: u32) -> bool {
+        if *self == Unlock::default() {
+            return true;
+        }
+        self.bankroll.is_some_and(|min| bankroll >= min) || self.level.is_some_and(|min| level >= min)
+    }
+}
+
+impl fmt::Display for Unlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.bankroll, self.level) {
+            (None, None) => write!(f, "open to all"),
+            (Some(bankroll), None) => write!(f, "a {} bankroll", bankroll),
+            (None, Some(level)) => write!(f, "level {}", level),
+            (Some(bankroll), Some(level)) => write!(f, "a {} bankroll or level {}", bankroll, level),
+        }
+    }
+}
+
+/// A table as written in its file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Table {
+    /// Short name, from the file name; chosen with `--table`.
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub unlock: Unlock,
+    #[serde(default)]
+    pub rules: TableRules,
+}
+
+impl Table {
+    /// Every table, the main floor first.
+    pub fn built_in() -> Vec<Table> {
+        BUILT_IN
+            .iter()
+            .map(|(id, text)| {
+                let mut table: Table = toml::from_str(text).expect("built-in tables parse");
+                table.id = id.to_string();
+                table.rules.validate().expect("built-in tables are valid");
+                table
+            })
+            .collect()
+    }
+
+    pub fn find(id: &str) -> Option<Table> {
+        Table::built_in().into_iter().find(|table| table.id == id)
+    }
+
+    /// True if `profile` may sit here. Without a profile only the tables
+    /// open to all are.
+    pub fn is_open_to(&self, profile: Option<&Profile>) -> bool {
+        match profile {
+            Some(profile) => self.unlock.is_met(profile.bankroll.unwrap_or_default(), profile.progress().level),
+            None => self.unlock == Unlock::default(),
+        }
+    }
+
+    /// The stakes and side bets, for listings.
+    pub fn limits(&self) -> String {
+        let money = |value: Money| self.rules.currency.amount(value);
+        let stakes = match (self.rules.min_bet, self.rules.max_bet) {
+            (None, None) => "any stake".to_string(),
+            (Some(min), None) => format!("{} minimum", money(min)),
+            (None, Some(max)) => format!("{} maximum", money(max)),
+            (Some(min), Some(max)) => format!("{} to {} a bet", money(min), money(max)),
+        };
+        let stakes = match self.rules.stake_increment {
+            Some(increment) => format!("{} in steps of {}", stakes, money(increment)),
+            None => stakes,
+        };
+        let stakes = match self.rules.min_buy_in {
+            Some(buy_in) => format!("{}, {} buy-in", stakes, money(buy_in)),
+            None => stakes,
+        };
+        if self.rules.side_bets.is_empty() {
+            return stakes;
+        }
+        let side_bets: Vec<&str> = self.rules.side_bets.iter().map(|bet| bet.name()).collect();
+        format!("{}; side bets: {}", stakes, side_bets.join(", "))
+    }
+}
+
+/// A table the player has bought in at.
+pub struct Seat {
+    pub table: Table,
+    pub game: Game,
+}
+
+/// Why a [`TableManager`] refused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeatError {
+    /// There's no `n`th open table.
+    NoSuchSeat(usize),
+    BuyIn(BuyInError),
+}
+
+impl fmt::Display for SeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeatError::NoSuchSeat(n) => write!(f, "there's no table {}", n + 1),
+            SeatError::BuyIn(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for SeatError {}
+
+impl From<BuyInError> for SeatError {
+    fn from(e: BuyInError) -> Self {
+        SeatError::BuyIn(e)
+    }
+}
+
+/// One player at several tables, paying in and out of one [`Wallet`].
+///
+/// Each table keeps its own game: its rules, chips, and bets, even at two
+/// tables of the same kind. One of them
+/// is current, the one the player is looking at; the others wait, bets and
+/// all, until the player switches back.
+pub struct TableManager {
+    wallet: Wallet,
+    seats: Vec<Seat>,
+    current: usize,
+}
+
+impl TableManager {
+    pub fn new(wallet: Wallet) -> Self {
+        TableManager { wallet, seats: Vec::new(), current: 0 }
+    }
+
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    pub fn seats(&self) -> &[Seat] {
+        &self.seats
+    }
+
+    /// The index of the current table, if any is open.
+    pub fn current_index(&self) -> Option<usize> {
+        (!self.seats.is_empty()).then_some(self.current)
+    }
+
+    pub fn current(&self) -> Option<&Seat> {
+        self.seats.get(self.current)
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut Seat> {
+        self.seats.get_mut(self.current)
+    }
+
+    /// Sits down at `table` with `game`, which plays by the table's rules from
+    /// here on, buying `amount` in chips. The new table becomes current.
+    pub fn open(&mut self, table: Table, mut game: Game, amount: Money) -> Result<usize, SeatError> {
+        game.set_rules(table.rules.clone());
+        self.wallet.buy_in(&mut game, amount)?;
+        self.seats.push(Seat { table, game });
+        self.current = self.seats.len() - 1;
+        Ok(self.current)
+    }
+
+    /// Makes table `index` the current one.
+    pub fn switch(&mut self, index: usize) -> Result<&mut Seat, SeatError> {
+        if index >= self.seats.len() {
+            return Err(SeatError::NoSuchSeat(index));
+        }
+        self.current = index;
+        Ok(&mut self.seats[index])
+    }
+
+    /// Buys `amount` more chips at table `index`.
+    pub fn top_up(&mut self, index: usize, amount: Money) -> Result<(), SeatError> {
+        let seat = self.seats.get_mut(index).ok_or(SeatError::NoSuchSeat(index))?;
+        self.wallet.buy_in(&mut seat.game, amount)?;
+        Ok(())
+    }
+
+    /// Cashes out of table `index` and gets up, returning the table and what
+    /// its chips came to. Bets not yet spun are taken back first.
+    pub fn leave(&mut self, index: usize) -> Result<(Table, Money), SeatError> {
+        if index >= self.seats.len() {
+            return Err(SeatError::NoSuchSeat(index));
+        }
+        let mut seat = self.seats.remove(index);
+        let amount = self.wallet.cash_out(&mut seat.game);
+        if self.current >= index && self.current > 0 {
+            self.current -= 1;
+        }
+        Ok((seat.table, amount))
+    }
+
+    /// Leaves every table, returning the wallet with everything cashed out.
+    pub fn leave_all(mut self) -> Wallet {
+        for seat in &mut self.seats {
+            self.wallet.cash_out(&mut seat.game);
+        }
+        self.wallet
+    }
+}
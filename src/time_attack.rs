@@ -0,0 +1,142 @@
+// src/time_attack.rs
+
+//! Time attack: make as much as possible before the clock, or the round
+//! count, runs out.
+//!
+//! The score is the profit less half the deepest drawdown along the way, so
+//! a steady climb beats the same profit scraped back from near ruin. Scores
+//! of sessions played under a profile go on its record, see
+//! [`LifetimeStats::best_time_attack`](crate::profile::LifetimeStats::best_time_attack).
+
+use crate::game::events::{EventSink, GameEvent};
+use crate::game::money::Money;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// When a time attack ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackLimit {
+    Time(Duration),
+    Rounds(u32),
+}
+
+impl AttackLimit {
+    /// Parses `5m`, `90s`, or `30r` (rounds).
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().to_lowercase();
+        let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+        let number: u32 = number.parse().ok().filter(|&n| n > 0)?;
+        match unit {
+            "m" | "min" => Some(AttackLimit::Time(Duration::from_secs(number as u64 * 60))),
+            "s" | "sec" => Some(AttackLimit::Time(Duration::from_secs(number as u64))),
+            "r" | "rounds" => Some(AttackLimit::Rounds(number)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AttackLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttackLimit::Time(limit) if limit.as_secs() % 60 == 0 => write!(f, "{} minute(s)", limit.as_secs() / 60),
+            AttackLimit::Time(limit) => write!(f, "{} seconds", limit.as_secs()),
+            AttackLimit::Rounds(rounds) => write!(f, "{} rounds", rounds),
+        }
+    }
+}
+
+/// A time attack in progress. Feed it the game's events to follow the balance.
+#[derive(Debug, Clone)]
+pub struct TimeAttack {
+    limit: AttackLimit,
+    started: Instant,
+    rounds: u32,
+    starting_balance: Money,
+    balance: Money,
+    peak: Money,
+    drawdown: Money,
+}
+
+impl TimeAttack {
+    /// Starts the clock.
+    pub fn start(limit: AttackLimit, starting_balance: Money) -> Self {
+        TimeAttack {
+            limit,
+            started: Instant::now(),
+            rounds: 0,
+            starting_balance,
+            balance: starting_balance,
+            peak: starting_balance,
+            drawdown: Money::ZERO,
+        }
+    }
+
+    pub fn limit(&self) -> AttackLimit {
+        self.limit
+    }
+
+    /// True once the time or rounds are used up.
+    pub fn is_over(&self) -> bool {
+        match self.limit {
+            AttackLimit::Time(limit) => self.started.elapsed() >= limit,
+            AttackLimit::Rounds(rounds) => self.rounds >= rounds,
+        }
+    }
+
+    /// What's left, for the round header.
+    pub fn remaining(&self) -> String {
+        match self.limit {
+            AttackLimit::Time(limit) => {
+                let left = limit.saturating_sub(self.started.elapsed()).as_secs();
+                format!("{}:{:02} left", left / 60, left % 60)
+            }
+            AttackLimit::Rounds(rounds) => format!("{} of {} rounds left", rounds.saturating_sub(self.rounds), rounds),
+        }
+    }
+
+    pub fn score(&self) -> AttackScore {
+        let profit = self.balance - self.starting_balance;
+        AttackScore {
+            rounds: self.rounds,
+            profit,
+            drawdown: self.drawdown,
+            score: profit - self.drawdown / 2,
+        }
+    }
+}
+
+impl EventSink for TimeAttack {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        match event {
+            GameEvent::BalanceChange { balance, .. } => {
+                self.balance = *balance;
+                self.peak = self.peak.max(*balance);
+                self.drawdown = self.drawdown.max(self.peak - *balance);
+            }
+            GameEvent::RoundComplete { .. } => self.rounds += 1,
+            GameEvent::SpinVoided { .. } => self.rounds = self.rounds.saturating_sub(1),
+            _ => {}
+        }
+    }
+}
+
+/// How a time attack went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackScore {
+    pub rounds: u32,
+    pub profit: Money,
+    /// Deepest fall from a high point of the balance.
+    pub drawdown: Money,
+    pub score: Money,
+}
+
+impl fmt::Display for AttackScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=== Time Attack Result ===")?;
+        writeln!(f, "Rounds played:  {}", self.rounds)?;
+        writeln!(f, "Profit:         {:+}", self.profit)?;
+        writeln!(f, "Max drawdown:   {}", self.drawdown)?;
+        write!(f, "Score:          {} (profit less half the drawdown)", self.score)
+    }
+}
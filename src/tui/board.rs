@@ -0,0 +1,189 @@
+// src/tui/board.rs
+
+//! The betting board in character cells: the numbers in three rows with the
+//! green pockets to their left, then the dozens, the even-money bets, the
+//! wheel's sector columns, and as many of its categories as fit.
+//!
+//! Cells are laid out from the board's top-left corner; [`Board::cell_at`]
+//! takes a position on the screen, so the board remembers where it was last
+//! drawn.
+
+use ratatui::Frame;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Paragraph;
+use roulette_game::game::bets::{Bet, BetType};
+use roulette_game::game::wheel::{Color as PocketColor, Wheel};
+use roulette_game::presentation;
+
+/// Width of a number's cell.
+const NUMBER_W: u16 = 5;
+const FELT: Color = Color::Rgb(20, 80, 50);
+
+struct Cell {
+    /// Position and size from the board's top-left corner.
+    area: Rect,
+    bet_type: BetType,
+    label: String,
+    color: Option<PocketColor>,
+}
+
+pub struct Board {
+    cells: Vec<Cell>,
+    width: u16,
+    height: u16,
+    /// Where the board was last drawn.
+    origin: Position,
+}
+
+impl Board {
+    pub fn new(wheel: &Wheel) -> Self {
+        let mut board = Board { cells: Vec::new(), width: 0, height: 0, origin: Position::ORIGIN };
+        let grid_columns = (wheel.highest_number() / 3) as u16;
+        let grid_width = grid_columns * NUMBER_W;
+
+        for (i, &id) in wheel.zero_pockets().iter().enumerate().take(3) {
+            let pocket = wheel.pocket(id);
+            board.push(Rect::new(0, i as u16, NUMBER_W, 1), BetType::StraightUp(pocket.ticker), pocket.label(), Some(pocket.color));
+        }
+        for column in 0..grid_columns {
+            for row in 0..3u16 {
+                let number = (column * 3 + (3 - row)) as u8;
+                let Some(pocket) = wheel.get_pocket(number) else { continue };
+                let area = Rect::new(NUMBER_W + column * NUMBER_W, row, NUMBER_W, 1);
+                board.push(area, BetType::StraightUp(pocket.ticker), pocket.label(), Some(pocket.color));
+            }
+        }
+
+        let dozens = [BetType::GrowthDozen, BetType::ValueDozen, BetType::BlueChipDozen];
+        board.add_row(3, grid_width, dozens.into_iter().map(|bet| (bet.to_string(), bet, None)));
+        let even_money = [
+            ("Low", BetType::Low, None),
+            ("Even", BetType::Even, None),
+            ("Red", BetType::Red, Some(PocketColor::Red)),
+            ("Black", BetType::Black, Some(PocketColor::Black)),
+            ("Odd", BetType::Odd, None),
+            ("High", BetType::High, None),
+        ];
+        board.add_row(4, grid_width, even_money.into_iter().map(|(label, bet, color)| (label.to_string(), bet, color)));
+        let columns = (1..=3u8).filter_map(|n| wheel.column(n).map(|column| (column.name.to_string(), BetType::Column(n), None)));
+        board.add_row(5, grid_width, columns);
+
+        // As on the windowed board, only categories wider than one stock,
+        // and none that take in a green pocket.
+        let categories = wheel
+            .categories()
+            .into_iter()
+            .filter(|&id| wheel.category_members(id).is_some_and(|members| members.len() > 1))
+            .filter(|&id| !wheel.zero_pockets().iter().any(|&zero| wheel.in_category(id, zero)));
+        let right = NUMBER_W + grid_width;
+        let (mut x, mut y) = (0, 6);
+        for id in categories {
+            let label = id.name().to_string();
+            let width = label.chars().count() as u16 + 2;
+            if x + width > right {
+                x = 0;
+                y += 1;
+            }
+            if y >= 9 {
+                break;
+            }
+            board.push(Rect::new(x, y, width, 1), BetType::Category(id), label, None);
+            x += width + 1;
+        }
+        board.width = right;
+        board.height = board.cells.iter().map(|cell| cell.area.bottom()).max().unwrap_or(0);
+        board
+    }
+
+    /// Adds a cell, with the palette's symbol on its label if it has a colour.
+    fn push(&mut self, area: Rect, bet_type: BetType, label: String, color: Option<PocketColor>) {
+        let label = match color {
+            Some(color) => presentation::palette().mark(color, &label),
+            None => label,
+        };
+        self.cells.push(Cell { area, bet_type, label, color });
+    }
+
+    /// Lays `bets` out side by side across the numbers, from the first
+    /// number's column.
+    fn add_row(&mut self, y: u16, width: u16, bets: impl Iterator<Item = (String, BetType, Option<PocketColor>)>) {
+        let bets: Vec<_> = bets.collect();
+        let count = bets.len().max(1) as u16;
+        for (i, (label, bet_type, color)) in bets.into_iter().enumerate() {
+            let i = i as u16;
+            let (left, right) = (width * i / count, width * (i + 1) / count);
+            self.push(Rect::new(NUMBER_W + left, y, right - left, 1), bet_type, label, color);
+        }
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn bet_type(&self, index: usize) -> &BetType {
+        &self.cells[index].bet_type
+    }
+
+    /// The cell under a point on the screen, as of the last draw.
+    pub fn cell_at(&self, x: u16, y: u16) -> Option<usize> {
+        let point = Position::new(x.checked_sub(self.origin.x)?, y.checked_sub(self.origin.y)?);
+        self.cells.iter().position(|cell| cell.area.contains(point))
+    }
+
+    /// The cell the cursor moves to from `from`, one step `dx` across or
+    /// `dy` down: the nearest cell that way, lining up as well as it can.
+    pub fn step(&self, from: usize, dx: i32, dy: i32) -> usize {
+        let here = self.cells[from].area;
+        let center = |area: Rect| area.x as i32 * 2 + area.width as i32;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| {
+                let area = cell.area;
+                match (dx.signum(), dy.signum()) {
+                    (0, 1) => area.y > here.y,
+                    (0, _) => area.y < here.y,
+                    (1, _) => area.y == here.y && area.x > here.x,
+                    _ => area.y == here.y && area.x < here.x,
+                }
+            })
+            .min_by_key(|(_, cell)| {
+                let rows = (cell.area.y as i32 - here.y as i32).abs();
+                (rows, (center(cell.area) - center(here)).abs())
+            })
+            .map_or(from, |(index, _)| index)
+    }
+
+    /// Draws the board at the top-left of `area`, highlighting the cursor's
+    /// cell and marking the cells with bets on them.
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect, cursor: usize, bets: &[Bet]) {
+        self.origin = area.as_position();
+        for (index, cell) in self.cells.iter().enumerate() {
+            let at = Rect::new(area.x + cell.area.x, area.y + cell.area.y, cell.area.width, cell.area.height);
+            let at = at.intersection(area);
+            if at.is_empty() {
+                continue;
+            }
+            let background = cell.color.map_or(FELT, pocket_color);
+            let mut style = Style::new().fg(Color::White).bg(background);
+            if bets.iter().any(|bet| bet.bet_type == cell.bet_type) {
+                style = style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+            }
+            if index == cursor {
+                style = style.fg(Color::Black).bg(Color::Yellow);
+            }
+            frame.render_widget(Paragraph::new(cell.label.as_str()).style(style).centered(), at);
+        }
+    }
+}
+
+/// A pocket's colour as the palette paints it.
+pub fn pocket_color(color: PocketColor) -> Color {
+    let presentation::Rgb(r, g, b) = presentation::palette().rgb(color);
+    Color::Rgb(r, g, b)
+}
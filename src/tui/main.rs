@@ -0,0 +1,332 @@
+// src/tui/main.rs
+
+//! Wall Street Roulette full-screen in the terminal (requires the `tui`
+//! feature).
+//!
+//! The betting board is drawn with ratatui, and every bet and spin goes
+//! through [`Game`] as in the line-based game. Click a cell to put the
+//! selected chip on it and scroll to pick a bigger or smaller chip. Every
+//! action has a key as well, so the board plays the same without a mouse:
+//! the terminal may not report one, and `--no-mouse` leaves it alone.
+//!
+//! ```text
+//! roulette_tui [--wheel european|american|mini] [--balance <AMOUNT>]
+//!              [--palette classic|color_blind|high_contrast] [--color-symbols] [--no-mouse]
+//! ```
+
+mod board;
+
+use board::Board;
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use roulette_game::console;
+use roulette_game::game::Game;
+use roulette_game::game::bets::Bet;
+use roulette_game::game::currency::Currency;
+use roulette_game::game::events::{EventSink, GameEvent};
+use roulette_game::game::money::Money;
+use roulette_game::game::wheel::Wheel;
+use roulette_game::presentation::{self, Palette, Scheme};
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+const DEFAULT_BALANCE: Money = Money::from_units(1_000);
+/// Chips offered when the table doesn't set its own.
+const DEFAULT_CHIPS: [u32; 5] = [1, 5, 25, 100, 500];
+/// Most lines kept in the round log.
+const LOG_LINES: usize = 8;
+
+/// Keeps the game's events so the screen can report how each bet settled.
+#[derive(Default)]
+struct EventLog {
+    events: Vec<GameEvent>,
+}
+
+impl EventSink for EventLog {
+    fn record(&mut self, _round: u32, event: &GameEvent) {
+        self.events.push(event.clone());
+    }
+}
+
+struct App {
+    game: Game,
+    board: Board,
+    events: Rc<RefCell<EventLog>>,
+    chips: Vec<u32>,
+    /// Index into `chips` of the chip a click or Enter places.
+    chip: usize,
+    /// The board cell the keyboard acts on.
+    cursor: usize,
+    /// Whether clicks and the scroll wheel are being reported.
+    mouse: bool,
+    /// Where each chip in the tray was last drawn, for clicks on it.
+    tray: Vec<Rect>,
+    status: String,
+    log: Vec<String>,
+    quit: bool,
+}
+
+impl App {
+    fn new(wheel: Wheel, balance: Money, mouse: bool) -> Self {
+        let board = Board::new(&wheel);
+        let mut game = Game::with_wheel(balance, Box::new(wheel));
+        let events = Rc::new(RefCell::new(EventLog::default()));
+        game.add_event_sink(Box::new(events.clone()));
+        let chips = game.rules().chips.as_ref().map_or(DEFAULT_CHIPS.to_vec(), |set| set.denominations().to_vec());
+        let status = if mouse {
+            "Click a cell to bet the selected chip; scroll to change chips."
+        } else {
+            "Move with the arrow keys and press Enter to bet the selected chip."
+        };
+        App {
+            game,
+            board,
+            events,
+            chips,
+            chip: 0,
+            cursor: 0,
+            mouse,
+            tray: Vec::new(),
+            status: status.to_string(),
+            log: Vec::new(),
+            quit: false,
+        }
+    }
+
+    fn chip_value(&self) -> u32 {
+        self.chips[self.chip]
+    }
+
+    /// Picks the next chip up, or down for a negative `steps`, stopping at
+    /// either end of the tray.
+    fn change_chip(&mut self, steps: i32) {
+        self.chip = (self.chip as i32 + steps).clamp(0, self.chips.len() as i32 - 1) as usize;
+        self.status = format!("Betting {} a click.", self.game.rules().currency.amount(self.chip_value()));
+    }
+
+    fn key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Left | KeyCode::Char('h') => self.cursor = self.board.step(self.cursor, -1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.cursor = self.board.step(self.cursor, 1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = self.board.step(self.cursor, 0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = self.board.step(self.cursor, 0, 1),
+            KeyCode::Enter | KeyCode::Char(' ') => self.place(self.cursor),
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char(']') => self.change_chip(1),
+            KeyCode::Char('-') | KeyCode::Char('[') => self.change_chip(-1),
+            KeyCode::Char('s') => self.spin(),
+            KeyCode::Char('c') => self.clear(),
+            KeyCode::Char('r') => self.rebet(),
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            _ => {}
+        }
+    }
+
+    fn mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let point = Position::new(event.column, event.row);
+                if let Some(chip) = self.tray.iter().position(|area| area.contains(point)) {
+                    self.chip = chip;
+                    self.change_chip(0);
+                } else if let Some(cell) = self.board.cell_at(event.column, event.row) {
+                    self.cursor = cell;
+                    self.place(cell);
+                }
+            }
+            MouseEventKind::ScrollUp => self.change_chip(1),
+            MouseEventKind::ScrollDown => self.change_chip(-1),
+            _ => {}
+        }
+    }
+
+    fn place(&mut self, cell: usize) {
+        let bet_type = self.board.bet_type(cell).clone();
+        let chip = self.game.rules().currency.amount(self.chip_value());
+        match console::quietly(|| self.game.try_place_bet(Bet::new(bet_type.clone(), self.chip_value()))) {
+            Ok(()) => self.status = format!("{} on {}.", chip, bet_type),
+            Err(e) => self.status = format!("Can't put {} on {}: {}.", chip, bet_type, e),
+        }
+    }
+
+    fn clear(&mut self) {
+        if self.game.get_current_bets().is_empty() {
+            return;
+        }
+        console::quietly(|| self.game.clear_bets());
+        self.events.borrow_mut().events.clear();
+        self.status = "Bets taken back.".to_string();
+    }
+
+    fn rebet(&mut self) {
+        match console::quietly(|| self.game.rebet()) {
+            Ok(placed) => self.status = format!("{} bet(s) placed again.", placed),
+            Err(e) => self.status = format!("Can't rebet: {}.", e),
+        }
+        self.events.borrow_mut().events.clear();
+    }
+
+    fn spin(&mut self) {
+        if !self.game.has_bets_in_play() {
+            self.status = "Place a bet first.".to_string();
+            return;
+        }
+        self.events.borrow_mut().events.clear();
+        console::quietly(|| self.game.spin_wheel_and_resolve());
+        let events = std::mem::take(&mut self.events.borrow_mut().events);
+        let currency = &self.game.rules().currency;
+        self.log = events.iter().filter_map(|event| describe(event, currency)).collect();
+        let excess = self.log.len().saturating_sub(LOG_LINES);
+        self.log.drain(..excess);
+        if let Some((pocket, returned)) = self.game.last_outcome() {
+            let pocket = self.game.wheel().pocket(pocket);
+            self.status = match returned {
+                Money::ZERO => format!("{} ({}). No win this time.", pocket.ticker, pocket.display_name),
+                _ => format!("{} ({}). {} back!", pocket.ticker, pocket.display_name, currency.amount(returned)),
+            };
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, board, tray, bottom, status, help] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(self.board.height() + 1),
+            Constraint::Length(2),
+            Constraint::Min(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let currency = &self.game.rules().currency;
+        let staked: Money = self.game.get_current_bets().iter().map(|bet| bet.amount).sum();
+        let mut title = vec![
+            "Wall Street Roulette".bold().yellow(),
+            format!("   Balance: {}", currency.amount(self.game.get_player_balance())).into(),
+        ];
+        if !staked.is_zero() {
+            title.push(format!("   On the table: {}", currency.amount(staked)).gray());
+        }
+        frame.render_widget(Paragraph::new(Line::from(title)), header);
+
+        let board = Rect { width: board.width.min(self.board.width()), ..board };
+        self.board.draw(frame, board, self.cursor, self.game.get_current_bets());
+
+        let mut spans = vec![Span::raw("Chips: ")];
+        let mut x = tray.x + 7;
+        self.tray.clear();
+        for (i, value) in self.chips.iter().enumerate() {
+            let text = format!(" {} ", value);
+            let width = text.len() as u16;
+            let style = if i == self.chip {
+                Style::new().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD)
+            } else {
+                Style::new().fg(Color::White).bg(Color::DarkGray)
+            };
+            spans.push(Span::styled(text, style));
+            spans.push(Span::raw(" "));
+            self.tray.push(Rect::new(x, tray.y, width, 1));
+            x += width + 1;
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), tray);
+
+        let [bets, log] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(bottom);
+        let lines: Vec<Line> = self
+            .game
+            .get_current_bets()
+            .iter()
+            .map(|bet| Line::from(format!("{} for {}", bet.bet_type, currency.amount(bet.amount))))
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Bets ")), bets);
+        let lines: Vec<Line> = self.log.iter().map(|line| Line::from(line.as_str())).collect();
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Last spin ")), log);
+
+        frame.render_widget(Paragraph::new(self.status.as_str()), status);
+        let keys = "arrows move · Enter bet · +/- chip · s spin · c clear · r rebet · q quit";
+        frame.render_widget(Paragraph::new(keys).dark_gray(), help);
+    }
+}
+
+/// One line of the round log, for the events worth reporting, with amounts
+/// in `currency`.
+fn describe(event: &GameEvent, currency: &Currency) -> Option<String> {
+    match event {
+        GameEvent::Spin { ticker, number, .. } => Some(format!("Ball lands on {} ({})", ticker, number)),
+        GameEvent::Settlement { bet_type, won: true, payout, .. } => Some(format!("  {}: paid {}", bet_type, currency.amount(*payout))),
+        GameEvent::Settlement { bet_type, payout: Money::ZERO, .. } => Some(format!("  {}: lost", bet_type)),
+        GameEvent::Settlement { bet_type, payout, .. } => Some(format!("  {}: {} back", bet_type, currency.amount(*payout))),
+        GameEvent::RoundComplete { wagered, returned, .. } => {
+            Some(format!("Wagered {}, returned {}", currency.amount(*wagered), currency.amount(*returned)))
+        }
+        _ => None,
+    }
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => app.key(key.code),
+            Event::Mouse(mouse) if app.mouse => app.mouse(mouse),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!(
+        "Usage: roulette_tui [--wheel european|american|mini] [--balance <AMOUNT>] [--palette classic|color_blind|high_contrast] [--color-symbols] [--no-mouse]"
+    );
+    std::process::exit(2);
+}
+
+fn parse_args() -> (Wheel, Money, bool) {
+    let mut wheel = Wheel::european();
+    let mut balance = DEFAULT_BALANCE;
+    let mut palette = Palette::default();
+    let mut mouse = true;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--wheel" => {
+                wheel = match args.next().as_deref() {
+                    Some("european") => Wheel::european(),
+                    Some("american") => Wheel::american(),
+                    Some("mini") => Wheel::mini(),
+                    _ => usage(),
+                }
+            }
+            "--balance" => {
+                balance = args.next().and_then(|amount| amount.parse().ok()).filter(|amount: &Money| !amount.is_zero()).unwrap_or_else(|| usage())
+            }
+            "--palette" => palette.scheme = args.next().as_deref().and_then(Scheme::parse).unwrap_or_else(|| usage()),
+            "--color-symbols" => palette.symbols = true,
+            "--no-mouse" => mouse = false,
+            _ => usage(),
+        }
+    }
+    presentation::set_palette(palette);
+    (wheel, balance, mouse)
+}
+
+fn main() -> io::Result<()> {
+    let (wheel, balance, mouse) = parse_args();
+    let mut terminal = ratatui::init();
+    // A terminal that can't take the request plays on from the keyboard.
+    let mouse = mouse && execute!(io::stdout(), EnableMouseCapture).is_ok();
+    let mut app = App::new(wheel, balance, mouse);
+    let result = run(&mut terminal, &mut app);
+    if mouse {
+        execute!(io::stdout(), DisableMouseCapture)?;
+    }
+    ratatui::restore();
+    result
+}
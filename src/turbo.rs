@@ -0,0 +1,154 @@
+// src/turbo.rs
+
+//! Turbo: bet the first round by hand, then watch the same bets ride spin
+//! after spin.
+//!
+//! [`Turbo`] wraps the controller that placed the first round. From then on
+//! it rebets and spins by itself with a short pause between spins, until the
+//! player presses Enter, the rebet can't be covered, or the balance falls as
+//! far as the stop-loss allows. Then the wrapped controller takes over again.
+
+use crate::game::Game;
+use crate::game::controller::{PlayerController, Turn};
+use crate::game::money::Money;
+use crate::game::wheel::{PocketId, Wheel};
+use crate::{input, say};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Pause between turbo spins unless another is asked for.
+pub const DEFAULT_TURBO_DELAY: Duration = Duration::from_millis(600);
+
+/// How often the pause looks for a keypress.
+const KEY_POLL: Duration = Duration::from_millis(50);
+
+/// A run of automatic spins in progress.
+struct Run {
+    starting_balance: Money,
+    spins: u32,
+}
+
+/// Repeats the last round's bets until told to stop; see the
+/// [module docs](self).
+pub struct Turbo {
+    inner: Box<dyn PlayerController>,
+    delay: Duration,
+    stop_loss: Option<Money>,
+    run: Option<Run>,
+    /// Turbo runs once per session; after it stops, `inner` plays on.
+    finished: bool,
+}
+
+impl Turbo {
+    /// Lets `inner` bet the first round, then takes over.
+    pub fn new(inner: Box<dyn PlayerController>) -> Self {
+        Turbo { inner, delay: DEFAULT_TURBO_DELAY, stop_loss: None, run: None, finished: false }
+    }
+
+    /// Pauses `delay` between spins; zero spins straight on.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Stops once the balance is `limit` below where turbo started.
+    pub fn with_stop_loss(mut self, limit: Money) -> Self {
+        self.stop_loss = Some(limit);
+        self
+    }
+
+    fn start(&mut self, game: &Game) {
+        say!("Turbo on: the same bets ride every spin. Press Enter to stop.");
+        self.run = Some(Run { starting_balance: game.get_player_balance(), spins: 0 });
+    }
+
+    /// Hands back to the wrapped controller, saying why unless the player
+    /// asked (`reason` is `None`).
+    fn stop(&mut self, game: &Game, reason: Option<String>) {
+        let Some(run) = self.run.take() else { return };
+        self.finished = true;
+        let balance = game.get_player_balance();
+        let currency = &game.rules().currency;
+        let change = match balance.checked_sub(run.starting_balance) {
+            Some(gain) => format!("up {}", currency.amount(gain)),
+            None => format!("down {}", currency.amount(run.starting_balance - balance)),
+        };
+        match reason {
+            Some(reason) => say!("Turbo stopped after {} spin(s), {}: {}.", run.spins, change, reason),
+            None => say!("Turbo stopped after {} spin(s), {}.", run.spins, change),
+        }
+    }
+
+    /// Waits out the pause between spins. True if a key was pressed.
+    fn pause(&self) -> bool {
+        let until = Instant::now() + self.delay;
+        loop {
+            if input::poll_line().is_some() {
+                return true;
+            }
+            let left = until.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return false;
+            }
+            thread::sleep(left.min(KEY_POLL));
+        }
+    }
+
+    fn stop_loss_hit(&self, game: &Game) -> bool {
+        let (Some(run), Some(limit)) = (&self.run, self.stop_loss) else { return false };
+        game.get_player_balance() + limit <= run.starting_balance
+    }
+}
+
+impl PlayerController for Turbo {
+    fn name(&self) -> &str {
+        "turbo"
+    }
+
+    fn place_bets(&mut self, game: &mut Game) -> Turn {
+        if self.run.is_none() {
+            return self.inner.place_bets(game);
+        }
+        match game.rebet() {
+            Ok(placed) if placed > 0 => {
+                let staked: Money = game.get_current_bets().iter().map(|bet| bet.amount).sum();
+                if let Some(run) = self.run.as_mut() {
+                    run.spins += 1;
+                    say!("Turbo spin {}: {} bet(s), {} riding.", run.spins, placed, game.rules().currency.amount(staked));
+                }
+                Turn::Play
+            }
+            Ok(_) => {
+                self.stop(game, Some("the balance can't cover the bets".to_string()));
+                self.inner.place_bets(game)
+            }
+            Err(e) => {
+                self.stop(game, Some(e.to_string()));
+                self.inner.place_bets(game)
+            }
+        }
+    }
+
+    fn spin_settled(&mut self, wheel: &Wheel, pocket: PocketId, returned: Money) {
+        self.inner.spin_settled(wheel, pocket, returned);
+    }
+
+    fn play_again(&mut self, game: &mut Game) -> bool {
+        if self.run.is_none() {
+            if self.finished {
+                return self.inner.play_again(game);
+            }
+            self.start(game);
+            return true;
+        }
+        if self.stop_loss_hit(game) {
+            let limit = self.stop_loss.unwrap_or_default();
+            self.stop(game, Some(format!("hit the {} stop-loss", game.rules().currency.amount(limit))));
+        } else if self.pause() {
+            self.stop(game, None);
+        } else {
+            return true;
+        }
+        self.inner.play_again(game)
+    }
+}
@@ -0,0 +1,215 @@
+// src/tutorial.rs
+
+//! A guided first session.
+//!
+//! The tutorial walks a new player through each major kind of bet on a
+//! practice table of its own. The player types every bet in the
+//! [bet syntax](crate::game::dsl) they will use later, and the outcome is
+//! chosen in advance with [`Game::resolve_with_pocket`] so each lesson shows
+//! what it means to: a win with its payout, a miss, and a zero.
+
+use crate::game::Game;
+use crate::game::bets::BetType;
+use crate::game::dsl;
+use crate::game::money::Money;
+use crate::game::wheel::{PocketId, Wheel};
+use crate::{prompt, say};
+use std::io::{self, BufRead};
+
+/// Chips the practice table starts with. They don't carry over.
+pub const TUTORIAL_BALANCE: Money = Money::from_units(1000);
+
+/// How a lesson's spin is made to land.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Landing {
+    /// On a pocket the bet covers.
+    Win,
+    /// On a pocket it doesn't, zeros aside.
+    Miss,
+    /// On the zero.
+    Zero,
+}
+
+/// One step of the tutorial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lesson {
+    StraightUp,
+    Split,
+    EvenMoney,
+    Dozen,
+    Column,
+    Category,
+    Zero,
+}
+
+const LESSONS: [Lesson; 7] = [
+    Lesson::StraightUp,
+    Lesson::Split,
+    Lesson::EvenMoney,
+    Lesson::Dozen,
+    Lesson::Column,
+    Lesson::Category,
+    Lesson::Zero,
+];
+
+impl Lesson {
+    fn title(self) -> &'static str {
+        match self {
+            Lesson::StraightUp => "Straight up: one stock",
+            Lesson::Split => "Split: two stocks",
+            Lesson::EvenMoney => "Even money: red, black, odd, even, low, high",
+            Lesson::Dozen => "Dozens: Growth, Value, and Blue Chip",
+            Lesson::Column => "Columns",
+            Lesson::Category => "Categories: a whole sector",
+            Lesson::Zero => "The zero",
+        }
+    }
+
+    fn intro(self) -> &'static str {
+        match self {
+            Lesson::StraightUp => {
+                "Every pocket on the wheel is a stock. Betting on a single one is the\n\
+                 riskiest bet and pays the most. Pick any ticker from the wheel."
+            }
+            Lesson::Split => "A split covers two stocks at once, for half the payout of a straight-up bet.",
+            Lesson::EvenMoney => {
+                "Outside bets cover a big share of the wheel. Red or black, odd or even,\n\
+                 low or high each cover almost half of it and pay what you staked."
+            }
+            Lesson::Dozen => "The dozens group the stocks by style: growth, value, or bluechip. Each pays 2 to 1.",
+            Lesson::Column => {
+                "The three columns split the stocks by sector: column 1 is tech and telecom,\n\
+                 2 energy, finance, and autos, and 3 consumer, health, and industry.\n\
+                 This time, watch what happens when the ball misses."
+            }
+            Lesson::Category => "Category bets cover a sector or theme, like the Magnificent Seven.",
+            Lesson::Zero => {
+                "The wheel also has a zero pocket (two on American wheels). Let's put an\n\
+                 even-money bet on and see what the zero does to it."
+            }
+        }
+    }
+
+    fn example(self) -> &'static str {
+        match self {
+            Lesson::StraightUp => "straight AAPL 10",
+            Lesson::Split => "split AAPL MSFT 10",
+            Lesson::EvenMoney | Lesson::Zero => "red 10",
+            Lesson::Dozen => "growth 10",
+            Lesson::Column => "column 2 10",
+            Lesson::Category => "category Magnificent Seven 10",
+        }
+    }
+
+    fn accepts(self, bet_type: &BetType) -> bool {
+        match self {
+            Lesson::StraightUp => matches!(bet_type, BetType::StraightUp(_)),
+            Lesson::Split => matches!(bet_type, BetType::Split(_, _)),
+            Lesson::EvenMoney | Lesson::Zero => bet_type.is_even_money(),
+            Lesson::Dozen => matches!(bet_type, BetType::GrowthDozen | BetType::ValueDozen | BetType::BlueChipDozen),
+            Lesson::Column => matches!(bet_type, BetType::Column(_)),
+            Lesson::Category => matches!(bet_type, BetType::Category(_)),
+        }
+    }
+
+    fn landing(self) -> Landing {
+        match self {
+            Lesson::Column => Landing::Miss,
+            Lesson::Zero => Landing::Zero,
+            _ => Landing::Win,
+        }
+    }
+}
+
+/// The pocket to settle `bet_type` on for `landing`.
+fn landing_pocket(wheel: &Wheel, bet_type: &BetType, landing: Landing) -> Option<PocketId> {
+    if landing == Landing::Zero {
+        return wheel.zero_pockets().first().copied();
+    }
+    (0..wheel.get_all_pockets().len()).map(PocketId).find(|&id| {
+        !wheel.pocket(id).is_zero() && bet_type.covers(wheel, id) == (landing == Landing::Win)
+    })
+}
+
+/// Runs the tutorial, reading the player's bets from `input`. Returns false
+/// if the player quit before the end.
+pub fn run(input: &mut impl BufRead) -> io::Result<bool> {
+    let mut game = Game::new(TUTORIAL_BALANCE);
+    say!("\n=== Tutorial ===");
+    say!("You have {} of practice chips. Type 'quit' at any point to skip ahead to the real game.", TUTORIAL_BALANCE);
+    for (step, lesson) in LESSONS.into_iter().enumerate() {
+        say!("\n--- Lesson {} of {}: {} ---", step + 1, LESSONS.len(), lesson.title());
+        say!("{}", lesson.intro());
+        let Some(bet_type) = take_bet(&mut game, input, lesson)? else {
+            return Ok(false);
+        };
+        let wheel = game.wheel();
+        let covered = (0..wheel.get_all_pockets().len()).filter(|&id| bet_type.covers(wheel, PocketId(id))).count();
+        say!("That bet covers {} of the wheel's {} pockets.", covered, wheel.get_all_pockets().len());
+        let Some(pocket) = landing_pocket(wheel, &bet_type, lesson.landing()) else {
+            // Only a wheel without a zero could get here; nothing to show.
+            game.clear_bets();
+            continue;
+        };
+        game.resolve_with_pocket(pocket);
+        explain(&game, &bet_type, lesson);
+        if game.get_player_balance().is_zero() {
+            say!("\nThat was the last of your practice chips.");
+            return Ok(false);
+        }
+    }
+    say!("\nThat's every kind of bet. You finished the tutorial with {} of practice chips.", game.get_player_balance());
+    Ok(true)
+}
+
+/// Reads bet lines until one fits the lesson and is placed. `None` if the
+/// player quit.
+fn take_bet(game: &mut Game, input: &mut impl BufRead, lesson: Lesson) -> io::Result<Option<BetType>> {
+    let mut line = String::new();
+    loop {
+        prompt!("Try: {}\n> ", lesson.example());
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match line.trim() {
+            "" => continue,
+            "quit" | "skip" => return Ok(None),
+            spec => match dsl::parse_bet(spec, game.wheel()) {
+                Ok(bet) if !lesson.accepts(&bet.bet_type) => {
+                    say!("That's a {} bet; this lesson is about something else.", bet.bet_type);
+                }
+                Ok(bet) => {
+                    let bet_type = bet.bet_type.clone();
+                    if game.place_bet(bet) {
+                        return Ok(Some(bet_type));
+                    }
+                }
+                Err(e) => say!("Can't read that bet: {}.", e),
+            },
+        }
+    }
+}
+
+fn explain(game: &Game, bet_type: &BetType, lesson: Lesson) {
+    let Some((pocket, returned)) = game.last_outcome() else { return };
+    let ticker = &game.wheel().pocket(pocket).ticker;
+    let odds = game.payouts().multiplier(bet_type);
+    match lesson.landing() {
+        Landing::Win => {
+            let stake = returned / (odds + 1);
+            say!(
+                "{} pays {} to 1: your {} stake won {} and came back with it, {} in all.",
+                bet_type, odds, stake, stake * odds, returned
+            );
+        }
+        Landing::Miss => say!(
+            "The ball landed on {}, outside your bet, so the stake is lost. Bets that cover more of the wheel miss less often but pay less.",
+            ticker
+        ),
+        Landing::Zero => say!(
+            "{} is a zero pocket. Outside bets lose when the zero comes up, and that's where the house gets its edge. Some tables soften it with en prison or la partage.",
+            ticker
+        ),
+    }
+}
@@ -0,0 +1,352 @@
+// src/wheel_file.rs
+
+//! Wheel files: a custom layout written out pocket by pocket, played with
+//! `--wheel <FILE>` or `wheel_file` in the config, and the checks
+//! `lint-wheel` runs over one.
+//!
+//! ```toml
+//! name = "Meme Stocks"
+//! categories = ["Meme", "Technology"]   # every category a pocket names
+//!
+//! [[pockets]]
+//! number = 0               # 0 to 36, or "00"
+//! ticker = "SPY"
+//! name = "S&P 500 ETF"     # color is green for the zeros, as on a standard wheel otherwise
+//!
+//! [[pockets]]
+//! number = 1
+//! ticker = "GME"
+//! name = "GameStop"
+//! color = "Red"
+//! dozen = "Growth"         # or "Value" or "BlueChip"
+//! categories = ["Meme"]
+//! ```
+//!
+//! The pockets go round the wheel in the order they're listed, and each is
+//! also a category of its own ticker, as on the built-in wheels. The wheel
+//! pays the standard odds.
+//!
+//! [`WheelFile::lint`] reads the file as written, so it can point at
+//! mistakes that building a wheel from it would hide or trip over;
+//! [`WheelFile::build`] refuses a file with any of its errors.
+
+use crate::config::ConfigError;
+use crate::game::category::CategoryId;
+use crate::game::metadata::PocketMetadata;
+use crate::game::payout::PayoutTable;
+use crate::game::ticker::Ticker;
+use crate::game::wheel::{Color, DOUBLE_ZERO, Dozen, Pocket, Wheel};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Pockets each dozen covers on a standard wheel, which dozen payouts assume.
+const DOZEN_SIZE: usize = 12;
+
+/// Numbered pockets on a standard wheel, which the standard odds assume.
+const STANDARD_NUMBERS: usize = 36;
+
+/// A wheel layout as written in a wheel file; see the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WheelFile {
+    pub name: String,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub pockets: Vec<PocketEntry>,
+}
+
+/// One pocket of a [`WheelFile`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PocketEntry {
+    pub number: PocketNumber,
+    pub ticker: String,
+    pub name: String,
+    pub color: Option<Color>,
+    pub dozen: Option<Dozen>,
+    #[serde(default)]
+    pub categories: Vec<String>,
+}
+
+/// A pocket's number: 0 to 36, or `"00"` for the double zero.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum PocketNumber {
+    Number(i64),
+    Label(String),
+}
+
+impl PocketNumber {
+    /// The number as the wheel stores it, or `None` if no pocket can have it.
+    pub fn value(&self) -> Option<u8> {
+        match self {
+            PocketNumber::Number(n) => u8::try_from(*n).ok().filter(|n| *n <= 36),
+            PocketNumber::Label(label) if label.trim() == "00" => Some(DOUBLE_ZERO),
+            PocketNumber::Label(label) => label.trim().parse().ok().filter(|n| *n <= 36),
+        }
+    }
+}
+
+impl fmt::Display for PocketNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PocketNumber::Number(n) => write!(f, "{}", n),
+            PocketNumber::Label(label) => write!(f, "\"{}\"", label),
+        }
+    }
+}
+
+impl PocketEntry {
+    fn is_zero(&self) -> bool {
+        self.number.value().is_some_and(|n| n == 0 || n == DOUBLE_ZERO)
+    }
+
+    /// The color as given, or as the number has it on a standard wheel.
+    fn color(&self) -> Option<Color> {
+        self.color.or_else(|| self.number.value().map(Color::of_number))
+    }
+}
+
+/// How much a [`Diagnostic`] matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The file can't make a working wheel.
+    Error,
+    /// The wheel works, but some bets won't pay as their names promise.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem [`WheelFile::lint`] found, and what to do about it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    /// The change that would fix it.
+    pub help: String,
+}
+
+impl Diagnostic {
+    fn error(message: String, help: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Error, message, help: help.into() }
+    }
+
+    fn warning(message: String, help: impl Into<String>) -> Self {
+        Diagnostic { severity: Severity::Warning, message, help: help.into() }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}\n  help: {}", self.severity, self.message, self.help)
+    }
+}
+
+impl WheelFile {
+    pub fn load(path: &Path) -> Result<WheelFile, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|e| ConfigError::Io(path.to_path_buf(), e))?;
+        toml::from_str(&text).map_err(|e| ConfigError::Parse(path.to_path_buf(), e.to_string()))
+    }
+
+    /// The wheel the file describes, or the first error [`lint`](Self::lint)
+    /// finds in it. Warnings don't stop it.
+    pub fn build(&self) -> Result<Wheel, String> {
+        if let Some(error) = self.lint().into_iter().find(|diagnostic| diagnostic.severity == Severity::Error) {
+            return Err(format!("{}; {}", error.message, error.help));
+        }
+        let pockets = self
+            .pockets
+            .iter()
+            .filter_map(|entry| {
+                let ticker = Ticker::parse(&entry.ticker).ok()?;
+                let own = CategoryId::intern(ticker.as_str());
+                Some(Pocket {
+                    ticker,
+                    display_name: entry.name.clone(),
+                    categories: entry.categories.iter().map(|category| CategoryId::intern(category.trim())).chain([own]).collect(),
+                    // Dozen bets lose on the zeros whatever the file says.
+                    dozen: entry.dozen.filter(|_| !entry.is_zero()),
+                    metadata: PocketMetadata::default(),
+                    number: entry.number.value()?,
+                    color: entry.color()?,
+                })
+            })
+            .collect();
+        Ok(Wheel::from_pockets(&self.name, pockets, PayoutTable::STANDARD))
+    }
+
+    /// Everything wrong with the layout, errors first, in the order of the
+    /// pockets they concern. Empty when the wheel is sound.
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let mut found = Vec::new();
+        if self.pockets.is_empty() {
+            found.push(Diagnostic::error("the wheel has no pockets".to_string(), "add a [[pockets]] entry for each pocket"));
+            return found;
+        }
+        self.lint_pockets(&mut found);
+        self.lint_categories(&mut found);
+        self.lint_dozens(&mut found);
+        self.lint_colors(&mut found);
+        found.sort_by_key(|diagnostic| diagnostic.severity);
+        found
+    }
+
+    /// Bad or repeated numbers and tickers, and a missing zero.
+    fn lint_pockets(&self, found: &mut Vec<Diagnostic>) {
+        let mut numbers: BTreeMap<u8, Vec<&PocketEntry>> = BTreeMap::new();
+        let mut tickers: BTreeMap<String, Vec<&PocketEntry>> = BTreeMap::new();
+        for pocket in &self.pockets {
+            match pocket.number.value() {
+                Some(number) => numbers.entry(number).or_default().push(pocket),
+                None => found.push(Diagnostic::error(
+                    format!("pocket {} has number {}", pocket.ticker, pocket.number),
+                    "number pockets 0 to 36, or \"00\" for a double zero",
+                )),
+            }
+            match Ticker::parse(&pocket.ticker) {
+                Ok(ticker) => tickers.entry(ticker.to_string()).or_default().push(pocket),
+                Err(e) => found.push(Diagnostic::error(format!("pocket {}: {}", pocket.number, e), "give it the stock's exchange ticker")),
+            }
+        }
+        for (number, pockets) in numbers.iter().filter(|(_, pockets)| pockets.len() > 1) {
+            found.push(Diagnostic::error(
+                format!("number {} is on {} pockets: {}", number_label(*number), pockets.len(), tickers_of(pockets)),
+                "give every pocket but one another number",
+            ));
+        }
+        for (ticker, pockets) in tickers.iter().filter(|(_, pockets)| pockets.len() > 1) {
+            let numbers: Vec<String> = pockets.iter().map(|pocket| pocket.number.to_string()).collect();
+            found.push(Diagnostic::error(
+                format!("ticker {} is on {} pockets, numbered {}", ticker, pockets.len(), numbers.join(", ")),
+                "a straight-up bet names one pocket; give the others tickers of their own",
+            ));
+        }
+        let numbered = numbers.keys().filter(|&&number| number != 0 && number != DOUBLE_ZERO).count();
+        if numbered != STANDARD_NUMBERS {
+            found.push(Diagnostic::warning(
+                format!("the wheel has {} numbered pocket(s), but it pays the standard odds, as if it had {}", numbered, STANDARD_NUMBERS),
+                "number pockets 1 to 36, or set [rules] payouts to odds that suit the wheel",
+            ));
+        }
+        if !self.pockets.iter().any(PocketEntry::is_zero) {
+            found.push(Diagnostic::warning(
+                "there is no zero pocket, so the house has no edge on even-money bets".to_string(),
+                "add a pocket numbered 0 (and \"00\" for an American wheel)",
+            ));
+        }
+    }
+
+    /// Categories nobody declared, and declared ones too small to bet on.
+    fn lint_categories(&self, found: &mut Vec<Diagnostic>) {
+        let mut members: BTreeMap<&str, Vec<&PocketEntry>> =
+            self.categories.iter().map(|category| (category.trim(), Vec::new())).collect();
+        for pocket in &self.pockets {
+            for category in &pocket.categories {
+                match members.get_mut(category.trim()) {
+                    Some(pockets) => pockets.push(pocket),
+                    None => found.push(Diagnostic::error(
+                        format!("pocket {} is in category '{}', which isn't declared", pocket.ticker, category.trim()),
+                        format!("add \"{}\" to categories, or fix its spelling", category.trim()),
+                    )),
+                }
+            }
+        }
+        for (category, pockets) in members {
+            match pockets.as_slice() {
+                [] => found.push(Diagnostic::warning(
+                    format!("category '{}' has no pockets, so a bet on it can never win", category),
+                    "put pockets in it, or take it out of categories",
+                )),
+                [only] => found.push(Diagnostic::warning(
+                    format!("category '{}' only has {}, so betting on it is a straight-up bet at a category's payout", category, only.ticker),
+                    "put more pockets in it, or take it out of categories",
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    /// Dozens that don't hold the twelve pockets their payout assumes.
+    fn lint_dozens(&self, found: &mut Vec<Diagnostic>) {
+        for pocket in self.pockets.iter().filter(|pocket| pocket.is_zero() && pocket.dozen.is_some()) {
+            found.push(Diagnostic::warning(
+                format!("zero pocket {} is in a dozen", pocket.ticker),
+                "dozen bets lose on the zeros; take its dozen out",
+            ));
+        }
+        if self.pockets.iter().all(|pocket| pocket.dozen.is_none()) {
+            found.push(Diagnostic::warning(
+                "no pocket is in a dozen, so dozen bets can never win".to_string(),
+                "give each non-zero pocket a dozen: Growth, Value, or BlueChip",
+            ));
+            return;
+        }
+        for dozen in Dozen::ALL {
+            let count = self.pockets.iter().filter(|pocket| pocket.dozen == Some(dozen)).count();
+            if count != DOZEN_SIZE {
+                found.push(Diagnostic::warning(
+                    format!("the {} has {} pocket(s), but dozen bets pay 2 to 1 as if it had {}", dozen, count, DOZEN_SIZE),
+                    match count < DOZEN_SIZE {
+                        true => format!("move {} pocket(s) into it from another dozen", DOZEN_SIZE - count),
+                        false => format!("move {} pocket(s) out of it into another dozen", count - DOZEN_SIZE),
+                    },
+                ));
+            }
+        }
+        let unplaced: Vec<&PocketEntry> =
+            self.pockets.iter().filter(|pocket| !pocket.is_zero() && pocket.dozen.is_none()).collect();
+        if !unplaced.is_empty() {
+            found.push(Diagnostic::warning(
+                format!("{} non-zero pocket(s) are in no dozen: {}", unplaced.len(), tickers_of(&unplaced)),
+                "give each a dozen, unless dozen bets are meant to skip them",
+            ));
+        }
+    }
+
+    /// Red and black out of balance, and zeros that aren't green.
+    fn lint_colors(&self, found: &mut Vec<Diagnostic>) {
+        let count = |color: Color| self.pockets.iter().filter(|pocket| !pocket.is_zero() && pocket.color() == Some(color)).count();
+        let (red, black) = (count(Color::Red), count(Color::Black));
+        if red.abs_diff(black) > 1 {
+            let (more, fewer) = if red > black { ("red", "black") } else { ("black", "red") };
+            found.push(Diagnostic::warning(
+                format!("{} pockets are red and {} black, so the two color bets don't have the same odds", red, black),
+                format!("make {} {} pocket(s) {}", red.abs_diff(black) / 2, more, fewer),
+            ));
+        }
+        let green: Vec<&PocketEntry> =
+            self.pockets.iter().filter(|pocket| !pocket.is_zero() && pocket.color() == Some(Color::Green)).collect();
+        if !green.is_empty() {
+            found.push(Diagnostic::warning(
+                format!("{} is green but not a zero, so red and black both lose on it", tickers_of(&green)),
+                "make it red or black, or number it 0",
+            ));
+        }
+        for pocket in self.pockets.iter().filter(|pocket| pocket.is_zero() && pocket.color() != Some(Color::Green)) {
+            found.push(Diagnostic::warning(
+                format!("zero pocket {} isn't green, so a color bet wins on it", pocket.ticker),
+                "leave out its color, or set it to \"Green\"",
+            ));
+        }
+    }
+}
+
+fn number_label(number: u8) -> String {
+    if number == DOUBLE_ZERO { "00".to_string() } else { number.to_string() }
+}
+
+fn tickers_of(pockets: &[&PocketEntry]) -> String {
+    pockets.iter().map(|pocket| pocket.ticker.as_str()).collect::<Vec<_>>().join(", ")
+}